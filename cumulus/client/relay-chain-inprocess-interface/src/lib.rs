@@ -417,6 +417,10 @@ fn build_polkadot_full_node(
 		prepare_workers_hard_max_num: None,
 		prepare_workers_soft_max_num: None,
 		keep_finalized_for: None,
+		keep_finalized_pov_for: None,
+		availability_disk_quota: None,
+		overseer_watchdog: Default::default(),
+		statement_response_limits: Default::default(),
 	};
 
 	let (relay_chain_full_node, paranode_req_receiver) = match config.network.network_backend {
@@ -285,6 +285,7 @@ async fn new_minimal_relay_chain<Block: BlockT, Network: NetworkBackend<RelayBlo
 		req_protocol_names: request_protocol_names,
 		peerset_protocol_names,
 		notification_services,
+		watchdog_config: Default::default(),
 	};
 
 	let overseer_handle =
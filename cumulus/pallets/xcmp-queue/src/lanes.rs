@@ -0,0 +1,237 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Cumulus.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Service lanes for inbound messages.
+//!
+//! The message queue services the queues of all origins round-robin, so a congested sibling can
+//! use all the weight available for servicing messages in a block. The origins of messages are
+//! classified into [`Lane`]s by a [`ClassifyLane`] implementation. Each lane can reserve weight
+//! that lower priority lanes may not use, and can be limited to a maximum weight per block, as
+//! configured by [`LaneServiceWeights`].
+//!
+//! Lanes are only serviced this way when the `MessageProcessor` of `pallet-message-queue` is
+//! wrapped in [`LaneServiceWeightLimiter`].
+
+use crate::{Config, LaneServiceWeightUsed};
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use cumulus_primitives_core::{AggregateMessageOrigin, IsSystem, ParaId};
+use frame_support::{
+	traits::{Contains, Get, ProcessMessage, ProcessMessageError},
+	weights::{Weight, WeightMeter},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Zero, RuntimeDebug};
+
+/// A lane in which inbound messages are serviced, by decreasing priority.
+#[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Lane {
+	/// Messages which must not be delayed, like governance ones.
+	Critical,
+	/// Messages from system parachains.
+	System,
+	/// All other messages.
+	Regular,
+}
+
+/// Classifies the origins of inbound messages into [`Lane`]s.
+pub trait ClassifyLane {
+	/// The lane in which the messages of `origin` are serviced.
+	fn classify(origin: &AggregateMessageOrigin) -> Lane;
+}
+
+/// Services all messages in [`Lane::Regular`].
+impl ClassifyLane for () {
+	fn classify(_: &AggregateMessageOrigin) -> Lane {
+		Lane::Regular
+	}
+}
+
+/// Services the messages of the relay chain, of the local chain and of the siblings in `Critical`
+/// in [`Lane::Critical`], the messages of other system parachains in [`Lane::System`] and all other
+/// messages in [`Lane::Regular`].
+pub struct SystemParachainLanes<Critical>(PhantomData<Critical>);
+impl<Critical: Contains<ParaId>> ClassifyLane for SystemParachainLanes<Critical> {
+	fn classify(origin: &AggregateMessageOrigin) -> Lane {
+		match origin {
+			AggregateMessageOrigin::Here | AggregateMessageOrigin::Parent => Lane::Critical,
+			AggregateMessageOrigin::Sibling(id) if Critical::contains(id) => Lane::Critical,
+			AggregateMessageOrigin::Sibling(id) if id.is_system() => Lane::System,
+			AggregateMessageOrigin::Sibling(_) => Lane::Regular,
+		}
+	}
+}
+
+impl Lane {
+	/// The lanes which are serviced with a higher priority than this one.
+	pub fn higher(self) -> &'static [Lane] {
+		match self {
+			Lane::Critical => &[],
+			Lane::System => &[Lane::Critical],
+			Lane::Regular => &[Lane::Critical, Lane::System],
+		}
+	}
+}
+
+/// The weight available for servicing the messages of a [`Lane`] in a block.
+#[derive(Copy, Clone, Default, Eq, PartialEq, RuntimeDebug)]
+pub struct LaneServiceWeight {
+	/// The weight which lower priority lanes may not use in a block.
+	///
+	/// The reservation shrinks as the lane uses weight, so that lower priority lanes can use
+	/// the weight that the lane used beyond it.
+	pub reserved: Weight,
+	/// The maximum weight used for servicing the messages of the lane in a block.
+	///
+	/// `None` means that the lane is only limited by the service weight of the message queue.
+	pub limit: Option<Weight>,
+}
+
+/// The [`LaneServiceWeight`] of each [`Lane`].
+#[derive(Copy, Clone, Default, Eq, PartialEq, RuntimeDebug)]
+pub struct LaneServiceWeights {
+	/// The service weight of [`Lane::Critical`].
+	pub critical: LaneServiceWeight,
+	/// The service weight of [`Lane::System`].
+	pub system: LaneServiceWeight,
+	/// The service weight of [`Lane::Regular`].
+	///
+	/// Its reservation has no effect, since there is no lower priority lane.
+	pub regular: LaneServiceWeight,
+}
+
+impl LaneServiceWeights {
+	/// The service weight of `lane`.
+	pub fn of(&self, lane: Lane) -> LaneServiceWeight {
+		match lane {
+			Lane::Critical => self.critical,
+			Lane::System => self.system,
+			Lane::Regular => self.regular,
+		}
+	}
+
+	/// Whether the messages of `lane` are serviced without any bookkeeping, since the lane is not
+	/// limited and neither it nor a higher priority lane reserves weight.
+	fn is_unrestricted(&self, lane: Lane) -> bool {
+		let weight = self.of(lane);
+		weight.limit.is_none() &&
+			weight.reserved.is_zero() &&
+			lane.higher().iter().all(|higher| self.of(*higher).reserved.is_zero())
+	}
+}
+
+/// The weight used for servicing the messages of each [`Lane`] in a block.
+#[derive(
+	Copy, Clone, Default, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct LanesWeightUsed {
+	/// The weight used by [`Lane::Critical`].
+	pub critical: Weight,
+	/// The weight used by [`Lane::System`].
+	pub system: Weight,
+	/// The weight used by [`Lane::Regular`].
+	pub regular: Weight,
+}
+
+impl LanesWeightUsed {
+	/// The weight used by `lane`.
+	pub fn of(&self, lane: Lane) -> Weight {
+		match lane {
+			Lane::Critical => self.critical,
+			Lane::System => self.system,
+			Lane::Regular => self.regular,
+		}
+	}
+
+	fn of_mut(&mut self, lane: Lane) -> &mut Weight {
+		match lane {
+			Lane::Critical => &mut self.critical,
+			Lane::System => &mut self.system,
+			Lane::Regular => &mut self.regular,
+		}
+	}
+}
+
+/// Wraps a `MessageProcessor`, servicing the messages of each [`Lane`] as classified by
+/// `Classifier` with the service weights of `Weights`.
+///
+/// A message can't use the weight still reserved for higher priority lanes, nor more than the
+/// weight left to the limit of its lane. Such messages are reported as overweight, so that the
+/// message queue stops servicing their queue and retries in the next block.
+///
+/// Messages which need more weight than the limit of their lane are permanently overweight, since
+/// they could never be serviced. They can be executed with `execute_overweight` of
+/// `pallet-message-queue` once the limit of their lane is raised.
+pub struct LaneServiceWeightLimiter<T, MessageProcessor, Classifier, Weights>(
+	PhantomData<(T, MessageProcessor, Classifier, Weights)>,
+);
+impl<T, MessageProcessor, Classifier, Weights> ProcessMessage
+	for LaneServiceWeightLimiter<T, MessageProcessor, Classifier, Weights>
+where
+	T: Config,
+	MessageProcessor: ProcessMessage<Origin = AggregateMessageOrigin>,
+	Classifier: ClassifyLane,
+	Weights: Get<LaneServiceWeights>,
+{
+	type Origin = AggregateMessageOrigin;
+
+	fn process_message(
+		message: &[u8],
+		origin: Self::Origin,
+		meter: &mut WeightMeter,
+		id: &mut [u8; 32],
+	) -> Result<bool, ProcessMessageError> {
+		let lane = Classifier::classify(&origin);
+		let weights = Weights::get();
+		if weights.is_unrestricted(lane) {
+			return MessageProcessor::process_message(message, origin, meter, id)
+		}
+
+		let bookkeeping = T::DbWeight::get().reads_writes(1, 1);
+		if meter.try_consume(bookkeeping).is_err() {
+			return Err(ProcessMessageError::Overweight(bookkeeping))
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut used = match LaneServiceWeightUsed::<T>::get() {
+			Some((block, used)) if block == now => used,
+			_ => LanesWeightUsed::default(),
+		};
+		let reserved = lane.higher().iter().fold(Weight::zero(), |reserved, higher| {
+			reserved.saturating_add(weights.of(*higher).reserved.saturating_sub(used.of(*higher)))
+		});
+		let mut available = meter.remaining().saturating_sub(reserved);
+		let limit = weights.of(lane).limit;
+		if let Some(limit) = limit {
+			available = available.min(limit.saturating_sub(used.of(lane)));
+		}
+
+		let mut lane_meter = WeightMeter::with_limit(available);
+		let result = MessageProcessor::process_message(message, origin, &mut lane_meter, id);
+
+		meter.consume(lane_meter.consumed());
+		let lane_used = used.of_mut(lane);
+		*lane_used = lane_used.saturating_add(lane_meter.consumed());
+		LaneServiceWeightUsed::<T>::put((now, used));
+
+		match result {
+			Err(ProcessMessageError::Overweight(required))
+				if limit.map_or(false, |limit| required.any_gt(limit)) =>
+				Err(ProcessMessageError::Overweight(Weight::MAX)),
+			result => result,
+		}
+	}
+}
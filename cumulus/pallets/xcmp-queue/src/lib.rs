@@ -47,6 +47,7 @@ mod tests;
 mod benchmarking;
 #[cfg(feature = "bridging")]
 pub mod bridging;
+pub mod lanes;
 pub mod weights;
 pub mod weights_ext;
 
@@ -82,6 +83,7 @@ use xcm::{latest::prelude::*, VersionedLocation, VersionedXcm, WrapVersion, MAX_
 use xcm_builder::InspectMessageQueues;
 use xcm_executor::traits::ConvertOrigin;
 
+use lanes::LanesWeightUsed;
 pub use pallet::*;
 
 /// Index used to identify overweight XCMs.
@@ -364,6 +366,13 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type DeliveryFeeFactor<T: Config> =
 		StorageMap<_, Twox64Concat, ParaId, FixedU128, ValueQuery, GetMinFeeFactor<Pallet<T>>>;
+
+	/// The weight used for servicing the messages of each lane, and the block it was used in.
+	///
+	/// Only written by [`lanes::LaneServiceWeightLimiter`].
+	#[pallet::storage]
+	pub(super) type LaneServiceWeightUsed<T: Config> =
+		StorageValue<_, (BlockNumberFor<T>, LanesWeightUsed), OptionQuery>;
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
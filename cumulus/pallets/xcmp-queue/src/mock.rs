@@ -15,12 +15,13 @@
 
 use super::*;
 use crate as xcmp_queue;
+use crate::lanes::{LaneServiceWeights, SystemParachainLanes};
 use core::marker::PhantomData;
 use cumulus_pallet_parachain_system::AnyRelayNumber;
 use cumulus_primitives_core::{ChannelInfo, IsSystem, ParaId};
 use frame_support::{
 	derive_impl, parameter_types,
-	traits::{BatchesFootprints, ConstU32, Everything, OriginTrait},
+	traits::{BatchesFootprints, ConstU32, Equals, Everything, OriginTrait},
 	BoundedSlice,
 };
 use frame_system::EnsureRoot;
@@ -218,6 +219,20 @@ parameter_types! {
 	pub const ByteFee: Balance = 1_000_000;
 }
 
+parameter_types! {
+	/// A sibling whose messages are serviced in the critical lane.
+	pub CriticalSibling: ParaId = ParaId::from(1001);
+	pub static LaneServiceWeightLimits: LaneServiceWeights = Default::default();
+}
+
+/// Services the messages of the message processor `P` in lanes.
+pub type LaneLimiter<P> = crate::lanes::LaneServiceWeightLimiter<
+	Test,
+	P,
+	SystemParachainLanes<Equals<CriticalSibling>>,
+	LaneServiceWeightLimits,
+>;
+
 pub type PriceForSiblingParachainDelivery = polkadot_runtime_common::xcm_sender::ExponentialPrice<
 	FeeAssetId,
 	BaseDeliveryFee,
@@ -20,13 +20,15 @@ use super::{
 use XcmpMessageFormat::*;
 
 use codec::Input;
-use cumulus_primitives_core::{ParaId, XcmpMessageHandler};
+use cumulus_primitives_core::{AggregateMessageOrigin, ParaId, XcmpMessageHandler};
 use frame_support::{
 	assert_err, assert_noop, assert_ok, assert_storage_noop, hypothetically,
-	traits::{BatchFootprint, Hooks},
+	traits::{BatchFootprint, Equals, Hooks, ProcessMessage, ProcessMessageError},
 	StorageNoopGuard,
 };
+use lanes::{Lane, LaneServiceWeight, LaneServiceWeights, LanesWeightUsed};
 use mock::{new_test_ext, ParachainSystem, RuntimeOrigin as Origin, Test, XcmpQueue};
+use pallet_message_queue::mock_helpers::NoopMessageProcessor;
 use sp_runtime::traits::{BadOrigin, Zero};
 use std::iter::{once, repeat};
 use xcm::{MAX_INSTRUCTIONS_TO_DECODE, MAX_XCM_DECODE_DEPTH};
@@ -1074,3 +1076,166 @@ fn page_not_modified_when_fragment_does_not_fit() {
 		}
 	});
 }
+
+#[test]
+fn system_parachain_lanes_classify_origins() {
+	use lanes::{ClassifyLane, SystemParachainLanes};
+	type Lanes = SystemParachainLanes<Equals<mock::CriticalSibling>>;
+
+	assert_eq!(Lanes::classify(&AggregateMessageOrigin::Here), Lane::Critical);
+	assert_eq!(Lanes::classify(&AggregateMessageOrigin::Parent), Lane::Critical);
+	assert_eq!(Lanes::classify(&AggregateMessageOrigin::Sibling(1001.into())), Lane::Critical);
+	assert_eq!(Lanes::classify(&AggregateMessageOrigin::Sibling(1000.into())), Lane::System);
+	assert_eq!(Lanes::classify(&AggregateMessageOrigin::Sibling(2000.into())), Lane::Regular);
+}
+
+/// Processes a message of 10 weight from `origin` with the lane limiter of the mock.
+fn process_in_lane(
+	origin: AggregateMessageOrigin,
+	meter: &mut WeightMeter,
+) -> Result<bool, ProcessMessageError> {
+	mock::LaneLimiter::<NoopMessageProcessor<AggregateMessageOrigin, 10>>::process_message(
+		&[],
+		origin,
+		meter,
+		&mut [0; 32],
+	)
+}
+
+#[test]
+fn lane_service_weight_is_limited_per_block() {
+	let process = |sibling: u32| {
+		let mut meter = WeightMeter::new();
+		process_in_lane(AggregateMessageOrigin::Sibling(sibling.into()), &mut meter)
+			.map(|_| meter.consumed())
+	};
+	let message_weight = Weight::from_parts(10, 10);
+	let regular_used = |used: Weight| LanesWeightUsed { regular: used, ..Default::default() };
+
+	new_test_ext().execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(1);
+		mock::LaneServiceWeightLimits::set(LaneServiceWeights {
+			regular: LaneServiceWeight { limit: Some(message_weight * 3), ..Default::default() },
+			..Default::default()
+		});
+
+		// The regular lane can service three messages in this block.
+		for _ in 0..3 {
+			assert_eq!(process(2000), Ok(message_weight));
+		}
+		assert_eq!(process(2001), Err(ProcessMessageError::Overweight(message_weight)));
+		assert_eq!(
+			LaneServiceWeightUsed::<Test>::get(),
+			Some((1, regular_used(message_weight * 3)))
+		);
+
+		// Other lanes are not limited.
+		for _ in 0..5 {
+			assert_eq!(process(1000), Ok(message_weight));
+			assert_eq!(process(1001), Ok(message_weight));
+		}
+		assert_eq!(
+			LaneServiceWeightUsed::<Test>::get(),
+			Some((1, regular_used(message_weight * 3)))
+		);
+
+		// The limit applies again in the next block.
+		frame_system::Pallet::<Test>::set_block_number(2);
+		assert_eq!(process(2000), Ok(message_weight));
+		assert_eq!(LaneServiceWeightUsed::<Test>::get(), Some((2, regular_used(message_weight))));
+	});
+}
+
+#[test]
+fn message_over_lane_limit_is_permanently_overweight() {
+	let message_weight = Weight::from_parts(10, 10);
+	let regular = AggregateMessageOrigin::Sibling(2000.into());
+
+	new_test_ext().execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(1);
+		mock::LaneServiceWeightLimits::set(LaneServiceWeights {
+			regular: LaneServiceWeight {
+				limit: Some(message_weight.saturating_sub(Weight::from_parts(1, 1))),
+				..Default::default()
+			},
+			..Default::default()
+		});
+
+		// The message could never be serviced in its lane.
+		assert_eq!(
+			process_in_lane(regular.clone(), &mut WeightMeter::new()),
+			Err(ProcessMessageError::Overweight(Weight::MAX))
+		);
+
+		// A message which fits in the limit of its lane is only temporarily overweight.
+		mock::LaneServiceWeightLimits::set(LaneServiceWeights {
+			regular: LaneServiceWeight {
+				limit: Some(Weight::from_parts(15, 15)),
+				..Default::default()
+			},
+			..Default::default()
+		});
+		assert_ok!(process_in_lane(regular.clone(), &mut WeightMeter::new()));
+		assert_eq!(
+			process_in_lane(regular, &mut WeightMeter::new()),
+			Err(ProcessMessageError::Overweight(message_weight))
+		);
+	});
+}
+
+#[test]
+fn lane_reservation_is_not_used_by_lower_lanes() {
+	let message_weight = Weight::from_parts(10, 10);
+	let critical = AggregateMessageOrigin::Parent;
+	let regular = AggregateMessageOrigin::Sibling(2000.into());
+
+	new_test_ext().execute_with(|| {
+		frame_system::Pallet::<Test>::set_block_number(1);
+		mock::LaneServiceWeightLimits::set(LaneServiceWeights {
+			critical: LaneServiceWeight { reserved: message_weight * 2, limit: None },
+			..Default::default()
+		});
+		let mut meter = WeightMeter::with_limit(message_weight * 3);
+
+		// Only the weight which is not reserved for the critical lane can be used.
+		assert_ok!(process_in_lane(regular.clone(), &mut meter));
+		assert_eq!(
+			process_in_lane(regular.clone(), &mut meter),
+			Err(ProcessMessageError::Overweight(message_weight))
+		);
+
+		// The reservation shrinks as the critical lane uses it.
+		assert_ok!(process_in_lane(critical.clone(), &mut meter));
+		assert_eq!(
+			process_in_lane(regular.clone(), &mut meter),
+			Err(ProcessMessageError::Overweight(message_weight))
+		);
+		assert_ok!(process_in_lane(critical.clone(), &mut meter));
+		assert!(meter.remaining().is_zero());
+
+		// Once the reservation is used, the regular lane can use all the weight left.
+		let mut meter = WeightMeter::with_limit(message_weight * 2);
+		assert_ok!(process_in_lane(regular.clone(), &mut meter));
+		assert_ok!(process_in_lane(regular, &mut meter));
+		assert_eq!(
+			LaneServiceWeightUsed::<Test>::get(),
+			Some((
+				1,
+				LanesWeightUsed {
+					critical: message_weight * 2,
+					regular: message_weight * 3,
+					..Default::default()
+				}
+			))
+		);
+
+		// The weight is reserved again in the next block.
+		frame_system::Pallet::<Test>::set_block_number(2);
+		let mut meter = WeightMeter::with_limit(message_weight * 3);
+		assert_ok!(process_in_lane(regular.clone(), &mut meter));
+		assert_eq!(
+			process_in_lane(regular, &mut meter),
+			Err(ProcessMessageError::Overweight(message_weight))
+		);
+	});
+}
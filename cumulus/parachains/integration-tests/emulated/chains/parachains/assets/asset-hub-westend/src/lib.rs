@@ -44,6 +44,7 @@ decl_test_parachains! {
 			MessageOrigin: cumulus_primitives_core::AggregateMessageOrigin,
 			DigestProvider: (),
 			AdditionalInherentCode: (),
+			CirculatingSupply: circulating_supply,
 		},
 		pallets = {
 			PolkadotXcm: asset_hub_westend_runtime::PolkadotXcm,
@@ -58,6 +59,19 @@ decl_test_parachains! {
 	},
 }
 
+/// The supply of WND outside of the teleport checking account, see
+/// `xcm_emulator::check_conservation`.
+pub fn circulating_supply(
+	id: &emulated_integration_tests_common::xcm_emulator::AssetId,
+) -> Option<u128> {
+	if id.0 != emulated_integration_tests_common::xcm_emulator::Location::parent() {
+		return None
+	}
+	let checking = asset_hub_westend_runtime::PolkadotXcm::check_account();
+	let checked = asset_hub_westend_runtime::Balances::free_balance(&checking);
+	Some(asset_hub_westend_runtime::Balances::total_issuance().saturating_sub(checked))
+}
+
 // AssetHubWestend implementation
 impl_accounts_helpers_for_parachain!(AssetHubWestend);
 impl_assert_events_helpers_for_parachain!(AssetHubWestend);
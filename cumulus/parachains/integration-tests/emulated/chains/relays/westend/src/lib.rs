@@ -32,6 +32,7 @@ decl_test_relay_chains! {
 		runtime = westend_runtime,
 		core = {
 			SovereignAccountOf: westend_runtime::xcm_config::LocationConverter,
+			CirculatingSupply: westend_runtime::xcm_config::circulating_supply,
 		},
 		pallets = {
 			XcmPallet: westend_runtime::XcmPallet,
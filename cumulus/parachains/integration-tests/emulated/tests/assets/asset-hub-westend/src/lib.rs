@@ -40,8 +40,8 @@ mod imports {
 		test_parachain_is_trusted_teleporter, test_parachain_is_trusted_teleporter_for_relay,
 		test_relay_is_trusted_teleporter, test_xcm_fee_querying_apis_work_for_asset_hub,
 		xcm_emulator::{
-			assert_expected_events, bx, Chain, Parachain as Para, RelayChain as Relay, Test,
-			TestArgs, TestContext, TestExt,
+			assert_expected_events, bx, check_conservation, Chain, Parachain as Para,
+			RelayChain as Relay, Test, TestArgs, TestContext, TestExt,
 		},
 		xcm_helpers::{
 			fee_asset, get_amount_from_versioned_assets, non_fee_asset, xcm_transact_paid_execution,
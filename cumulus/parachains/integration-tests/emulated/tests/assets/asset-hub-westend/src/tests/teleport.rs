@@ -277,6 +277,28 @@ fn teleport_via_transfer_assets_from_and_to_relay() {
 	);
 }
 
+#[test]
+fn teleport_from_and_to_relay_conserves_assets() {
+	let amount = WESTEND_ED * 100;
+	let native_asset: Assets = (Here, amount).into();
+
+	check_conservation(|| {
+		test_relay_is_trusted_teleporter!(
+			Westend,
+			vec![AssetHubWestend],
+			(native_asset, amount),
+			limited_teleport_assets
+		);
+
+		test_parachain_is_trusted_teleporter_for_relay!(
+			AssetHubWestend,
+			Westend,
+			amount,
+			limited_teleport_assets
+		);
+	});
+}
+
 /// Limited Teleport of native asset from Relay Chain to Asset Hub
 /// shouldn't work when there is not enough balance in Asset Hub's `CheckAccount`
 #[test]
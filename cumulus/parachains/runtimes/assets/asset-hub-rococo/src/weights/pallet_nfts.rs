@@ -639,7 +639,11 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 	/// Proof: `Nfts::Account` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::PendingSwapOf` (r:0 w:1)
 	/// Proof: `Nfts::PendingSwapOf` (`max_values`: None, `max_size`: Some(71), added: 2546, mode: `MaxEncodedLen`)
-	fn buy_item() -> Weight {
+	/// Not benchmarked with royalties yet, to be regenerated with the benchmark bot. Bounded by the
+	/// weight measured without royalties, the read of `CollectionRoyaltyOf` and one
+	/// `transfer_keep_alive` per royalty recipient.
+	/// The range of component `r` is `[0, 10]`.
+	fn buy_item(r: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `632`
 		//  Estimated: `4326`
@@ -648,6 +652,12 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 			.saturating_add(Weight::from_parts(0, 4326))
 			.saturating_add(T::DbWeight::get().reads(6))
 			.saturating_add(T::DbWeight::get().writes(5))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(Weight::from_parts(0, 2836))
+			.saturating_add(
+				<crate::weights::pallet_balances::WeightInfo<T> as pallet_balances::WeightInfo>::transfer_keep_alive()
+					.saturating_mul(r.into())
+			)
 	}
 	/// The range of component `n` is `[0, 10]`.
 	fn pay_tips(n: u32, ) -> Weight {
@@ -704,7 +714,11 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 	/// Proof: `Nfts::Account` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::ItemPriceOf` (r:0 w:2)
 	/// Proof: `Nfts::ItemPriceOf` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
-	fn claim_swap() -> Weight {
+	/// Not benchmarked with royalties yet, to be regenerated with the benchmark bot. Bounded by the
+	/// weight measured without royalties, the read of `CollectionRoyaltyOf` and one
+	/// `transfer_keep_alive` per royalty recipient.
+	/// The range of component `r` is `[0, 10]`.
+	fn claim_swap(r: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `761`
 		//  Estimated: `7662`
@@ -713,6 +727,12 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 			.saturating_add(Weight::from_parts(0, 7662))
 			.saturating_add(T::DbWeight::get().reads(9))
 			.saturating_add(T::DbWeight::get().writes(10))
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(Weight::from_parts(0, 2836))
+			.saturating_add(
+				<crate::weights::pallet_balances::WeightInfo<T> as pallet_balances::WeightInfo>::transfer_keep_alive()
+					.saturating_mul(r.into())
+			)
 	}
 	/// Storage: `Nfts::CollectionRoleOf` (r:2 w:0)
 	/// Proof: `Nfts::CollectionRoleOf` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
@@ -776,4 +796,9 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 2954).saturating_mul(n.into()))
 	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by
+	/// `set_collection_metadata`, which also reads the collection and writes a single entry.
+	fn set_collection_royalty() -> Weight {
+		<Self as pallet_nfts::WeightInfo>::set_collection_metadata()
+	}
 }
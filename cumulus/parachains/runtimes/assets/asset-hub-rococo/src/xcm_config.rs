@@ -28,7 +28,7 @@ use frame_support::{
 	traits::{
 		fungible::HoldConsideration,
 		tokens::imbalance::{ResolveAssetTo, ResolveTo},
-		ConstU32, Contains, Equals, Everything, LinearStoragePrice, PalletInfoAccess,
+		ConstU32, Contains, Disabled, Equals, Everything, LinearStoragePrice, PalletInfoAccess,
 	},
 };
 use frame_system::EnsureRoot;
@@ -483,6 +483,9 @@ impl pallet_xcm::Config for Runtime {
 		AuthorizeAliasHoldReason,
 		LinearStoragePrice<DepositPerItem, DepositPerByte, Balance>,
 	>;
+	type AssetTrapConsideration = Disabled;
+	type AssetTrapExpiry = ();
+	type RecurringTransferConsideration = Disabled;
 }
 
 impl cumulus_pallet_xcm::Config for Runtime {
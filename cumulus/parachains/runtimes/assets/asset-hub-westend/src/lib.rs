@@ -45,6 +45,7 @@ use assets_common::{
 use bp_asset_hub_westend::CreateForeignAssetDeposit;
 use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use cumulus_pallet_parachain_system::{RelayNumberMonotonicallyIncreases, RelaychainDataProvider};
+use cumulus_pallet_xcmp_queue::lanes::{LaneServiceWeight, LaneServiceWeights};
 use cumulus_primitives_core::{
 	relay_chain::AccountIndex, AggregateMessageOrigin, ClaimQueueOffset, CoreSelector, ParaId,
 };
@@ -492,7 +493,9 @@ parameter_types! {
 }
 
 impl pallet_vesting::Config for Runtime {
+	type TransferPolicyOrigin = EnsureRoot<AccountId>;
 	const MAX_VESTING_SCHEDULES: u32 = 100;
+	const MAX_VESTING_PAGES: u32 = 4;
 	type BlockNumberProvider = RelaychainDataProvider<Runtime>;
 	type BlockNumberToBalance = ConvertInto;
 	type Currency = Balances;
@@ -899,6 +902,19 @@ impl parachain_info::Config for Runtime {}
 
 parameter_types! {
 	pub MessageQueueServiceWeight: Weight = Perbill::from_percent(35) * RuntimeBlockWeights::get().max_block;
+	/// A quarter of the message service weight is kept for the relay chain, and other parachains
+	/// can use at most half of it.
+	pub MessageQueueLaneServiceWeights: LaneServiceWeights = LaneServiceWeights {
+		critical: LaneServiceWeight {
+			reserved: Perbill::from_percent(25) * MessageQueueServiceWeight::get(),
+			limit: None,
+		},
+		system: Default::default(),
+		regular: LaneServiceWeight {
+			reserved: Weight::zero(),
+			limit: Some(Perbill::from_percent(50) * MessageQueueServiceWeight::get()),
+		},
+	};
 }
 
 impl pallet_message_queue::Config for Runtime {
@@ -909,10 +925,15 @@ impl pallet_message_queue::Config for Runtime {
 		cumulus_primitives_core::AggregateMessageOrigin,
 	>;
 	#[cfg(not(feature = "runtime-benchmarks"))]
-	type MessageProcessor = xcm_builder::ProcessXcmMessage<
-		AggregateMessageOrigin,
-		xcm_executor::XcmExecutor<xcm_config::XcmConfig>,
-		RuntimeCall,
+	type MessageProcessor = cumulus_pallet_xcmp_queue::lanes::LaneServiceWeightLimiter<
+		Runtime,
+		xcm_builder::ProcessXcmMessage<
+			AggregateMessageOrigin,
+			xcm_executor::XcmExecutor<xcm_config::XcmConfig>,
+			RuntimeCall,
+		>,
+		cumulus_pallet_xcmp_queue::lanes::SystemParachainLanes<Nothing>,
+		MessageQueueLaneServiceWeights,
 	>;
 	type Size = u32;
 	// The XCMP queue pallet is only ever able to handle the `Sibling(ParaId)` origin:
@@ -1101,6 +1122,10 @@ parameter_types! {
 	pub const NftsMetadataDepositBase: Balance = UniquesMetadataDepositBase::get();
 	pub const NftsAttributeDepositBase: Balance = UniquesAttributeDepositBase::get();
 	pub const NftsDepositPerByte: Balance = UniquesDepositPerByte::get();
+	// The account id and the share of a recipient.
+	pub const NftsRoyaltyDepositPerRecipient: Balance = deposit(0, 34);
+	// A quarter of the sale price.
+	pub const NftsMaxRoyalty: u16 = 2_500;
 }
 
 impl pallet_nfts::Config for Runtime {
@@ -1116,6 +1141,7 @@ impl pallet_nfts::Config for Runtime {
 	type MetadataDepositBase = NftsMetadataDepositBase;
 	type AttributeDepositBase = NftsAttributeDepositBase;
 	type DepositPerByte = NftsDepositPerByte;
+	type RoyaltyDepositPerRecipient = NftsRoyaltyDepositPerRecipient;
 	type StringLimit = ConstU32<256>;
 	type KeyLimit = ConstU32<64>;
 	type ValueLimit = ConstU32<256>;
@@ -1124,6 +1150,8 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = ConstU32<10>;
 	type MaxDeadlineDuration = NftsMaxDeadlineDuration;
 	type MaxAttributesPerCall = ConstU32<10>;
+	type MaxRoyaltyRecipients = ConstU32<10>;
+	type MaxRoyalty = NftsMaxRoyalty;
 	type Features = NftsPalletFeatures;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as Verify>::Signer;
@@ -1936,6 +1964,26 @@ pallet_revive::impl_runtime_apis_plus_revive!(
 		}
 	}
 
+	impl xcm_runtime_apis::transfer_fees::TransferFeesApi<Block, RuntimeCall, OriginCaller> for Runtime {
+		fn estimate_transfer_fees(
+			origin: OriginCaller,
+			call: RuntimeCall,
+			fee_asset: VersionedAssetId,
+			destination_fee_infos: Vec<(VersionedLocation, xcm_runtime_apis::transfer_fees::DestinationFeeInfo)>,
+			result_xcms_version: XcmVersion,
+		) -> Result<xcm_runtime_apis::transfer_fees::TransferFeeEstimate, xcm_runtime_apis::transfer_fees::Error> {
+			type Trader = <xcm_config::XcmConfig as xcm_executor::Config>::Trader;
+
+			PolkadotXcm::estimate_transfer_fees::<xcm_config::XcmRouter, OriginCaller, RuntimeCall, Trader>(
+				origin,
+				call,
+				fee_asset,
+				destination_fee_infos,
+				result_xcms_version,
+			)
+		}
+	}
+
 	impl xcm_runtime_apis::conversions::LocationToAccountApi<Block, AccountId> for Runtime {
 		fn convert_location(location: VersionedLocation) -> Result<
 			AccountId,
@@ -1972,6 +2020,19 @@ pallet_revive::impl_runtime_apis_plus_revive!(
 		}
 	}
 
+	impl xcm_runtime_apis::trapped_assets::TrappedAssetsApi<Block, BlockNumber> for Runtime {
+		fn trapped_assets(
+			origin: VersionedLocation,
+			start_after: Option<sp_core::H256>,
+			max: u32,
+		) -> Result<
+			Vec<xcm_runtime_apis::trapped_assets::TrappedAssets<BlockNumber>>,
+			xcm_runtime_apis::trapped_assets::Error
+		> {
+			PolkadotXcm::trapped_assets(origin, start_after, max)
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi<Block, Balance, RuntimeCall>
 		for Runtime
 	{
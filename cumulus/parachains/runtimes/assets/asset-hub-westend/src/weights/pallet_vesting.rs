@@ -262,4 +262,29 @@ impl<T: frame_system::Config> pallet_vesting::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by
+	/// `force_remove_vesting_schedule` on the source account, `force_vested_transfer` to the target
+	/// account and the read of `Vesting::TransferPolicy`.
+	/// The range of component `l` is `[0, 49]`.
+	/// The range of component `s` is `[1, 100]`.
+	fn transfer_vesting(l: u32, s: u32, ) -> Weight {
+		<Self as pallet_vesting::WeightInfo>::force_remove_vesting_schedule(l, s)
+			.saturating_add(<Self as pallet_vesting::WeightInfo>::force_vested_transfer(l, s))
+			.saturating_add(Weight::from_parts(0, 496))
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. A single write of
+	/// `Vesting::TransferPolicy`, with a rough estimate of the execution time.
+	fn set_transfer_policy() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by `vest_unlocked`,
+	/// which decodes and writes as many schedules, and the proof sizes of `Vesting::VestingPages`
+	/// and `Vesting::VestingPagesInfo`.
+	/// The range of component `s` is `[1, 100]`.
+	fn update_overflow_page(s: u32, ) -> Weight {
+		<Self as pallet_vesting::WeightInfo>::vest_unlocked(0, s)
+			.saturating_add(Weight::from_parts(0, 8664))
+	}
 }
@@ -401,6 +401,55 @@ impl<T: frame_system::Config> pallet_xcm::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Shaped after the
+	/// `set_destination_fee_info` benchmark, which writes a single `DestinationFeeInfos` entry,
+	/// with a rough estimate of the execution time.
+	fn set_destination_fee_info() -> Weight {
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by `claim_assets`
+	/// and the accesses to the trap's record and its deposit.
+	fn claim_trapped_assets() -> Weight {
+		<Self as pallet_xcm::WeightInfo>::claim_assets()
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by `claim_assets`
+	/// and the accesses to the trap's record and its deposit.
+	fn sweep_trapped_assets() -> Weight {
+		<Self as pallet_xcm::WeightInfo>::claim_assets()
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Shaped after the
+	/// `drop_assets` benchmark, which traps assets trapped before: it reads `AssetTraps` twice and
+	/// updates it, and updates the trap's `AssetTrapRecords` entry and the hold of its deposit on
+	/// the depositor's account, with rough estimates of the execution time and proof size.
+	fn drop_assets() -> Weight {
+		Weight::from_parts(40_000_000, 10_000)
+			.saturating_add(T::DbWeight::get().reads(5))
+			.saturating_add(T::DbWeight::get().writes(4))
+	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by
+	/// `add_authorized_alias`, which also stores an entry and holds its deposit, and by the
+	/// accesses to the id counter and to the agendas the transfer may be scheduled in.
+	fn schedule_recurring_transfer() -> Weight {
+		<Self as pallet_xcm::WeightInfo>::add_authorized_alias()
+			.saturating_add(T::DbWeight::get().reads_writes(11, 2))
+	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by
+	/// `remove_authorized_alias`, which also removes an entry and releases its deposit, and by the
+	/// update of the agenda of the transfer.
+	fn cancel_recurring_transfer() -> Weight {
+		<Self as pallet_xcm::WeightInfo>::remove_authorized_alias()
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by
+	/// `transfer_assets` and by the accesses to the transfer and to the agendas its next transfer
+	/// may be scheduled in.
+	fn execute_recurring_transfer() -> Weight {
+		<Self as pallet_xcm::WeightInfo>::transfer_assets()
+			.saturating_add(T::DbWeight::get().reads_writes(11, 2))
+	}
 	fn weigh_message() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
@@ -35,13 +35,17 @@ use frame_support::{
 };
 use frame_system::EnsureRoot;
 use pallet_xcm::{AuthorizedAliasers, XcmPassthrough};
-use parachains_common::xcm_config::{
-	AllSiblingSystemParachains, ConcreteAssetFromSystem, RelayOrOtherSystemParachains,
+use parachains_common::{
+	xcm_config::{
+		AllSiblingSystemParachains, ConcreteAssetFromSystem, RelayOrOtherSystemParachains,
+	},
+	BlockNumber,
 };
 use polkadot_parachain_primitives::primitives::Sibling;
 use polkadot_runtime_common::xcm_sender::ExponentialPrice;
 use snowbridge_outbound_queue_primitives::v2::exporter::PausableExporter;
 use sp_runtime::traits::{AccountIdConversion, TryConvertInto};
+use testnet_parachains_constants::westend::time::DAYS;
 use westend_runtime_constants::{
 	system_parachain::{ASSET_HUB_ID, COLLECTIVES_ID},
 	xcm::body::FELLOWSHIP_ADMIN_INDEX,
@@ -548,6 +552,9 @@ pub type XcmRouter = WithUniqueTopic<(
 parameter_types! {
 	pub Collectives: Location = Location::new(1, [Parachain(COLLECTIVES_ID)]);
 	pub const AuthorizeAliasHoldReason: RuntimeHoldReason = RuntimeHoldReason::PolkadotXcm(pallet_xcm::HoldReason::AuthorizeAlias);
+	pub const AssetTrapHoldReason: RuntimeHoldReason = RuntimeHoldReason::PolkadotXcm(pallet_xcm::HoldReason::AssetTrap);
+	pub const AssetTrapExpiry: Option<BlockNumber> = Some(30 * DAYS);
+	pub const RecurringTransferHoldReason: RuntimeHoldReason = RuntimeHoldReason::PolkadotXcm(pallet_xcm::HoldReason::RecurringTransfer);
 }
 
 impl pallet_xcm::Config for Runtime {
@@ -585,6 +592,21 @@ impl pallet_xcm::Config for Runtime {
 		AuthorizeAliasHoldReason,
 		LinearStoragePrice<DepositPerItem, DepositPerByte, Balance>,
 	>;
+	// Trapped assets are recorded for listing with `TrappedAssetsApi` and claiming by hash, for a
+	// deposit taken from the sovereign account of the origin they are trapped for.
+	type AssetTrapConsideration = HoldConsideration<
+		AccountId,
+		Balances,
+		AssetTrapHoldReason,
+		LinearStoragePrice<DepositPerItem, DepositPerByte, Balance>,
+	>;
+	type AssetTrapExpiry = AssetTrapExpiry;
+	type RecurringTransferConsideration = HoldConsideration<
+		AccountId,
+		Balances,
+		RecurringTransferHoldReason,
+		LinearStoragePrice<DepositPerItem, DepositPerByte, Balance>,
+	>;
 }
 
 impl cumulus_pallet_xcm::Config for Runtime {
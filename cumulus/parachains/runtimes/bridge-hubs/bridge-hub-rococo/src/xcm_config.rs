@@ -25,8 +25,8 @@ use core::marker::PhantomData;
 use frame_support::{
 	parameter_types,
 	traits::{
-		fungible::HoldConsideration, tokens::imbalance::ResolveTo, ConstU32, Contains, Equals,
-		Everything, LinearStoragePrice, Nothing,
+		fungible::HoldConsideration, tokens::imbalance::ResolveTo, ConstU32, Contains, Disabled,
+		Equals, Everything, LinearStoragePrice, Nothing,
 	},
 };
 use frame_system::EnsureRoot;
@@ -301,6 +301,9 @@ impl pallet_xcm::Config for Runtime {
 		AuthorizeAliasHoldReason,
 		LinearStoragePrice<DepositPerItem, DepositPerByte, Balance>,
 	>;
+	type AssetTrapConsideration = Disabled;
+	type AssetTrapExpiry = ();
+	type RecurringTransferConsideration = Disabled;
 }
 
 impl cumulus_pallet_xcm::Config for Runtime {
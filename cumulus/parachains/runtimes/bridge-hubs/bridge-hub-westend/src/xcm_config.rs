@@ -24,8 +24,8 @@ use bridge_hub_common::DenyExportMessageFrom;
 use frame_support::{
 	parameter_types,
 	traits::{
-		fungible::HoldConsideration, tokens::imbalance::ResolveTo, ConstU32, Contains, Equals,
-		Everything, EverythingBut, LinearStoragePrice, Nothing,
+		fungible::HoldConsideration, tokens::imbalance::ResolveTo, ConstU32, Contains, Disabled,
+		Equals, Everything, EverythingBut, LinearStoragePrice, Nothing,
 	},
 };
 use frame_system::EnsureRoot;
@@ -310,6 +310,9 @@ impl pallet_xcm::Config for Runtime {
 		AuthorizeAliasHoldReason,
 		LinearStoragePrice<DepositPerItem, DepositPerByte, Balance>,
 	>;
+	type AssetTrapConsideration = Disabled;
+	type AssetTrapExpiry = ();
+	type RecurringTransferConsideration = Disabled;
 }
 
 impl cumulus_pallet_xcm::Config for Runtime {
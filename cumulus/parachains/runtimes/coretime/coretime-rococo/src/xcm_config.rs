@@ -286,6 +286,9 @@ impl pallet_xcm::Config for Runtime {
 	type RemoteLockConsumerIdentifier = ();
 	// Aliasing is disabled: xcm_executor::Config::Aliasers is set to `Nothing`.
 	type AuthorizedAliasConsideration = Disabled;
+	type AssetTrapConsideration = Disabled;
+	type AssetTrapExpiry = ();
+	type RecurringTransferConsideration = Disabled;
 }
 
 impl cumulus_pallet_xcm::Config for Runtime {
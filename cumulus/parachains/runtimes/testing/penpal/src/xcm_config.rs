@@ -47,8 +47,8 @@ use frame_support::{
 	parameter_types,
 	traits::{
 		fungible::HoldConsideration, tokens::imbalance::ResolveAssetTo, ConstU32, Contains,
-		ContainsPair, Equals, Everything, EverythingBut, Get, LinearStoragePrice, Nothing,
-		PalletInfoAccess,
+		ContainsPair, Disabled, Equals, Everything, EverythingBut, Get, LinearStoragePrice,
+		Nothing, PalletInfoAccess,
 	},
 	weights::Weight,
 };
@@ -507,6 +507,9 @@ impl pallet_xcm::Config for Runtime {
 		AuthorizeAliasHoldReason,
 		LinearStoragePrice<DepositPerItem, DepositPerByte, Balance>,
 	>;
+	type AssetTrapConsideration = Disabled;
+	type AssetTrapExpiry = ();
+	type RecurringTransferConsideration = Disabled;
 }
 
 impl cumulus_pallet_xcm::Config for Runtime {
@@ -82,10 +82,10 @@ pub use polkadot_runtime_parachains::inclusion::{AggregateMessageOrigin, UmpQueu
 pub use polkadot_parachain_primitives::primitives::RelayChainBlockNumber;
 use sp_core::{crypto::AccountId32, H256};
 pub use xcm::latest::prelude::{
-	AccountId32 as AccountId32Junction, Ancestor, Assets, Here, Location,
+	AccountId32 as AccountId32Junction, Ancestor, AssetId, Assets, Here, Location,
 	Parachain as ParachainJunction, Parent, WeightLimit, XcmHash,
 };
-pub use xcm_executor::traits::ConvertLocation;
+pub use xcm_executor::{conservation::ConservationViolation, traits::ConvertLocation};
 use xcm_simulator::helpers::TopicIdTracker;
 
 pub type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
@@ -113,6 +113,48 @@ thread_local! {
 	pub static INITIALIZED: RefCell<HashMap<String, bool>> = RefCell::new(HashMap::new());
 	/// Most recent `HeadData` of each parachain, encoded.
 	pub static LAST_HEAD: RefCell<HashMap<String, HashMap<u32, HeadData>>> = RefCell::new(HashMap::new());
+	/// Assets not conserved by the executed XCMs, each violation is: `(chain, violation)`.
+	/// `None` unless running within `check_conservation`.
+	#[allow(clippy::type_complexity)]
+	pub static CONSERVATION_VIOLATIONS: RefCell<Option<Vec<(&'static str, ConservationViolation)>>>
+		= RefCell::new(None);
+}
+
+/// Run `f`, checking the conservation of the assets moved by the XCMs executed on chains which
+/// declare their `CirculatingSupply`.
+///
+/// `execute_with` and `assert_expected_events!` panic as soon as an asset was not conserved.
+pub fn check_conservation<R>(f: impl FnOnce() -> R) -> R {
+	CONSERVATION_VIOLATIONS.with(|v| *v.borrow_mut() = Some(Vec::new()));
+	let r = f();
+	assert_assets_conserved();
+	CONSERVATION_VIOLATIONS.with(|v| *v.borrow_mut() = None);
+	r
+}
+
+/// Panic if an asset was not conserved by the XCMs executed within `check_conservation`.
+pub fn assert_assets_conserved() {
+	let violations = CONSERVATION_VIOLATIONS
+		.with(|v| v.borrow_mut().as_mut().map(core::mem::take))
+		.unwrap_or_default();
+	if !violations.is_empty() {
+		panic!("Assets were not conserved:\n{:#?}", violations);
+	}
+}
+
+/// Run `f`, recording the assets not conserved by the XCMs it executes on `C`.
+#[doc(hidden)]
+pub fn with_conservation_checks<C: Chain, R>(f: impl FnOnce() -> R) -> R {
+	if CONSERVATION_VIOLATIONS.with(|v| v.borrow().is_none()) {
+		return f()
+	}
+	let (r, violations) = xcm_executor::conservation::check_conservation(C::circulating_supply, f);
+	CONSERVATION_VIOLATIONS.with(|v| {
+		if let Some(all) = v.borrow_mut().as_mut() {
+			all.extend(violations.into_iter().map(|violation| (type_name::<C>(), violation)));
+		}
+	});
+	r
 }
 pub trait CheckAssertion<Origin, Destination, Hops, Args>
 where
@@ -252,6 +294,13 @@ pub trait Chain: TestExt {
 	fn account_data_of(account: AccountIdOf<Self::Runtime>) -> AccountData<Balance>;
 
 	fn events() -> Vec<<Self as Chain>::RuntimeEvent>;
+
+	/// The circulating supply of an asset, or `None` if its conservation is not checked.
+	///
+	/// See [`check_conservation`].
+	fn circulating_supply(_id: &AssetId) -> Option<u128> {
+		None
+	}
 }
 
 pub trait RelayChain: Chain {
@@ -379,6 +428,7 @@ macro_rules! decl_test_relay_chains {
 				runtime = $runtime:ident,
 				core = {
 					SovereignAccountOf: $sovereign_acc_of:path,
+					$( CirculatingSupply: $circulating_supply:path, )?
 				},
 				pallets = {
 					$($pallet_name:ident: $pallet_path:path,)*
@@ -411,6 +461,12 @@ macro_rules! decl_test_relay_chains {
 						.map(|record| record.event.clone())
 						.collect()
 				}
+
+				$(
+					fn circulating_supply(id: &$crate::AssetId) -> Option<u128> {
+						$circulating_supply(id)
+					}
+				)?
 			}
 
 			impl<N: $crate::Network> $crate::RelayChain for $name<N> {
@@ -553,7 +609,7 @@ macro_rules! __impl_test_ext_for_relay_chain {
 				// Execute
 				let r = $local_ext.with(|v| {
 					$crate::log::info!(target: "xcm::emulator::execute_with", "Executing as {}", stringify!($name));
-					v.borrow_mut().execute_with(execute)
+					v.borrow_mut().execute_with(|| $crate::with_conservation_checks::<Self, _>(execute))
 				});
 
 				// Send messages if needed
@@ -587,6 +643,7 @@ macro_rules! __impl_test_ext_for_relay_chain {
 				});
 
 				<$network>::process_messages();
+				$crate::assert_assets_conserved();
 
 				r
 			}
@@ -594,7 +651,7 @@ macro_rules! __impl_test_ext_for_relay_chain {
 			fn ext_wrapper<R>(func: impl FnOnce() -> R) -> R {
 				$local_ext.with(|v| {
 					v.borrow_mut().execute_with(|| {
-						func()
+						$crate::with_conservation_checks::<Self, _>(func)
 					})
 				})
 			}
@@ -618,6 +675,7 @@ macro_rules! decl_test_parachains {
 					MessageOrigin: $message_origin:path,
 					$( DigestProvider: $digest_provider:ty,)?
 					$( AdditionalInherentCode: $additional_inherent_code:ty,)?
+					$( CirculatingSupply: $circulating_supply:path, )?
 				},
 				pallets = {
 					$($pallet_name:ident: $pallet_path:path,)*
@@ -650,6 +708,12 @@ macro_rules! decl_test_parachains {
 						.map(|record| record.event.clone())
 						.collect()
 				}
+
+				$(
+					fn circulating_supply(id: &$crate::AssetId) -> Option<u128> {
+						$circulating_supply(id)
+					}
+				)?
 			}
 
 			impl<N: $crate::Network> $crate::Parachain for $name<N> {
@@ -905,7 +969,7 @@ macro_rules! __impl_test_ext_for_parachain {
 				// Execute
 				let r = $local_ext.with(|v| {
 					$crate::log::info!(target: "xcm::emulator::execute_with", "Executing as {}", stringify!($name));
-					v.borrow_mut().execute_with(execute)
+					v.borrow_mut().execute_with(|| $crate::with_conservation_checks::<Self, _>(execute))
 				});
 
 				// Finalize the block
@@ -964,6 +1028,7 @@ macro_rules! __impl_test_ext_for_parachain {
 				// normally this would come through the `set_validation_data`,
 				// but we go around that.
 				<$network>::process_messages();
+				$crate::assert_assets_conserved();
 
 				r
 			}
@@ -971,7 +1036,7 @@ macro_rules! __impl_test_ext_for_parachain {
 			fn ext_wrapper<R>(func: impl FnOnce() -> R) -> R {
 				$local_ext.with(|v| {
 					v.borrow_mut().execute_with(|| {
-						func()
+						$crate::with_conservation_checks::<Self, _>(func)
 					})
 				})
 			}
@@ -1324,6 +1389,8 @@ macro_rules! __impl_check_assertion {
 #[macro_export]
 macro_rules! assert_expected_events {
     ( $chain:ident, vec![$( $event_pat:pat => { $($attr:ident : $condition:expr, )* }, )*] ) => {
+		$crate::assert_assets_conserved();
+
 		let mut messages: Vec<String> = Vec::new();
 		let mut events = <$chain as $crate::Chain>::events();
 
@@ -64,6 +64,209 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Re-validate a parachain candidate offline.
+	///
+	/// The candidate is looked up in the candidate events of the relay chain and validated with
+	/// the PoV held by the local availability store, using the same PVF workers as the node. The
+	/// node must not be running.
+	ValidateCandidate(ValidateCandidateCmd),
+
+	/// Inspect the disputes stored by the dispute coordinator.
+	///
+	/// Lists the recent disputes with their status and optionally the votes cast on them. The
+	/// node must not be running.
+	InspectDisputes(InspectDisputesCmd),
+
+	/// Inspect the approval state of unfinalized relay chain blocks.
+	///
+	/// Renders the assignments, approvals and no-shows stored by approval-voting for the
+	/// candidates of each block and optionally replays the approval check. The node must not be
+	/// running.
+	InspectApprovals(InspectApprovalsCmd),
+
+	/// Export the availability data of a range of relay chain blocks to files.
+	///
+	/// Writes the receipts, available data and erasure chunks held by the local availability
+	/// store for the candidates included by the blocks, SCALE encoded. The node must not be
+	/// running.
+	ExportAvailability(ExportAvailabilityCmd),
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, Parser)]
+pub struct ValidateCandidateCmd {
+	/// The hash of the candidate to validate.
+	#[arg(value_name = "CANDIDATE_HASH")]
+	pub candidate_hash: sp_core::H256,
+
+	/// The relay chain block to start searching for the candidate at.
+	///
+	/// Defaults to the best block.
+	#[arg(long, value_name = "HASH")]
+	pub at: Option<sp_core::H256>,
+
+	/// The number of ancestors of the starting block to search for the candidate.
+	#[arg(long, default_value_t = 256)]
+	pub search_depth: u32,
+
+	/// Path to the directory where auxiliary worker binaries reside.
+	///
+	/// If not specified, the main binary's directory is searched first, then
+	/// `/usr/lib/polkadot` is searched.
+	#[arg(long, value_name = "PATH")]
+	pub workers_path: Option<PathBuf>,
+
+	/// TESTING ONLY: disable the version check between the node and the workers.
+	#[arg(long, hide = true)]
+	pub disable_worker_version_check: bool,
+
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[clap(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for ValidateCandidateCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, Parser)]
+pub struct InspectDisputesCmd {
+	/// Only list the disputes which are still active.
+	#[arg(long)]
+	pub active: bool,
+
+	/// Only list the disputes of the given session.
+	#[arg(long, value_name = "SESSION")]
+	pub session: Option<u32>,
+
+	/// Only list the disputes of the given candidate.
+	#[arg(long, value_name = "CANDIDATE_HASH")]
+	pub candidate: Option<sp_core::H256>,
+
+	/// Print the votes cast on the listed disputes.
+	#[arg(long)]
+	pub votes: bool,
+
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for InspectDisputesCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, Parser)]
+pub struct InspectApprovalsCmd {
+	/// Only inspect the given block.
+	#[arg(long, value_name = "HASH", conflicts_with = "number")]
+	pub block: Option<sp_core::H256>,
+
+	/// Only inspect the blocks at the given height.
+	#[arg(long, value_name = "NUMBER")]
+	pub number: Option<u32>,
+
+	/// Replay the approval check of the candidates, with the session parameters of the runtime.
+	#[arg(long)]
+	pub replay: bool,
+
+	/// The tick, in units of 500ms since the UNIX epoch, to replay the approval check at.
+	///
+	/// Defaults to the current tick.
+	#[arg(long, value_name = "TICK", requires = "replay")]
+	pub tick: Option<u64>,
+
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[clap(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for InspectApprovalsCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, Parser)]
+pub struct ExportAvailabilityCmd {
+	/// The first block of the range to export.
+	#[arg(long, value_name = "NUMBER")]
+	pub from: u32,
+
+	/// The last block of the range to export.
+	#[arg(long, value_name = "NUMBER")]
+	pub to: u32,
+
+	/// The directory to write the files to.
+	#[arg(long, value_name = "PATH")]
+	pub output: PathBuf,
+
+	/// Only export the available data, not the erasure chunks.
+	#[arg(long)]
+	pub no_chunks: bool,
+
+	#[clap(flatten)]
+	pub shared_params: sc_cli::SharedParams,
+
+	#[clap(flatten)]
+	pub pruning_params: sc_cli::PruningParams,
+
+	#[clap(flatten)]
+	pub database_params: sc_cli::DatabaseParams,
+}
+
+impl sc_cli::CliConfiguration for ExportAvailabilityCmd {
+	fn shared_params(&self) -> &sc_cli::SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&sc_cli::PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&sc_cli::DatabaseParams> {
+		Some(&self.database_params)
+	}
 }
 
 #[allow(missing_docs)]
@@ -155,10 +358,63 @@ pub struct RunCmd {
 	pub disable_worker_version_check: bool,
 
 	/// How long finalized data should be kept in the availability store (in hours).
-	/// Only used for testnets. If not specified, set to 1 hour. Always set to 25 hours for live
-	/// networks.
+	/// If not specified, set to 1 hour for testnets and 25 hours for live networks. Live networks
+	/// never keep finalized data for less than 25 hours.
 	#[arg(long)]
 	pub keep_finalized_for: Option<u32>,
+
+	/// How long the full available data (PoV) of finalized candidates should be kept in the
+	/// availability store (in hours).
+	///
+	/// The erasure chunks are kept for `--keep-finalized-for`, the available data can still be
+	/// recovered from them. If not specified, the available data is kept as long as the chunks.
+	#[arg(long)]
+	pub keep_finalized_pov_for: Option<u32>,
+
+	/// Limit the disk space used by the availability store (in MiB).
+	///
+	/// When the limit is exceeded, the data of candidates finalized more than 25 hours ago (1 hour
+	/// on testnets) is evicted, least recently used first. Data which must still be kept is never
+	/// evicted, so the limit may be exceeded.
+	#[arg(long, value_name = "MiB")]
+	pub availability_disk_quota: Option<u64>,
+
+	/// Disable the watchdog reporting subsystems which stopped processing their messages.
+	#[arg(long)]
+	pub disable_subsystem_watchdog: bool,
+
+	/// For how long a subsystem may not process any message or signal while some are pending
+	/// before it is reported as stalled (in seconds). Defaults to 30 seconds.
+	#[arg(long, value_name = "SECONDS")]
+	pub subsystem_stall_timeout: Option<u64>,
+
+	/// The stall timeout of a single subsystem, as `<subsystem>=<seconds>`, overriding
+	/// `--subsystem-stall-timeout`.
+	///
+	/// Subsystems are named as in the logs of the overseer, e.g.
+	/// `approval-voting-parallel-subsystem=60`. Can be given multiple times.
+	#[arg(long, value_name = "SUBSYSTEM=SECONDS", value_parser = parse_subsystem_stall_timeout)]
+	pub subsystem_stall_timeout_for: Vec<(String, u64)>,
+
+	/// Rate at which the budget of a peer for answers to its candidate requests is refilled (in
+	/// KiB per second). Defaults to 512 KiB/s.
+	///
+	/// Requests of peers which used up their budget are refused until it recovers.
+	#[arg(long, value_name = "KiB/s")]
+	pub statement_response_rate: Option<u32>,
+
+	/// Budget of a peer for answers to its candidate requests when it hasn't sent any for a
+	/// while (in KiB). Defaults to 4 MiB.
+	#[arg(long, value_name = "KiB")]
+	pub statement_response_burst: Option<u32>,
+}
+
+fn parse_subsystem_stall_timeout(s: &str) -> Result<(String, u64), String> {
+	let (subsystem, seconds) = s
+		.split_once('=')
+		.ok_or_else(|| format!("expected `<subsystem>=<seconds>`, got `{s}`"))?;
+	let seconds = seconds.parse().map_err(|e| format!("invalid number of seconds: {e}"))?;
+	Ok((subsystem.to_owned(), seconds))
 }
 
 #[allow(missing_docs)]
@@ -23,6 +23,7 @@ use log::info;
 use polkadot_service::{
 	self,
 	benchmarking::{benchmark_inherent_data, TransferKeepAliveBuilder},
+	overseer::{ResponseBandwidthLimits, StallThresholds, WatchdogConfig},
 	HeaderBackend, IdentifyVariant,
 };
 #[cfg(feature = "pyroscope")]
@@ -34,6 +35,7 @@ use sp_keyring::Sr25519Keyring;
 pub use crate::error::Error;
 #[cfg(feature = "pyroscope")]
 use std::net::ToSocketAddrs;
+use std::time::Duration;
 
 type Result<T> = std::result::Result<T, Error>;
 
@@ -203,6 +205,31 @@ where
 
 	let secure_validator_mode = cli.run.base.validator && !cli.run.insecure_validator;
 
+	let overseer_watchdog = {
+		let mut watchdog = WatchdogConfig::default();
+		watchdog.enabled = !cli.run.disable_subsystem_watchdog;
+		if let Some(seconds) = cli.run.subsystem_stall_timeout {
+			watchdog.thresholds.max_idle = Duration::from_secs(seconds);
+		}
+		for (subsystem, seconds) in &cli.run.subsystem_stall_timeout_for {
+			let thresholds =
+				StallThresholds { max_idle: Duration::from_secs(*seconds), ..watchdog.thresholds };
+			watchdog = watchdog.with_subsystem_thresholds(subsystem.clone(), thresholds);
+		}
+		watchdog
+	};
+
+	let statement_response_limits = {
+		let mut limits = ResponseBandwidthLimits::default();
+		if let Some(kib) = cli.run.statement_response_rate {
+			limits.rate = kib.saturating_mul(1024);
+		}
+		if let Some(kib) = cli.run.statement_response_burst {
+			limits.burst = kib.saturating_mul(1024);
+		}
+		limits
+	};
+
 	runner.run_node_until_exit(move |config| async move {
 		let hwbench = (!cli.run.no_hardware_benchmarks)
 			.then(|| {
@@ -235,6 +262,13 @@ where
 				prepare_workers_hard_max_num: cli.run.prepare_workers_hard_max_num,
 				prepare_workers_soft_max_num: cli.run.prepare_workers_soft_max_num,
 				keep_finalized_for: cli.run.keep_finalized_for,
+				keep_finalized_pov_for: cli.run.keep_finalized_pov_for,
+				availability_disk_quota: cli
+					.run
+					.availability_disk_quota
+					.map(|mib| mib.saturating_mul(1024 * 1024)),
+				overseer_watchdog,
+				statement_response_limits,
 			},
 		)
 		.map(|full| full.task_manager)?;
@@ -468,6 +502,98 @@ pub fn run() -> Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			Ok(runner.sync_run(|config| cmd.run::<polkadot_service::Block>(&config))?)
 		},
+		Some(Subcommand::ValidateCandidate(cmd)) => {
+			use polkadot_service::validate_candidate::{
+				validate_candidate, CandidateHash, ValidateCandidateParams,
+			};
+
+			let runner = cli.create_runner(cmd)?;
+			let chain_spec = &runner.config().chain_spec;
+
+			set_default_ss58_version(chain_spec);
+
+			let params = ValidateCandidateParams {
+				candidate_hash: CandidateHash(cmd.candidate_hash),
+				at: cmd.at,
+				search_depth: cmd.search_depth,
+				workers_path: cmd.workers_path.clone(),
+				workers_names: None,
+				node_version: (!cmd.disable_worker_version_check).then(|| NODE_VERSION.to_string()),
+			};
+
+			Ok(runner.async_run(|mut config| {
+				let (client, _, _, task_manager) = polkadot_service::new_chain_ops(&mut config)?;
+				let run = async move {
+					let report = validate_candidate(client, &config, params).await?;
+					print_validation_report(&report);
+					Ok::<_, Error>(())
+				};
+				Ok((run, task_manager))
+			})?)
+		},
+		Some(Subcommand::InspectDisputes(cmd)) => {
+			use polkadot_service::inspect_disputes::{
+				inspect_disputes, CandidateHash, InspectDisputesParams,
+			};
+
+			let runner = cli.create_runner(cmd)?;
+			let params = InspectDisputesParams {
+				active_only: cmd.active,
+				session: cmd.session,
+				candidate_hash: cmd.candidate.map(CandidateHash),
+				with_votes: cmd.votes,
+			};
+
+			Ok(runner.sync_run(|config| {
+				let report = inspect_disputes(&config, params)?;
+				print_disputes_report(&report);
+				Ok::<_, Error>(())
+			})?)
+		},
+		Some(Subcommand::InspectApprovals(cmd)) => {
+			use polkadot_service::inspect_approvals::{inspect_approvals, InspectApprovalsParams};
+
+			let runner = cli.create_runner(cmd)?;
+			let params = InspectApprovalsParams {
+				block_hash: cmd.block,
+				block_number: cmd.number,
+				replay: cmd.replay,
+				tick: cmd.tick,
+			};
+
+			Ok(runner.async_run(|mut config| {
+				let (client, _, _, task_manager) = polkadot_service::new_chain_ops(&mut config)?;
+				let run = async move {
+					let report = inspect_approvals(client, &config, params)?;
+					print_approvals_report(&report);
+					Ok::<_, Error>(())
+				};
+				Ok((run, task_manager))
+			})?)
+		},
+		Some(Subcommand::ExportAvailability(cmd)) => {
+			use polkadot_service::export_availability::{
+				export_availability, ExportAvailabilityParams,
+			};
+
+			let runner = cli.create_runner(cmd)?;
+			let params = ExportAvailabilityParams {
+				from: cmd.from,
+				to: cmd.to,
+				output: cmd.output.clone(),
+				with_chunks: !cmd.no_chunks,
+			};
+
+			Ok(runner.async_run(|mut config| {
+				let (client, _, _, task_manager) = polkadot_service::new_chain_ops(&mut config)?;
+				let run = async move {
+					let report = export_availability(client, &config, params)?;
+					print_export_report(&report);
+					Ok::<_, Error>(())
+				};
+				Ok((run, task_manager))
+			})?)
+		},
 	}?;
 
 	#[cfg(feature = "pyroscope")]
@@ -477,3 +603,137 @@ pub fn run() -> Result<()> {
 	}
 	Ok(())
 }
+
+fn print_validation_report(report: &polkadot_service::validate_candidate::ValidateCandidateReport) {
+	use polkadot_node_primitives::ValidationResult;
+
+	let descriptor = &report.candidate_receipt.descriptor;
+	println!("Candidate:     {:?}", report.candidate_receipt.hash());
+	println!("Para:          {}", descriptor.para_id());
+	println!("Relay parent:  {:?}", descriptor.relay_parent());
+	println!("Found at:      {:?}", report.found_at);
+	println!("Session:       {}", report.session_index);
+	match &report.result {
+		Ok(ValidationResult::Valid(..)) => println!("Result:        valid"),
+		Ok(ValidationResult::Invalid(reason)) => println!("Result:        invalid ({reason:?})"),
+		Err(err) => println!("Result:        validation failed ({})", err.0),
+	}
+	println!("Elapsed:       {} ms", report.elapsed.as_millis());
+	for (name, value) in &report.stats {
+		println!("{name}: {value}");
+	}
+}
+
+fn print_disputes_report(report: &polkadot_service::inspect_disputes::InspectDisputesReport) {
+	use polkadot_service::inspect_disputes::DisputeStatus;
+
+	match report.earliest_session {
+		Some(session) => println!("Earliest session: {session}"),
+		None => println!("Earliest session: none"),
+	}
+	println!("Disputes:         {}", report.disputes.len());
+	for dispute in &report.disputes {
+		let status = match dispute.status {
+			DisputeStatus::Active => "active".to_string(),
+			DisputeStatus::Confirmed => "confirmed".to_string(),
+			DisputeStatus::ConcludedFor(at) => format!("concluded valid at {at}"),
+			DisputeStatus::ConcludedAgainst(at) => format!("concluded invalid at {at}"),
+		};
+		println!();
+		println!("Candidate:     {:?}", dispute.candidate_hash);
+		println!("Session:       {}", dispute.session);
+		println!("Status:        {status}");
+		if let Some(votes) = &dispute.votes {
+			let descriptor = &votes.candidate_receipt.descriptor;
+			println!("Para:          {}", descriptor.para_id());
+			println!("Relay parent:  {:?}", descriptor.relay_parent());
+			for (validator, (kind, _)) in votes.valid.raw() {
+				println!("Valid:         validator {} ({kind:?})", validator.0);
+			}
+			for (validator, (kind, _)) in &votes.invalid {
+				println!("Invalid:       validator {} ({kind:?})", validator.0);
+			}
+		}
+	}
+}
+
+fn print_approvals_report(report: &polkadot_service::inspect_approvals::InspectApprovalsReport) {
+	use polkadot_service::inspect_approvals::{ApprovalCheckOutcome, RequiredAssignments};
+
+	match report.stored_blocks {
+		Some((earliest, latest)) => println!("Stored blocks: {earliest}..={latest}"),
+		None => println!("Stored blocks: none"),
+	}
+	for block in &report.blocks {
+		println!();
+		println!("Block:         #{} {:?}", block.block_number, block.block_hash);
+		println!("Session:       {}", block.session);
+		println!("Slot:          {}", block.slot);
+		println!("Approved:      {}", block.approved);
+		for candidate in &block.candidates {
+			println!();
+			println!("  Candidate:   {:?}", candidate.candidate_hash);
+			println!("  Para:        {}", candidate.para_id);
+			println!("  Core:        {}", candidate.core_index.0);
+			println!("  Backing:     group {}", candidate.backing_group.0);
+			println!("  Approved:    {}", candidate.approved);
+			if let Some(tranche) = candidate.our_assignment {
+				println!("  Our tranche: {tranche}");
+			}
+			for (tranche, assignments) in &candidate.tranches {
+				let assignments = assignments
+					.iter()
+					.map(|(validator, tick)| format!("{}@{tick}", validator.0))
+					.collect::<Vec<_>>();
+				println!("  Tranche {tranche}:   {}", assignments.join(", "));
+			}
+			let approvals = candidate.approvals.iter().map(|v| v.0.to_string()).collect::<Vec<_>>();
+			println!("  Approvals:   {}", approvals.join(", "));
+			if let Some(check) = &candidate.check {
+				let required = match check.required {
+					RequiredAssignments::All => "all validators".to_string(),
+					RequiredAssignments::Pending { considered, .. } =>
+						format!("pending, considered tranches up to {considered}"),
+					RequiredAssignments::Exact { needed, tolerated_missing, .. } =>
+						format!("tranches up to {needed}, {tolerated_missing} missing tolerated"),
+				};
+				let outcome = match check.outcome {
+					ApprovalCheckOutcome::Unapproved => "unapproved".to_string(),
+					ApprovalCheckOutcome::Approved(no_shows) =>
+						format!("approved with {no_shows} no-shows"),
+					ApprovalCheckOutcome::ApprovedOneThird => "approved by a third".to_string(),
+				};
+				let no_shows = check.no_shows.iter().map(|v| v.0.to_string()).collect::<Vec<_>>();
+				println!("  Check at:    tick {} (tranche {})", check.tick, check.tranche_now);
+				println!("  Required:    {required}");
+				println!("  No-shows:    {}", no_shows.join(", "));
+				println!("  Outcome:     {outcome}");
+			}
+		}
+	}
+}
+
+fn print_export_report(report: &polkadot_service::export_availability::ExportAvailabilityReport) {
+	for candidate in &report.candidates {
+		let available_data = if candidate.available_data { "available data" } else { "no data" };
+		println!(
+			"#{} {:?}  candidate {:?}  para {}  {}, {} chunks",
+			candidate.block_number,
+			candidate.block_hash,
+			candidate.candidate_hash.0,
+			candidate.para_id,
+			available_data,
+			candidate.chunks,
+		);
+	}
+	let exported = report
+		.candidates
+		.iter()
+		.filter(|candidate| candidate.available_data || candidate.chunks > 0)
+		.count();
+	println!(
+		"Exported {exported} of {} candidates included by {} blocks",
+		report.candidates.len(),
+		report.blocks
+	);
+}
@@ -354,6 +354,12 @@ async fn run_main_loop<Context>(
 								)
 							).await;
 						},
+						// Inspection requests are not urgent, they queue behind the regular work.
+						ApprovalVotingParallelMessage::InspectBlock(block_hash, tick, tx) => {
+							to_approval_voting_worker
+								.send_message(ApprovalVotingMessage::InspectBlock(block_hash, tick, tx))
+								.await;
+						},
 						// Now the message the approval distribution subsystem would've handled and need to
 						// be forwarded to the workers.
 						ApprovalVotingParallelMessage::NewBlocks(msg) => {
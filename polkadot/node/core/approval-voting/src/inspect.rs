@@ -0,0 +1,207 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Inspection of the approval state of relay chain blocks.
+//!
+//! Renders the assignments and approvals recorded for the candidates of a block and replays the
+//! approval check at a chosen tick, to find out why a block is not approved: missing assignments,
+//! no-shows or missing approvals.
+
+use polkadot_node_primitives::approval::{
+	time::{slot_number_to_tick, Tick},
+	v1::DelayTranche,
+};
+use polkadot_node_subsystem::{
+	messages::{
+		ApprovalCheckOutcome, ApprovalCheckState, BlockApprovalState, CandidateApprovalState,
+		RequiredAssignments,
+	},
+	SubsystemResult,
+};
+use polkadot_node_subsystem_util::database::Database;
+use polkadot_primitives::{BlockNumber, CandidateHash, Hash, ValidatorIndex};
+use sp_consensus_slots::Slot;
+use std::sync::Arc;
+
+use crate::{
+	approval_checking::{self, Check, RequiredTranches, TranchesToApproveResult},
+	approval_db::common::{Config as DatabaseConfig, DbBackend},
+	backend::Backend,
+	persisted_entries::{ApprovalEntry, BlockEntry, CandidateEntry},
+	Config,
+};
+
+/// The parameters of the approval check of a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ApprovalCheckParams {
+	/// The tick to run the check at.
+	pub tick: Tick,
+	/// The number of approvals needed, from the session info of the block.
+	pub needed_approvals: u32,
+	/// The number of slots after which an assigned validator which did not approve is a no-show,
+	/// from the session info of the block.
+	pub no_show_slots: u32,
+}
+
+/// Read-only access to the approval-voting database, for offline inspection.
+pub struct ApprovalDbReader {
+	backend: DbBackend,
+	slot_duration_millis: u64,
+}
+
+impl ApprovalDbReader {
+	/// Create a reader of the approval data stored in the given database.
+	pub fn new(store: Arc<dyn Database>, config: Config) -> Self {
+		Self {
+			backend: DbBackend::new(
+				store,
+				DatabaseConfig { col_approval_data: config.col_approval_data },
+			),
+			slot_duration_millis: config.slot_duration_millis,
+		}
+	}
+
+	/// The range of block numbers with stored approval data, if any.
+	pub fn stored_blocks(&self) -> SubsystemResult<Option<(BlockNumber, BlockNumber)>> {
+		Ok(self.backend.load_stored_blocks()?.map(|range| (range.0, range.1)))
+	}
+
+	/// The hashes of the blocks at the given height with stored approval data.
+	pub fn blocks_at_height(&self, height: BlockNumber) -> SubsystemResult<Vec<Hash>> {
+		self.backend.load_blocks_at_height(&height)
+	}
+
+	/// The approval state of the given block, with the outcome of the approval check if `check`
+	/// is given. `None` if the block is unknown.
+	pub fn block_approval_state(
+		&self,
+		block_hash: Hash,
+		check: Option<ApprovalCheckParams>,
+	) -> SubsystemResult<Option<BlockApprovalState>> {
+		let Some(block_entry) = self.backend.load_block_entry(&block_hash)? else {
+			return Ok(None)
+		};
+
+		block_approval_state(
+			&block_entry,
+			|candidate_hash| self.backend.load_candidate_entry(candidate_hash),
+			self.slot_duration_millis,
+			check,
+		)
+		.map(Some)
+	}
+}
+
+/// The approval state of the candidates of a block, see [`BlockApprovalState`].
+pub(crate) fn block_approval_state(
+	block_entry: &BlockEntry,
+	load_candidate_entry: impl Fn(&CandidateHash) -> SubsystemResult<Option<CandidateEntry>>,
+	slot_duration_millis: u64,
+	check: Option<ApprovalCheckParams>,
+) -> SubsystemResult<BlockApprovalState> {
+	let block_hash = block_entry.block_hash();
+
+	let mut candidates = Vec::with_capacity(block_entry.candidates().len());
+	for (core_index, candidate_hash) in block_entry.candidates() {
+		let Some(candidate_entry) = load_candidate_entry(candidate_hash)? else { continue };
+		let Some(approval_entry) = candidate_entry.approval_entry(&block_hash) else { continue };
+
+		candidates.push(CandidateApprovalState {
+			candidate_hash: *candidate_hash,
+			para_id: candidate_entry.candidate_receipt().descriptor.para_id(),
+			core_index: *core_index,
+			backing_group: approval_entry.backing_group(),
+			approved: block_entry.is_candidate_approved(candidate_hash),
+			tranches: approval_entry
+				.tranches()
+				.iter()
+				.map(|tranche| (tranche.tranche(), tranche.assignments().to_vec()))
+				.collect(),
+			approvals: candidate_entry
+				.approvals()
+				.iter_ones()
+				.map(|index| ValidatorIndex(index as _))
+				.collect(),
+			our_assignment: approval_entry.our_assignment().map(|assignment| assignment.tranche()),
+			check: check.map(|params| {
+				check_state(
+					block_entry,
+					&candidate_entry,
+					approval_entry,
+					slot_duration_millis,
+					params,
+				)
+			}),
+		});
+	}
+
+	Ok(BlockApprovalState {
+		block_hash,
+		block_number: block_entry.block_number(),
+		session: block_entry.session(),
+		slot: block_entry.slot().into(),
+		approved: block_entry.is_fully_approved(),
+		candidates,
+	})
+}
+
+/// Run the approval check of a candidate as the subsystem would at `params.tick`.
+fn check_state(
+	block_entry: &BlockEntry,
+	candidate_entry: &CandidateEntry,
+	approval_entry: &ApprovalEntry,
+	slot_duration_millis: u64,
+	params: ApprovalCheckParams,
+) -> ApprovalCheckState {
+	let block_tick = slot_number_to_tick(slot_duration_millis, block_entry.slot());
+	let no_show_duration =
+		slot_number_to_tick(slot_duration_millis, Slot::from(u64::from(params.no_show_slots)));
+	let tranche_now = params.tick.saturating_sub(block_tick) as DelayTranche;
+
+	let TranchesToApproveResult { required_tranches, no_show_validators, .. } =
+		approval_checking::tranches_to_approve(
+			approval_entry,
+			candidate_entry.approvals(),
+			tranche_now,
+			block_tick,
+			no_show_duration,
+			params.needed_approvals as _,
+		);
+	let outcome = match approval_checking::check_approval(
+		candidate_entry,
+		approval_entry,
+		required_tranches.clone(),
+	) {
+		Check::Unapproved => ApprovalCheckOutcome::Unapproved,
+		Check::Approved(no_shows, _) => ApprovalCheckOutcome::Approved(no_shows),
+		Check::ApprovedOneThird => ApprovalCheckOutcome::ApprovedOneThird,
+	};
+	let required = match required_tranches {
+		RequiredTranches::All => RequiredAssignments::All,
+		RequiredTranches::Pending { considered, next_no_show, .. } =>
+			RequiredAssignments::Pending { considered, next_no_show },
+		RequiredTranches::Exact { needed, tolerated_missing, next_no_show, .. } =>
+			RequiredAssignments::Exact { needed, tolerated_missing, next_no_show },
+	};
+
+	ApprovalCheckState {
+		tick: params.tick,
+		tranche_now,
+		required,
+		no_shows: no_show_validators,
+		outcome,
+	}
+}
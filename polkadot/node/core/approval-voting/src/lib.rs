@@ -37,10 +37,10 @@ use polkadot_node_subsystem::{
 	messages::{
 		ApprovalCheckError, ApprovalCheckResult, ApprovalDistributionMessage,
 		ApprovalVotingMessage, AssignmentCheckError, AssignmentCheckResult,
-		AvailabilityRecoveryMessage, BlockDescription, CandidateValidationMessage, ChainApiMessage,
-		ChainSelectionMessage, CheckedIndirectAssignment, CheckedIndirectSignedApprovalVote,
-		DisputeCoordinatorMessage, HighestApprovedAncestorBlock, PvfExecKind, RuntimeApiMessage,
-		RuntimeApiRequest,
+		AvailabilityRecoveryMessage, BlockApprovalState, BlockDescription,
+		CandidateValidationMessage, ChainApiMessage, ChainSelectionMessage,
+		CheckedIndirectAssignment, CheckedIndirectSignedApprovalVote, DisputeCoordinatorMessage,
+		HighestApprovedAncestorBlock, PvfExecKind, RuntimeApiMessage, RuntimeApiRequest,
 	},
 	overseer, FromOrchestra, OverseerSignal, SpawnedSubsystem, SubsystemError, SubsystemResult,
 	SubsystemSender,
@@ -101,9 +101,12 @@ pub mod approval_db;
 mod backend;
 pub mod criteria;
 mod import;
+mod inspect;
 mod ops;
 mod persisted_entries;
 
+pub use inspect::{ApprovalCheckParams, ApprovalDbReader};
+
 use crate::{
 	approval_checking::{Check, TranchesToApproveResult},
 	approval_db::common::{Config as DatabaseConfig, DbBackend},
@@ -2163,12 +2166,54 @@ async fn handle_from_overseer<
 				.await?;
 				Vec::new()
 			},
+			ApprovalVotingMessage::InspectBlock(block_hash, tick, tx) => {
+				let approval_state =
+					inspect_block(sender, state, db, session_info_provider, block_hash, tick)
+						.await?;
+				let _ = tx.send(approval_state);
+				Vec::new()
+			},
 		},
 	};
 
 	Ok(actions)
 }
 
+/// The approval state of a block, with the approval check replayed at `tick` or the current tick.
+///
+/// The check is omitted if the session info of the block is not available.
+async fn inspect_block<Sender: SubsystemSender<RuntimeApiMessage>>(
+	sender: &mut Sender,
+	state: &State,
+	db: &OverlayedBackend<'_, impl Backend>,
+	session_info_provider: &mut RuntimeInfo,
+	block_hash: Hash,
+	tick: Option<Tick>,
+) -> SubsystemResult<Option<BlockApprovalState>> {
+	let Some(block_entry) = db.load_block_entry(&block_hash)? else { return Ok(None) };
+
+	let check = get_session_info(
+		session_info_provider,
+		sender,
+		block_entry.parent_hash(),
+		block_entry.session(),
+	)
+	.await
+	.map(|session_info| ApprovalCheckParams {
+		tick: tick.unwrap_or_else(|| state.clock.tick_now()),
+		needed_approvals: session_info.needed_approvals,
+		no_show_slots: session_info.no_show_slots,
+	});
+
+	inspect::block_approval_state(
+		&block_entry,
+		|candidate_hash| db.load_candidate_entry(candidate_hash),
+		state.slot_duration_millis,
+		check,
+	)
+	.map(Some)
+}
+
 /// Retrieve approval signatures.
 ///
 /// This involves an unbounded message send to approval-distribution, the caller has to ensure that
@@ -34,7 +34,8 @@ use polkadot_node_primitives::{
 };
 use polkadot_node_subsystem::{
 	messages::{
-		AllMessages, ApprovalVotingMessage, AssignmentCheckResult, AvailabilityRecoveryMessage,
+		AllMessages, ApprovalCheckOutcome, ApprovalCheckState, ApprovalVotingMessage,
+		AssignmentCheckResult, AvailabilityRecoveryMessage,
 	},
 	ActiveLeavesUpdate, SubsystemContext,
 };
@@ -2828,6 +2829,110 @@ fn approved_ancestor_test(
 	});
 }
 
+async fn inspect_block(
+	overseer: &mut VirtualOverseer,
+	block_hash: Hash,
+) -> Option<BlockApprovalState> {
+	let (tx, rx) = oneshot::channel();
+	overseer_send(
+		overseer,
+		FromOrchestra::Communication {
+			msg: ApprovalVotingMessage::InspectBlock(block_hash, None, tx),
+		},
+	)
+	.await;
+	rx.await.unwrap()
+}
+
+#[test]
+fn subsystem_inspects_block_approval_state() {
+	test_harness(HarnessConfig::default(), |test_harness| async move {
+		let TestHarness { mut virtual_overseer, sync_oracle_handle: _sync_oracle_handle, .. } =
+			test_harness;
+
+		assert_matches!(
+			overseer_recv(&mut virtual_overseer).await,
+			AllMessages::ChainApi(ChainApiMessage::FinalizedBlockNumber(rx)) => {
+				rx.send(Ok(0)).unwrap();
+			}
+		);
+
+		let block_hash = Hash::repeat_byte(0x01);
+		let mut candidate_receipt = dummy_candidate_receipt_v2(block_hash);
+		candidate_receipt.descriptor.set_para_id(ParaId::from(1_u32));
+		let candidate_hash = candidate_receipt.hash();
+		let validator = ValidatorIndex(0);
+
+		ChainBuilder::new()
+			.add_block(
+				block_hash,
+				ChainBuilder::GENESIS_HASH,
+				1,
+				BlockConfig {
+					slot: Slot::from(1),
+					candidates: Some(vec![(candidate_receipt, CoreIndex(0), GroupIndex(0))]),
+					session_info: None,
+					end_syncing: false,
+				},
+			)
+			.build(&mut virtual_overseer)
+			.await;
+
+		assert_eq!(inspect_block(&mut virtual_overseer, Hash::repeat_byte(0x02)).await, None);
+
+		let rx = import_assignment(&mut virtual_overseer, block_hash, 0, validator, 0).await;
+		assert_eq!(rx.await, Ok(AssignmentCheckResult::Accepted));
+
+		let state = inspect_block(&mut virtual_overseer, block_hash).await.unwrap();
+		assert_eq!(state.block_number, 1);
+		assert_eq!(state.slot, 1);
+		assert!(!state.approved);
+		assert_matches!(&state.candidates[..], [candidate] => {
+			assert_eq!(candidate.candidate_hash, candidate_hash);
+			assert_eq!(candidate.para_id, ParaId::from(1_u32));
+			assert_eq!(candidate.core_index, CoreIndex(0));
+			assert!(!candidate.approved);
+			assert_matches!(&candidate.tranches[..], [(0, assignments)] => {
+				assert_eq!(assignments.iter().map(|(v, _)| *v).collect::<Vec<_>>(), vec![validator]);
+			});
+			assert!(candidate.approvals.is_empty());
+			assert_matches!(
+				candidate.check,
+				Some(ApprovalCheckState { outcome: ApprovalCheckOutcome::Unapproved, .. })
+			);
+		});
+
+		let rx = import_approval(
+			&mut virtual_overseer,
+			block_hash,
+			0,
+			validator,
+			candidate_hash,
+			1,
+			true,
+			None,
+		)
+		.await;
+		assert_eq!(rx.await, Ok(ApprovalCheckResult::Accepted));
+
+		let state = inspect_block(&mut virtual_overseer, block_hash).await.unwrap();
+		assert!(state.approved);
+		assert_matches!(&state.candidates[..], [candidate] => {
+			assert!(candidate.approved);
+			assert_eq!(candidate.approvals, vec![validator]);
+			assert_matches!(
+				candidate.check,
+				Some(ApprovalCheckState {
+					outcome: ApprovalCheckOutcome::Approved(_) | ApprovalCheckOutcome::ApprovedOneThird,
+					..
+				})
+			);
+		});
+
+		virtual_overseer
+	});
+}
+
 #[test]
 fn subsystem_approved_ancestor_all_approved() {
 	// Don't skip any approvals, highest approved ancestor should be 4.
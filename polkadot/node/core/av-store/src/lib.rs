@@ -67,6 +67,8 @@ const CHUNK_PREFIX: &[u8; 5] = b"chunk";
 const META_PREFIX: &[u8; 4] = b"meta";
 const UNFINALIZED_PREFIX: &[u8; 11] = b"unfinalized";
 const PRUNE_BY_TIME_PREFIX: &[u8; 13] = b"prune_by_time";
+const PRUNE_POV_BY_TIME_PREFIX: &[u8; 17] = b"prune_pov_by_time";
+const USAGE_PREFIX: &[u8; 5] = b"usage";
 
 // We have some keys we want to map to empty values because existence of the key is enough. We use
 // this because rocksdb doesn't support empty values.
@@ -296,6 +298,61 @@ fn write_pruning_key(
 	tx.put(config.col_meta, &key, TOMBSTONE_VALUE);
 }
 
+fn delete_pov_pruning_key(
+	tx: &mut DBTransaction,
+	config: &Config,
+	t: impl Into<BETimestamp>,
+	h: &CandidateHash,
+) {
+	let key = (PRUNE_POV_BY_TIME_PREFIX, t.into(), h).encode();
+	tx.delete(config.col_meta, &key);
+}
+
+fn write_pov_pruning_key(
+	tx: &mut DBTransaction,
+	config: &Config,
+	t: impl Into<BETimestamp>,
+	h: &CandidateHash,
+) {
+	let key = (PRUNE_POV_BY_TIME_PREFIX, t.into(), h).encode();
+	tx.put(config.col_meta, &key, TOMBSTONE_VALUE);
+}
+
+// The number of bytes stored for the candidate, only tracked if a disk quota is configured.
+fn load_usage(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	hash: &CandidateHash,
+) -> Result<Option<u64>, Error> {
+	let key = (USAGE_PREFIX, hash).encode();
+
+	query_inner(db, config.col_meta, &key)
+}
+
+fn write_usage(tx: &mut DBTransaction, config: &Config, hash: &CandidateHash, bytes: u64) {
+	let key = (USAGE_PREFIX, hash).encode();
+
+	tx.put_vec(config.col_meta, &key, bytes.encode());
+}
+
+fn delete_usage(tx: &mut DBTransaction, config: &Config, hash: &CandidateHash) {
+	let key = (USAGE_PREFIX, hash).encode();
+
+	tx.delete(config.col_meta, &key[..]);
+}
+
+fn add_usage(
+	db: &Arc<dyn Database>,
+	tx: &mut DBTransaction,
+	config: &Config,
+	hash: &CandidateHash,
+	bytes: u64,
+) -> Result<(), Error> {
+	let usage = load_usage(db, config, hash)?.unwrap_or(0);
+	write_usage(tx, config, hash, usage.saturating_add(bytes));
+	Ok(())
+}
+
 fn finalized_block_range(finalized: BlockNumber) -> (Vec<u8>, Vec<u8>) {
 	// We use big-endian encoding to iterate in ascending order.
 	let start = UNFINALIZED_PREFIX.encode();
@@ -322,6 +379,13 @@ fn pruning_range(now: impl Into<BETimestamp>) -> (Vec<u8>, Vec<u8>) {
 	(start, end)
 }
 
+fn pov_pruning_range(now: impl Into<BETimestamp>) -> (Vec<u8>, Vec<u8>) {
+	let start = PRUNE_POV_BY_TIME_PREFIX.encode();
+	let end = (PRUNE_POV_BY_TIME_PREFIX, BETimestamp(now.into().0 + 1)).encode();
+
+	(start, end)
+}
+
 fn decode_unfinalized_key(s: &[u8]) -> Result<(BlockNumber, Hash, CandidateHash), CodecError> {
 	if !s.starts_with(UNFINALIZED_PREFIX) {
 		return Err("missing magic string".into())
@@ -340,6 +404,23 @@ fn decode_pruning_key(s: &[u8]) -> Result<(Duration, CandidateHash), CodecError>
 		.map(|(t, ch)| (t.into(), ch))
 }
 
+fn decode_pov_pruning_key(s: &[u8]) -> Result<(Duration, CandidateHash), CodecError> {
+	if !s.starts_with(PRUNE_POV_BY_TIME_PREFIX) {
+		return Err("missing magic string".into())
+	}
+
+	<(BETimestamp, CandidateHash)>::decode(&mut &s[PRUNE_POV_BY_TIME_PREFIX.len()..])
+		.map(|(t, ch)| (t.into(), ch))
+}
+
+fn decode_usage_key(s: &[u8]) -> Result<CandidateHash, CodecError> {
+	if !s.starts_with(USAGE_PREFIX) {
+		return Err("missing magic string".into())
+	}
+
+	CandidateHash::decode(&mut &s[USAGE_PREFIX.len()..])
+}
+
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
 pub enum Error {
@@ -416,8 +497,24 @@ struct PruningConfig {
 	/// How long finalized data should be kept.
 	keep_finalized_for: Duration,
 
+	/// How long the full available data of finalized candidates should be kept. Not longer than
+	/// `keep_finalized_for`.
+	keep_finalized_pov_for: Duration,
+
 	/// How often to perform data pruning.
 	pruning_interval: Duration,
+
+	/// The maximum number of bytes of data to store, if any.
+	disk_quota: Option<u64>,
+
+	/// How long finalized data is kept regardless of the disk quota.
+	min_keep_finalized_for: Duration,
+}
+
+impl PruningConfig {
+	fn prunes_pov_early(&self) -> bool {
+		self.keep_finalized_pov_for < self.keep_finalized_for
+	}
 }
 
 /// Configuration for the availability store.
@@ -429,6 +526,33 @@ pub struct Config {
 	pub col_meta: u32,
 	/// How long finalized data should be kept (in hours).
 	pub keep_finalized_for: u32,
+	/// How long the full available data of finalized candidates should be kept (in hours), if
+	/// shorter than `keep_finalized_for`. The chunks are kept for `keep_finalized_for`, the
+	/// available data can then still be recovered from them.
+	pub keep_finalized_pov_for: Option<u32>,
+	/// Limit on the disk space used by the store, if any.
+	pub disk_quota: Option<DiskQuota>,
+}
+
+/// Limit on the disk space used by the availability store.
+///
+/// When the stored data exceeds the quota, the data of finalized candidates which were finalized
+/// at least `min_keep_finalized_for` ago is evicted early, least recently used first. The data of
+/// unfinalized candidates is never evicted, so the quota may be exceeded. Only data stored while a
+/// quota is configured is accounted for.
+///
+/// The quota is meant as a safety net rather than a tight bound:
+/// - Access times are only kept in memory. After a restart, candidates are evicted in the order
+///   they were finalized until they are queried again.
+/// - Every pruning pass reads the usage of all stored candidates and, while the quota is exceeded,
+///   the meta of each of them. The cost of a pass grows with the number of stored candidates, and
+///   it is paid on every pass while data which must be kept exceeds the quota.
+#[derive(Debug, Clone, Copy)]
+pub struct DiskQuota {
+	/// The maximum number of bytes of available data and chunks to store.
+	pub max_bytes: u64,
+	/// How long finalized data is kept regardless of the quota (in hours).
+	pub min_keep_finalized_for: u32,
 }
 
 trait Clock: Send + Sync {
@@ -454,6 +578,8 @@ pub struct AvailabilityStoreSubsystem {
 	metrics: Metrics,
 	clock: Box<dyn Clock>,
 	sync_oracle: Box<dyn SyncOracle + Send + Sync>,
+	/// When the data of candidates was last queried, only tracked if a disk quota is configured.
+	last_access: HashMap<CandidateHash, Duration>,
 }
 
 impl AvailabilityStoreSubsystem {
@@ -464,10 +590,19 @@ impl AvailabilityStoreSubsystem {
 		sync_oracle: Box<dyn SyncOracle + Send + Sync>,
 		metrics: Metrics,
 	) -> Self {
+		let hours = |hours: u32| Duration::from_secs(hours as u64 * 3600);
+		let keep_finalized_for = hours(config.keep_finalized_for);
 		let pruning_config = PruningConfig {
 			keep_unavailable_for: KEEP_UNAVAILABLE_FOR,
-			keep_finalized_for: Duration::from_secs(config.keep_finalized_for as u64 * 3600),
+			keep_finalized_for,
+			keep_finalized_pov_for: config
+				.keep_finalized_pov_for
+				.map_or(keep_finalized_for, |h| hours(h).min(keep_finalized_for)),
 			pruning_interval: PRUNING_INTERVAL,
+			disk_quota: config.disk_quota.map(|quota| quota.max_bytes),
+			min_keep_finalized_for: config
+				.disk_quota
+				.map_or(Duration::ZERO, |quota| hours(quota.min_keep_finalized_for)),
 		};
 
 		Self::with_pruning_config_and_clock(
@@ -480,6 +615,26 @@ impl AvailabilityStoreSubsystem {
 		)
 	}
 
+	/// Load the available data of the given candidate, if stored.
+	///
+	/// Allows inspecting the store without running the subsystem, e.g. from CLI tools.
+	pub fn available_data(
+		&self,
+		candidate_hash: &CandidateHash,
+	) -> Result<Option<AvailableData>, Error> {
+		load_available_data(&self.db, &self.config, candidate_hash)
+	}
+
+	/// Load all chunks of the given candidate which are stored, by validator index.
+	///
+	/// Allows inspecting the store without running the subsystem, e.g. from CLI tools.
+	pub fn chunks(
+		&self,
+		candidate_hash: &CandidateHash,
+	) -> Result<Vec<(ValidatorIndex, ErasureChunk)>, Error> {
+		load_all_chunks(&self.db, &self.config, &self.metrics, candidate_hash)
+	}
+
 	/// Create a new `AvailabilityStoreSubsystem` with a given config on disk.
 	fn with_pruning_config_and_clock(
 		db: Arc<dyn Database>,
@@ -498,8 +653,18 @@ impl AvailabilityStoreSubsystem {
 			known_blocks: KnownUnfinalizedBlocks::default(),
 			sync_oracle,
 			finalized_number: None,
+			last_access: HashMap::new(),
 		}
 	}
+
+	/// Record that the data of the candidate was queried, for the eviction of the least recently
+	/// used data.
+	fn note_access(&mut self, candidate_hash: CandidateHash) -> Result<(), Error> {
+		if self.pruning_config.disk_quota.is_some() {
+			self.last_access.insert(candidate_hash, self.clock.now()?);
+		}
+		Ok(())
+	}
 }
 
 /// We keep the hashes and numbers of all unfinalized
@@ -657,15 +822,22 @@ async fn start_prune_all<Context>(
 	let metrics = subsystem.metrics.clone();
 	let db = subsystem.db.clone();
 	let config = subsystem.config;
+	let pruning_config = subsystem.pruning_config.clone();
 	let time_now = subsystem.clock.now()?;
 
+	// Accesses before the finalization of a candidate do not matter for the eviction, and all
+	// candidates finalized before `keep_finalized_for` are pruned anyway.
+	let keep_finalized_for = pruning_config.keep_finalized_for;
+	subsystem.last_access.retain(|_, at| *at + keep_finalized_for > time_now);
+	let last_access = subsystem.last_access.clone();
+
 	ctx.spawn_blocking(
 		"av-store-prunning",
 		Box::pin(async move {
 			let _timer = metrics.time_pruning();
 
 			gum::debug!(target: LOG_TARGET, "Prunning started");
-			let result = prune_all(&db, &config, time_now);
+			let result = prune_all(&db, &config, &pruning_config, &metrics, time_now, &last_access);
 
 			if let Err(err) = pruning_result_tx.send(result).await {
 				// This usually means that the node is closing down, log it just in case
@@ -1056,6 +1228,14 @@ fn update_blocks_at_finalized_height(
 				now + subsystem.pruning_config.keep_finalized_for,
 				&candidate_hash,
 			);
+			if meta.data_available && subsystem.pruning_config.prunes_pov_early() {
+				write_pov_pruning_key(
+					db_transaction,
+					&subsystem.config,
+					now + subsystem.pruning_config.keep_finalized_pov_for,
+					&candidate_hash,
+				);
+			}
 		} else {
 			meta.state = match meta.state {
 				State::Finalized(_) => continue,   // sanity.
@@ -1096,6 +1276,7 @@ fn process_message(
 ) -> Result<(), Error> {
 	match msg {
 		AvailabilityStoreMessage::QueryAvailableData(candidate, tx) => {
+			subsystem.note_access(candidate)?;
 			let _ = tx.send(load_available_data(&subsystem.db, &subsystem.config, &candidate)?);
 		},
 		AvailabilityStoreMessage::QueryDataAvailability(candidate, tx) => {
@@ -1105,6 +1286,7 @@ fn process_message(
 		},
 		AvailabilityStoreMessage::QueryChunk(candidate, validator_index, tx) => {
 			let _timer = subsystem.metrics.time_get_chunk();
+			subsystem.note_access(candidate)?;
 			let _ =
 				tx.send(load_chunk(&subsystem.db, &subsystem.config, &candidate, validator_index)?);
 		},
@@ -1128,39 +1310,13 @@ fn process_message(
 			let _ = tx.send(maybe_chunk_size);
 		},
 		AvailabilityStoreMessage::QueryAllChunks(candidate, tx) => {
-			match load_meta(&subsystem.db, &subsystem.config, &candidate)? {
-				None => {
-					let _ = tx.send(Vec::new());
-				},
-				Some(meta) => {
-					let mut chunks = Vec::new();
-
-					for (validator_index, _) in
-						meta.chunks_stored.iter().enumerate().filter(|(_, b)| **b)
-					{
-						let validator_index = ValidatorIndex(validator_index as _);
-						let _timer = subsystem.metrics.time_get_chunk();
-						match load_chunk(
-							&subsystem.db,
-							&subsystem.config,
-							&candidate,
-							validator_index,
-						)? {
-							Some(c) => chunks.push((validator_index, c)),
-							None => {
-								gum::warn!(
-									target: LOG_TARGET,
-									?candidate,
-									?validator_index,
-									"No chunk found for set bit in meta"
-								);
-							},
-						}
-					}
-
-					let _ = tx.send(chunks);
-				},
-			}
+			subsystem.note_access(candidate)?;
+			let _ = tx.send(load_all_chunks(
+				&subsystem.db,
+				&subsystem.config,
+				&subsystem.metrics,
+				&candidate,
+			)?);
 		},
 		AvailabilityStoreMessage::QueryChunkAvailability(candidate, validator_index, tx) => {
 			let a = load_meta(&subsystem.db, &subsystem.config, &candidate)?.map_or(false, |m| {
@@ -1175,6 +1331,7 @@ fn process_message(
 			match store_chunk(
 				&subsystem.db,
 				&subsystem.config,
+				subsystem.pruning_config.disk_quota.is_some(),
 				candidate_hash,
 				validator_index,
 				chunk,
@@ -1237,10 +1394,39 @@ fn process_message(
 	Ok(())
 }
 
+fn load_all_chunks(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	metrics: &Metrics,
+	candidate: &CandidateHash,
+) -> Result<Vec<(ValidatorIndex, ErasureChunk)>, Error> {
+	let Some(meta) = load_meta(db, config, candidate)? else { return Ok(Vec::new()) };
+
+	let mut chunks = Vec::new();
+	for (validator_index, _) in meta.chunks_stored.iter().enumerate().filter(|(_, b)| **b) {
+		let validator_index = ValidatorIndex(validator_index as _);
+		let _timer = metrics.time_get_chunk();
+		match load_chunk(db, config, candidate, validator_index)? {
+			Some(c) => chunks.push((validator_index, c)),
+			None => {
+				gum::warn!(
+					target: LOG_TARGET,
+					?candidate,
+					?validator_index,
+					"No chunk found for set bit in meta"
+				);
+			},
+		}
+	}
+
+	Ok(chunks)
+}
+
 // Ok(true) on success, Ok(false) on failure, and Err on internal error.
 fn store_chunk(
 	db: &Arc<dyn Database>,
 	config: &Config,
+	track_usage: bool,
 	candidate_hash: CandidateHash,
 	validator_index: ValidatorIndex,
 	chunk: ErasureChunk,
@@ -1259,6 +1445,9 @@ fn store_chunk(
 
 			write_chunk(&mut tx, config, &candidate_hash, validator_index, &chunk);
 			write_meta(&mut tx, config, &candidate_hash, &meta);
+			if track_usage {
+				add_usage(db, &mut tx, config, &candidate_hash, chunk.encoded_size() as u64)?;
+			}
 		},
 		None => return Ok(false), // out of bounds.
 	}
@@ -1330,21 +1519,40 @@ fn store_available_data(
 		.collect();
 
 	let chunk_indices = availability_chunk_indices(&node_features, n_validators, core_index)?;
+	let mut stored_bytes = available_data.encoded_size();
 	for (validator_index, chunk_index) in chunk_indices.into_iter().enumerate() {
+		let erasure_chunk = &erasure_chunks[chunk_index.0 as usize];
+		// Chunks stored before are overwritten.
+		if !meta.chunks_stored.get(validator_index).map_or(false, |b| *b) {
+			stored_bytes += erasure_chunk.encoded_size();
+		}
 		write_chunk(
 			&mut tx,
 			&subsystem.config,
 			&candidate_hash,
 			ValidatorIndex(validator_index as u32),
-			&erasure_chunks[chunk_index.0 as usize],
+			erasure_chunk,
 		);
 	}
 
+	// The available data of candidates finalized before it was stored is pruned from the time of
+	// the finalization on.
+	if let State::Finalized(at) = meta.state {
+		if subsystem.pruning_config.prunes_pov_early() {
+			let at: Duration = at.into();
+			let prune_at = at + subsystem.pruning_config.keep_finalized_pov_for;
+			write_pov_pruning_key(&mut tx, &subsystem.config, prune_at, &candidate_hash);
+		}
+	}
+
 	meta.data_available = true;
 	meta.chunks_stored = bitvec::bitvec![u8, BitOrderLsb0; 1; n_validators];
 
 	write_meta(&mut tx, &subsystem.config, &candidate_hash, &meta);
 	write_available_data(&mut tx, &subsystem.config, &candidate_hash, &available_data);
+	if subsystem.pruning_config.disk_quota.is_some() {
+		add_usage(&subsystem.db, &mut tx, &subsystem.config, &candidate_hash, stored_bytes as u64)?;
+	}
 
 	subsystem.db.write(tx)?;
 
@@ -1353,7 +1561,14 @@ fn store_available_data(
 	Ok(())
 }
 
-fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(), Error> {
+fn prune_all(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	pruning_config: &PruningConfig,
+	metrics: &Metrics,
+	now: Duration,
+	last_access: &HashMap<CandidateHash, Duration>,
+) -> Result<(), Error> {
 	let (range_start, range_end) = pruning_range(now);
 
 	let mut tx = DBTransaction::new();
@@ -1361,6 +1576,7 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(
 		.iter_with_prefix(config.col_meta, &range_start[..])
 		.take_while(|r| r.as_ref().map_or(true, |(k, _v)| &k[..] < &range_end[..]));
 
+	let mut pruned = HashSet::new();
 	for r in iter {
 		let (k, _v) = r?;
 		tx.delete(config.col_meta, &k[..]);
@@ -1371,37 +1587,165 @@ fn prune_all(db: &Arc<dyn Database>, config: &Config, now: Duration) -> Result<(
 		};
 
 		delete_meta(&mut tx, config, &candidate_hash);
+		delete_usage(&mut tx, config, &candidate_hash);
+		pruned.insert(candidate_hash);
 
 		// Clean up all attached data of the candidate.
 		if let Some(meta) = load_meta(db, config, &candidate_hash)? {
-			// delete available data.
-			if meta.data_available {
-				delete_available_data(&mut tx, config, &candidate_hash)
-			}
+			delete_candidate_data(&mut tx, config, &candidate_hash, meta);
+		}
+	}
 
-			// delete chunks.
-			for (i, b) in meta.chunks_stored.iter().enumerate() {
-				if *b {
-					delete_chunk(&mut tx, config, &candidate_hash, ValidatorIndex(i as _));
-				}
-			}
+	// Prune the available data of finalized candidates whose chunks are kept for longer.
+	let (range_start, range_end) = pov_pruning_range(now);
+	let iter = db
+		.iter_with_prefix(config.col_meta, &range_start[..])
+		.take_while(|r| r.as_ref().map_or(true, |(k, _v)| &k[..] < &range_end[..]));
 
-			// delete unfinalized block references. Pruning references don't need to be
-			// manually taken care of as we are deleting them as we go in the outer loop.
-			if let State::Unfinalized(_, blocks) = meta.state {
-				for (block_number, block_hash) in blocks {
-					delete_unfinalized_inclusion(
-						&mut tx,
-						config,
-						block_number.0,
-						&block_hash,
-						&candidate_hash,
-					);
-				}
-			}
+	for r in iter {
+		let (k, _v) = r?;
+		tx.delete(config.col_meta, &k[..]);
+
+		let (_, candidate_hash) = match decode_pov_pruning_key(&k[..]) {
+			Ok(m) => m,
+			Err(_) => continue, // sanity
+		};
+		if pruned.contains(&candidate_hash) {
+			continue
 		}
+
+		let mut meta = match load_meta(db, config, &candidate_hash)? {
+			Some(meta) if meta.data_available => meta,
+			_ => continue,
+		};
+		if let Some(usage) = load_usage(db, config, &candidate_hash)? {
+			let key = (AVAILABLE_PREFIX, &candidate_hash).encode();
+			let size = db.get(config.col_data, &key)?.map_or(0, |v| v.len() as u64);
+			write_usage(&mut tx, config, &candidate_hash, usage.saturating_sub(size));
+		}
+
+		delete_available_data(&mut tx, config, &candidate_hash);
+		meta.data_available = false;
+		write_meta(&mut tx, config, &candidate_hash, &meta);
 	}
 
 	db.write(tx)?;
+
+	if let Some(max_bytes) = pruning_config.disk_quota {
+		let min_keep_finalized_for = pruning_config.min_keep_finalized_for;
+		let (usage, evicted) =
+			enforce_disk_quota(db, config, max_bytes, min_keep_finalized_for, now, last_access)?;
+
+		metrics.on_candidates_evicted(evicted);
+		metrics.on_disk_usage(usage);
+		if usage > max_bytes {
+			gum::warn!(
+				target: LOG_TARGET,
+				usage,
+				max_bytes,
+				"Availability store exceeds the disk quota with data which must be kept",
+			);
+		}
+	}
+
 	Ok(())
 }
+
+// Delete the available data, the chunks and the unfinalized block references of a candidate.
+// Pruning references are not deleted.
+fn delete_candidate_data(
+	tx: &mut DBTransaction,
+	config: &Config,
+	candidate_hash: &CandidateHash,
+	meta: CandidateMeta,
+) {
+	// delete available data.
+	if meta.data_available {
+		delete_available_data(tx, config, candidate_hash)
+	}
+
+	// delete chunks.
+	for (i, b) in meta.chunks_stored.iter().enumerate() {
+		if *b {
+			delete_chunk(tx, config, candidate_hash, ValidatorIndex(i as _));
+		}
+	}
+
+	// delete unfinalized block references.
+	if let State::Unfinalized(_, blocks) = meta.state {
+		for (block_number, block_hash) in blocks {
+			delete_unfinalized_inclusion(tx, config, block_number.0, &block_hash, candidate_hash);
+		}
+	}
+}
+
+// Evict the least recently used finalized candidates until the stored data fits into `max_bytes`.
+// Returns the number of bytes stored afterwards and the number of evicted candidates.
+//
+// Scans the usage of all candidates, and loads the meta of all of them when over the quota, see
+// `DiskQuota` for the resulting limits.
+fn enforce_disk_quota(
+	db: &Arc<dyn Database>,
+	config: &Config,
+	max_bytes: u64,
+	min_keep_finalized_for: Duration,
+	now: Duration,
+	last_access: &HashMap<CandidateHash, Duration>,
+) -> Result<(u64, usize), Error> {
+	let mut usage = Vec::new();
+	let mut total: u64 = 0;
+	for r in db.iter_with_prefix(config.col_meta, &USAGE_PREFIX[..]) {
+		let (k, v) = r?;
+		let candidate_hash = match decode_usage_key(&k[..]) {
+			Ok(h) => h,
+			Err(_) => continue, // sanity
+		};
+		let bytes = u64::decode(&mut &v[..])?;
+		total = total.saturating_add(bytes);
+		usage.push((candidate_hash, bytes));
+	}
+
+	if total <= max_bytes {
+		return Ok((total, 0))
+	}
+
+	let mut tx = DBTransaction::new();
+	let mut evictable = Vec::new();
+	for (candidate_hash, bytes) in usage {
+		match load_meta(db, config, &candidate_hash)? {
+			Some(meta) => {
+				let State::Finalized(at) = meta.state else { continue };
+				let at: Duration = at.into();
+				if at + min_keep_finalized_for <= now {
+					let last_used = last_access.get(&candidate_hash).map_or(at, |t| at.max(*t));
+					evictable.push((last_used, candidate_hash, bytes, meta));
+				}
+			},
+			None => {
+				// sanity: the candidate was pruned without its usage.
+				delete_usage(&mut tx, config, &candidate_hash);
+				total = total.saturating_sub(bytes);
+			},
+		}
+	}
+	evictable.sort_by_key(|(last_used, candidate_hash, _, _)| (*last_used, *candidate_hash));
+
+	let mut evicted = 0;
+	for (_, candidate_hash, bytes, meta) in evictable {
+		if total <= max_bytes {
+			break
+		}
+
+		gum::debug!(target: LOG_TARGET, ?candidate_hash, bytes, "Evicting candidate data");
+
+		// The pruning keys of the candidate are removed once they are due.
+		delete_candidate_data(&mut tx, config, &candidate_hash, meta);
+		delete_meta(&mut tx, config, &candidate_hash);
+		delete_usage(&mut tx, config, &candidate_hash);
+		total = total.saturating_sub(bytes);
+		evicted += 1;
+	}
+
+	db.write(tx)?;
+	Ok((total, evicted))
+}
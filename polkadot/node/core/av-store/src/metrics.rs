@@ -26,6 +26,8 @@ pub(crate) struct MetricsInner {
 	store_available_data: prometheus::Histogram,
 	store_chunk: prometheus::Histogram,
 	get_chunk: prometheus::Histogram,
+	evicted_candidates_total: prometheus::Counter<prometheus::U64>,
+	disk_usage: prometheus::Gauge<prometheus::U64>,
 }
 
 /// Availability metrics.
@@ -41,6 +43,18 @@ impl Metrics {
 		}
 	}
 
+	pub(crate) fn on_candidates_evicted(&self, count: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.evicted_candidates_total.inc_by(count as u64);
+		}
+	}
+
+	pub(crate) fn on_disk_usage(&self, bytes: u64) {
+		if let Some(metrics) = &self.0 {
+			metrics.disk_usage.set(bytes);
+		}
+	}
+
 	/// Provide a timer for `prune_povs` which observes on drop.
 	pub(crate) fn time_pruning(&self) -> Option<metrics::prometheus::prometheus::HistogramTimer> {
 		self.0.as_ref().map(|metrics| metrics.pruning.start_timer())
@@ -152,6 +166,20 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			evicted_candidates_total: prometheus::register(
+				prometheus::Counter::new(
+					"polkadot_parachain_av_store_evicted_candidates_total",
+					"Number of candidates whose data was evicted to stay within the disk quota.",
+				)?,
+				registry,
+			)?,
+			disk_usage: prometheus::register(
+				prometheus::Gauge::new(
+					"polkadot_parachain_av_store_disk_usage_bytes",
+					"Bytes of available data and chunks accounted for the disk quota.",
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
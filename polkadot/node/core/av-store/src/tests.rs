@@ -43,8 +43,13 @@ mod columns {
 	pub const NUM_COLUMNS: u32 = 2;
 }
 
-const TEST_CONFIG: Config =
-	Config { col_data: columns::DATA, col_meta: columns::META, keep_finalized_for: 1 };
+const TEST_CONFIG: Config = Config {
+	col_data: columns::DATA,
+	col_meta: columns::META,
+	keep_finalized_for: 1,
+	keep_finalized_pov_for: None,
+	disk_quota: None,
+};
 
 type VirtualOverseer =
 	polkadot_node_subsystem_test_helpers::TestSubsystemContextHandle<AvailabilityStoreMessage>;
@@ -97,7 +102,10 @@ impl Default for TestState {
 		let pruning_config = PruningConfig {
 			keep_unavailable_for: Duration::from_secs(1),
 			keep_finalized_for: Duration::from_secs(2),
+			keep_finalized_pov_for: Duration::from_secs(2),
 			pruning_interval: Duration::from_millis(250),
+			disk_quota: None,
+			min_keep_finalized_for: Duration::ZERO,
 		};
 
 		let clock = TestClock { inner: Arc::new(Mutex::new(Duration::from_secs(0))) };
@@ -893,6 +901,150 @@ fn stored_data_kept_until_finalized() {
 	});
 }
 
+#[test]
+fn finalized_available_data_pruned_before_chunks() {
+	let store = test_store();
+	let mut test_state = TestState::default();
+	test_state.pruning_config.keep_finalized_pov_for = Duration::from_secs(1);
+
+	test_harness(test_state.clone(), store.clone(), |mut virtual_overseer| async move {
+		let n_validators = 10;
+		let pov = PoV { block_data: BlockData(vec![4, 5, 6]) };
+		let candidate = TestCandidateBuilder { pov_hash: pov.hash(), ..Default::default() }.build();
+		let candidate_hash = candidate.hash();
+		let available_data = AvailableData {
+			pov: Arc::new(pov),
+			validation_data: test_state.persisted_validation_data.clone(),
+		};
+
+		store_data(&mut virtual_overseer, candidate_hash, n_validators, available_data.clone())
+			.await;
+
+		let block_number = 10;
+		let a_leaf = import_leaf(
+			&mut virtual_overseer,
+			Hash::repeat_byte(2),
+			block_number,
+			vec![candidate_included(candidate)],
+			(0..n_validators).map(|_| Sr25519Keyring::Alice.public().into()).collect(),
+		)
+		.await;
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::BlockFinalized(a_leaf, block_number),
+		)
+		.await;
+
+		test_state.clock.inc(test_state.pruning_config.keep_finalized_pov_for);
+		test_state.wait_for_pruning().await;
+
+		// The available data is gone, but the chunks are kept.
+		assert!(query_available_data(&mut virtual_overseer, candidate_hash).await.is_none());
+		assert!(has_all_chunks(&mut virtual_overseer, candidate_hash, n_validators, true).await);
+
+		test_state.clock.inc(test_state.pruning_config.keep_finalized_for);
+		test_state.wait_for_pruning().await;
+
+		assert!(has_all_chunks(&mut virtual_overseer, candidate_hash, n_validators, false).await);
+		virtual_overseer
+	});
+}
+
+#[test]
+fn disk_quota_evicts_least_recently_used_finalized_data() {
+	let store = test_store();
+	let mut test_state = TestState::default();
+	let n_validators = 10;
+
+	let pov_1 = PoV { block_data: BlockData(vec![1, 2, 3]) };
+	let candidate_1 = TestCandidateBuilder { pov_hash: pov_1.hash(), ..Default::default() }.build();
+	let candidate_1_hash = candidate_1.hash();
+	let available_data_1 = AvailableData {
+		pov: Arc::new(pov_1),
+		validation_data: test_state.persisted_validation_data.clone(),
+	};
+
+	let pov_2 = PoV { block_data: BlockData(vec![4, 5, 6]) };
+	let candidate_2 = TestCandidateBuilder { pov_hash: pov_2.hash(), ..Default::default() }.build();
+	let candidate_2_hash = candidate_2.hash();
+	let available_data_2 = AvailableData {
+		pov: Arc::new(pov_2),
+		validation_data: test_state.persisted_validation_data.clone(),
+	};
+
+	// Enough for one of the two candidates.
+	let candidate_size = stored_size(&available_data_1, n_validators);
+	assert_eq!(candidate_size, stored_size(&available_data_2, n_validators));
+	test_state.pruning_config.disk_quota = Some(candidate_size);
+	test_state.pruning_config.min_keep_finalized_for = Duration::from_secs(2);
+	test_state.pruning_config.keep_finalized_for = Duration::from_secs(10);
+	test_state.pruning_config.keep_finalized_pov_for = Duration::from_secs(10);
+
+	test_harness(test_state.clone(), store.clone(), |mut virtual_overseer| async move {
+		store_data(&mut virtual_overseer, candidate_1_hash, n_validators, available_data_1.clone())
+			.await;
+		store_data(&mut virtual_overseer, candidate_2_hash, n_validators, available_data_2.clone())
+			.await;
+
+		assert_eq!(
+			load_usage(&store, &TEST_CONFIG, &candidate_1_hash).unwrap(),
+			Some(candidate_size)
+		);
+		assert_eq!(
+			load_usage(&store, &TEST_CONFIG, &candidate_2_hash).unwrap(),
+			Some(candidate_size)
+		);
+
+		let block_number = 10;
+		let a_leaf = import_leaf(
+			&mut virtual_overseer,
+			Hash::repeat_byte(2),
+			block_number,
+			vec![candidate_included(candidate_1), candidate_included(candidate_2)],
+			(0..n_validators).map(|_| Sr25519Keyring::Alice.public().into()).collect(),
+		)
+		.await;
+
+		// The data of unfinalized candidates is never evicted.
+		test_state.wait_for_pruning().await;
+		assert!(has_all_chunks(&mut virtual_overseer, candidate_1_hash, n_validators, true).await);
+		assert!(has_all_chunks(&mut virtual_overseer, candidate_2_hash, n_validators, true).await);
+
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::BlockFinalized(a_leaf, block_number),
+		)
+		.await;
+
+		// Use the first candidate after the finalization, the second one is then evicted first
+		// once both may be evicted.
+		test_state.clock.inc(Duration::from_secs(1));
+		assert_eq!(
+			query_available_data(&mut virtual_overseer, candidate_1_hash).await.unwrap(),
+			available_data_1,
+		);
+		test_state.wait_for_pruning().await;
+		assert_eq!(
+			load_usage(&store, &TEST_CONFIG, &candidate_2_hash).unwrap(),
+			Some(candidate_size)
+		);
+
+		test_state.clock.inc(Duration::from_secs(1));
+		test_state.wait_for_pruning().await;
+
+		assert!(query_available_data(&mut virtual_overseer, candidate_2_hash).await.is_none());
+		assert!(has_all_chunks(&mut virtual_overseer, candidate_2_hash, n_validators, false).await);
+		assert_eq!(load_usage(&store, &TEST_CONFIG, &candidate_2_hash).unwrap(), None);
+
+		assert_eq!(
+			query_available_data(&mut virtual_overseer, candidate_1_hash).await.unwrap(),
+			available_data_1,
+		);
+		assert!(has_all_chunks(&mut virtual_overseer, candidate_1_hash, n_validators, true).await);
+		virtual_overseer
+	});
+}
+
 #[test]
 fn we_dont_miss_anything_if_import_notifications_are_missed() {
 	let store = test_store();
@@ -1215,6 +1367,49 @@ fn forkfullness_works() {
 	});
 }
 
+// The number of bytes accounted for storing the available data and its chunks.
+fn stored_size(available_data: &AvailableData, n_validators: u32) -> u64 {
+	let chunks =
+		polkadot_erasure_coding::obtain_chunks_v1(n_validators as _, available_data).unwrap();
+	let branches = polkadot_erasure_coding::branches(chunks.as_ref());
+	let chunks_size: usize = chunks
+		.iter()
+		.zip(branches.map(|(proof, _)| proof))
+		.enumerate()
+		.map(|(index, (chunk, proof))| {
+			ErasureChunk { chunk: chunk.clone(), proof, index: ChunkIndex(index as u32) }
+				.encoded_size()
+		})
+		.sum();
+
+	(available_data.encoded_size() + chunks_size) as u64
+}
+
+async fn store_data(
+	virtual_overseer: &mut VirtualOverseer,
+	candidate_hash: CandidateHash,
+	n_validators: u32,
+	available_data: AvailableData,
+) {
+	let chunks =
+		polkadot_erasure_coding::obtain_chunks_v1(n_validators as _, &available_data).unwrap();
+	let branches = polkadot_erasure_coding::branches(chunks.as_ref());
+
+	let (tx, rx) = oneshot::channel();
+	let msg = AvailabilityStoreMessage::StoreAvailableData {
+		candidate_hash,
+		n_validators,
+		available_data,
+		tx,
+		node_features: NodeFeatures::EMPTY,
+		core_index: CoreIndex(1),
+		expected_erasure_root: branches.root(),
+	};
+	virtual_overseer.send(FromOrchestra::Communication { msg }).await;
+
+	rx.await.unwrap().unwrap();
+}
+
 async fn query_available_data(
 	virtual_overseer: &mut VirtualOverseer,
 	candidate_hash: CandidateHash,
@@ -848,6 +848,37 @@ where
 	}
 }
 
+/// Validate a candidate with the given validation host the way approval checkers do, without
+/// running the subsystem.
+///
+/// This allows reproducing the validation of a candidate offline. Checks that only apply to
+/// backing, i.e. the session index and the UMP signals, are skipped.
+pub async fn validate_candidate_offline(
+	validation_host: ValidationHost,
+	persisted_validation_data: PersistedValidationData,
+	validation_code: ValidationCode,
+	candidate_receipt: CandidateReceipt,
+	pov: Arc<PoV>,
+	executor_params: ExecutorParams,
+	validation_code_bomb_limit: u32,
+) -> Result<ValidationResult, ValidationFailed> {
+	validate_candidate_exhaustive(
+		// Only checked for backing.
+		SessionIndex::default(),
+		validation_host,
+		persisted_validation_data,
+		validation_code,
+		candidate_receipt,
+		pov,
+		executor_params,
+		PvfExecKind::Approval,
+		&Metrics::default(),
+		None,
+		validation_code_bomb_limit,
+	)
+	.await
+}
+
 async fn validate_candidate_exhaustive(
 	expected_session_index: SessionIndex,
 	mut validation_backend: impl ValidationBackend + Send,
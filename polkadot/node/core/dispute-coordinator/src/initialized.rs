@@ -35,7 +35,8 @@ use polkadot_node_primitives::{
 use polkadot_node_subsystem::{
 	messages::{
 		ApprovalVotingParallelMessage, BlockDescription, ChainSelectionMessage,
-		DisputeCoordinatorMessage, DisputeDistributionMessage, ImportStatementsResult,
+		DisputeCoordinatorMessage, DisputeCoordinatorState, DisputeDistributionMessage,
+		ImportStatementsResult,
 	},
 	overseer, ActivatedLeaf, ActiveLeavesUpdate, FromOrchestra, OverseerSignal, RuntimeApiError,
 };
@@ -927,6 +928,28 @@ impl Initialized {
 
 				let _ = tx.send(undisputed_chain);
 			},
+			DisputeCoordinatorMessage::QueryState(tx) => {
+				gum::trace!(target: LOG_TARGET, "DisputeCoordinatorMessage::QueryState");
+				let (priority_queue, best_effort_queue) = self.participation.queued();
+				let mut spam_slots = self.spam_slots.occupied().collect::<Vec<_>>();
+				spam_slots.sort();
+
+				let _ = tx.send(DisputeCoordinatorState {
+					running_participations: self.participation.running().copied().collect(),
+					priority_queue,
+					best_effort_queue,
+					unconfirmed_disputes: self
+						.spam_slots
+						.unconfirmed()
+						.iter()
+						.map(|((session, candidate_hash), validators)| {
+							(*session, *candidate_hash, validators.iter().copied().collect())
+						})
+						.collect(),
+					spam_slots,
+					max_spam_slots: SpamSlots::max_per_validator(),
+				});
+			},
 		}
 
 		Ok(Box::new(|| Ok(())))
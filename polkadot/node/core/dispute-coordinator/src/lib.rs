@@ -27,15 +27,14 @@
 
 use std::sync::Arc;
 
-use error::FatalError;
 use futures::FutureExt;
 
 use gum::CandidateHash;
 use sc_keystore::LocalKeystore;
 
 use polkadot_node_primitives::{
-	CandidateVotes, DisputeMessage, DisputeMessageCheckError, SignedDisputeStatement,
-	DISPUTE_WINDOW,
+	CandidateVotes, DisputeMessage, DisputeMessageCheckError, DisputeStatus,
+	SignedDisputeStatement, DISPUTE_WINDOW,
 };
 use polkadot_node_subsystem::{
 	messages::DisputeDistributionMessage, overseer, ActivatedLeaf, FromOrchestra, OverseerSignal,
@@ -68,6 +67,7 @@ use self::{
 pub(crate) mod backend;
 pub(crate) mod db;
 pub(crate) mod error;
+pub use error::FatalError;
 
 /// Subsystem after receiving the first active leaf.
 mod initialized;
@@ -157,6 +157,59 @@ impl<Context: Send> DisputeCoordinatorSubsystem {
 	}
 }
 
+/// Read-only access to the dispute data stored by the dispute coordinator.
+///
+/// Allows inspecting the database without running the subsystem, e.g. from CLI tools. The
+/// participation queues and spam slots are only kept in memory, query them from the running
+/// subsystem via `DisputeCoordinatorMessage::QueryState`.
+pub struct DisputesDbReader {
+	backend: DbBackend,
+}
+
+impl DisputesDbReader {
+	/// Create a reader of the dispute data in the given store.
+	pub fn new(store: Arc<dyn Database>, config: Config) -> Self {
+		Self { backend: DbBackend::new(store, config.column_config(), Metrics::default()) }
+	}
+
+	/// The earliest session whose votes are kept, if any.
+	pub fn earliest_session(&self) -> FatalResult<Option<SessionIndex>> {
+		self.backend.load_earliest_session()
+	}
+
+	/// All recent disputes with their status, ordered by session.
+	pub fn recent_disputes(
+		&self,
+	) -> FatalResult<Vec<(SessionIndex, CandidateHash, DisputeStatus)>> {
+		Ok(self
+			.backend
+			.load_recent_disputes()?
+			.unwrap_or_default()
+			.into_iter()
+			.map(|((session, candidate_hash), status)| (session, candidate_hash, status))
+			.collect())
+	}
+
+	/// The recent disputes which are still active, see `DisputeCoordinatorMessage::ActiveDisputes`.
+	pub fn active_disputes(
+		&self,
+	) -> FatalResult<Vec<(SessionIndex, CandidateHash, DisputeStatus)>> {
+		let recent_disputes = self.backend.load_recent_disputes()?.unwrap_or_default();
+		Ok(get_active_with_status(recent_disputes.into_iter(), SystemClock.now())
+			.map(|((session, candidate_hash), status)| (session, candidate_hash, status))
+			.collect())
+	}
+
+	/// The votes on the given candidate, if any.
+	pub fn candidate_votes(
+		&self,
+		session: SessionIndex,
+		candidate_hash: &CandidateHash,
+	) -> FatalResult<Option<CandidateVotes>> {
+		Ok(self.backend.load_candidate_votes(session, candidate_hash)?.map(Into::into))
+	}
+}
+
 #[overseer::contextbounds(DisputeCoordinator, prefix = self::overseer)]
 impl DisputeCoordinatorSubsystem {
 	/// Create a new instance of the subsystem.
@@ -151,6 +151,19 @@ impl Participation {
 		}
 	}
 
+	/// Candidates whose participation is currently running.
+	pub fn running(&self) -> impl Iterator<Item = &CandidateHash> {
+		self.running_participations.iter()
+	}
+
+	/// The queued participations as `(session, candidate_hash)`, priority and best effort, each in
+	/// the order they will be processed.
+	pub fn queued(
+		&self,
+	) -> (Vec<(SessionIndex, CandidateHash)>, Vec<(SessionIndex, CandidateHash)>) {
+		self.queue.queued()
+	}
+
 	/// Queue a dispute for the node to participate in.
 	///
 	/// If capacity is available right now and we already got some relay chain head via
@@ -218,6 +218,17 @@ impl Queues {
 		None
 	}
 
+	/// The queued requests as `(session, candidate_hash)`, priority and best effort, each in the
+	/// order they will be dequeued.
+	pub fn queued(
+		&self,
+	) -> (Vec<(SessionIndex, CandidateHash)>, Vec<(SessionIndex, CandidateHash)>) {
+		let list = |queue: &BTreeMap<CandidateComparator, ParticipationRequest>| {
+			queue.values().map(|req| (req.session, req.candidate_hash)).collect()
+		};
+		(list(&self.priority), list(&self.best_effort))
+	}
+
 	/// Reprioritizes any participation requests pertaining to the
 	/// passed candidates from best effort to priority.
 	pub async fn prioritize_if_present(
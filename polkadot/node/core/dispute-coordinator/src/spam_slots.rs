@@ -127,6 +127,23 @@ impl SpamSlots {
 			}
 		}
 	}
+	/// The occupied spam slots, as `(session, validator, count)`.
+	pub fn occupied(&self) -> impl Iterator<Item = (SessionIndex, ValidatorIndex, SpamCount)> + '_ {
+		self.slots
+			.iter()
+			.map(|((session, validator), count)| (*session, *validator, *count))
+	}
+
+	/// All unconfirmed disputes with the validators that raised them.
+	pub fn unconfirmed(&self) -> &UnconfirmedDisputes {
+		&self.unconfirmed
+	}
+
+	/// The number of spam slots available to each validator per session.
+	pub fn max_per_validator() -> SpamCount {
+		MAX_SPAM_VOTES
+	}
+
 	/// Prune all spam slots for sessions older than the given index.
 	pub fn prune_old(&mut self, oldest_index: SessionIndex) {
 		self.unconfirmed.retain(|(session, _), _| *session >= oldest_index);
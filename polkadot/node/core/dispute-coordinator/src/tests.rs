@@ -38,7 +38,8 @@ use polkadot_node_primitives::{
 use polkadot_node_subsystem::{
 	messages::{
 		ApprovalVotingParallelMessage, ChainApiMessage, ChainSelectionMessage,
-		DisputeCoordinatorMessage, DisputeDistributionMessage, ImportStatementsResult,
+		DisputeCoordinatorMessage, DisputeCoordinatorState, DisputeDistributionMessage,
+		ImportStatementsResult,
 	},
 	overseer::FromOrchestra,
 	OverseerSignal,
@@ -74,14 +75,14 @@ use polkadot_primitives_test_helpers::{
 };
 
 use crate::{
-	backend::Backend,
+	backend::{Backend, OverlayedBackend},
 	metrics::Metrics,
 	participation::{participation_full_happy_path, participation_missing_availability},
 	status::Clock,
-	Config, DisputeCoordinatorSubsystem,
+	Config, DisputeCoordinatorSubsystem, DisputesDbReader,
 };
 
-use super::db::v1::DbBackend;
+use super::db::v1::{CandidateVotes as DbCandidateVotes, DbBackend};
 
 const TEST_TIMEOUT: Duration = Duration::from_secs(2);
 
@@ -839,6 +840,172 @@ fn too_many_unconfirmed_statements_are_considered_spam() {
 	});
 }
 
+#[test]
+fn query_state_reports_unconfirmed_disputes_and_spam_slots() {
+	test_harness(|mut test_state, mut virtual_overseer| {
+		Box::pin(async move {
+			let session = 1;
+
+			test_state.handle_resume_sync(&mut virtual_overseer, session).await;
+
+			let candidate_receipt1 = make_valid_candidate_receipt();
+			let candidate_hash1 = candidate_receipt1.hash();
+			let candidate_receipt2 = make_invalid_candidate_receipt();
+			let candidate_hash2 = candidate_receipt2.hash();
+
+			test_state
+				.activate_leaf_at_session(&mut virtual_overseer, session, 1, Vec::new())
+				.await;
+
+			let (valid_vote1, invalid_vote1) = generate_opposing_votes_pair(
+				&test_state,
+				ValidatorIndex(3),
+				ValidatorIndex(1),
+				candidate_hash1,
+				session,
+				VoteType::Backing,
+			)
+			.await;
+
+			let (valid_vote2, invalid_vote2) = generate_opposing_votes_pair(
+				&test_state,
+				ValidatorIndex(3),
+				ValidatorIndex(1),
+				candidate_hash2,
+				session,
+				VoteType::Backing,
+			)
+			.await;
+
+			virtual_overseer
+				.send(FromOrchestra::Communication {
+					msg: DisputeCoordinatorMessage::ImportStatements {
+						candidate_receipt: candidate_receipt1.clone(),
+						session,
+						statements: vec![
+							(valid_vote1, ValidatorIndex(3)),
+							(invalid_vote1, ValidatorIndex(1)),
+						],
+						pending_confirmation: None,
+					},
+				})
+				.await;
+
+			handle_disabled_validators_queries(&mut virtual_overseer, Vec::new()).await;
+			handle_approval_vote_request(&mut virtual_overseer, &candidate_hash1, HashMap::new())
+				.await;
+
+			let (pending_confirmation, confirmation_rx) = oneshot::channel();
+			virtual_overseer
+				.send(FromOrchestra::Communication {
+					msg: DisputeCoordinatorMessage::ImportStatements {
+						candidate_receipt: candidate_receipt2.clone(),
+						session,
+						statements: vec![
+							(valid_vote2, ValidatorIndex(3)),
+							(invalid_vote2, ValidatorIndex(1)),
+						],
+						pending_confirmation: Some(pending_confirmation),
+					},
+				})
+				.await;
+
+			handle_approval_vote_request(&mut virtual_overseer, &candidate_hash2, HashMap::new())
+				.await;
+
+			// Result should be invalid, because it should be considered spam.
+			assert_matches!(confirmation_rx.await, Ok(ImportStatementsResult::InvalidImport));
+
+			{
+				let (tx, rx) = oneshot::channel();
+				virtual_overseer
+					.send(FromOrchestra::Communication {
+						msg: DisputeCoordinatorMessage::QueryState(tx),
+					})
+					.await;
+
+				// Only the first dispute occupies the spam slot of the invalid voter:
+				assert_eq!(
+					rx.await.unwrap(),
+					DisputeCoordinatorState {
+						unconfirmed_disputes: vec![(
+							session,
+							candidate_hash1,
+							vec![ValidatorIndex(1)]
+						)],
+						spam_slots: vec![(session, ValidatorIndex(1), 1)],
+						max_spam_slots: 1,
+						..Default::default()
+					}
+				);
+			}
+
+			virtual_overseer.send(FromOrchestra::Signal(OverseerSignal::Conclude)).await;
+
+			// No more messages expected:
+			assert!(virtual_overseer.try_recv().await.is_none());
+
+			test_state
+		})
+	});
+}
+
+#[test]
+fn disputes_db_reader_reads_the_stored_disputes() {
+	let test_state = TestState::default();
+	let mut backend = DbBackend::new(
+		test_state.db.clone(),
+		test_state.config.column_config(),
+		Metrics::default(),
+	);
+
+	let candidate_receipt = make_valid_candidate_receipt();
+	let active_candidate = candidate_receipt.hash();
+	let concluded_candidate = CandidateHash(Hash::repeat_byte(1));
+
+	let mut overlay_db = OverlayedBackend::new(&backend);
+	overlay_db.write_earliest_session(1);
+	overlay_db.write_recent_disputes(
+		vec![
+			((2, concluded_candidate), DisputeStatus::ConcludedFor(0)),
+			((1, active_candidate), DisputeStatus::Active),
+		]
+		.into_iter()
+		.collect(),
+	);
+	overlay_db.write_candidate_votes(
+		1,
+		active_candidate,
+		DbCandidateVotes { candidate_receipt, valid: Vec::new(), invalid: Vec::new() },
+	);
+	backend.write(overlay_db.into_write_ops()).unwrap();
+
+	let reader = DisputesDbReader::new(test_state.db.clone(), test_state.config);
+
+	assert_eq!(reader.earliest_session().unwrap(), Some(1));
+	assert_eq!(
+		reader.recent_disputes().unwrap(),
+		vec![
+			(1, active_candidate, DisputeStatus::Active),
+			(2, concluded_candidate, DisputeStatus::ConcludedFor(0)),
+		]
+	);
+	// The concluded dispute is long past its activity window.
+	assert_eq!(
+		reader.active_disputes().unwrap(),
+		vec![(1, active_candidate, DisputeStatus::Active)]
+	);
+	assert_matches!(
+		reader.candidate_votes(1, &active_candidate).unwrap(),
+		Some(votes) => {
+			assert_eq!(votes.candidate_receipt.hash(), active_candidate);
+			assert!(votes.valid.raw().is_empty());
+			assert!(votes.invalid.is_empty());
+		}
+	);
+	assert_matches!(reader.candidate_votes(2, &concluded_candidate).unwrap(), None);
+}
+
 #[test]
 fn approval_vote_import_works() {
 	test_harness(|mut test_state, mut virtual_overseer| {
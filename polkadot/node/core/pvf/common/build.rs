@@ -0,0 +1,75 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Exposes the version of Wasmtime that `sc-executor-wasmtime` is built with, see
+//! `WASMTIME_VERSION`.
+//!
+//! The version is read from the lockfile of the workspace being built, nested `cargo`
+//! invocations would fail in offline and vendored builds. If it can't be determined the variable
+//! is left unset and a warning is emitted.
+
+use std::{
+	env, fs,
+	path::{Path, PathBuf},
+};
+
+fn main() {
+	let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+	let Some(lockfile) =
+		manifest_dir.ancestors().map(|dir| dir.join("Cargo.lock")).find(|p| p.is_file())
+	else {
+		println!(
+			"cargo:warning=No `Cargo.lock` found, the Wasmtime version of artifacts is unknown"
+		);
+		return
+	};
+	println!("cargo:rerun-if-changed={}", lockfile.display());
+
+	match wasmtime_version(&lockfile) {
+		Ok(version) => println!("cargo:rustc-env=WASMTIME_VERSION={version}"),
+		Err(error) => println!(
+			"cargo:warning=Failed to find the Wasmtime version in {}: {error}",
+			lockfile.display()
+		),
+	}
+}
+
+/// The version of the single `wasmtime` package in the lockfile.
+fn wasmtime_version(lockfile: &Path) -> Result<String, String> {
+	let lockfile = fs::read_to_string(lockfile).map_err(|e| e.to_string())?;
+	let mut versions = Vec::new();
+	for package in lockfile.split("[[package]]").skip(1) {
+		let field = |key: &str| {
+			package.lines().find_map(|line| {
+				line.strip_prefix(key)?
+					.trim()
+					.strip_prefix('=')?
+					.trim()
+					.strip_prefix('"')?
+					.strip_suffix('"')
+			})
+		};
+		if field("name") == Some("wasmtime") {
+			versions.extend(field("version").map(ToOwned::to_owned));
+		}
+	}
+
+	match versions.as_slice() {
+		[version] => Ok(version.clone()),
+		[] => Err("no `wasmtime` package".into()),
+		_ => Err(format!("several `wasmtime` packages: {}", versions.join(", "))),
+	}
+}
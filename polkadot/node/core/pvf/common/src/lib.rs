@@ -98,6 +98,12 @@ pub fn compute_checksum(data: &[u8]) -> ArtifactChecksum {
 	ArtifactChecksum(H256::from_slice(&sp_crypto_hashing::twox_256(data)))
 }
 
+/// The version of Wasmtime that artifacts are compiled with.
+///
+/// Artifacts compiled by a different version must not be executed. Taken from the `wasmtime`
+/// package of the lockfile at build time, `None` if the build couldn't determine it.
+pub const WASMTIME_VERSION: Option<&str> = option_env!("WASMTIME_VERSION");
+
 #[cfg(all(test, not(feature = "test-utils")))]
 mod tests {
 	use super::*;
@@ -18,7 +18,9 @@
 //!
 //! # Lifecycle of an artifact
 //!
-//! 1. During node start-up, we prune all the cached artifacts, if any.
+//! 1. During node start-up, we restore the artifacts recorded in the manifest of the previous run,
+//!    provided that they were produced by the same node and Wasmtime version and that their
+//!    checksum still matches. All other cached artifacts, if any, are pruned.
 //!
 //! 2. In order to be executed, a PVF should be prepared first. This means that artifacts should
 //!    have an [`ArtifactState::Prepared`] entry for that artifact in the table. If not, the
@@ -53,17 +55,23 @@
 //! 7. There is a separate process for pruning the prepared artifacts whose `last_time_needed` is
 //!    older by a predefined parameter. This process is run very rarely (say, once a day). Once the
 //!    artifact is expired it is removed from disk eagerly atomically.
+//!
+//! 8. Whenever the set of prepared artifacts changes, the manifest is rewritten, so that the
+//!    artifacts survive a restart of the node.
 
-use crate::{host::PrecheckResultSender, worker_interface::WORKER_DIR_PREFIX};
+use crate::{host::PrecheckResultSender, worker_interface::WORKER_DIR_PREFIX, LOG_TARGET};
 use always_assert::always;
-use polkadot_node_core_pvf_common::{error::PrepareError, pvf::PvfPrepData, ArtifactChecksum};
+use codec::{Decode, Encode};
+use polkadot_node_core_pvf_common::{
+	compute_checksum, error::PrepareError, pvf::PvfPrepData, ArtifactChecksum, WASMTIME_VERSION,
+};
 use polkadot_parachain_primitives::primitives::ValidationCodeHash;
 use polkadot_primitives::ExecutorParamsPrepHash;
 use std::{
-	collections::HashMap,
+	collections::{HashMap, HashSet},
 	fs,
 	path::{Path, PathBuf},
-	time::{Duration, SystemTime},
+	time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 /// The extension to use for cached artifacts.
@@ -72,6 +80,62 @@ const ARTIFACT_EXTENSION: &str = "pvf";
 /// The prefix that artifacts used to start with under the old naming scheme.
 const ARTIFACT_OLD_PREFIX: &str = "wasmtime_";
 
+/// The name of the file recording the prepared artifacts across restarts.
+const MANIFEST_FILE_NAME: &str = "artifacts.manifest";
+
+/// Returns the version of the artifact cache for the given node version.
+///
+/// Artifacts are only restored if they were recorded under the same version. Without a node
+/// version or a Wasmtime version, the artifacts of different node builds can't be told apart, so
+/// none are restored.
+pub fn cache_version(node_version: Option<&str>) -> Option<String> {
+	let wasmtime_version = WASMTIME_VERSION?;
+	node_version.map(|node_version| format!("{}/wasmtime-{}", node_version, wasmtime_version))
+}
+
+/// An entry of the manifest, describing a single prepared artifact.
+#[derive(Debug, Encode, Decode)]
+struct ManifestEntry {
+	code_hash: ValidationCodeHash,
+	executor_params_prep_hash: ExecutorParamsPrepHash,
+	/// The file name of the artifact within the cache directory.
+	file_name: String,
+	checksum: ArtifactChecksum,
+	size: u64,
+	/// Seconds since the UNIX epoch.
+	last_time_needed: u64,
+}
+
+/// The prepared artifacts of a previous run, persisted in the cache directory.
+#[derive(Debug, Encode, Decode)]
+struct Manifest {
+	/// See [`cache_version`].
+	version: String,
+	entries: Vec<ManifestEntry>,
+}
+
+impl ManifestEntry {
+	/// Returns the path of the artifact, or `None` if the file name does not name an artifact
+	/// directly within the cache directory.
+	fn path(&self, cache_path: &Path) -> Option<PathBuf> {
+		let file_name = Path::new(&self.file_name);
+		let valid = file_name.file_name() == Some(file_name.as_os_str()) &&
+			file_name.extension().map_or(false, |ext| ext == ARTIFACT_EXTENSION);
+		valid.then(|| cache_path.join(file_name))
+	}
+}
+
+/// The outcome of restoring the artifacts of a previous run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WarmStartStats {
+	/// Artifacts that were restored and do not need to be prepared again.
+	pub restored: usize,
+	/// Artifacts that were recorded under a different version or are missing on disk.
+	pub stale: usize,
+	/// Artifacts whose checksum did not match.
+	pub corrupted: usize,
+}
+
 pub fn generate_artifact_path(cache_path: &Path) -> PathBuf {
 	let file_name = {
 		use array_bytes::Hex;
@@ -170,6 +234,12 @@ pub enum ArtifactState {
 /// A container of all known artifact ids and their states.
 pub struct Artifacts {
 	inner: HashMap<ArtifactId, ArtifactState>,
+	/// Where the manifest is persisted. `None` if the table is not backed by a cache directory or
+	/// the cache has no version.
+	manifest_path: Option<PathBuf>,
+	/// See [`cache_version`].
+	version: Option<String>,
+	warm_start_stats: WarmStartStats,
 }
 
 /// Parameters we use to cleanup artifacts
@@ -202,7 +272,12 @@ impl ArtifactsCleanupConfig {
 impl Artifacts {
 	#[cfg(test)]
 	pub(crate) fn empty() -> Self {
-		Self { inner: HashMap::new() }
+		Self {
+			inner: HashMap::new(),
+			manifest_path: None,
+			version: cache_version(None),
+			warm_start_stats: Default::default(),
+		}
 	}
 
 	#[cfg(test)]
@@ -230,27 +305,165 @@ impl Artifacts {
 		}
 	}
 
-	/// Create an empty table and the cache directory on-disk if it doesn't exist.
-	pub async fn new(cache_path: &Path) -> Self {
+	/// Create the table from the artifacts of the previous run and the cache directory on-disk if
+	/// it doesn't exist.
+	///
+	/// Only artifacts recorded in the manifest under the same [`cache_version`] and whose checksum
+	/// matches are restored.
+	pub async fn new(cache_path: &Path, node_version: Option<&str>) -> Self {
 		// Make sure that the cache path directory and all its parents are created.
 		let _ = tokio::fs::create_dir_all(cache_path).await;
 
-		// Delete any leftover artifacts and worker dirs from previous runs. We don't delete the
-		// entire cache directory in case the user made a mistake and set it to e.g. their home
-		// directory. This is a best-effort to do clean-up, so ignore any errors.
+		let manifest_path = cache_path.join(MANIFEST_FILE_NAME);
+		let version = cache_version(node_version);
+		let mut artifacts = Self {
+			inner: HashMap::new(),
+			manifest_path: version.is_some().then(|| manifest_path.clone()),
+			version,
+			warm_start_stats: Default::default(),
+		};
+
+		let manifest = tokio::fs::read(&manifest_path)
+			.await
+			.ok()
+			.and_then(|bytes| Manifest::decode(&mut &bytes[..]).ok());
+		let mut restored_paths = HashSet::new();
+		match manifest {
+			Some(manifest) if Some(&manifest.version) == artifacts.version.as_ref() =>
+				for entry in manifest.entries {
+					restored_paths.extend(artifacts.restore(cache_path, entry).await);
+				},
+			Some(manifest) => {
+				gum::info!(
+					target: LOG_TARGET,
+					version = %manifest.version,
+					expected = ?artifacts.version,
+					"discarding {} artifacts prepared by a different version",
+					manifest.entries.len(),
+				);
+				artifacts.warm_start_stats.stale += manifest.entries.len();
+			},
+			None => {},
+		}
+		if artifacts.version.is_none() {
+			gum::warn!(
+				target: LOG_TARGET,
+				"the node version is unknown, prepared artifacts are not kept across restarts",
+			);
+			let _ = fs::remove_file(&manifest_path);
+		}
+
+		// Delete any leftover artifacts that were not restored and worker dirs from previous runs.
+		// We don't delete the entire cache directory in case the user made a mistake and set it to
+		// e.g. their home directory. This is a best-effort to do clean-up, so ignore any errors.
 		for entry in fs::read_dir(cache_path).into_iter().flatten().flatten() {
 			let path = entry.path();
 			let Some(file_name) = path.file_name().and_then(|f| f.to_str()) else { continue };
 			if path.is_dir() && file_name.starts_with(WORKER_DIR_PREFIX) {
 				let _ = fs::remove_dir_all(path);
-			} else if path.extension().map_or(false, |ext| ext == ARTIFACT_EXTENSION) ||
-				file_name.starts_with(ARTIFACT_OLD_PREFIX)
+			} else if (path.extension().map_or(false, |ext| ext == ARTIFACT_EXTENSION) ||
+				file_name.starts_with(ARTIFACT_OLD_PREFIX)) &&
+				!restored_paths.contains(&path)
 			{
 				let _ = fs::remove_file(path);
 			}
 		}
 
-		Self { inner: HashMap::new() }
+		gum::debug!(
+			target: LOG_TARGET,
+			stats = ?artifacts.warm_start_stats,
+			"restored artifacts of the previous run",
+		);
+		artifacts.persist().await;
+		artifacts
+	}
+
+	/// Restore the artifact described by the manifest entry if its checksum matches, returning
+	/// its path.
+	async fn restore(&mut self, cache_path: &Path, entry: ManifestEntry) -> Option<PathBuf> {
+		let Some(path) = entry.path(cache_path) else {
+			self.warm_start_stats.corrupted += 1;
+			return None
+		};
+		let Ok(blob) = tokio::fs::read(&path).await else {
+			self.warm_start_stats.stale += 1;
+			return None
+		};
+		if compute_checksum(&blob) != entry.checksum {
+			gum::warn!(
+				target: LOG_TARGET,
+				code_hash = ?entry.code_hash,
+				"checksum mismatch of the cached artifact {}, discarding",
+				path.display(),
+			);
+			self.warm_start_stats.corrupted += 1;
+			return None
+		}
+
+		let artifact_id = ArtifactId::new(entry.code_hash, entry.executor_params_prep_hash);
+		let state = ArtifactState::Prepared {
+			checksum: entry.checksum,
+			path: path.clone(),
+			last_time_needed: UNIX_EPOCH + Duration::from_secs(entry.last_time_needed),
+			size: entry.size,
+		};
+		if self.inner.insert(artifact_id, state).is_some() {
+			// A duplicated entry, the file of the replaced one is pruned.
+			self.warm_start_stats.corrupted += 1;
+		} else {
+			self.warm_start_stats.restored += 1;
+		}
+		Some(path)
+	}
+
+	/// Returns the outcome of restoring the artifacts of the previous run.
+	pub fn warm_start_stats(&self) -> WarmStartStats {
+		self.warm_start_stats
+	}
+
+	/// Write the manifest of the prepared artifacts to the cache directory.
+	///
+	/// This is a best-effort operation: on failure, the artifacts will be prepared again after a
+	/// restart.
+	pub async fn persist(&self) {
+		let (Some(manifest_path), Some(version)) = (&self.manifest_path, &self.version) else {
+			return
+		};
+
+		let entries = self
+			.inner
+			.iter()
+			.filter_map(|(artifact_id, state)| match state {
+				ArtifactState::Prepared { checksum, path, last_time_needed, size } =>
+					Some(ManifestEntry {
+						code_hash: artifact_id.code_hash,
+						executor_params_prep_hash: artifact_id.executor_params_prep_hash,
+						file_name: path.file_name()?.to_str()?.to_owned(),
+						checksum: *checksum,
+						size: *size,
+						last_time_needed: last_time_needed
+							.duration_since(UNIX_EPOCH)
+							.map_or(0, |d| d.as_secs()),
+					}),
+				_ => None,
+			})
+			.collect();
+		let manifest = Manifest { version: version.clone(), entries };
+
+		// Write to a temporary file first, so that a crash never leaves a truncated manifest.
+		let tmp_path = manifest_path.with_extension("tmp");
+		let result = match tokio::fs::write(&tmp_path, manifest.encode()).await {
+			Ok(()) => tokio::fs::rename(&tmp_path, manifest_path).await,
+			Err(err) => Err(err),
+		};
+		if let Err(err) = result {
+			gum::warn!(
+				target: LOG_TARGET,
+				"failed to persist the artifact manifest {}: {}",
+				manifest_path.display(),
+				err,
+			);
+		}
 	}
 
 	/// Returns the state of the given artifact by its ID.
@@ -363,19 +576,130 @@ mod tests {
 		fs::write(cache_path.join("polkadot_..."), "test").unwrap();
 		fs::create_dir(cache_path.join("worker-prepare-test")).unwrap();
 
-		let artifacts = Artifacts::new(cache_path).await;
+		let artifacts = Artifacts::new(cache_path, Some("1.0.0")).await;
 
 		let entries: Vec<String> = fs::read_dir(&cache_path)
 			.unwrap()
 			.map(|entry| entry.unwrap().file_name().into_string().unwrap())
 			.collect();
-		assert_eq!(entries.len(), 3);
+		assert_eq!(entries.len(), 4);
+		assert!(entries.contains(&String::from(MANIFEST_FILE_NAME)));
 		assert!(entries.contains(&String::from("abcd.pvfartifact")));
 		assert!(entries.contains(&String::from("polkadot_...")));
 		assert!(entries.contains(&String::from("worker-prepare-test")));
 		assert_eq!(artifacts.len(), 0);
 	}
 
+	#[tokio::test]
+	async fn artifacts_restored_on_startup() {
+		let tempdir = tempfile::tempdir().unwrap();
+		let cache_path = tempdir.path();
+		let last_time_needed = UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+		let mut artifacts = Artifacts::new(cache_path, Some("1.0.0")).await;
+		let mut prepare = |id: u32, content: &[u8], checksum: ArtifactChecksum| {
+			let path = generate_artifact_path(cache_path);
+			fs::write(&path, content).unwrap();
+			artifacts.insert_prepared(artifact_id(id), path.clone(), checksum, last_time_needed, 4);
+			path
+		};
+		let path1 = prepare(1, b"good", compute_checksum(b"good"));
+		let path2 = prepare(2, b"evil", compute_checksum(b"good"));
+		let path3 = prepare(3, b"gone", compute_checksum(b"gone"));
+		artifacts.persist().await;
+		fs::remove_file(&path3).unwrap();
+
+		let mut artifacts = Artifacts::new(cache_path, Some("1.0.0")).await;
+
+		assert_eq!(
+			artifacts.warm_start_stats(),
+			WarmStartStats { restored: 1, stale: 1, corrupted: 1 }
+		);
+		assert_eq!(artifacts.artifact_ids(), vec![artifact_id(1)]);
+		assert_matches::assert_matches!(
+			artifacts.artifact_state_mut(&artifact_id(1)),
+			Some(ArtifactState::Prepared { path, last_time_needed: t, size: 4, .. })
+				if *path == path1 && *t == last_time_needed
+		);
+		assert!(path1.exists());
+		assert!(!path2.exists());
+
+		// The manifest only lists the restored artifact now.
+		let artifacts = Artifacts::new(cache_path, Some("1.0.0")).await;
+		assert_eq!(
+			artifacts.warm_start_stats(),
+			WarmStartStats { restored: 1, stale: 0, corrupted: 0 }
+		);
+	}
+
+	#[tokio::test]
+	async fn artifacts_of_other_version_cleared_on_startup() {
+		let tempdir = tempfile::tempdir().unwrap();
+		let cache_path = tempdir.path();
+
+		let mut artifacts = Artifacts::new(cache_path, Some("1.0.0")).await;
+		let path = generate_artifact_path(cache_path);
+		fs::write(&path, b"code").unwrap();
+		artifacts.insert_prepared(
+			artifact_id(1),
+			path.clone(),
+			compute_checksum(b"code"),
+			SystemTime::now(),
+			4,
+		);
+		artifacts.persist().await;
+
+		let artifacts = Artifacts::new(cache_path, Some("1.0.1")).await;
+
+		assert_eq!(
+			artifacts.warm_start_stats(),
+			WarmStartStats { restored: 0, stale: 1, corrupted: 0 }
+		);
+		assert_eq!(artifacts.len(), 0);
+		assert!(!path.exists());
+	}
+
+	#[tokio::test]
+	async fn artifacts_not_restored_without_node_version() {
+		let tempdir = tempfile::tempdir().unwrap();
+		let cache_path = tempdir.path();
+
+		let mut artifacts = Artifacts::new(cache_path, Some("1.0.0")).await;
+		let path = generate_artifact_path(cache_path);
+		fs::write(&path, b"code").unwrap();
+		artifacts.insert_prepared(
+			artifact_id(1),
+			path.clone(),
+			compute_checksum(b"code"),
+			SystemTime::now(),
+			4,
+		);
+		artifacts.persist().await;
+
+		let mut artifacts = Artifacts::new(cache_path, None).await;
+
+		assert_eq!(
+			artifacts.warm_start_stats(),
+			WarmStartStats { restored: 0, stale: 1, corrupted: 0 }
+		);
+		assert_eq!(artifacts.len(), 0);
+		assert!(!path.exists());
+		assert!(!cache_path.join(MANIFEST_FILE_NAME).exists());
+
+		// Nothing is recorded for the next run either.
+		let path = generate_artifact_path(cache_path);
+		fs::write(&path, b"code").unwrap();
+		artifacts.insert_prepared(
+			artifact_id(1),
+			path.clone(),
+			compute_checksum(b"code"),
+			SystemTime::now(),
+			4,
+		);
+		artifacts.persist().await;
+		assert!(!cache_path.join(MANIFEST_FILE_NAME).exists());
+	}
+
 	#[tokio::test]
 	async fn test_pruned_by_cache_size() {
 		let mock_now = SystemTime::now();
@@ -389,7 +713,7 @@ mod tests {
 		let artifact_id2 = artifact_id(2);
 		let artifact_id3 = artifact_id(3);
 
-		let mut artifacts = Artifacts::new(cache_path).await;
+		let mut artifacts = Artifacts::new(cache_path, None).await;
 		let cleanup_config = ArtifactsCleanupConfig::new(1500, Duration::from_secs(0));
 
 		artifacts.insert_prepared(
@@ -437,7 +761,7 @@ mod tests {
 		let artifact_id2 = artifact_id(2);
 		let artifact_id3 = artifact_id(3);
 
-		let mut artifacts = Artifacts::new(cache_path).await;
+		let mut artifacts = Artifacts::new(cache_path, None).await;
 		let cleanup_config = ArtifactsCleanupConfig::new(1500, Duration::from_secs(12));
 
 		artifacts.insert_prepared(
@@ -280,7 +280,8 @@ pub async fn start(
 	gum::debug!(target: LOG_TARGET, ?config, "starting PVF validation host");
 
 	// Make sure the cache is initialized before doing anything else.
-	let artifacts = Artifacts::new(&config.cache_path).await;
+	let artifacts = Artifacts::new(&config.cache_path, config.node_version.as_deref()).await;
+	metrics.observe_warm_start(artifacts.warm_start_stats());
 
 	// Run checks for supported security features once per host startup. If some checks fail, warn
 	// if Secure Validator Mode is disabled and return an error otherwise.
@@ -905,6 +906,10 @@ async fn handle_prepare_done(
 		},
 	};
 
+	if matches!(state, ArtifactState::Prepared { .. }) {
+		artifacts.persist().await;
+	}
+
 	Ok(())
 }
 
@@ -968,6 +973,9 @@ async fn handle_cleanup_pulse(
 		sweeper_tx.send(path).await.map_err(|_| Fatal)?;
 	}
 
+	// Also records the updated `last_time_needed` of the remaining artifacts.
+	artifacts.persist().await;
+
 	Ok(())
 }
 
@@ -998,6 +1006,7 @@ async fn handle_artifact_removal(
 		validation_code_hash = ?artifact_id.code_hash,
 		"PVF pruning: pruning artifact by request from the execute queue",
 	);
+	artifacts.persist().await;
 	sweeper_tx.send(path).await.map_err(|_| Fatal)?;
 	Ok(())
 }
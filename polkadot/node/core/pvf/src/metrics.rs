@@ -16,6 +16,7 @@
 
 //! Prometheus metrics related to the validation host.
 
+use crate::artifacts::WarmStartStats;
 use polkadot_node_core_pvf_common::prepare::MemoryStats;
 use polkadot_node_metrics::metrics::{self, prometheus};
 use polkadot_node_subsystem::messages::PvfExecKind;
@@ -122,6 +123,18 @@ impl Metrics {
 		}
 	}
 
+	/// Outcome of restoring the artifacts of the previous run on startup.
+	pub(crate) fn observe_warm_start(&self, stats: WarmStartStats) {
+		if let Some(metrics) = &self.0 {
+			let WarmStartStats { restored, stale, corrupted } = stats;
+			for (outcome, count) in
+				[("restored", restored), ("stale", stale), ("corrupted", corrupted)]
+			{
+				metrics.warm_start_artifacts.with_label_values(&[outcome]).inc_by(count as u64);
+			}
+		}
+	}
+
 	/// When preparation pipeline concluded working on an item.
 	pub(crate) fn on_execute_kind(&self, kind: PvfExecKind) {
 		if let Some(metrics) = &self.0 {
@@ -155,6 +168,7 @@ struct MetricsInner {
 	pov_size: prometheus::HistogramVec,
 	code_size: prometheus::Histogram,
 	exec_kind_selected: prometheus::CounterVec<prometheus::U64>,
+	warm_start_artifacts: prometheus::CounterVec<prometheus::U64>,
 }
 
 impl metrics::Metrics for Metrics {
@@ -388,6 +402,16 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			warm_start_artifacts: prometheus::register(
+				prometheus::CounterVec::new(
+					prometheus::Opts::new(
+						"polkadot_pvf_warm_start_artifacts",
+						"The number of cached artifacts of the previous run, by outcome of restoring them",
+					),
+					&["outcome"],
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(inner)))
 	}
@@ -573,7 +573,7 @@ async fn corrupted_prepared_artifact_does_not_dispute() {
 }
 
 #[tokio::test]
-async fn cache_cleared_on_startup() {
+async fn cache_restored_on_startup() {
 	// Don't drop this host, it owns the `TempDir` which gets cleared on drop.
 	let host = TestHost::new().await;
 
@@ -582,16 +582,16 @@ async fn cache_cleared_on_startup() {
 		.await
 		.unwrap();
 
-	// The cache dir should contain one artifact and one worker dir.
+	// The cache dir should contain one artifact, the manifest and one worker dir.
 	let cache_dir = host.cache_dir.path().to_owned();
-	assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 2);
+	assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 3);
 
-	// Start a new host, previous artifact should be cleared.
+	// Start a new host, the previous artifact should be restored and the worker dir cleared.
 	let _host = TestHost::new_with_config(|cfg| {
 		cfg.cache_path = cache_dir.clone();
 	})
 	.await;
-	assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 0);
+	assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 2);
 }
 
 // This test checks if the adder parachain runtime can be prepared with 10Mb preparation memory
@@ -42,6 +42,10 @@ enum NemesisVariant {
 	DisputeFinalizedCandidates(DisputeFinalizedCandidatesOptions),
 	/// Spam many request statements instead of sending a single one.
 	SpamStatementRequests(SpamStatementRequestsOptions),
+	/// Withhold the availability data of backed candidates.
+	WithholdAvailability(WithholdAvailabilityOptions),
+	/// Withhold or delay own approval assignments and votes.
+	ApprovalNoShow(ApprovalNoShowOptions),
 }
 
 #[derive(Debug, Parser)]
@@ -105,6 +109,24 @@ impl MalusCli {
 
 				polkadot_cli::run_node(cli, SpamStatementRequests { spam_factor }, finality_delay)?
 			},
+			NemesisVariant::WithholdAvailability(opts) => {
+				let WithholdAvailabilityOptions { percentage, cli } = opts;
+
+				polkadot_cli::run_node(cli, WithholdAvailability { percentage }, finality_delay)?
+			},
+			NemesisVariant::ApprovalNoShow(opts) => {
+				let ApprovalNoShowOptions { misbehavior, delay_ms, percentage, cli } = opts;
+
+				polkadot_cli::run_node(
+					cli,
+					ApprovalNoShow {
+						misbehavior,
+						delay: std::time::Duration::from_millis(delay_ms),
+						percentage,
+					},
+					finality_delay,
+				)?
+			},
 		}
 		Ok(())
 	}
@@ -244,4 +266,63 @@ mod tests {
 			assert!(opts.cli.run.base.bob);
 		});
 	}
+
+	#[test]
+	fn withhold_availability_works() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"withhold-availability",
+			"--percentage",
+			"50",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::WithholdAvailability(opts),
+			..
+		} => {
+			assert_eq!(opts.percentage, 50);
+			assert!(opts.cli.run.base.bob);
+		});
+	}
+
+	#[test]
+	fn approval_no_show_works() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"approval-no-show",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::ApprovalNoShow(opts),
+			..
+		} => {
+			assert_eq!(opts.misbehavior, ApprovalMisbehavior::NoShow);
+			assert_eq!(opts.percentage, 100);
+			assert!(opts.cli.run.base.bob);
+		});
+	}
+
+	#[test]
+	fn approval_delay_assignments_works() {
+		let cli = MalusCli::try_parse_from(IntoIterator::into_iter([
+			"malus",
+			"approval-no-show",
+			"--misbehavior",
+			"delay-assignments",
+			"--delay-ms",
+			"30000",
+			"--bob",
+		]))
+		.unwrap();
+		assert_matches::assert_matches!(cli, MalusCli {
+			variant: NemesisVariant::ApprovalNoShow(opts),
+			..
+		} => {
+			assert_eq!(opts.misbehavior, ApprovalMisbehavior::DelayAssignments);
+			assert_eq!(opts.delay_ms, 30000);
+			assert!(opts.cli.run.base.bob);
+		});
+	}
 }
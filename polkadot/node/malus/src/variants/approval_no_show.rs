@@ -0,0 +1,341 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node variant that misbehaves as an approval checker.
+//!
+//! This malus variant checks candidates honestly, but tampers with the distribution of its own
+//! assignments and approvals:
+//! - `no-show`: the assignments are distributed, the approvals never are. Other validators see the
+//!   node as a no-show and have to escalate to the next tranches to cover for it.
+//! - `delay-assignments`: the assignments and approvals are distributed with a delay, so they may
+//!   arrive after the no-show timeout.
+//!
+//! Messages of other validators relayed by the node are left untouched. The node learns its own
+//! validator index from the gossip topology of each session.
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use futures::channel::mpsc;
+use futures_timer::Delay;
+use polkadot_cli::{
+	service::{
+		AuxStore, Error, ExtendedOverseerGenArgs, Overseer, OverseerConnector, OverseerGen,
+		OverseerGenArgs, OverseerHandle,
+	},
+	validator_overseer_builder, Cli,
+};
+use polkadot_node_network_protocol::{v3 as protocol_v3, ValidationProtocols};
+use polkadot_node_subsystem::SpawnGlue;
+use polkadot_node_subsystem_types::{ChainApiBackend, RuntimeApiSubsystemClient};
+use polkadot_primitives::{SessionIndex, ValidatorIndex};
+use sp_core::traits::SpawnNamed;
+
+use rand::distributions::{Bernoulli, Distribution};
+
+// Filter wrapping related types.
+use crate::{
+	interceptor::*,
+	shared::{launch_processing_task, MALUS},
+};
+
+use std::{
+	collections::BTreeMap,
+	sync::{Arc, Mutex},
+	time::Duration,
+};
+
+/// The number of sessions our validator index is remembered for.
+const KEEP_OWN_INDICES_SESSIONS: usize = 3;
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+#[value(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum ApprovalMisbehavior {
+	/// Distribute assignments, but never the approvals.
+	NoShow,
+	/// Distribute assignments and approvals with a delay.
+	DelayAssignments,
+}
+
+/// Wraps around approval-voting-parallel and filters our own outgoing approval messages.
+#[derive(Clone)]
+struct ApprovalWithholder<Spawner> {
+	spawner: Spawner,
+	misbehavior: ApprovalMisbehavior,
+	delay: Duration,
+	distribution: Bernoulli,
+	/// Our validator index in the most recent sessions.
+	own_indices: Arc<Mutex<BTreeMap<SessionIndex, ValidatorIndex>>>,
+	/// Messages to send once `delay` elapsed.
+	delayed_tx: mpsc::UnboundedSender<NetworkBridgeTxMessage>,
+	/// The receiving end of `delayed_tx`, taken when the task sending the messages is launched.
+	delayed_rx: Arc<Mutex<Option<mpsc::UnboundedReceiver<NetworkBridgeTxMessage>>>>,
+}
+
+impl<Spawner> ApprovalWithholder<Spawner> {
+	fn is_own(&self, validator: &ValidatorIndex) -> bool {
+		self.own_indices
+			.lock()
+			.expect("poisoned lock")
+			.values()
+			.any(|own| own == validator)
+	}
+
+	fn misbehaves(&self, validator: &ValidatorIndex) -> bool {
+		self.is_own(validator) && self.distribution.sample(&mut rand::thread_rng())
+	}
+
+	/// Split the message into the part to pass through and the part to withhold.
+	fn split(
+		&self,
+		message: &protocol_v3::ApprovalDistributionMessage,
+	) -> (
+		Option<protocol_v3::ApprovalDistributionMessage>,
+		Option<protocol_v3::ApprovalDistributionMessage>,
+	) {
+		match message {
+			protocol_v3::ApprovalDistributionMessage::Assignments(assignments) => {
+				if self.misbehavior != ApprovalMisbehavior::DelayAssignments {
+					return (Some(message.clone()), None)
+				}
+				let (withheld, passed): (Vec<_>, Vec<_>) = assignments
+					.iter()
+					.cloned()
+					.partition(|(cert, _)| self.misbehaves(&cert.validator));
+				(
+					(!passed.is_empty())
+						.then(|| protocol_v3::ApprovalDistributionMessage::Assignments(passed)),
+					(!withheld.is_empty())
+						.then(|| protocol_v3::ApprovalDistributionMessage::Assignments(withheld)),
+				)
+			},
+			protocol_v3::ApprovalDistributionMessage::Approvals(approvals) => {
+				let (withheld, passed): (Vec<_>, Vec<_>) =
+					approvals.iter().cloned().partition(|vote| match self.misbehavior {
+						ApprovalMisbehavior::NoShow => self.misbehaves(&vote.validator),
+						// Approvals must not overtake the delayed assignments.
+						ApprovalMisbehavior::DelayAssignments => self.is_own(&vote.validator),
+					});
+				(
+					(!passed.is_empty())
+						.then(|| protocol_v3::ApprovalDistributionMessage::Approvals(passed)),
+					(!withheld.is_empty())
+						.then(|| protocol_v3::ApprovalDistributionMessage::Approvals(withheld)),
+				)
+			},
+		}
+	}
+}
+
+impl<Sender, Spawner> MessageInterceptor<Sender> for ApprovalWithholder<Spawner>
+where
+	Sender: overseer::ApprovalVotingParallelSenderTrait + Clone + Send + 'static,
+	Spawner: 'static + SpawnNamed + Clone + Unpin,
+{
+	type Message = ApprovalVotingParallelMessage;
+
+	/// Learn our validator index from the gossip topology and launch the task sending the delayed
+	/// messages on the first message.
+	fn intercept_incoming(
+		&self,
+		subsystem_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		if let Some(delayed_rx) = self.delayed_rx.lock().expect("poisoned lock").take() {
+			let sender = subsystem_sender.clone();
+			let delay = self.delay;
+			launch_processing_task(&self.spawner, delayed_rx, move |msg| {
+				let mut sender = sender.clone();
+				async move {
+					Delay::new(delay).await;
+					sender.send_message(msg).await;
+				}
+			});
+		}
+
+		match msg {
+			FromOrchestra::Communication {
+				msg:
+					ApprovalVotingParallelMessage::NetworkBridgeUpdate(
+						NetworkBridgeEvent::NewGossipTopology(ref topology),
+					),
+			} => {
+				if let Some(local_index) = topology.local_index {
+					gum::info!(
+						target: MALUS,
+						session = topology.session,
+						?local_index,
+						"😈 Learned our validator index.",
+					);
+					let mut own_indices = self.own_indices.lock().expect("poisoned lock");
+					own_indices.insert(topology.session, local_index);
+					while own_indices.len() > KEEP_OWN_INDICES_SESSIONS {
+						own_indices.pop_first();
+					}
+				}
+				Some(msg)
+			},
+			FromOrchestra::Communication { msg } => Some(FromOrchestra::Communication { msg }),
+			FromOrchestra::Signal(signal) => Some(FromOrchestra::Signal(signal)),
+		}
+	}
+
+	fn need_intercept_outgoing(
+		&self,
+		msg: &overseer::ApprovalVotingParallelOutgoingMessages,
+	) -> bool {
+		matches!(
+			msg,
+			overseer::ApprovalVotingParallelOutgoingMessages::NetworkBridgeTxMessage(
+				NetworkBridgeTxMessage::SendValidationMessage(
+					_,
+					ValidationProtocols::V3(protocol_v3::ValidationProtocol::ApprovalDistribution(
+						_
+					)),
+				)
+			)
+		)
+	}
+
+	/// Withhold our own assignments and approvals from the outgoing approval-distribution
+	/// messages.
+	fn intercept_outgoing(
+		&self,
+		msg: &overseer::ApprovalVotingParallelOutgoingMessages,
+	) -> Option<overseer::ApprovalVotingParallelOutgoingMessages> {
+		let overseer::ApprovalVotingParallelOutgoingMessages::NetworkBridgeTxMessage(
+			NetworkBridgeTxMessage::SendValidationMessage(
+				peers,
+				ValidationProtocols::V3(protocol_v3::ValidationProtocol::ApprovalDistribution(
+					message,
+				)),
+			),
+		) = msg
+		else {
+			unreachable!("Only approval-distribution messages are intercepted; qed");
+		};
+
+		let into_network_message = |message| {
+			NetworkBridgeTxMessage::SendValidationMessage(
+				peers.clone(),
+				ValidationProtocols::V3(protocol_v3::ValidationProtocol::ApprovalDistribution(
+					message,
+				)),
+			)
+		};
+
+		let (passed, withheld) = self.split(message);
+		if let Some(withheld) = withheld {
+			match self.misbehavior {
+				ApprovalMisbehavior::NoShow => {
+					gum::info!(target: MALUS, ?withheld, "😈 Not distributing our approvals.");
+				},
+				ApprovalMisbehavior::DelayAssignments => {
+					gum::info!(
+						target: MALUS,
+						?withheld,
+						"😈 Delaying our assignments and approvals by {:?}.",
+						self.delay,
+					);
+					let _ = self.delayed_tx.unbounded_send(into_network_message(withheld));
+				},
+			}
+		}
+
+		passed.map(|message| {
+			overseer::ApprovalVotingParallelOutgoingMessages::NetworkBridgeTxMessage(
+				into_network_message(message),
+			)
+		})
+	}
+}
+
+//----------------------------------------------------------------------------------
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct ApprovalNoShowOptions {
+	/// How the node misbehaves as an approval checker.
+	#[arg(long, value_enum, ignore_case = true, default_value_t = ApprovalMisbehavior::NoShow)]
+	pub misbehavior: ApprovalMisbehavior,
+
+	/// Applies only to `--misbehavior delay-assignments`: the delay in milliseconds.
+	#[clap(long, ignore_case = true, default_value_t = 12_000)]
+	pub delay_ms: u64,
+
+	/// Determines the percentage of our assignments or approvals that are withheld.
+	/// Must be in the range [0..=100].
+	#[clap(short, long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub percentage: u8,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// ApprovalNoShow implementation wrapper which implements `OverseerGen` glue.
+pub(crate) struct ApprovalNoShow {
+	/// How the node misbehaves as an approval checker.
+	pub misbehavior: ApprovalMisbehavior,
+	/// The delay of the assignments and approvals.
+	pub delay: Duration,
+	/// The probability of withholding an assignment or approval.
+	pub percentage: u8,
+}
+
+impl OverseerGen for ApprovalNoShow {
+	fn generate<Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'_, Spawner, RuntimeClient>,
+		ext_args: Option<ExtendedOverseerGenArgs>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: RuntimeApiSubsystemClient + ChainApiBackend + AuxStore + 'static,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		gum::info!(
+			target: MALUS,
+			"😈 Started Malus node misbehaving as approval checker: {:?}, with a {:?} percent chance per assignment or approval.",
+			self.misbehavior,
+			&self.percentage,
+		);
+
+		let (delayed_tx, delayed_rx) = mpsc::unbounded();
+		let withholder = ApprovalWithholder {
+			spawner: args.spawner.clone(),
+			misbehavior: self.misbehavior,
+			delay: self.delay,
+			distribution: Bernoulli::new(f64::from(self.percentage) / 100.0)
+				.expect("Invalid probability! Percentage must be in range [0..=100]."),
+			own_indices: Default::default(),
+			delayed_tx,
+			delayed_rx: Arc::new(Mutex::new(Some(delayed_rx))),
+		};
+
+		validator_overseer_builder(
+			args,
+			ext_args.expect("Extended arguments required to build validator overseer are provided"),
+		)?
+		.replace_approval_voting_parallel(move |approval_voting| {
+			InterceptedSubsystem::new(approval_voting, withholder)
+		})
+		.build_with_connector(connector)
+		.map_err(|e| e.into())
+	}
+}
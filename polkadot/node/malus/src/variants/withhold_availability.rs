@@ -0,0 +1,183 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A malicious node variant that withholds the availability data of the candidates it backs.
+//!
+//! This malus variant behaves honestly in backing: it validates candidates, signs statements and
+//! stores the erasure chunks of the candidates it backs. Afterwards it refuses to serve the chunks
+//! and the full available data of those candidates, while still claiming them available in its
+//! bitfields. Other validators have to fetch their chunks from the remaining backers and approval
+//! checkers have to fall back from backer fetching to chunk recovery.
+//!
+//! Attention: For usage with `zombienet` only!
+
+#![allow(missing_docs)]
+
+use polkadot_cli::{
+	service::{
+		AuxStore, Error, ExtendedOverseerGenArgs, Overseer, OverseerConnector, OverseerGen,
+		OverseerGenArgs, OverseerHandle,
+	},
+	validator_overseer_builder, Cli,
+};
+use polkadot_node_subsystem::SpawnGlue;
+use polkadot_node_subsystem_types::{ChainApiBackend, RuntimeApiSubsystemClient};
+use polkadot_primitives::CandidateHash;
+use sp_core::traits::SpawnNamed;
+
+use rand::distributions::{Bernoulli, Distribution};
+
+// Filter wrapping related types.
+use crate::{interceptor::*, shared::MALUS};
+
+use std::{
+	collections::VecDeque,
+	sync::{Arc, Mutex},
+};
+
+/// The number of candidates whose data is withheld, older candidates are served again.
+const MAX_WITHHELD_CANDIDATES: usize = 1024;
+
+/// Wraps around the availability store and hides the data of the candidates backed by us.
+#[derive(Clone)]
+struct AvailabilityWithholder {
+	distribution: Bernoulli,
+	/// The most recent candidates backed by us whose data is withheld.
+	withheld: Arc<Mutex<VecDeque<CandidateHash>>>,
+}
+
+impl AvailabilityWithholder {
+	fn is_withheld(&self, candidate_hash: &CandidateHash) -> bool {
+		self.withheld.lock().expect("poisoned lock").contains(candidate_hash)
+	}
+}
+
+impl<Sender> MessageInterceptor<Sender> for AvailabilityWithholder
+where
+	Sender: overseer::AvailabilityStoreSenderTrait + Clone + Send + 'static,
+{
+	type Message = AvailabilityStoreMessage;
+
+	/// Note the candidates stored after backing and answer the queries for their chunks and data
+	/// as if nothing was stored.
+	fn intercept_incoming(
+		&self,
+		_subsystem_sender: &mut Sender,
+		msg: FromOrchestra<Self::Message>,
+	) -> Option<FromOrchestra<Self::Message>> {
+		match msg {
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::StoreAvailableData { ref candidate_hash, .. },
+			} => {
+				// Only backers store the full available data, other validators store their chunk.
+				if self.distribution.sample(&mut rand::thread_rng()) {
+					gum::info!(
+						target: MALUS,
+						?candidate_hash,
+						"😈 Withholding the availability data of a backed candidate.",
+					);
+					let mut withheld = self.withheld.lock().expect("poisoned lock");
+					if withheld.len() == MAX_WITHHELD_CANDIDATES {
+						withheld.pop_front();
+					}
+					withheld.push_back(*candidate_hash);
+				}
+				Some(msg)
+			},
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryChunk(candidate_hash, validator_index, tx),
+			} if self.is_withheld(&candidate_hash) => {
+				gum::debug!(
+					target: MALUS,
+					?candidate_hash,
+					?validator_index,
+					"😈 Withholding erasure chunk.",
+				);
+				let _ = tx.send(None);
+				None
+			},
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryAllChunks(candidate_hash, tx),
+			} if self.is_withheld(&candidate_hash) => {
+				let _ = tx.send(Vec::new());
+				None
+			},
+			FromOrchestra::Communication {
+				msg: AvailabilityStoreMessage::QueryAvailableData(candidate_hash, tx),
+			} if self.is_withheld(&candidate_hash) => {
+				gum::debug!(target: MALUS, ?candidate_hash, "😈 Withholding available data.");
+				let _ = tx.send(None);
+				None
+			},
+			FromOrchestra::Communication { msg } => Some(FromOrchestra::Communication { msg }),
+			FromOrchestra::Signal(signal) => Some(FromOrchestra::Signal(signal)),
+		}
+	}
+}
+
+//----------------------------------------------------------------------------------
+
+#[derive(Debug, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct WithholdAvailabilityOptions {
+	/// Determines the percentage of backed candidates whose availability data is withheld.
+	/// Must be in the range [0..=100].
+	#[clap(short, long, ignore_case = true, default_value_t = 100, value_parser = clap::value_parser!(u8).range(0..=100))]
+	pub percentage: u8,
+
+	#[clap(flatten)]
+	pub cli: Cli,
+}
+
+/// WithholdAvailability implementation wrapper which implements `OverseerGen` glue.
+pub(crate) struct WithholdAvailability {
+	/// The probability of withholding the data of a backed candidate.
+	pub percentage: u8,
+}
+
+impl OverseerGen for WithholdAvailability {
+	fn generate<Spawner, RuntimeClient>(
+		&self,
+		connector: OverseerConnector,
+		args: OverseerGenArgs<'_, Spawner, RuntimeClient>,
+		ext_args: Option<ExtendedOverseerGenArgs>,
+	) -> Result<(Overseer<SpawnGlue<Spawner>, Arc<RuntimeClient>>, OverseerHandle), Error>
+	where
+		RuntimeClient: RuntimeApiSubsystemClient + ChainApiBackend + AuxStore + 'static,
+		Spawner: 'static + SpawnNamed + Clone + Unpin,
+	{
+		gum::info!(
+			target: MALUS,
+			"😈 Started Malus node that withholds the availability data of {:?} percent of the candidates it backs.",
+			&self.percentage,
+		);
+
+		let withholder = AvailabilityWithholder {
+			distribution: Bernoulli::new(f64::from(self.percentage) / 100.0)
+				.expect("Invalid probability! Percentage must be in range [0..=100]."),
+			withheld: Default::default(),
+		};
+
+		validator_overseer_builder(
+			args,
+			ext_args.expect("Extended arguments required to build validator overseer are provided"),
+		)?
+		.replace_availability_store(move |av_store| InterceptedSubsystem::new(av_store, withholder))
+		.build_with_connector(connector)
+		.map_err(|e| e.into())
+	}
+}
@@ -831,7 +831,7 @@ async fn process_msg<Context>(
 				);
 			}
 		},
-		msg @ (Invalid(..) | Seconded(..)) => {
+		msg @ (Invalid(..) | Seconded(..) | QueryReputations(..)) => {
 			gum::warn!(
 				target: LOG_TARGET,
 				"{:?} message is not expected on the collator side of the protocol",
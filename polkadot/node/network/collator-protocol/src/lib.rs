@@ -21,6 +21,8 @@
 #![deny(unused_crate_dependencies)]
 #![recursion_limit = "256"]
 
+#[cfg(feature = "experimental-collator-protocol")]
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use futures::{
@@ -28,6 +30,8 @@ use futures::{
 	FutureExt, TryFutureExt,
 };
 
+#[cfg(feature = "experimental-collator-protocol")]
+use polkadot_node_subsystem_util::database::Database;
 use polkadot_node_subsystem_util::reputation::ReputationAggregator;
 use sp_keystore::KeystorePtr;
 
@@ -76,13 +80,22 @@ pub enum ProtocolSide {
 		/// Prometheus metrics for validators.
 		metrics: validator_side::Metrics,
 	},
-	/// Experimental variant of the validator side. Do not use in production.
+	/// The validator side, also keeping the collator reputations of the experimental peer
+	/// manager. Do not use in production.
 	#[cfg(feature = "experimental-collator-protocol")]
 	ValidatorExperimental {
 		/// The keystore holding validator keys.
 		keystore: KeystorePtr,
+		/// An eviction policy for inactive peers or validators.
+		eviction_policy: CollatorEvictionPolicy,
 		/// Prometheus metrics for validators.
-		metrics: validator_side_experimental::Metrics,
+		metrics: validator_side::Metrics,
+		/// Prometheus metrics for the collator reputations.
+		reputation_metrics: validator_side_experimental::Metrics,
+		/// The database the collator reputations are persisted to.
+		db: Arc<dyn Database>,
+		/// The column of `db` holding the collator reputations.
+		col_reputation_data: u32,
 	},
 	/// Collators operate on a parachain.
 	Collator {
@@ -99,6 +112,14 @@ pub enum ProtocolSide {
 	None,
 }
 
+/// An in-memory database and its column to keep the collator reputations of
+/// [`ProtocolSide::ValidatorExperimental`] in. The reputations are then lost on restart.
+#[cfg(feature = "experimental-collator-protocol")]
+pub fn in_memory_reputation_db() -> (Arc<dyn Database>, u32) {
+	let db = kvdb_memorydb::create(1);
+	(Arc::new(polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(db, &[0])), 0)
+}
+
 /// The collator protocol subsystem.
 pub struct CollatorProtocolSubsystem {
 	protocol_side: ProtocolSide,
@@ -124,10 +145,30 @@ impl<Context> CollatorProtocolSubsystem {
 					.map_err(|e| SubsystemError::with_origin("collator-protocol", e))
 					.boxed(),
 			#[cfg(feature = "experimental-collator-protocol")]
-			ProtocolSide::ValidatorExperimental { keystore, metrics } =>
-				validator_side_experimental::run(ctx, keystore, metrics)
-					.map_err(|e| SubsystemError::with_origin("collator-protocol", e))
-					.boxed(),
+			ProtocolSide::ValidatorExperimental {
+				keystore,
+				eviction_policy,
+				metrics,
+				reputation_metrics,
+				db,
+				col_reputation_data,
+			} => {
+				let collator_reputations = validator_side_experimental::Reputations::new(
+					keystore.clone(),
+					reputation_metrics,
+					db,
+					col_reputation_data,
+				);
+				validator_side::run_with_reputations(
+					ctx,
+					keystore,
+					eviction_policy,
+					metrics,
+					collator_reputations,
+				)
+				.map_err(|e| SubsystemError::with_origin("collator-protocol", e))
+				.boxed()
+			},
 			ProtocolSide::Collator { peer_id, collator_pair, request_receiver_v2, metrics } =>
 				collator_side::run(ctx, peer_id, collator_pair, request_receiver_v2, metrics)
 					.map_err(|e| SubsystemError::with_origin("collator-protocol", e))
@@ -405,6 +405,10 @@ struct State {
 
 	/// Aggregated reputation change
 	reputation: ReputationAggregator,
+
+	/// The collator reputations kept by the experimental peer manager, if enabled.
+	#[cfg(feature = "experimental-collator-protocol")]
+	collator_reputations: Option<crate::validator_side_experimental::Reputations>,
 }
 
 impl State {
@@ -1452,6 +1456,26 @@ async fn handle_network_msg<Context>(
 	Ok(())
 }
 
+/// Update the collator reputations on an overseer signal.
+///
+/// The reputations are no longer kept after a fatal error, the validator side keeps running.
+#[cfg(feature = "experimental-collator-protocol")]
+async fn handle_collator_reputations_signal<Sender: CollatorProtocolSenderTrait>(
+	sender: &mut Sender,
+	state: &mut State,
+	signal: &OverseerSignal,
+) {
+	let Some(collator_reputations) = state.collator_reputations.as_mut() else { return };
+	if let Err(err) = collator_reputations.handle_signal(sender, signal).await {
+		gum::error!(
+			target: LOG_TARGET,
+			?err,
+			"Failed to update the collator reputations, they are no longer kept",
+		);
+		state.collator_reputations = None;
+	}
+}
+
 /// The main message receiver switch.
 #[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
 async fn process_msg<Context>(
@@ -1478,6 +1502,19 @@ async fn process_msg<Context>(
 				"DistributeCollation message is not expected on the validator side of the protocol",
 			);
 		},
+		QueryReputations(para_id, tx) => {
+			#[cfg(feature = "experimental-collator-protocol")]
+			if let Some(collator_reputations) = &state.collator_reputations {
+				let _ = tx.send(collator_reputations.reputations(para_id).await);
+				return
+			}
+			gum::debug!(
+				target: LOG_TARGET,
+				?para_id,
+				"Collator reputations are only kept by the experimental validator side",
+			);
+			drop(tx);
+		},
 		NetworkBridgeUpdate(event) => {
 			if let Err(e) = handle_network_msg(ctx, state, keystore, event).await {
 				gum::warn!(
@@ -1612,20 +1649,45 @@ pub(crate) async fn run<Context>(
 	.await
 }
 
+/// Run the validator side, keeping the collator reputations alongside.
+#[cfg(feature = "experimental-collator-protocol")]
+#[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
+pub(crate) async fn run_with_reputations<Context>(
+	ctx: Context,
+	keystore: KeystorePtr,
+	eviction_policy: crate::CollatorEvictionPolicy,
+	metrics: Metrics,
+	collator_reputations: crate::validator_side_experimental::Reputations,
+) -> std::result::Result<(), std::convert::Infallible> {
+	let state =
+		State { metrics, collator_reputations: Some(collator_reputations), ..Default::default() };
+	run_with_state(ctx, keystore, eviction_policy, state, REPUTATION_CHANGE_INTERVAL).await
+}
+
 #[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
 async fn run_inner<Context>(
-	mut ctx: Context,
+	ctx: Context,
 	keystore: KeystorePtr,
 	eviction_policy: crate::CollatorEvictionPolicy,
 	metrics: Metrics,
 	reputation: ReputationAggregator,
 	reputation_interval: Duration,
+) -> std::result::Result<(), std::convert::Infallible> {
+	let state = State { metrics, reputation, ..Default::default() };
+	run_with_state(ctx, keystore, eviction_policy, state, reputation_interval).await
+}
+
+#[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
+async fn run_with_state<Context>(
+	mut ctx: Context,
+	keystore: KeystorePtr,
+	eviction_policy: crate::CollatorEvictionPolicy,
+	mut state: State,
+	reputation_interval: Duration,
 ) -> std::result::Result<(), std::convert::Infallible> {
 	let new_reputation_delay = || futures_timer::Delay::new(reputation_interval).fuse();
 	let mut reputation_delay = new_reputation_delay();
 
-	let mut state = State { metrics, reputation, ..Default::default() };
-
 	let next_inactivity_stream = tick_stream(ACTIVITY_POLL);
 	futures::pin_mut!(next_inactivity_stream);
 
@@ -1650,6 +1712,11 @@ async fn run_inner<Context>(
 						).await;
 					}
 					Ok(FromOrchestra::Signal(OverseerSignal::Conclude)) | Err(_) => break,
+					#[cfg(feature = "experimental-collator-protocol")]
+					Ok(FromOrchestra::Signal(signal)) => {
+						handle_collator_reputations_signal(ctx.sender(), &mut state, &signal).await;
+					},
+					#[cfg(not(feature = "experimental-collator-protocol"))]
 					Ok(FromOrchestra::Signal(_)) => continue,
 				}
 			},
@@ -1158,3 +1158,94 @@ fn view_change_clears_old_collators() {
 		virtual_overseer
 	})
 }
+
+#[test]
+fn query_reputations_is_refused_without_collator_reputations() {
+	let test_state = TestState::default();
+
+	test_harness(ReputationAggregator::new(|_| true), |test_harness| async move {
+		let TestHarness { mut virtual_overseer, .. } = test_harness;
+
+		let (tx, rx) = oneshot::channel();
+		overseer_send(
+			&mut virtual_overseer,
+			CollatorProtocolMessage::QueryReputations(Some(test_state.chain_ids[0]), tx),
+		)
+		.await;
+		assert!(rx.await.is_err());
+
+		virtual_overseer
+	})
+}
+
+#[cfg(feature = "experimental-collator-protocol")]
+#[test]
+fn collator_reputations_are_kept_alongside_the_validator_side() {
+	use polkadot_node_subsystem::{errors::RuntimeApiError, ActiveLeavesUpdate};
+
+	sp_tracing::init_for_tests();
+
+	let pool = sp_core::testing::TaskExecutor::new();
+	let (context, mut virtual_overseer) = test_helpers::make_subsystem_context(pool.clone());
+
+	let keystore: KeystorePtr = Arc::new(sc_keystore::LocalKeystore::in_memory());
+	let (db, col_reputation_data) = crate::in_memory_reputation_db();
+	let collator_reputations = crate::validator_side_experimental::Reputations::new(
+		keystore.clone(),
+		Default::default(),
+		db,
+		col_reputation_data,
+	);
+	let subsystem = run_with_reputations(
+		context,
+		keystore,
+		Default::default(),
+		Metrics::default(),
+		collator_reputations,
+	);
+
+	let test_fut = async move {
+		// The reputations are answered, and empty until they are loaded.
+		let (tx, rx) = oneshot::channel();
+		overseer_send(&mut virtual_overseer, CollatorProtocolMessage::QueryReputations(None, tx))
+			.await;
+		assert_eq!(rx.await.unwrap(), Vec::new());
+
+		// They are loaded on the first activated leaf.
+		let leaf = Hash::repeat_byte(1);
+		overseer_signal(
+			&mut virtual_overseer,
+			OverseerSignal::ActiveLeaves(ActiveLeavesUpdate::start_work(
+				test_helpers::mock::new_leaf(leaf, 1),
+			)),
+		)
+		.await;
+		assert_matches!(
+			overseer_recv(&mut virtual_overseer).await,
+			AllMessages::RuntimeApi(RuntimeApiMessage::Request(
+				hash,
+				RuntimeApiRequest::Validators(tx),
+			)) => {
+				assert_eq!(hash, leaf);
+				// Loading is retried on the next leaf after a runtime API error.
+				tx.send(Err(RuntimeApiError::NotSupported { runtime_api_name: "validators" }))
+					.unwrap();
+			}
+		);
+
+		// The reputations are still kept.
+		let (tx, rx) = oneshot::channel();
+		overseer_send(
+			&mut virtual_overseer,
+			CollatorProtocolMessage::QueryReputations(Some(ParaId::from(1)), tx),
+		)
+		.await;
+		assert_eq!(rx.await.unwrap(), Vec::new());
+
+		overseer_signal(&mut virtual_overseer, OverseerSignal::Conclude).await;
+	};
+
+	futures::pin_mut!(test_fut);
+	futures::pin_mut!(subsystem);
+	executor::block_on(future::join(test_fut, subsystem)).1.unwrap();
+}
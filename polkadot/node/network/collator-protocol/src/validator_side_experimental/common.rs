@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use std::num::NonZeroU16;
+use std::{num::NonZeroU16, time::Duration};
 
 use polkadot_node_network_protocol::peer_set::CollationVersion;
 use polkadot_primitives::Id as ParaId;
@@ -43,6 +43,10 @@ pub const VALID_INCLUDED_CANDIDATE_BUMP: u16 = 50;
 /// authored by the peer)
 pub const INACTIVITY_DECAY: u16 = 1;
 
+/// Reputations persisted to disk are decayed by `INACTIVITY_DECAY` for each such interval the node
+/// was offline, when loaded on startup. A downtime of a week resets all reputations.
+pub const OFFLINE_DECAY_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Maximum number of stored peer scores for a paraid. Should be greater than
 /// `CONNECTED_PEERS_PARA_LIMIT`.
 pub const MAX_STORED_SCORES_PER_PARA: u8 = 150;
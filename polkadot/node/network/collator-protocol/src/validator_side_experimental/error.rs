@@ -44,6 +44,9 @@ pub enum Error {
 	#[fatal]
 	#[error("Receiving message from overseer failed: {0}")]
 	SubsystemReceive(#[source] SubsystemError),
+	#[fatal]
+	#[error("Reading the persisted collator reputations failed: {0}")]
+	ReputationDatabase(#[source] std::io::Error),
 }
 
 /// Utility for eating top level errors and log them.
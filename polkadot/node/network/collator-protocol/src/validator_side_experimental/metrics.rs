@@ -14,15 +14,80 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
+use crate::validator_side_experimental::peer_manager::{ReputationUpdate, ReputationUpdateKind};
 use polkadot_node_subsystem_util::metrics::{self, prometheus};
 
 #[derive(Clone, Default)]
-pub struct Metrics;
+pub struct Metrics(Option<MetricsInner>);
+
+impl Metrics {
+	/// Note the current number of stored collator reputations.
+	pub fn note_stored_reputations(&self, stored: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.stored_reputations.set(stored as u64);
+		}
+	}
+
+	/// Note the given reputation updates.
+	pub fn on_reputation_updates(&self, updates: &[ReputationUpdate]) {
+		if let Some(metrics) = &self.0 {
+			for update in updates {
+				let kind = match update.kind {
+					ReputationUpdateKind::Bump => "bump",
+					ReputationUpdateKind::Slash => "slash",
+				};
+				metrics.reputation_updates.with_label_values(&[kind]).inc();
+			}
+		}
+	}
+
+	/// Note the number of reputations removed on startup because they decayed to zero while the
+	/// node was offline.
+	pub fn on_reputations_expired(&self, expired: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.expired_reputations.inc_by(expired as u64);
+		}
+	}
+}
+
+#[derive(Clone)]
+struct MetricsInner {
+	stored_reputations: prometheus::Gauge<prometheus::U64>,
+	reputation_updates: prometheus::CounterVec<prometheus::U64>,
+	expired_reputations: prometheus::Counter<prometheus::U64>,
+}
 
 impl metrics::Metrics for Metrics {
 	fn try_register(
-		_registry: &prometheus::Registry,
+		registry: &prometheus::Registry,
 	) -> std::result::Result<Self, prometheus::PrometheusError> {
-		Ok(Metrics)
+		let metrics = MetricsInner {
+			stored_reputations: prometheus::register(
+				prometheus::Gauge::new(
+					"polkadot_parachain_collator_protocol_stored_reputations",
+					"Number of collator reputations stored by the validator",
+				)?,
+				registry,
+			)?,
+			reputation_updates: prometheus::register(
+				prometheus::CounterVec::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_collator_protocol_reputation_updates_total",
+						"Number of collator reputation updates, by kind",
+					),
+					&["kind"],
+				)?,
+				registry,
+			)?,
+			expired_reputations: prometheus::register(
+				prometheus::Counter::new(
+					"polkadot_parachain_collator_protocol_expired_reputations_total",
+					"Number of collator reputations which decayed to zero while the node was offline",
+				)?,
+				registry,
+			)?,
+		};
+
+		Ok(Metrics(Some(metrics)))
 	}
 }
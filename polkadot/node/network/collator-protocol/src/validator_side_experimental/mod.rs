@@ -25,18 +25,18 @@ mod metrics;
 mod peer_manager;
 mod state;
 
-use std::collections::VecDeque;
+use std::{collections::VecDeque, sync::Arc};
 
 use common::MAX_STORED_SCORES_PER_PARA;
 use error::{log_error, FatalError, FatalResult, Result};
 use fatality::Split;
 use peer_manager::{Db, PeerManager};
 use polkadot_node_subsystem::{
-	overseer, ActivatedLeaf, CollatorProtocolSenderTrait, FromOrchestra, OverseerSignal,
+	messages::CollatorReputation, CollatorProtocolSenderTrait, OverseerSignal,
 };
 use polkadot_node_subsystem_util::{
-	find_validator_group, request_claim_queue, request_validator_groups, request_validators,
-	runtime::recv_runtime, signing_key_and_index,
+	database::Database, find_validator_group, request_claim_queue, request_validator_groups,
+	request_validators, runtime::recv_runtime, signing_key_and_index,
 };
 use polkadot_primitives::{Hash, Id as ParaId};
 use sp_keystore::KeystorePtr;
@@ -46,75 +46,87 @@ pub use metrics::Metrics;
 
 use crate::LOG_TARGET;
 
-/// The main run loop.
-#[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
-pub(crate) async fn run<Context>(
-	mut ctx: Context,
+/// Keeps the collator reputations of the experimental peer manager, persisted to the node DB,
+/// while the validator side services the collator protocol.
+pub(crate) struct Reputations {
 	keystore: KeystorePtr,
 	metrics: Metrics,
-) -> FatalResult<()> {
-	if let Some(_state) = initialize(&mut ctx, keystore, metrics).await? {
-		// run_inner(state);
+	db: Arc<dyn Database>,
+	col_reputation_data: u32,
+	/// Initialized on the first activated leaf.
+	state: Option<State<Db>>,
+}
+
+impl Reputations {
+	/// Keep the collator reputations in the column `col_reputation_data` of `db`.
+	pub(crate) fn new(
+		keystore: KeystorePtr,
+		metrics: Metrics,
+		db: Arc<dyn Database>,
+		col_reputation_data: u32,
+	) -> Self {
+		Self { keystore, metrics, db, col_reputation_data, state: None }
 	}
 
-	Ok(())
-}
+	/// Update the reputations on an overseer signal, loading them on the first activated leaf.
+	pub(crate) async fn handle_signal<Sender: CollatorProtocolSenderTrait>(
+		&mut self,
+		sender: &mut Sender,
+		signal: &OverseerSignal,
+	) -> FatalResult<()> {
+		match (signal, &mut self.state) {
+			(OverseerSignal::ActiveLeaves(update), None) => {
+				let Some(leaf) = &update.activated else { return Ok(()) };
+				self.state = self.initialize(sender, leaf.hash).await?;
+			},
+			(OverseerSignal::BlockFinalized(hash, number), Some(state)) => {
+				log_error(state.handle_finalized_block(sender, *hash, *number).await)?;
+			},
+			_ => {},
+		}
 
-#[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
-async fn initialize<Context>(
-	ctx: &mut Context,
-	keystore: KeystorePtr,
-	metrics: Metrics,
-) -> FatalResult<Option<State<Db>>> {
-	loop {
-		let first_leaf = match wait_for_first_leaf(ctx).await? {
-			Some(activated_leaf) => activated_leaf,
-			None => return Ok(None),
-		};
+		Ok(())
+	}
 
-		let scheduled_paras = match scheduled_paras(ctx.sender(), first_leaf.hash, &keystore).await
-		{
+	/// The stored collator reputations, optionally only those of the given para.
+	///
+	/// Empty until the reputations are loaded.
+	pub(crate) async fn reputations(&self, para_id: Option<ParaId>) -> Vec<CollatorReputation> {
+		match &self.state {
+			Some(state) => state.reputations(para_id).await,
+			None => Vec::new(),
+		}
+	}
+
+	/// Load the reputations at the given leaf, returning `None` to retry on the next one.
+	async fn initialize<Sender: CollatorProtocolSenderTrait>(
+		&self,
+		sender: &mut Sender,
+		leaf: Hash,
+	) -> FatalResult<Option<State<Db>>> {
+		let scheduled_paras = match scheduled_paras(sender, leaf, &self.keystore).await {
 			Ok(paras) => paras,
 			Err(err) => {
 				log_error(Err(err))?;
-				continue
+				return Ok(None)
 			},
 		};
 
-		let backend = Db::new(MAX_STORED_SCORES_PER_PARA).await;
-
-		match PeerManager::startup(backend, ctx.sender(), scheduled_paras.into_iter().collect())
-			.await
-		{
-			Ok(peer_manager) => return Ok(Some(State::new(peer_manager, keystore, metrics))),
+		let backend = Db::open(
+			MAX_STORED_SCORES_PER_PARA,
+			self.db.clone(),
+			self.col_reputation_data,
+			self.metrics.clone(),
+		)
+		.await
+		.map_err(FatalError::ReputationDatabase)?;
+
+		match PeerManager::startup(backend, sender, scheduled_paras.into_iter().collect()).await {
+			Ok(peer_manager) =>
+				Ok(Some(State::new(peer_manager, self.keystore.clone(), self.metrics.clone()))),
 			Err(err) => {
 				log_error(Err(err))?;
-				continue
-			},
-		}
-	}
-}
-
-/// Wait for `ActiveLeavesUpdate`, returns `None` if `Conclude` signal came first.
-#[overseer::contextbounds(CollatorProtocol, prefix = self::overseer)]
-async fn wait_for_first_leaf<Context>(ctx: &mut Context) -> FatalResult<Option<ActivatedLeaf>> {
-	loop {
-		match ctx.recv().await.map_err(FatalError::SubsystemReceive)? {
-			FromOrchestra::Signal(OverseerSignal::Conclude) => return Ok(None),
-			FromOrchestra::Signal(OverseerSignal::ActiveLeaves(update)) => {
-				if let Some(activated) = update.activated {
-					return Ok(Some(activated))
-				}
-			},
-			FromOrchestra::Signal(OverseerSignal::BlockFinalized(_, _)) => {},
-			FromOrchestra::Communication { msg } => {
-				// TODO: we should actually disconnect peers connected on collation protocol while
-				// we're still bootstrapping. OR buffer these messages until we've bootstrapped.
-				gum::warn!(
-					target: LOG_TARGET,
-					?msg,
-					"Received msg before first active leaves update. This is not expected - message will be dropped."
-				)
+				Ok(None)
 			},
 		}
 	}
@@ -17,6 +17,7 @@
 use crate::validator_side_experimental::{common::Score, peer_manager::ReputationUpdate};
 use async_trait::async_trait;
 use polkadot_node_network_protocol::PeerId;
+use polkadot_node_subsystem::messages::CollatorReputation;
 use polkadot_primitives::{BlockNumber, Id as ParaId};
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 
@@ -43,4 +44,7 @@ pub trait Backend {
 		bumps: BTreeMap<ParaId, HashMap<PeerId, Score>>,
 		decay_value: Option<Score>,
 	) -> Vec<ReputationUpdate>;
+	/// Get the stored reputations, optionally only those of the given paraid, ordered by paraid
+	/// and descending score.
+	async fn reputations(&self, para_id: Option<ParaId>) -> Vec<CollatorReputation>;
 }
@@ -14,24 +14,44 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::validator_side_experimental::{
-	common::Score,
-	peer_manager::{backend::Backend, ReputationUpdate, ReputationUpdateKind},
+use crate::{
+	validator_side_experimental::{
+		common::{Score, INACTIVITY_DECAY, OFFLINE_DECAY_INTERVAL},
+		metrics::Metrics,
+		peer_manager::{backend::Backend, ReputationUpdate, ReputationUpdateKind},
+	},
+	LOG_TARGET,
 };
 use async_trait::async_trait;
+use codec::{Decode, Encode};
 use polkadot_node_network_protocol::PeerId;
+use polkadot_node_subsystem::messages::CollatorReputation;
+use polkadot_node_subsystem_util::database::{DBTransaction, Database};
 use polkadot_primitives::{BlockNumber, Hash, Id as ParaId};
 use std::{
 	collections::{btree_map, hash_map, BTreeMap, BTreeSet, HashMap},
+	io,
+	sync::Arc,
 	time::{SystemTime, UNIX_EPOCH},
 };
 
-/// This is an in-memory temporary implementation for the DB, to be used only for prototyping and
-/// testing purposes.
+// The column the reputations are persisted to may be shared with other subsystems, so all the keys
+// start with `collator-reputation`.
+
+/// Prefix of the keys of the persisted reputations, which are followed by the para id and the
+/// peer id.
+const REPUTATION_PREFIX: &[u8; 25] = b"collator-reputation-score";
+/// Key of the persisted [`Meta`].
+const META_KEY: &[u8; 24] = b"collator-reputation-meta";
+
+/// An in-memory implementation of the DB, which can persist the reputations to a column of the
+/// node's database so that they survive restarts.
 pub struct Db {
 	db: BTreeMap<ParaId, HashMap<PeerId, ScoreEntry>>,
 	last_finalized: Option<BlockNumber>,
 	stored_limit_per_para: u8,
+	persistence: Option<Persistence>,
+	metrics: Metrics,
 }
 
 impl Db {
@@ -39,18 +59,113 @@ impl Db {
 	///
 	/// `stored_limit_per_para` is the maximum number of reputations that can be stored per para.
 	pub async fn new(stored_limit_per_para: u8) -> Self {
-		Self { db: BTreeMap::new(), last_finalized: None, stored_limit_per_para }
+		Self {
+			db: BTreeMap::new(),
+			last_finalized: None,
+			stored_limit_per_para,
+			persistence: None,
+			metrics: Metrics::default(),
+		}
+	}
+
+	/// Create a new instance of the DB, persisting the reputations to the `col` column of `store`.
+	///
+	/// The reputations persisted by a previous run are loaded and decayed by `INACTIVITY_DECAY` for
+	/// each `OFFLINE_DECAY_INTERVAL` elapsed since they were last written. Reputations which
+	/// decay to zero are removed.
+	pub async fn open(
+		stored_limit_per_para: u8,
+		store: Arc<dyn Database>,
+		col: u32,
+		metrics: Metrics,
+	) -> io::Result<Self> {
+		let meta = store.get(col, META_KEY)?.and_then(|raw| match Meta::decode(&mut &raw[..]) {
+			Ok(meta) => Some(meta),
+			Err(err) => {
+				gum::warn!(target: LOG_TARGET, ?err, "Ignoring corrupted reputation metadata");
+				None
+			},
+		});
+		let decay = meta
+			.as_ref()
+			.map_or(0, |meta| offline_decay(now().saturating_sub(meta.written_at)));
+
+		let mut db: BTreeMap<ParaId, HashMap<PeerId, ScoreEntry>> = BTreeMap::new();
+		let mut tx = DBTransaction::new();
+		let mut expired = 0;
+		for item in store.iter_with_prefix(col, REPUTATION_PREFIX) {
+			let (key, value) = item?;
+			let Some((para_id, peer_id, mut entry)) = decode_reputation(&key, &value) else {
+				gum::warn!(target: LOG_TARGET, ?key, "Removing corrupted reputation entry");
+				tx.delete(col, &key);
+				continue
+			};
+
+			if u16::from(entry.score) <= decay {
+				tx.delete(col, &key);
+				expired += 1;
+				continue
+			}
+
+			if decay > 0 {
+				entry.score.saturating_sub(decay);
+				tx.put_vec(col, &key, entry.encode_value());
+			}
+			db.entry(para_id).or_default().insert(peer_id, entry);
+		}
+
+		let instance = Self {
+			db,
+			last_finalized: meta.and_then(|meta| meta.last_finalized),
+			stored_limit_per_para,
+			persistence: Some(Persistence { store, col }),
+			metrics,
+		};
+
+		gum::debug!(
+			target: LOG_TARGET,
+			stored = instance.stored(),
+			expired,
+			decay,
+			"Loaded persisted collator reputations",
+		);
+
+		instance.metrics.on_reputations_expired(expired);
+		instance.persist(tx, BTreeMap::new())?;
+
+		Ok(instance)
 	}
 }
 
 type Timestamp = u128;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 struct ScoreEntry {
 	score: Score,
 	last_bumped: Timestamp,
 }
 
+impl ScoreEntry {
+	fn encode_value(&self) -> Vec<u8> {
+		(u16::from(self.score), self.last_bumped).encode()
+	}
+}
+
+/// The column of the node's database the reputations are persisted to.
+struct Persistence {
+	store: Arc<dyn Database>,
+	col: u32,
+}
+
+/// Metadata persisted along with the reputations.
+#[derive(Encode, Decode)]
+struct Meta {
+	/// The latest finalized block for which bumps were processed.
+	last_finalized: Option<BlockNumber>,
+	/// The time the reputations were last written.
+	written_at: Timestamp,
+}
+
 #[async_trait]
 impl Backend for Db {
 	async fn processed_finalized_block_number(&self) -> Option<BlockNumber> {
@@ -62,6 +177,8 @@ impl Backend for Db {
 	}
 
 	async fn slash(&mut self, peer_id: &PeerId, para_id: &ParaId, value: Score) {
+		let previous = self.snapshot([*para_id]);
+
 		if let btree_map::Entry::Occupied(mut per_para_entry) = self.db.entry(*para_id) {
 			if let hash_map::Entry::Occupied(mut e) = per_para_entry.get_mut().entry(*peer_id) {
 				let score = e.get_mut().score;
@@ -71,6 +188,13 @@ impl Backend for Db {
 				} else {
 					e.get_mut().score.saturating_sub(value.into());
 				}
+
+				self.metrics.on_reputation_updates(&[ReputationUpdate {
+					peer_id: *peer_id,
+					para_id: *para_id,
+					value,
+					kind: ReputationUpdateKind::Slash,
+				}]);
 			}
 
 			// If the per_para length went to 0, remove it completely
@@ -78,10 +202,20 @@ impl Backend for Db {
 				per_para_entry.remove();
 			}
 		}
+
+		self.persist_or_log(DBTransaction::new(), previous);
 	}
 
 	async fn prune_paras(&mut self, registered_paras: BTreeSet<ParaId>) {
+		let mut tx = DBTransaction::new();
+		if let Some(persistence) = &self.persistence {
+			for para_id in self.db.keys().filter(|para| !registered_paras.contains(*para)) {
+				tx.delete_prefix(persistence.col, &reputation_prefix(para_id));
+			}
+		}
+
 		self.db.retain(|para, _| registered_paras.contains(&para));
+		self.persist_or_log(tx, BTreeMap::new());
 	}
 
 	async fn process_bumps(
@@ -95,18 +229,106 @@ impl Backend for Db {
 		}
 
 		self.last_finalized = Some(leaf_number);
-		self.bump_reputations(bumps, decay_value)
+		let previous = self.snapshot(bumps.keys().copied());
+		let updates = self.bump_reputations(bumps, decay_value);
+
+		self.metrics.on_reputation_updates(&updates);
+		self.persist_or_log(DBTransaction::new(), previous);
+
+		updates
+	}
+
+	async fn reputations(&self, para_id: Option<ParaId>) -> Vec<CollatorReputation> {
+		self.db
+			.iter()
+			.filter(|(para, _)| para_id.map_or(true, |para_id| para_id == **para))
+			.flat_map(|(para_id, per_para)| {
+				let mut reputations = per_para
+					.iter()
+					.map(|(peer_id, entry)| CollatorReputation {
+						para_id: *para_id,
+						peer_id: *peer_id,
+						score: entry.score.into(),
+						last_bumped: entry.last_bumped.try_into().unwrap_or(u64::MAX),
+					})
+					.collect::<Vec<_>>();
+				reputations.sort_by(|a, b| b.score.cmp(&a.score));
+				reputations
+			})
+			.collect()
 	}
 }
 
 impl Db {
+	/// The current reputations of `paras`, to be passed to [`Self::persist`] once modified.
+	fn snapshot(
+		&self,
+		paras: impl IntoIterator<Item = ParaId>,
+	) -> BTreeMap<ParaId, HashMap<PeerId, ScoreEntry>> {
+		if self.persistence.is_none() {
+			return BTreeMap::new()
+		}
+
+		paras
+			.into_iter()
+			.map(|para_id| (para_id, self.db.get(&para_id).cloned().unwrap_or_default()))
+			.collect()
+	}
+
+	/// Write the changes to the reputations of the paras of `previous`, which holds their
+	/// reputations before they were modified, along with the operations of `tx` and the metadata.
+	fn persist(
+		&self,
+		mut tx: DBTransaction,
+		previous: BTreeMap<ParaId, HashMap<PeerId, ScoreEntry>>,
+	) -> io::Result<()> {
+		self.metrics.note_stored_reputations(self.stored());
+
+		let Some(Persistence { store, col }) = &self.persistence else { return Ok(()) };
+
+		let no_reputations = HashMap::new();
+		for (para_id, previous) in previous {
+			let current = self.db.get(&para_id).unwrap_or(&no_reputations);
+			for peer_id in previous.keys().filter(|peer_id| !current.contains_key(peer_id)) {
+				tx.delete(*col, &reputation_key(&para_id, peer_id));
+			}
+			for (peer_id, entry) in current {
+				if previous.get(peer_id) != Some(entry) {
+					tx.put_vec(*col, &reputation_key(&para_id, peer_id), entry.encode_value());
+				}
+			}
+		}
+
+		let meta = Meta { last_finalized: self.last_finalized, written_at: now() };
+		tx.put_vec(*col, META_KEY, meta.encode());
+
+		store.write(tx)
+	}
+
+	/// Like [`Self::persist`], but failures are only logged. The reputations are kept in memory
+	/// regardless.
+	fn persist_or_log(
+		&self,
+		tx: DBTransaction,
+		previous: BTreeMap<ParaId, HashMap<PeerId, ScoreEntry>>,
+	) {
+		if let Err(err) = self.persist(tx, previous) {
+			gum::warn!(target: LOG_TARGET, ?err, "Failed to persist collator reputations");
+		}
+	}
+
+	/// The total number of stored reputations.
+	fn stored(&self) -> usize {
+		self.db.values().map(HashMap::len).sum()
+	}
+
 	fn bump_reputations(
 		&mut self,
 		bumps: BTreeMap<ParaId, HashMap<PeerId, Score>>,
 		maybe_decay_value: Option<Score>,
 	) -> Vec<ReputationUpdate> {
 		let mut reported_updates = vec![];
-		let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis();
+		let now = now();
 
 		for (para, bumps_per_para) in bumps {
 			reported_updates.reserve(bumps_per_para.len());
@@ -216,6 +438,40 @@ impl Db {
 	}
 }
 
+fn now() -> Timestamp {
+	SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis()
+}
+
+/// The decay applied to the persisted reputations after the node was offline for `offline`
+/// milliseconds.
+fn offline_decay(offline: Timestamp) -> u16 {
+	(offline / OFFLINE_DECAY_INTERVAL.as_millis())
+		.saturating_mul(INACTIVITY_DECAY.into())
+		.try_into()
+		.unwrap_or(u16::MAX)
+}
+
+fn reputation_prefix(para_id: &ParaId) -> Vec<u8> {
+	let mut key = REPUTATION_PREFIX.to_vec();
+	para_id.using_encoded(|encoded| key.extend_from_slice(encoded));
+	key
+}
+
+fn reputation_key(para_id: &ParaId, peer_id: &PeerId) -> Vec<u8> {
+	let mut key = reputation_prefix(para_id);
+	key.extend_from_slice(&peer_id.to_bytes());
+	key
+}
+
+fn decode_reputation(key: &[u8], value: &[u8]) -> Option<(ParaId, PeerId, ScoreEntry)> {
+	let mut rest = key.strip_prefix(&REPUTATION_PREFIX[..])?;
+	let para_id = ParaId::decode(&mut rest).ok()?;
+	let peer_id = PeerId::from_bytes(rest).ok()?;
+	let (score, last_bumped) = <(u16, Timestamp)>::decode(&mut &value[..]).ok()?;
+
+	Some((para_id, peer_id, ScoreEntry { score: Score::new(score)?, last_bumped }))
+}
+
 #[cfg(test)]
 mod tests {
 	use std::time::Duration;
@@ -762,4 +1018,147 @@ mod tests {
 		assert_eq!(db.len(), 0);
 		assert_eq!(db.query(&peer_id, &ParaId::from(300)).await, None);
 	}
+
+	fn make_store() -> Arc<dyn Database> {
+		crate::in_memory_reputation_db().0
+	}
+
+	#[tokio::test]
+	// Test that the reputations survive a restart.
+	async fn test_persistence() {
+		let store = make_store();
+		let mut db = Db::open(10, store.clone(), 0, Metrics::default()).await.unwrap();
+		assert_eq!(db.processed_finalized_block_number().await, None);
+
+		let peer_id = PeerId::random();
+		let another_peer_id = PeerId::random();
+		db.process_bumps(
+			5,
+			[
+				(
+					ParaId::from(100),
+					[
+						(peer_id, Score::new(10).unwrap()),
+						(another_peer_id, Score::new(20).unwrap()),
+					]
+					.into_iter()
+					.collect(),
+				),
+				(ParaId::from(200), [(peer_id, Score::new(30).unwrap())].into_iter().collect()),
+			]
+			.into_iter()
+			.collect(),
+			None,
+		)
+		.await;
+		db.slash(&another_peer_id, &ParaId::from(100), Score::new(5).unwrap()).await;
+		drop(db);
+
+		let mut db = Db::open(10, store.clone(), 0, Metrics::default()).await.unwrap();
+		assert_eq!(db.processed_finalized_block_number().await, Some(5));
+		assert_eq!(db.query(&peer_id, &ParaId::from(100)).await, Some(Score::new(10).unwrap()));
+		assert_eq!(
+			db.query(&another_peer_id, &ParaId::from(100)).await,
+			Some(Score::new(15).unwrap())
+		);
+		assert_eq!(db.query(&peer_id, &ParaId::from(200)).await, Some(Score::new(30).unwrap()));
+		assert_eq!(
+			db.reputations(Some(ParaId::from(100)))
+				.await
+				.into_iter()
+				.map(|reputation| (reputation.peer_id, reputation.score))
+				.collect::<Vec<_>>(),
+			vec![(another_peer_id, 15), (peer_id, 10)]
+		);
+
+		// Removed entries and pruned paras are removed from disk as well.
+		db.slash(&peer_id, &ParaId::from(100), Score::new(10).unwrap()).await;
+		db.prune_paras([ParaId::from(100)].into_iter().collect()).await;
+		drop(db);
+
+		let db = Db::open(10, store, 0, Metrics::default()).await.unwrap();
+		assert_eq!(db.len(), 1);
+		assert_eq!(db.query(&peer_id, &ParaId::from(100)).await, None);
+		assert_eq!(
+			db.query(&another_peer_id, &ParaId::from(100)).await,
+			Some(Score::new(15).unwrap())
+		);
+		assert_eq!(db.query(&peer_id, &ParaId::from(200)).await, None);
+	}
+
+	#[tokio::test]
+	// Test that the reputations are decayed on load for the time the node was offline.
+	async fn test_offline_decay_on_load() {
+		let store = make_store();
+		let mut db = Db::open(10, store.clone(), 0, Metrics::default()).await.unwrap();
+
+		let peer_id = PeerId::random();
+		let another_peer_id = PeerId::random();
+		db.process_bumps(
+			1,
+			[(
+				ParaId::from(100),
+				[(peer_id, Score::new(10).unwrap()), (another_peer_id, Score::new(100).unwrap())]
+					.into_iter()
+					.collect(),
+			)]
+			.into_iter()
+			.collect(),
+			None,
+		)
+		.await;
+		drop(db);
+
+		// Pretend the node was offline for 20 decay intervals.
+		let offline = 20 * OFFLINE_DECAY_INTERVAL.as_millis();
+		let meta = Meta { last_finalized: Some(1), written_at: now() - offline };
+		let mut tx = DBTransaction::new();
+		tx.put_vec(0, META_KEY, meta.encode());
+		store.write(tx).unwrap();
+
+		let expected_decay = 20 * INACTIVITY_DECAY;
+		let db = Db::open(10, store.clone(), 0, Metrics::default()).await.unwrap();
+		assert_eq!(db.query(&peer_id, &ParaId::from(100)).await, None);
+		assert_eq!(
+			db.query(&another_peer_id, &ParaId::from(100)).await,
+			Some(Score::new(100 - expected_decay).unwrap())
+		);
+		drop(db);
+
+		// The decay is only applied once.
+		let db = Db::open(10, store, 0, Metrics::default()).await.unwrap();
+		assert_eq!(db.processed_finalized_block_number().await, Some(1));
+		assert_eq!(
+			db.query(&another_peer_id, &ParaId::from(100)).await,
+			Some(Score::new(100 - expected_decay).unwrap())
+		);
+	}
+
+	#[tokio::test]
+	// Test that the keys of other subsystems sharing the column are left alone.
+	async fn test_shared_column() {
+		let store = make_store();
+		let mut tx = DBTransaction::new();
+		tx.put_vec(0, b"candidate-votes", b"votes".to_vec());
+		tx.put_vec(0, b"Rep", b"not a reputation".to_vec());
+		store.write(tx).unwrap();
+
+		let peer_id = PeerId::random();
+		let mut db = Db::open(10, store.clone(), 0, Metrics::default()).await.unwrap();
+		db.process_bumps(
+			1,
+			[(ParaId::from(100), [(peer_id, Score::new(10).unwrap())].into_iter().collect())]
+				.into_iter()
+				.collect(),
+			None,
+		)
+		.await;
+		db.prune_paras(BTreeSet::new()).await;
+		drop(db);
+
+		let db = Db::open(10, store.clone(), 0, Metrics::default()).await.unwrap();
+		assert_eq!(db.len(), 0);
+		assert_eq!(store.get(0, b"candidate-votes").unwrap(), Some(b"votes".to_vec()));
+		assert_eq!(store.get(0, b"Rep").unwrap(), Some(b"not a reputation".to_vec()));
+	}
 }
@@ -39,7 +39,7 @@ use polkadot_node_network_protocol::{
 	PeerId,
 };
 use polkadot_node_subsystem::{
-	messages::{ChainApiMessage, NetworkBridgeTxMessage},
+	messages::{ChainApiMessage, CollatorReputation, NetworkBridgeTxMessage},
 	ActivatedLeaf, CollatorProtocolSenderTrait,
 };
 use polkadot_node_subsystem_util::{
@@ -345,6 +345,11 @@ impl<B: Backend> PeerManager<B> {
 		}
 	}
 
+	/// Get the stored reputations, optionally only those of the given paraid.
+	pub async fn reputations(&self, para_id: Option<ParaId>) -> Vec<CollatorReputation> {
+		self.db.reputations(para_id).await
+	}
+
 	/// Retrieve the score of the connected peer. We assume the peer is declared for this paraid.
 	pub fn connected_peer_score(&self, peer_id: &PeerId, para_id: &ParaId) -> Option<Score> {
 		self.connected.peer_score(peer_id, para_id)
@@ -14,7 +14,11 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::validator_side_experimental::{peer_manager::Backend, Metrics, PeerManager};
+use crate::validator_side_experimental::{
+	error::Result, peer_manager::Backend, Metrics, PeerManager,
+};
+use polkadot_node_subsystem::{messages::CollatorReputation, CollatorProtocolSenderTrait};
+use polkadot_primitives::{BlockNumber, Hash, Id as ParaId};
 use sp_keystore::KeystorePtr;
 
 /// All state relevant for the validator side of the protocol lives here.
@@ -29,4 +33,21 @@ impl<B: Backend> State<B> {
 	pub fn new(peer_manager: PeerManager<B>, keystore: KeystorePtr, metrics: Metrics) -> Self {
 		Self { peer_manager, keystore, metrics }
 	}
+
+	/// Update the collator reputations on a new finalized block.
+	pub async fn handle_finalized_block<Sender: CollatorProtocolSenderTrait>(
+		&mut self,
+		sender: &mut Sender,
+		hash: Hash,
+		number: BlockNumber,
+	) -> Result<()> {
+		self.peer_manager
+			.update_reputations_on_new_finalized_block(sender, (hash, number))
+			.await
+	}
+
+	/// Get the stored collator reputations, optionally only those of the given para.
+	pub async fn reputations(&self, para_id: Option<ParaId>) -> Vec<CollatorReputation> {
+		self.peer_manager.reputations(para_id).await
+	}
 }
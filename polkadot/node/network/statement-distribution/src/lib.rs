@@ -46,6 +46,7 @@ pub(crate) mod metrics;
 use metrics::Metrics;
 
 mod v2;
+pub use v2::ResponseBandwidthLimits;
 
 const LOG_TARGET: &str = "parachain::statement-distribution";
 
@@ -59,6 +60,8 @@ pub struct StatementDistributionSubsystem {
 	metrics: Metrics,
 	/// Aggregated reputation change
 	reputation: ReputationAggregator,
+	/// Per-peer limits on the bandwidth spent answering candidate requests.
+	response_limits: ResponseBandwidthLimits,
 }
 
 #[overseer::subsystem(StatementDistribution, error=SubsystemError, prefix=self::overseer)]
@@ -121,7 +124,19 @@ impl StatementDistributionSubsystem {
 		req_receiver: IncomingRequestReceiver<AttestedCandidateRequest>,
 		metrics: Metrics,
 	) -> Self {
-		Self { keystore, req_receiver: Some(req_receiver), metrics, reputation: Default::default() }
+		Self {
+			keystore,
+			req_receiver: Some(req_receiver),
+			metrics,
+			reputation: Default::default(),
+			response_limits: Default::default(),
+		}
+	}
+
+	/// Override the per-peer limits on the bandwidth spent answering candidate requests.
+	pub fn with_response_limits(mut self, response_limits: ResponseBandwidthLimits) -> Self {
+		self.response_limits = response_limits;
+		self
 	}
 
 	async fn run<Context>(self, ctx: Context) -> std::result::Result<(), FatalError> {
@@ -136,7 +151,7 @@ impl StatementDistributionSubsystem {
 		let new_reputation_delay = || futures_timer::Delay::new(reputation_interval).fuse();
 		let mut reputation_delay = new_reputation_delay();
 
-		let mut state = crate::v2::State::new(self.keystore.clone());
+		let mut state = crate::v2::State::new(self.keystore.clone(), self.response_limits);
 
 		// Sender/receiver for getting news from our candidate responder task.
 		let (res_sender, mut res_receiver) = mpsc::channel(1);
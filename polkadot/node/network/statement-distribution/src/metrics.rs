@@ -37,6 +37,8 @@ struct MetricsInner {
 	// V2+
 	peer_rate_limit_request_drop: prometheus::Counter<prometheus::U64>,
 	max_parallel_requests_reached: prometheus::Counter<prometheus::U64>,
+	bandwidth_limit_request_drop: prometheus::Counter<prometheus::U64>,
+	response_bytes_sent: prometheus::Counter<prometheus::U64>,
 }
 
 /// Statement Distribution metrics.
@@ -143,6 +145,21 @@ impl Metrics {
 			metrics.max_parallel_requests_reached.inc();
 		}
 	}
+
+	/// Update dropped requests counter when a request is refused because
+	/// the peer exceeded its response bandwidth budget
+	pub fn on_request_dropped_bandwidth_limit(&self) {
+		if let Some(metrics) = &self.0 {
+			metrics.bandwidth_limit_request_drop.inc();
+		}
+	}
+
+	/// Account the size of a response sent to a peer.
+	pub fn on_response_sent(&self, size: usize) {
+		if let Some(metrics) = &self.0 {
+			metrics.response_bytes_sent.inc_by(size as u64);
+		}
+	}
 }
 
 impl metrics::Metrics for Metrics {
@@ -236,6 +253,20 @@ impl metrics::Metrics for Metrics {
 				)?,
 				registry,
 			)?,
+			bandwidth_limit_request_drop: prometheus::register(
+				prometheus::Counter::new(
+					"polkadot_parachain_statement_distribution_bandwidth_limit_request_drop_total",
+					"Number of statement distribution requests refused because the peer exceeded its response bandwidth budget.",
+				)?,
+				registry,
+			)?,
+			response_bytes_sent: prometheus::register(
+				prometheus::Counter::new(
+					"polkadot_parachain_statement_distribution_response_bytes_sent_total",
+					"Number of bytes sent in responses to attested candidate requests.",
+				)?,
+				registry,
+			)?,
 		};
 		Ok(Metrics(Some(metrics)))
 	}
@@ -0,0 +1,232 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Per-peer accounting of the bandwidth we spend answering attested candidate requests.
+//!
+//! The responder task already makes sure that a peer has at most one request in flight, but a
+//! peer which keeps re-requesting candidates it is allowed to fetch can still make us upload
+//! arbitrary amounts of data. Every connected peer gets a token bucket of response bytes which
+//! is refilled at a constant rate up to a burst capacity. A request is only answered while the
+//! bucket is not empty and the size of the response is deducted afterwards, so the balance may
+//! go negative after a large response and the peer has to wait for it to recover.
+//!
+//! The buckets outlive the connections, so that a peer can't get a fresh budget by reconnecting.
+//! A bucket is only forgotten once it has refilled, at which point a new one would be identical.
+
+use polkadot_node_network_protocol::PeerId;
+
+use std::{
+	collections::HashMap,
+	time::{Duration, Instant},
+};
+
+/// Default rate at which a peer's response budget is refilled, in bytes per second.
+const DEFAULT_RESPONSE_BANDWIDTH_RATE: u32 = 512 * 1024;
+
+/// Default amount of response bytes a peer can consume in a burst.
+const DEFAULT_RESPONSE_BANDWIDTH_BURST: u32 = 4 * 1024 * 1024;
+
+/// Limits on the bandwidth spent answering the attested candidate requests of a single peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResponseBandwidthLimits {
+	/// Bytes per second by which the budget of a peer is refilled.
+	pub rate: u32,
+	/// Maximum budget of a peer, in bytes.
+	pub burst: u32,
+}
+
+impl Default for ResponseBandwidthLimits {
+	fn default() -> Self {
+		Self { rate: DEFAULT_RESPONSE_BANDWIDTH_RATE, burst: DEFAULT_RESPONSE_BANDWIDTH_BURST }
+	}
+}
+
+struct TokenBucket {
+	balance: i64,
+	last_refill: Instant,
+}
+
+impl TokenBucket {
+	fn new(limits: &ResponseBandwidthLimits, now: Instant) -> Self {
+		TokenBucket { balance: limits.burst as i64, last_refill: now }
+	}
+
+	fn refill(&mut self, limits: &ResponseBandwidthLimits, now: Instant) {
+		let elapsed = now.saturating_duration_since(self.last_refill);
+		let refill = tokens_for(elapsed, limits.rate);
+
+		// Only move the refill point forward once at least one byte was earned, so frequent
+		// requests don't lose the fractional part.
+		if refill > 0 {
+			self.balance = self.balance.saturating_add(refill).min(limits.burst as i64);
+			self.last_refill = now;
+		}
+	}
+
+	fn is_full(&self, limits: &ResponseBandwidthLimits) -> bool {
+		self.balance >= limits.burst as i64
+	}
+}
+
+fn tokens_for(elapsed: Duration, rate: u32) -> i64 {
+	let tokens = elapsed.as_micros().saturating_mul(rate as u128) / 1_000_000;
+	i64::try_from(tokens).unwrap_or(i64::MAX)
+}
+
+/// Response bandwidth budgets of the peers we recently served.
+pub(crate) struct ResponseBandwidth {
+	limits: ResponseBandwidthLimits,
+	buckets: HashMap<PeerId, TokenBucket>,
+}
+
+impl ResponseBandwidth {
+	/// Create new, empty budgets with the given limits.
+	pub(crate) fn new(limits: ResponseBandwidthLimits) -> Self {
+		ResponseBandwidth { limits, buckets: HashMap::new() }
+	}
+
+	/// Whether the peer has budget left for another response at `now`.
+	///
+	/// Peers we haven't served before start with a full budget.
+	pub(crate) fn has_budget(&mut self, peer: PeerId, now: Instant) -> bool {
+		let limits = &self.limits;
+		let bucket = self.buckets.entry(peer).or_insert_with(|| TokenBucket::new(limits, now));
+		bucket.refill(limits, now);
+
+		bucket.balance > 0
+	}
+
+	/// Deduct the size of a response sent to the peer from its budget.
+	pub(crate) fn charge(&mut self, peer: &PeerId, bytes: usize) {
+		if let Some(bucket) = self.buckets.get_mut(peer) {
+			bucket.balance = bucket.balance.saturating_sub(bytes as i64);
+		}
+	}
+
+	/// Forget the budgets which have refilled at `now`, of connected and disconnected peers alike.
+	///
+	/// The budget of a peer which reconnects is kept until then, rather than reset.
+	pub(crate) fn prune(&mut self, now: Instant) {
+		let limits = &self.limits;
+		self.buckets.retain(|_, bucket| {
+			bucket.refill(limits, now);
+			!bucket.is_full(limits)
+		});
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const LIMITS: ResponseBandwidthLimits = ResponseBandwidthLimits { rate: 1000, burst: 5000 };
+
+	#[test]
+	fn burst_is_served_then_throttled() {
+		let mut bandwidth = ResponseBandwidth::new(LIMITS);
+		let peer = PeerId::random();
+		let now = Instant::now();
+
+		for _ in 0..5 {
+			assert!(bandwidth.has_budget(peer, now));
+			bandwidth.charge(&peer, 1000);
+		}
+
+		assert!(!bandwidth.has_budget(peer, now));
+
+		// Other peers are not affected.
+		assert!(bandwidth.has_budget(PeerId::random(), now));
+	}
+
+	#[test]
+	fn large_response_is_served_and_paid_back() {
+		let mut bandwidth = ResponseBandwidth::new(LIMITS);
+		let peer = PeerId::random();
+		let now = Instant::now();
+
+		assert!(bandwidth.has_budget(peer, now));
+		bandwidth.charge(&peer, 8000);
+
+		// 3000 bytes in debt, which takes 3 seconds to recover.
+		assert!(!bandwidth.has_budget(peer, now + Duration::from_secs(3)));
+		assert!(bandwidth.has_budget(peer, now + Duration::from_millis(3001)));
+	}
+
+	#[test]
+	fn refill_is_capped_at_burst() {
+		let mut bandwidth = ResponseBandwidth::new(LIMITS);
+		let peer = PeerId::random();
+		let now = Instant::now();
+
+		assert!(bandwidth.has_budget(peer, now));
+		bandwidth.charge(&peer, 1000);
+
+		let later = now + Duration::from_secs(60);
+		assert!(bandwidth.has_budget(peer, later));
+		bandwidth.charge(&peer, 5000);
+		assert!(!bandwidth.has_budget(peer, later));
+	}
+
+	#[test]
+	fn frequent_checks_do_not_lose_refill() {
+		let mut bandwidth = ResponseBandwidth::new(LIMITS);
+		let peer = PeerId::random();
+		let now = Instant::now();
+
+		assert!(bandwidth.has_budget(peer, now));
+		bandwidth.charge(&peer, 5000);
+
+		// Each step is worth less than a byte on its own.
+		let step = Duration::from_micros(500);
+		let mut t = now;
+		for _ in 0..2000 {
+			t += step;
+			let _ = bandwidth.has_budget(peer, t);
+		}
+
+		// A full second passed, so 1000 bytes are available again.
+		bandwidth.charge(&peer, 1000);
+		assert!(!bandwidth.has_budget(peer, t));
+		assert!(bandwidth.has_budget(peer, t + Duration::from_millis(1)));
+	}
+
+	#[test]
+	fn pruning_keeps_budget_until_refilled() {
+		let mut bandwidth = ResponseBandwidth::new(LIMITS);
+		let peer = PeerId::random();
+		let now = Instant::now();
+
+		assert!(bandwidth.has_budget(peer, now));
+		bandwidth.charge(&peer, 5000);
+
+		// The peer disconnects and reconnects, without getting a fresh budget.
+		bandwidth.prune(now);
+		assert!(!bandwidth.has_budget(peer, now));
+
+		let later = now + Duration::from_secs(4);
+		bandwidth.prune(later);
+		assert_eq!(bandwidth.buckets.len(), 1);
+		bandwidth.charge(&peer, 4000);
+		assert!(!bandwidth.has_budget(peer, later));
+
+		// Only forgotten once the full burst was refilled.
+		bandwidth.prune(later + Duration::from_millis(4999));
+		assert_eq!(bandwidth.buckets.len(), 1);
+		bandwidth.prune(later + Duration::from_secs(5));
+		assert!(bandwidth.buckets.is_empty());
+		assert!(bandwidth.has_budget(peer, later + Duration::from_secs(5)));
+	}
+}
@@ -54,6 +54,7 @@ use polkadot_primitives::{
 	SigningContext, UncheckedSignedStatement, ValidatorId, ValidatorIndex,
 };
 
+use codec::Encode;
 use sp_keystore::KeystorePtr;
 
 use fatality::Nested;
@@ -78,6 +79,8 @@ use crate::{
 	metrics::Metrics,
 	LOG_TARGET,
 };
+use bandwidth::ResponseBandwidth;
+pub use bandwidth::ResponseBandwidthLimits;
 use candidates::{BadAdvertisement, Candidates, PostConfirmation};
 use cluster::{Accept as ClusterAccept, ClusterTracker, RejectIncoming as ClusterRejectIncoming};
 use grid::GridTracker;
@@ -87,6 +90,7 @@ use statement_store::{StatementOrigin, StatementStore};
 
 pub use requests::{RequestManager, ResponseManager, UnhandledResponse};
 
+mod bandwidth;
 mod candidates;
 mod cluster;
 mod grid;
@@ -147,6 +151,8 @@ const COST_INVALID_REQUEST: Rep = Rep::CostMajor("Peer sent unparsable request")
 const COST_INVALID_REQUEST_BITFIELD_SIZE: Rep =
 	Rep::CostMajor("Attested candidate request bitfields have wrong size");
 const COST_UNEXPECTED_REQUEST: Rep = Rep::CostMajor("Unexpected attested candidate request");
+const COST_EXCESSIVE_REQUEST_BANDWIDTH: Rep =
+	Rep::CostMinor("Attested candidate requests exceeded the response bandwidth budget");
 
 const BENEFIT_VALID_RESPONSE: Rep = Rep::BenefitMajor("Peer Answered Candidate Request");
 const BENEFIT_VALID_STATEMENT: Rep = Rep::BenefitMajor("Peer provided a valid statement");
@@ -313,11 +319,12 @@ pub(crate) struct State {
 	authorities: HashMap<AuthorityDiscoveryId, PeerId>,
 	request_manager: RequestManager,
 	response_manager: ResponseManager,
+	response_bandwidth: ResponseBandwidth,
 }
 
 impl State {
 	/// Create a new state.
-	pub(crate) fn new(keystore: KeystorePtr) -> Self {
+	pub(crate) fn new(keystore: KeystorePtr, response_limits: ResponseBandwidthLimits) -> Self {
 		State {
 			implicit_view: Default::default(),
 			candidates: Default::default(),
@@ -328,6 +335,7 @@ impl State {
 			authorities: HashMap::new(),
 			request_manager: RequestManager::new(),
 			response_manager: ResponseManager::new(),
+			response_bandwidth: ResponseBandwidth::new(response_limits),
 			unused_topologies: HashMap::new(),
 		}
 	}
@@ -472,6 +480,7 @@ pub(crate) async fn handle_network_update<Context>(
 					state.authorities.remove(&discovery_key);
 				}
 			}
+			state.response_bandwidth.prune(Instant::now());
 		},
 		NetworkBridgeEvent::NewGossipTopology(topology) => {
 			let new_session_index = &topology.session;
@@ -3107,7 +3116,7 @@ pub(crate) fn seconded_and_sufficient(
 }
 
 /// Answer an incoming request for a candidate.
-pub(crate) fn answer_request(state: &mut State, message: ResponderMessage) {
+pub(crate) fn answer_request(state: &mut State, message: ResponderMessage, metrics: &Metrics) {
 	let ResponderMessage { request, sent_feedback } = message;
 	let AttestedCandidateRequest { candidate_hash, ref mask } = &request.payload;
 
@@ -3146,6 +3155,24 @@ pub(crate) fn answer_request(state: &mut State, message: ResponderMessage) {
 		Some(d) => d,
 	};
 
+	// check the peer hasn't used up its response bandwidth.
+	if !state.response_bandwidth.has_budget(request.peer, Instant::now()) {
+		gum::debug!(
+			target: LOG_TARGET,
+			?candidate_hash,
+			peer = ?request.peer,
+			"Peer exceeded its response bandwidth budget, refusing request"
+		);
+		metrics.on_request_dropped_bandwidth_limit();
+		let _ = request.send_outgoing_response(OutgoingResponse {
+			result: Err(()),
+			reputation_changes: vec![COST_EXCESSIVE_REQUEST_BANDWIDTH],
+			sent_feedback: None,
+		});
+
+		return
+	}
+
 	let group_index = confirmed.group_index();
 	let group = per_session
 		.groups
@@ -3287,6 +3314,10 @@ pub(crate) fn answer_request(state: &mut State, message: ResponderMessage) {
 		statements,
 	};
 
+	let response_size = response.encoded_size();
+	state.response_bandwidth.charge(&request.peer, response_size);
+	metrics.on_response_sent(response_size);
+
 	let _ = request.send_response(response);
 }
 
@@ -1247,6 +1247,7 @@ fn delayed_reputation_changes() {
 			keystore,
 			req_receiver: Some(candidate_req_receiver),
 			metrics: Default::default(),
+			response_limits: Default::default(),
 			reputation: ReputationAggregator::new(|_| false),
 		};
 
@@ -381,6 +381,14 @@ impl TestState {
 fn test_harness<T: Future<Output = VirtualOverseer>>(
 	config: TestConfig,
 	test: impl FnOnce(TestState, VirtualOverseer) -> T,
+) {
+	test_harness_with_response_limits(config, Default::default(), test)
+}
+
+fn test_harness_with_response_limits<T: Future<Output = VirtualOverseer>>(
+	config: TestConfig,
+	response_limits: ResponseBandwidthLimits,
+	test: impl FnOnce(TestState, VirtualOverseer) -> T,
 ) {
 	let pool = sp_core::testing::TaskExecutor::new();
 	let keystore = if let LocalRole::Validator = config.local_validator {
@@ -404,6 +412,7 @@ fn test_harness<T: Future<Output = VirtualOverseer>>(
 			keystore,
 			req_receiver: Some(candidate_req_receiver),
 			metrics: Default::default(),
+			response_limits,
 			reputation: ReputationAggregator::new(|_| true),
 		};
 
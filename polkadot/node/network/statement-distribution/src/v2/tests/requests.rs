@@ -2413,6 +2413,142 @@ fn local_node_checks_that_peer_can_request_before_responding() {
 	});
 }
 
+#[test]
+fn local_node_refuses_requests_over_response_bandwidth_limit() {
+	let config = TestConfig {
+		validator_count: 20,
+		group_size: 3,
+		local_validator: LocalRole::Validator,
+		allow_v2_descriptors: false,
+	};
+	// A single response exhausts the budget, which then takes minutes to recover.
+	let response_limits = ResponseBandwidthLimits { rate: 1, burst: 1 };
+
+	let relay_parent = Hash::repeat_byte(1);
+	let peer_a = PeerId::random();
+	let peer_b = PeerId::random();
+
+	test_harness_with_response_limits(
+		config,
+		response_limits,
+		|mut state, mut overseer| async move {
+			let local_validator = state.local.clone().unwrap();
+			let local_group_index = local_validator.group_index.unwrap();
+			let local_para = ParaId::from(local_group_index.0);
+
+			let test_leaf = state.make_dummy_leaf(relay_parent);
+
+			let (candidate, pvd) = make_candidate(
+				relay_parent,
+				1,
+				local_para,
+				test_leaf.para_data(local_para).head_data.clone(),
+				vec![4, 5, 6].into(),
+				Hash::repeat_byte(42).into(),
+			);
+			let candidate_hash = candidate.hash();
+
+			// Peers A and B are in group and have relay parent in view.
+			let other_group_validators = state.group_validators(local_group_index, true);
+
+			connect_peer(
+				&mut overseer,
+				peer_a.clone(),
+				Some(vec![state.discovery_id(other_group_validators[0])].into_iter().collect()),
+			)
+			.await;
+
+			connect_peer(
+				&mut overseer,
+				peer_b.clone(),
+				Some(vec![state.discovery_id(other_group_validators[1])].into_iter().collect()),
+			)
+			.await;
+
+			send_peer_view_change(&mut overseer, peer_a.clone(), view![relay_parent]).await;
+			send_peer_view_change(&mut overseer, peer_b.clone(), view![relay_parent]).await;
+
+			// Finish setup
+			activate_leaf(&mut overseer, &test_leaf, &state, true, vec![]).await;
+
+			let mask = StatementFilter::blank(state.config.group_size);
+
+			// Confirm candidate.
+			let signed = state.sign_statement(
+				local_validator.validator_index,
+				CompactStatement::Seconded(candidate_hash),
+				&SigningContext { session_index: 1, parent_hash: relay_parent },
+			);
+			let full_signed = signed
+				.clone()
+				.convert_to_superpayload(StatementWithPVD::Seconded(candidate.clone(), pvd.clone()))
+				.unwrap();
+
+			overseer
+				.send(FromOrchestra::Communication {
+					msg: StatementDistributionMessage::Share(relay_parent, full_signed),
+				})
+				.await;
+
+			assert_matches!(
+				overseer.recv().await,
+				AllMessages::NetworkBridgeTx(NetworkBridgeTxMessage::SendValidationMessage(
+					peers,
+					ValidationProtocols::V3(protocol_v3::ValidationProtocol::StatementDistribution(
+						protocol_v3::StatementDistributionMessage::Statement(r, s)
+					))
+				)) => {
+					assert_eq!(peers, vec![peer_a.clone(), peer_b.clone()]);
+					assert_eq!(r, relay_parent);
+					assert_eq!(s.unchecked_payload(), &CompactStatement::Seconded(candidate_hash));
+				}
+			);
+
+			answer_expected_hypothetical_membership_request(&mut overseer, vec![]).await;
+
+			let request =
+				request_v2::AttestedCandidateRequest { candidate_hash, mask: mask.clone() };
+
+			// The first request of peer A is answered and uses up its budget.
+			{
+				let response = state.send_request(peer_a, request.clone()).await.await.unwrap();
+				assert_matches!(response.result, Ok(_));
+			}
+
+			// Asking again is refused until the budget recovers.
+			{
+				let response = state.send_request(peer_a, request.clone()).await.await.unwrap();
+				assert_matches!(
+					response,
+					RawOutgoingResponse {
+						result,
+						reputation_changes,
+						sent_feedback
+					} => {
+						assert_matches!(result, Err(()));
+						assert_eq!(reputation_changes, vec![COST_EXCESSIVE_REQUEST_BANDWIDTH.into()]);
+						assert_matches!(sent_feedback, None);
+					}
+				);
+			}
+
+			// Other peers have budgets of their own.
+			{
+				let response = state.send_request(peer_b, request).await.await.unwrap();
+				let request_v2::AttestedCandidateResponse { candidate_receipt, statements, .. } =
+					request_v2::AttestedCandidateResponse::decode(
+						&mut response.result.expect("We should have a proper answer").as_ref(),
+					)
+					.expect("Decoding should work");
+				assert_eq!(candidate_receipt, candidate);
+				assert_eq!(statements, vec![signed.into_unchecked()]);
+			}
+
+			overseer
+		},
+	);
+}
+
 #[test]
 fn local_node_respects_statement_mask() {
 	let validator_count = 6;
@@ -194,6 +194,7 @@ where
 		.prospective_parachains(subsystem.clone())
 		.activation_external_listeners(Default::default())
 		.active_leaves(Default::default())
+		.watchdog(Default::default())
 		.spawner(SpawnGlue(spawner))
 		.metrics(metrics)
 		.supports_parachains(supports_parachains);
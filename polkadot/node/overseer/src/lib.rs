@@ -96,6 +96,9 @@ pub use polkadot_node_subsystem_types::{
 pub mod metrics;
 pub use self::metrics::Metrics as OverseerMetrics;
 
+pub mod watchdog;
+pub use self::watchdog::{StallThresholds, WatchdogConfig};
+
 /// A dummy subsystem, mostly useful for placeholders and tests.
 pub mod dummy;
 pub use self::dummy::DummySubsystem;
@@ -669,9 +672,12 @@ pub struct Overseer<SupportsParachains> {
 
 	/// Various Prometheus metrics.
 	pub metrics: OverseerMetrics,
+
+	/// Configuration of the watchdog detecting stalled subsystems.
+	pub watchdog: WatchdogConfig,
 }
 
-/// Spawn the metrics metronome task.
+/// Spawn the metrics metronome task, which also drives the stall [`watchdog`].
 pub fn spawn_metronome_metrics<S, SupportsParachains>(
 	overseer: &mut Overseer<S, SupportsParachains>,
 	metronome_metrics: OverseerMetrics,
@@ -683,13 +689,12 @@ where
 	struct ExtractNameAndMeters;
 
 	impl<'a, T: 'a> MapSubsystem<&'a OrchestratedSubsystem<T>> for ExtractNameAndMeters {
-		type Output = Option<(&'static str, SubsystemMeters)>;
+		type Output = Option<(&'static str, &'static str, SubsystemMeters)>;
 
 		fn map_subsystem(&self, subsystem: &'a OrchestratedSubsystem<T>) -> Self::Output {
-			subsystem
-				.instance
-				.as_ref()
-				.map(|instance| (instance.name, instance.meters.clone()))
+			subsystem.instance.as_ref().map(|instance| {
+				(instance.name, std::any::type_name::<T>(), instance.meters.clone())
+			})
 		}
 	}
 	let subsystem_meters = overseer.map_subsystems(ExtractNameAndMeters);
@@ -723,18 +728,47 @@ where
 	#[cfg(not(any(target_os = "linux", feature = "jemalloc-allocator")))]
 	let collect_memory_stats: Box<dyn Fn(&OverseerMetrics) + Send> = Box::new(|_| {});
 
+	let mut stall_watchdog = watchdog::Watchdog::new(overseer.watchdog.clone());
+	let dump_spawner = overseer.spawner().clone();
+
 	let metronome = Metronome::new(std::time::Duration::from_millis(950)).for_each(move |_| {
 		collect_memory_stats(&metronome_metrics);
 
+		// Reading the meters drains the time of flight samples, so they are read once and shared
+		// between the metrics and the watchdog.
+		let readouts = subsystem_meters
+			.iter()
+			.flatten()
+			.map(|(name, message_type, meters)| (*name, *message_type, meters.read()))
+			.collect::<Vec<_>>();
+
+		let now = std::time::Instant::now();
+		let mut take_task_dump = false;
+		for &(name, message_type, ref readouts) in &readouts {
+			match stall_watchdog.observe(now, name, message_type, readouts.into()) {
+				Some(watchdog::StallEvent::Stalled(diagnostic)) => {
+					watchdog::log_stall(&diagnostic, true);
+					metronome_metrics.on_subsystem_stalled(name);
+					take_task_dump = stall_watchdog.task_dump();
+				},
+				Some(watchdog::StallEvent::StillStalled(diagnostic)) =>
+					watchdog::log_stall(&diagnostic, false),
+				Some(watchdog::StallEvent::Recovered { subsystem, stalled_for }) => {
+					gum::info!(target: LOG_TARGET, subsystem, ?stalled_for, "Subsystem recovered");
+					metronome_metrics.on_subsystem_recovered(name);
+				},
+				None => {},
+			}
+		}
+		if take_task_dump {
+			watchdog::spawn_task_dump(&dump_spawner);
+		}
+
 		// We combine the amount of messages from subsystems to the overseer
 		// as well as the amount of messages from external sources to the overseer
 		// into one `to_overseer` value.
 		metronome_metrics.channel_metrics_snapshot(
-			subsystem_meters
-				.iter()
-				.cloned()
-				.flatten()
-				.map(|(name, ref meters)| (name, meters.read())),
+			readouts.into_iter().map(|(name, _, readouts)| (name, readouts)),
 		);
 
 		futures::future::ready(())
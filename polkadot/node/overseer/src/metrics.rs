@@ -38,6 +38,9 @@ struct MetricsInner {
 	signals_sent: prometheus::GaugeVec<prometheus::U64>,
 	signals_received: prometheus::GaugeVec<prometheus::U64>,
 
+	subsystem_stalled: prometheus::GaugeVec<prometheus::U64>,
+	subsystem_stalls_total: prometheus::CounterVec<prometheus::U64>,
+
 	#[cfg(any(target_os = "linux", feature = "jemalloc-allocator"))]
 	memory_stats_resident: prometheus::Gauge<prometheus::U64>,
 	#[cfg(any(target_os = "linux", feature = "jemalloc-allocator"))]
@@ -78,6 +81,19 @@ impl Metrics {
 		}
 	}
 
+	pub(crate) fn on_subsystem_stalled(&self, name: &'static str) {
+		if let Some(metrics) = &self.0 {
+			metrics.subsystem_stalled.with_label_values(&[name]).set(1);
+			metrics.subsystem_stalls_total.with_label_values(&[name]).inc();
+		}
+	}
+
+	pub(crate) fn on_subsystem_recovered(&self, name: &'static str) {
+		if let Some(metrics) = &self.0 {
+			metrics.subsystem_stalled.with_label_values(&[name]).set(0);
+		}
+	}
+
 	pub(crate) fn channel_metrics_snapshot(
 		&self,
 		collection: impl IntoIterator<Item = (&'static str, SubsystemMeterReadouts)>,
@@ -258,6 +274,26 @@ impl MetricsTrait for Metrics {
 				)?,
 				registry,
 			)?,
+			subsystem_stalled: prometheus::register(
+				prometheus::GaugeVec::<prometheus::U64>::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_subsystem_stalled",
+						"Whether a subsystem is currently considered stalled by the overseer watchdog",
+					),
+					&["subsystem_name"],
+				)?,
+				registry,
+			)?,
+			subsystem_stalls_total: prometheus::register(
+				prometheus::CounterVec::<prometheus::U64>::new(
+					prometheus::Opts::new(
+						"polkadot_parachain_subsystem_stalls_total",
+						"Number of times a subsystem was detected as stalled by the overseer watchdog",
+					),
+					&["subsystem_name"],
+				)?,
+				registry,
+			)?,
 			#[cfg(any(target_os = "linux", feature = "jemalloc-allocator"))]
 			memory_stats_allocated: prometheus::register(
 				prometheus::Gauge::<prometheus::U64>::new(
@@ -1512,3 +1512,99 @@ fn overseer_check_subsystem_can_receive_their_priority_messages(
 		assert!(res.is_ok());
 	});
 }
+
+#[test]
+fn watchdog_detects_idle_subsystem_with_pending_work() {
+	use crate::watchdog::{ChannelLoad, StallDiagnostic, StallEvent, StallReason, Watchdog};
+
+	let mut watchdog = Watchdog::new(WatchdogConfig::default());
+	let start = std::time::Instant::now();
+	let max_idle = StallThresholds::default().max_idle;
+	let pending = ChannelLoad { bounded_depth: 3, blocked: 1, processed: 10, ..Default::default() };
+
+	assert_eq!(watchdog.observe(start, "sub", "Msg", pending), None);
+	assert_eq!(watchdog.observe(start + max_idle, "sub", "Msg", pending), None);
+
+	// Idle for too long while messages are pending.
+	let now = start + max_idle + Duration::from_secs(1);
+	let stalled = ChannelLoad { blocked: 4, ..pending };
+	assert_eq!(
+		watchdog.observe(now, "sub", "Msg", stalled),
+		Some(StallEvent::Stalled(StallDiagnostic {
+			subsystem: "sub",
+			message_type: "Msg",
+			reason: StallReason::Idle,
+			idle: max_idle + Duration::from_secs(1),
+			bounded_depth: 3,
+			unbounded_depth: 0,
+			blocked_sends: 0,
+			signal_backlog: 0,
+		}))
+	);
+
+	// Reported again only after another `max_idle`.
+	assert_eq!(watchdog.observe(now + Duration::from_secs(1), "sub", "Msg", stalled), None);
+	assert_matches!(
+		watchdog.observe(now + max_idle, "sub", "Msg", ChannelLoad { blocked: 6, ..stalled }),
+		Some(StallEvent::StillStalled(StallDiagnostic { blocked_sends: 2, .. }))
+	);
+
+	// Processing a message ends the stall.
+	assert_eq!(
+		watchdog.observe(
+			now + max_idle * 2,
+			"sub",
+			"Msg",
+			ChannelLoad { processed: 11, ..stalled }
+		),
+		Some(StallEvent::Recovered { subsystem: "sub", stalled_for: max_idle * 2 })
+	);
+}
+
+#[test]
+fn watchdog_ignores_idle_subsystem_without_pending_work() {
+	use crate::watchdog::{ChannelLoad, Watchdog};
+
+	let mut watchdog = Watchdog::new(WatchdogConfig::default());
+	let start = std::time::Instant::now();
+	let max_idle = StallThresholds::default().max_idle;
+	let idle = ChannelLoad { processed: 10, ..Default::default() };
+
+	assert_eq!(watchdog.observe(start, "sub", "Msg", idle), None);
+	assert_eq!(watchdog.observe(start + max_idle * 2, "sub", "Msg", idle), None);
+
+	// A message arriving after a quiet period does not count the quiet period as idle time.
+	let pending = ChannelLoad { bounded_depth: 1, ..idle };
+	assert_eq!(watchdog.observe(start + max_idle * 2, "sub", "Msg", pending), None);
+	assert_eq!(watchdog.observe(start + max_idle * 3, "sub", "Msg", pending), None);
+}
+
+#[test]
+fn watchdog_uses_per_subsystem_thresholds() {
+	use crate::watchdog::{ChannelLoad, StallDiagnostic, StallEvent, StallReason, Watchdog};
+
+	let config = WatchdogConfig::default().with_subsystem_thresholds(
+		"busy",
+		StallThresholds { max_queue_depth: Some(100), ..Default::default() },
+	);
+	let mut watchdog = Watchdog::new(config);
+	let now = std::time::Instant::now();
+	let load =
+		ChannelLoad { bounded_depth: 80, unbounded_depth: 30, processed: 1, ..Default::default() };
+
+	assert_eq!(watchdog.observe(now, "other", "Msg", load), None);
+	assert_matches!(
+		watchdog.observe(now, "busy", "Msg", load),
+		Some(StallEvent::Stalled(StallDiagnostic { reason: StallReason::QueueDepth, .. }))
+	);
+	assert_matches!(
+		watchdog.observe(now, "other", "Msg", ChannelLoad { signal_backlog: 17, ..load }),
+		Some(StallEvent::Stalled(StallDiagnostic { reason: StallReason::SignalBacklog, .. }))
+	);
+
+	let mut disabled = Watchdog::new(WatchdogConfig { enabled: false, ..Default::default() });
+	assert_eq!(
+		disabled.observe(now, "other", "Msg", ChannelLoad { signal_backlog: 17, ..load }),
+		None
+	);
+}
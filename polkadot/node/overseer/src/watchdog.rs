@@ -0,0 +1,347 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Detection of stalled subsystems.
+//!
+//! The watchdog is driven by the metrics metronome of the overseer. On every tick it compares the
+//! channel meters of each subsystem with the previous readout, to learn when the subsystem last
+//! processed a message or signal. A subsystem is considered stalled if it did not make progress for
+//! longer than [`StallThresholds::max_idle`] while messages or signals are pending, or if its
+//! queues or signal backlog grow beyond the configured limits.
+//!
+//! Stalls are reported through the `polkadot_parachain_subsystem_stalled` metric and a warning
+//! with the state of the channels of the subsystem. If the node is built with
+//! `RUSTFLAGS="--cfg tokio_unstable --cfg tokio_taskdump"`, a dump of the tokio tasks is logged as
+//! well.
+
+use crate::{Spawner, SubsystemMeterReadouts, LOG_TARGET};
+use std::{
+	collections::HashMap,
+	time::{Duration, Instant},
+};
+
+/// The limits beyond which a subsystem is considered stalled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StallThresholds {
+	/// For how long a subsystem may not process any message or signal while some are pending.
+	pub max_idle: Duration,
+	/// The maximum number of messages pending in the bounded and unbounded queues, if any.
+	pub max_queue_depth: Option<usize>,
+	/// The maximum number of signals pending, if any.
+	pub max_signal_backlog: Option<usize>,
+}
+
+impl Default for StallThresholds {
+	fn default() -> Self {
+		Self {
+			max_idle: Duration::from_secs(30),
+			max_queue_depth: None,
+			max_signal_backlog: Some(16),
+		}
+	}
+}
+
+/// Configuration of the stall watchdog.
+#[derive(Debug, Clone)]
+pub struct WatchdogConfig {
+	/// Whether stalled subsystems are detected at all.
+	pub enabled: bool,
+	/// The thresholds of the subsystems without an entry in `per_subsystem`.
+	pub thresholds: StallThresholds,
+	/// The thresholds of individual subsystems, by subsystem name.
+	pub per_subsystem: HashMap<String, StallThresholds>,
+	/// Whether to log a dump of the tokio tasks when a subsystem stalls. Only has an effect if the
+	/// node is built with the `tokio_unstable` and `tokio_taskdump` cfg flags.
+	pub task_dump: bool,
+}
+
+impl Default for WatchdogConfig {
+	fn default() -> Self {
+		Self {
+			enabled: true,
+			thresholds: StallThresholds::default(),
+			per_subsystem: HashMap::new(),
+			task_dump: true,
+		}
+	}
+}
+
+impl WatchdogConfig {
+	/// Use the given thresholds for the subsystem with the given name.
+	pub fn with_subsystem_thresholds(
+		mut self,
+		subsystem: impl Into<String>,
+		thresholds: StallThresholds,
+	) -> Self {
+		self.per_subsystem.insert(subsystem.into(), thresholds);
+		self
+	}
+
+	fn thresholds(&self, subsystem: &str) -> &StallThresholds {
+		self.per_subsystem.get(subsystem).unwrap_or(&self.thresholds)
+	}
+}
+
+/// The state of the channels of a subsystem, derived from its meters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct ChannelLoad {
+	/// Messages sent but not yet received on the bounded channel.
+	pub bounded_depth: usize,
+	/// Messages sent but not yet received on the unbounded channel.
+	pub unbounded_depth: usize,
+	/// Number of times a sender blocked on the full bounded channel.
+	pub blocked: usize,
+	/// Signals sent but not yet received.
+	pub signal_backlog: usize,
+	/// Total number of messages and signals received by the subsystem.
+	pub processed: usize,
+}
+
+impl From<&SubsystemMeterReadouts> for ChannelLoad {
+	fn from(readouts: &SubsystemMeterReadouts) -> Self {
+		Self {
+			bounded_depth: readouts.bounded.sent.saturating_sub(readouts.bounded.received),
+			unbounded_depth: readouts.unbounded.sent.saturating_sub(readouts.unbounded.received),
+			blocked: readouts.bounded.blocked,
+			signal_backlog: readouts.signals.sent.saturating_sub(readouts.signals.received),
+			processed: readouts.bounded.received +
+				readouts.unbounded.received +
+				readouts.signals.received,
+		}
+	}
+}
+
+impl ChannelLoad {
+	fn queue_depth(&self) -> usize {
+		self.bounded_depth + self.unbounded_depth
+	}
+
+	fn has_pending(&self) -> bool {
+		self.queue_depth() > 0 || self.signal_backlog > 0
+	}
+}
+
+/// Why a subsystem is considered stalled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StallReason {
+	/// No message or signal was processed for too long while some are pending.
+	Idle,
+	/// Too many messages are queued.
+	QueueDepth,
+	/// Too many signals are pending.
+	SignalBacklog,
+}
+
+/// The state of a stalled subsystem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StallDiagnostic {
+	/// The name of the subsystem.
+	pub subsystem: &'static str,
+	/// The type of the messages pending in the queues of the subsystem.
+	pub message_type: &'static str,
+	/// Why the subsystem is considered stalled.
+	pub reason: StallReason,
+	/// For how long the subsystem did not process any message or signal.
+	pub idle: Duration,
+	/// Messages pending in the bounded queue.
+	pub bounded_depth: usize,
+	/// Messages pending in the unbounded queue.
+	pub unbounded_depth: usize,
+	/// Times a sender blocked on the full bounded queue since the stall began.
+	pub blocked_sends: usize,
+	/// Signals pending.
+	pub signal_backlog: usize,
+}
+
+/// A change of the stall state of a subsystem, as observed by the [`Watchdog`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum StallEvent {
+	/// The subsystem stalled.
+	Stalled(StallDiagnostic),
+	/// The subsystem is still stalled, reported once per `max_idle`.
+	StillStalled(StallDiagnostic),
+	/// The subsystem made progress again after being stalled for the given duration.
+	Recovered { subsystem: &'static str, stalled_for: Duration },
+}
+
+struct SubsystemState {
+	processed: usize,
+	last_progress: Instant,
+	/// When the stall began, when it was last reported and the blocked count at the beginning.
+	stall: Option<(Instant, Instant, usize)>,
+}
+
+/// Tracks the progress of the subsystems to detect stalls, see the [module docs](self).
+pub(crate) struct Watchdog {
+	config: WatchdogConfig,
+	subsystems: HashMap<&'static str, SubsystemState>,
+}
+
+impl Watchdog {
+	pub(crate) fn new(config: WatchdogConfig) -> Self {
+		Self { config, subsystems: HashMap::new() }
+	}
+
+	/// Whether task dumps should be taken on stalls.
+	pub(crate) fn task_dump(&self) -> bool {
+		self.config.enabled && self.config.task_dump
+	}
+
+	/// Record the current channel load of a subsystem and report changes of its stall state.
+	pub(crate) fn observe(
+		&mut self,
+		now: Instant,
+		subsystem: &'static str,
+		message_type: &'static str,
+		load: ChannelLoad,
+	) -> Option<StallEvent> {
+		if !self.config.enabled {
+			return None
+		}
+
+		let thresholds = *self.config.thresholds(subsystem);
+		let state = self.subsystems.entry(subsystem).or_insert_with(|| SubsystemState {
+			processed: load.processed,
+			last_progress: now,
+			stall: None,
+		});
+
+		// A subsystem without pending work is not idle, it is waiting.
+		if load.processed != state.processed || !load.has_pending() {
+			state.processed = load.processed;
+			state.last_progress = now;
+		}
+		let idle = now.saturating_duration_since(state.last_progress);
+
+		let reason = if idle > thresholds.max_idle {
+			Some(StallReason::Idle)
+		} else if thresholds.max_queue_depth.map_or(false, |max| load.queue_depth() > max) {
+			Some(StallReason::QueueDepth)
+		} else if thresholds.max_signal_backlog.map_or(false, |max| load.signal_backlog > max) {
+			Some(StallReason::SignalBacklog)
+		} else {
+			None
+		};
+
+		let Some(reason) = reason else {
+			return state.stall.take().map(|(since, _, _)| StallEvent::Recovered {
+				subsystem,
+				stalled_for: now.saturating_duration_since(since),
+			})
+		};
+
+		let diagnostic = |blocked_before: usize| StallDiagnostic {
+			subsystem,
+			message_type,
+			reason,
+			idle,
+			bounded_depth: load.bounded_depth,
+			unbounded_depth: load.unbounded_depth,
+			blocked_sends: load.blocked.saturating_sub(blocked_before),
+			signal_backlog: load.signal_backlog,
+		};
+		match state.stall {
+			None => {
+				state.stall = Some((now, now, load.blocked));
+				Some(StallEvent::Stalled(diagnostic(load.blocked)))
+			},
+			Some((since, last_report, blocked_before))
+				if now.saturating_duration_since(last_report) >= thresholds.max_idle =>
+			{
+				state.stall = Some((since, now, blocked_before));
+				Some(StallEvent::StillStalled(diagnostic(blocked_before)))
+			},
+			Some(_) => None,
+		}
+	}
+}
+
+/// Log a stall diagnostic.
+pub(crate) fn log_stall(diagnostic: &StallDiagnostic, first: bool) {
+	let StallDiagnostic {
+		subsystem,
+		message_type,
+		reason,
+		idle,
+		bounded_depth,
+		unbounded_depth,
+		blocked_sends,
+		signal_backlog,
+	} = diagnostic;
+	gum::warn!(
+		target: LOG_TARGET,
+		subsystem,
+		message_type,
+		?reason,
+		?idle,
+		bounded_depth,
+		unbounded_depth,
+		blocked_sends,
+		signal_backlog,
+		"{}",
+		if first { "Subsystem stalled" } else { "Subsystem still stalled" },
+	);
+}
+
+#[cfg(all(
+	tokio_unstable,
+	tokio_taskdump,
+	target_os = "linux",
+	any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64")
+))]
+const TASK_DUMP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Log a dump of the tokio tasks of the current runtime in the background.
+///
+/// Tasks which do not yield, like a blocked subsystem, hold up the dump, so it is given up after
+/// [`TASK_DUMP_TIMEOUT`].
+#[cfg(all(
+	tokio_unstable,
+	tokio_taskdump,
+	target_os = "linux",
+	any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64")
+))]
+pub(crate) fn spawn_task_dump(spawner: &impl Spawner) {
+	use futures::FutureExt;
+
+	let Ok(handle) = tokio::runtime::Handle::try_current() else {
+		gum::debug!(target: LOG_TARGET, "Not running in a tokio runtime, no task dump taken");
+		return
+	};
+
+	let dump = async move {
+		futures::select! {
+			dump = handle.dump().fuse() => {
+				for (index, task) in dump.tasks().iter().enumerate() {
+					gum::warn!(target: LOG_TARGET, index, trace = %task.trace(), "Task dump");
+				}
+			},
+			_ = futures_timer::Delay::new(TASK_DUMP_TIMEOUT).fuse() => {
+				gum::warn!(target: LOG_TARGET, timeout = ?TASK_DUMP_TIMEOUT, "Task dump timed out");
+			},
+		}
+	};
+	spawner.spawn("stall-task-dump", Some("overseer"), Box::pin(dump));
+}
+
+/// Task dumps are not supported by this build.
+#[cfg(not(all(
+	tokio_unstable,
+	tokio_taskdump,
+	target_os = "linux",
+	any(target_arch = "aarch64", target_arch = "x86", target_arch = "x86_64")
+)))]
+pub(crate) fn spawn_task_dump(_spawner: &impl Spawner) {}
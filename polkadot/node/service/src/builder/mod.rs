@@ -24,7 +24,10 @@ pub(crate) use partial::{new_partial, new_partial_basics};
 
 use crate::{
 	grandpa_support, open_database,
-	overseer::{ExtendedOverseerGenArgs, OverseerGen, OverseerGenArgs},
+	overseer::{
+		ExtendedOverseerGenArgs, OverseerGen, OverseerGenArgs, ResponseBandwidthLimits,
+		WatchdogConfig,
+	},
 	parachains_db,
 	relay_chain_selection::SelectRelayChain,
 	workers, Chain, Error, FullBackend, FullClient, IdentifyVariant, IsParachainNode,
@@ -35,7 +38,9 @@ use gum::info;
 use mmr_gadget::MmrGadget;
 use polkadot_availability_recovery::FETCH_CHUNKS_THRESHOLD;
 use polkadot_node_core_approval_voting::Config as ApprovalVotingConfig;
-use polkadot_node_core_av_store::Config as AvailabilityConfig;
+use polkadot_node_core_av_store::{
+	Config as AvailabilityConfig, DiskQuota as AvailabilityDiskQuota,
+};
 use polkadot_node_core_candidate_validation::Config as CandidateValidationConfig;
 use polkadot_node_core_chain_selection::{
 	self as chain_selection_subsystem, Config as ChainSelectionConfig,
@@ -85,6 +90,16 @@ pub struct NewFullParams<OverseerGenerator: OverseerGen> {
 	pub prepare_workers_hard_max_num: Option<usize>,
 	/// How long finalized data should be kept in the availability store (in hours)
 	pub keep_finalized_for: Option<u32>,
+	/// How long the full available data of finalized candidates should be kept in the
+	/// availability store (in hours), if shorter than the chunks.
+	pub keep_finalized_pov_for: Option<u32>,
+	/// An optional limit on the disk space used by the availability store (in bytes).
+	pub availability_disk_quota: Option<u64>,
+	/// Configuration of the watchdog detecting stalled subsystems.
+	pub overseer_watchdog: WatchdogConfig,
+	/// Per-peer limits on the bandwidth spent answering statement distribution candidate
+	/// requests.
+	pub statement_response_limits: ResponseBandwidthLimits,
 	pub overseer_gen: OverseerGenerator,
 	pub overseer_message_channel_capacity_override: Option<usize>,
 	#[allow(dead_code)]
@@ -147,8 +162,14 @@ where
 			SelectRelayChain::new_longest_chain(basics.backend.clone())
 		};
 
-		let partial_components =
-			new_partial::<SelectRelayChain<_>>(&mut config, basics, select_chain)?;
+		// Only validators run a dispute coordinator whose state can be inspected.
+		let rpc_overseer_handle = config.role.is_authority().then(|| overseer_handle.clone());
+		let partial_components = new_partial::<SelectRelayChain<_>>(
+			&mut config,
+			basics,
+			select_chain,
+			rpc_overseer_handle,
+		)?;
 
 		let net_config = sc_network::config::FullNetworkConfiguration::<_, _, Network>::new(
 			&config.network,
@@ -199,6 +220,10 @@ where
 					prepare_workers_soft_max_num,
 					prepare_workers_hard_max_num,
 					keep_finalized_for,
+					keep_finalized_pov_for,
+					availability_disk_quota,
+					overseer_watchdog,
+					statement_response_limits,
 				},
 			overseer_connector,
 			partial_components:
@@ -416,14 +441,27 @@ where
 			let fetch_chunks_threshold =
 				if config.chain_spec.is_polkadot() { None } else { Some(FETCH_CHUNKS_THRESHOLD) };
 
+			// Live networks keep finalized data at least for the dispute window, it may only be
+			// kept for longer.
+			let min_keep_finalized_for =
+				if matches!(config.chain_spec.identify_chain(), Chain::Rococo) {
+					1
+				} else {
+					KEEP_FINALIZED_FOR_LIVE_NETWORKS
+				};
 			let availability_config = AvailabilityConfig {
 				col_data: parachains_db::REAL_COLUMNS.col_availability_data,
 				col_meta: parachains_db::REAL_COLUMNS.col_availability_meta,
 				keep_finalized_for: if matches!(config.chain_spec.identify_chain(), Chain::Rococo) {
 					keep_finalized_for.unwrap_or(1)
 				} else {
-					KEEP_FINALIZED_FOR_LIVE_NETWORKS
+					keep_finalized_for.map_or(KEEP_FINALIZED_FOR_LIVE_NETWORKS, |hours| {
+						hours.max(KEEP_FINALIZED_FOR_LIVE_NETWORKS)
+					})
 				},
+				keep_finalized_pov_for,
+				disk_quota: availability_disk_quota
+					.map(|max_bytes| AvailabilityDiskQuota { max_bytes, min_keep_finalized_for }),
 			};
 
 			Some(ExtendedOverseerGenArgs {
@@ -435,6 +473,7 @@ where
 				chunk_req_v1_receiver,
 				chunk_req_v2_receiver,
 				candidate_req_v2_receiver,
+				statement_response_limits,
 				approval_voting_config,
 				dispute_req_receiver,
 				dispute_coordinator_config,
@@ -615,6 +654,7 @@ where
 						req_protocol_names,
 						peerset_protocol_names,
 						notification_services,
+						watchdog_config: overseer_watchdog,
 					},
 					ext_overseer_args,
 				)
@@ -0,0 +1,152 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline export of the availability data of relay chain blocks.
+//!
+//! The candidates included by each block of a range of the best chain are looked up in the
+//! candidate events of the block, and what the availability store holds for them is written to
+//! files for post-mortem analysis. All files are SCALE encoded:
+//!
+//! ```text
+//! <output>/<block number>-<block hash>/<candidate hash>/receipt.scale
+//! <output>/<block number>-<block hash>/<candidate hash>/available_data.scale
+//! <output>/<block number>-<block hash>/<candidate hash>/chunk-<validator index>.scale
+//! ```
+//!
+//! Nothing is written for candidates whose data was already pruned. The parachains database is
+//! opened read-only and never migrated. The chain database is opened by the client as usual, so
+//! the node must not be running.
+
+use crate::{open_database_read_only, parachains_db, Error, FullClient};
+use codec::Encode;
+use polkadot_node_core_av_store::{
+	AvailabilityStoreSubsystem, Config as AvailabilityConfig, Metrics as AvailabilityMetrics,
+};
+use polkadot_primitives::{runtime_api::ParachainHost, vstaging::CandidateEvent};
+pub use polkadot_primitives::{BlockNumber, CandidateHash, Hash, Id as ParaId};
+use sc_service::Configuration;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use std::{path::PathBuf, sync::Arc};
+
+/// Parameters of [`export_availability`].
+#[derive(Debug, Clone)]
+pub struct ExportAvailabilityParams {
+	/// The first block of the range to export.
+	pub from: BlockNumber,
+	/// The last block of the range to export.
+	pub to: BlockNumber,
+	/// The directory to write the files to.
+	pub output: PathBuf,
+	/// Export the erasure chunks in addition to the available data.
+	pub with_chunks: bool,
+}
+
+/// A candidate whose availability data was looked up.
+#[derive(Debug)]
+pub struct ExportedCandidate {
+	/// The number of the block which included the candidate.
+	pub block_number: BlockNumber,
+	/// The hash of the block which included the candidate.
+	pub block_hash: Hash,
+	/// The hash of the candidate.
+	pub candidate_hash: CandidateHash,
+	/// The para of the candidate.
+	pub para_id: ParaId,
+	/// Whether the available data was exported.
+	pub available_data: bool,
+	/// The number of exported chunks.
+	pub chunks: usize,
+}
+
+/// The candidates of the exported block range.
+#[derive(Debug)]
+pub struct ExportAvailabilityReport {
+	/// The number of blocks of the range found in the best chain.
+	pub blocks: u32,
+	/// The candidates included by the blocks, ordered by block number.
+	pub candidates: Vec<ExportedCandidate>,
+}
+
+/// Export the availability data of the blocks described by `params`, see the
+/// [module docs](self).
+pub fn export_availability(
+	client: Arc<FullClient>,
+	config: &Configuration,
+	params: ExportAvailabilityParams,
+) -> Result<ExportAvailabilityReport, Error> {
+	let ExportAvailabilityParams { from, to, output, with_chunks } = params;
+	if from > to {
+		return Err(Error::AvailabilityExport(format!("empty block range {from}..={to}")))
+	}
+
+	let store = AvailabilityStoreSubsystem::new(
+		open_database_read_only(&config.database)?,
+		AvailabilityConfig {
+			col_data: parachains_db::REAL_COLUMNS.col_availability_data,
+			col_meta: parachains_db::REAL_COLUMNS.col_availability_meta,
+			keep_finalized_for: 0,
+			keep_finalized_pov_for: None,
+			disk_quota: None,
+		},
+		Box::new(sp_consensus::NoNetwork),
+		AvailabilityMetrics::default(),
+	);
+
+	let api = client.runtime_api();
+	let mut blocks = 0;
+	let mut candidates = Vec::new();
+	for block_number in from..=to {
+		let Some(block_hash) = client.hash(block_number)? else { break };
+		blocks += 1;
+
+		let events = api.candidate_events(block_hash).map_err(sp_blockchain::Error::from)?;
+		for event in events {
+			let CandidateEvent::CandidateIncluded(receipt, ..) = event else { continue };
+			let candidate_hash = receipt.hash();
+
+			let available_data = store.available_data(&candidate_hash)?;
+			let chunks = if with_chunks { store.chunks(&candidate_hash)? } else { Vec::new() };
+
+			if available_data.is_some() || !chunks.is_empty() {
+				let dir = output
+					.join(format!("{block_number}-{block_hash:?}"))
+					.join(format!("{:?}", candidate_hash.0));
+				std::fs::create_dir_all(&dir)?;
+
+				std::fs::write(dir.join("receipt.scale"), receipt.encode())?;
+				if let Some(available_data) = &available_data {
+					std::fs::write(dir.join("available_data.scale"), available_data.encode())?;
+				}
+				for (validator_index, chunk) in &chunks {
+					let file = dir.join(format!("chunk-{}.scale", validator_index.0));
+					std::fs::write(file, chunk.encode())?;
+				}
+			}
+
+			candidates.push(ExportedCandidate {
+				block_number,
+				block_hash,
+				candidate_hash,
+				para_id: receipt.descriptor.para_id(),
+				available_data: available_data.is_some(),
+				chunks: chunks.len(),
+			});
+		}
+	}
+
+	Ok(ExportAvailabilityReport { blocks, candidates })
+}
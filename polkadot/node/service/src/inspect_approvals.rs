@@ -0,0 +1,122 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline inspection of the approval state of relay chain blocks.
+//!
+//! Renders the assignments and approvals stored by approval-voting for the candidates of the
+//! unfinalized blocks and optionally replays the approval check at a chosen tick, with the
+//! session parameters fetched from the runtime.
+//!
+//! The parachains database is opened read-only and never migrated. The chain database is opened
+//! by the client as usual, so the node must not be running.
+
+use crate::{open_database_read_only, parachains_db, Error, FullClient};
+use polkadot_node_core_approval_voting::{
+	ApprovalCheckParams, ApprovalDbReader, Config as ApprovalVotingConfig,
+};
+pub use polkadot_node_primitives::approval::time::Tick;
+use polkadot_node_primitives::approval::time::{Clock, SystemClock};
+pub use polkadot_node_subsystem::messages::{
+	ApprovalCheckOutcome, BlockApprovalState, RequiredAssignments,
+};
+use polkadot_node_subsystem::SubsystemError;
+use polkadot_primitives::runtime_api::ParachainHost;
+pub use polkadot_primitives::{BlockNumber, Hash};
+use sc_service::Configuration;
+use sp_api::ProvideRuntimeApi;
+use std::sync::Arc;
+
+/// Parameters of [`inspect_approvals`].
+#[derive(Debug, Clone, Default)]
+pub struct InspectApprovalsParams {
+	/// Only inspect the given block.
+	pub block_hash: Option<Hash>,
+	/// Only inspect the blocks at the given height.
+	pub block_number: Option<BlockNumber>,
+	/// Replay the approval check of the candidates.
+	pub replay: bool,
+	/// The tick to replay the approval check at. Defaults to the current tick.
+	pub tick: Option<Tick>,
+}
+
+/// The approval state of relay chain blocks stored by approval-voting.
+#[derive(Debug)]
+pub struct InspectApprovalsReport {
+	/// The range of block numbers with stored approval data, if any.
+	pub stored_blocks: Option<(BlockNumber, BlockNumber)>,
+	/// The inspected blocks, ordered by number.
+	pub blocks: Vec<BlockApprovalState>,
+}
+
+/// Read the approval state of the blocks described by `params`, see the [module docs](self).
+pub fn inspect_approvals(
+	client: Arc<FullClient>,
+	config: &Configuration,
+	params: InspectApprovalsParams,
+) -> Result<InspectApprovalsReport, Error> {
+	let InspectApprovalsParams { block_hash, block_number, replay, tick } = params;
+
+	let reader = ApprovalDbReader::new(
+		open_database_read_only(&config.database)?,
+		ApprovalVotingConfig {
+			col_approval_data: parachains_db::REAL_COLUMNS.col_approval_data,
+			slot_duration_millis: sc_consensus_babe::configuration(&*client)?
+				.slot_duration()
+				.as_millis() as u64,
+		},
+	);
+	let db_error = |e: SubsystemError| Error::ApprovalsInspection(e.to_string());
+
+	let stored_blocks = reader.stored_blocks().map_err(db_error)?;
+	let block_hashes = match (block_hash, block_number, stored_blocks) {
+		(Some(block_hash), _, _) => vec![block_hash],
+		(None, Some(block_number), _) => reader.blocks_at_height(block_number).map_err(db_error)?,
+		(None, None, Some((earliest, latest))) => (earliest..=latest)
+			.map(|number| reader.blocks_at_height(number))
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(db_error)?
+			.concat(),
+		(None, None, None) => Vec::new(),
+	};
+
+	let tick = tick.unwrap_or_else(|| SystemClock.tick_now());
+	let mut blocks = Vec::with_capacity(block_hashes.len());
+	for block_hash in block_hashes {
+		let Some(state) = reader.block_approval_state(block_hash, None).map_err(db_error)? else {
+			continue
+		};
+		if !replay {
+			blocks.push(state);
+			continue
+		}
+
+		let session_info = client
+			.runtime_api()
+			.session_info(block_hash, state.session)
+			.map_err(sp_blockchain::Error::from)?
+			.ok_or_else(|| {
+				Error::ApprovalsInspection(format!("session {} not found", state.session))
+			})?;
+		let check = ApprovalCheckParams {
+			tick,
+			needed_approvals: session_info.needed_approvals,
+			no_show_slots: session_info.no_show_slots,
+		};
+		blocks.extend(reader.block_approval_state(block_hash, Some(check)).map_err(db_error)?);
+	}
+
+	Ok(InspectApprovalsReport { stored_blocks, blocks })
+}
@@ -0,0 +1,104 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline inspection of the disputes stored by the dispute coordinator.
+//!
+//! Lists the recent disputes with their status and, on request, the votes cast on them. The
+//! participation queues and spam slots are only kept in memory by a running node, use the
+//! `disputes_coordinatorState` RPC to inspect them.
+//!
+//! The database is opened read-only, so it may be inspected while the node is running.
+
+use crate::{open_database_read_only, parachains_db, Error};
+use polkadot_node_core_dispute_coordinator::{
+	Config as DisputeCoordinatorConfig, DisputesDbReader, FatalError,
+};
+pub use polkadot_node_primitives::{CandidateVotes, DisputeStatus};
+pub use polkadot_primitives::{CandidateHash, SessionIndex};
+use sc_service::Configuration;
+
+/// Parameters of [`inspect_disputes`].
+#[derive(Debug, Clone, Default)]
+pub struct InspectDisputesParams {
+	/// Only list the disputes which are still active.
+	pub active_only: bool,
+	/// Only list the disputes of the given session.
+	pub session: Option<SessionIndex>,
+	/// Only list the disputes of the given candidate.
+	pub candidate_hash: Option<CandidateHash>,
+	/// Load the votes cast on the listed disputes.
+	pub with_votes: bool,
+}
+
+/// A dispute stored by the dispute coordinator.
+#[derive(Debug)]
+pub struct DisputeReport {
+	/// The session of the dispute.
+	pub session: SessionIndex,
+	/// The disputed candidate.
+	pub candidate_hash: CandidateHash,
+	/// The status of the dispute.
+	pub status: DisputeStatus,
+	/// The votes cast on the candidate, if requested and stored.
+	pub votes: Option<CandidateVotes>,
+}
+
+/// The disputes stored by the dispute coordinator.
+#[derive(Debug)]
+pub struct InspectDisputesReport {
+	/// The earliest session whose votes are kept, if any.
+	pub earliest_session: Option<SessionIndex>,
+	/// The disputes matching the parameters, ordered by session.
+	pub disputes: Vec<DisputeReport>,
+}
+
+/// Read the disputes described by `params` from the database, see the [module docs](self).
+pub fn inspect_disputes(
+	config: &Configuration,
+	params: InspectDisputesParams,
+) -> Result<InspectDisputesReport, Error> {
+	let InspectDisputesParams { active_only, session, candidate_hash, with_votes } = params;
+
+	let reader = DisputesDbReader::new(
+		open_database_read_only(&config.database)?,
+		DisputeCoordinatorConfig {
+			col_dispute_data: parachains_db::REAL_COLUMNS.col_dispute_coordinator_data,
+		},
+	);
+	let db_error = |e: FatalError| Error::DisputesInspection(e.to_string());
+
+	let earliest_session = reader.earliest_session().map_err(db_error)?;
+	let disputes = if active_only { reader.active_disputes() } else { reader.recent_disputes() }
+		.map_err(db_error)?;
+
+	let disputes = disputes
+		.into_iter()
+		.filter(|(s, c, _)| {
+			session.map_or(true, |session| session == *s) &&
+				candidate_hash.map_or(true, |candidate_hash| candidate_hash == *c)
+		})
+		.map(|(session, candidate_hash, status)| {
+			let votes = if with_votes {
+				reader.candidate_votes(session, &candidate_hash).map_err(db_error)?
+			} else {
+				None
+			};
+			Ok(DisputeReport { session, candidate_hash, status, votes })
+		})
+		.collect::<Result<_, Error>>()?;
+
+	Ok(InspectDisputesReport { earliest_session, disputes })
+}
@@ -28,8 +28,16 @@ mod relay_chain_selection;
 #[cfg(feature = "full-node")]
 pub mod builder;
 #[cfg(feature = "full-node")]
+pub mod export_availability;
+#[cfg(feature = "full-node")]
+pub mod inspect_approvals;
+#[cfg(feature = "full-node")]
+pub mod inspect_disputes;
+#[cfg(feature = "full-node")]
 pub mod overseer;
 #[cfg(feature = "full-node")]
+pub mod validate_candidate;
+#[cfg(feature = "full-node")]
 pub mod workers;
 
 #[cfg(feature = "full-node")]
@@ -220,6 +228,22 @@ pub enum Error {
 	#[error("Expected at least one of polkadot, kusama, westend or rococo runtime feature")]
 	NoRuntime,
 
+	#[cfg(feature = "full-node")]
+	#[error("Failed to re-validate the candidate: {0}")]
+	CandidateRevalidation(String),
+
+	#[cfg(feature = "full-node")]
+	#[error("Failed to export the availability data: {0}")]
+	AvailabilityExport(String),
+
+	#[cfg(feature = "full-node")]
+	#[error("Failed to inspect the approvals: {0}")]
+	ApprovalsInspection(String),
+
+	#[cfg(feature = "full-node")]
+	#[error("Failed to inspect the disputes: {0}")]
+	DisputesInspection(String),
+
 	#[cfg(feature = "full-node")]
 	#[error("Worker binaries not executable, prepare binary: {prep_worker_path:?}, execute binary: {exec_worker_path:?}")]
 	InvalidWorkerBinaries { prep_worker_path: PathBuf, exec_worker_path: PathBuf },
@@ -345,6 +369,31 @@ pub fn open_database(db_source: &DatabaseSource) -> Result<Arc<dyn Database>, Er
 	Ok(parachains_db)
 }
 
+/// Open the parachains database read-only, see [`open_database`].
+#[cfg(feature = "full-node")]
+pub fn open_database_read_only(db_source: &DatabaseSource) -> Result<Arc<dyn Database>, Error> {
+	let parachains_db = match db_source {
+		DatabaseSource::RocksDb { path, .. } =>
+			parachains_db::open_read_only_rocksdb(path.clone())?,
+		DatabaseSource::ParityDb { path, .. } => parachains_db::open_read_only_paritydb(
+			path.parent().ok_or(Error::DatabasePathRequired)?.into(),
+		)?,
+		DatabaseSource::Auto { paritydb_path, rocksdb_path, .. } => {
+			if paritydb_path.is_dir() && paritydb_path.exists() {
+				parachains_db::open_read_only_paritydb(
+					paritydb_path.parent().ok_or(Error::DatabasePathRequired)?.into(),
+				)?
+			} else {
+				parachains_db::open_read_only_rocksdb(rocksdb_path.clone())?
+			}
+		},
+		DatabaseSource::Custom { .. } => {
+			unimplemented!("No polkadot subsystem db for custom source.");
+		},
+	};
+	Ok(parachains_db)
+}
+
 /// Is this node running as in-process node for a parachain node?
 #[cfg(feature = "full-node")]
 #[derive(Clone)]
@@ -400,7 +449,7 @@ macro_rules! chain_ops {
 		let chain_selection = LongestChain::new(basics.backend.clone());
 
 		let sc_service::PartialComponents { client, backend, import_queue, task_manager, .. } =
-			new_partial::<LongestChain<_, Block>>(&mut config, basics, chain_selection)?;
+			new_partial::<LongestChain<_, Block>>(&mut config, basics, chain_selection, None)?;
 		Ok((client, backend, import_queue, task_manager))
 	}};
 }
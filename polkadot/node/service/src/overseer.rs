@@ -37,6 +37,7 @@ use polkadot_overseer::{
 	metrics::Metrics as OverseerMetrics, MetricsTrait, Overseer, OverseerConnector, OverseerHandle,
 	SpawnGlue,
 };
+pub use polkadot_overseer::{StallThresholds, WatchdogConfig};
 
 use parking_lot::Mutex;
 use sc_authority_discovery::Service as AuthorityDiscoveryService;
@@ -72,7 +73,9 @@ pub use polkadot_node_core_prospective_parachains::ProspectiveParachainsSubsyste
 pub use polkadot_node_core_provisioner::ProvisionerSubsystem;
 pub use polkadot_node_core_pvf_checker::PvfCheckerSubsystem;
 pub use polkadot_node_core_runtime_api::RuntimeApiSubsystem;
-pub use polkadot_statement_distribution::StatementDistributionSubsystem;
+pub use polkadot_statement_distribution::{
+	ResponseBandwidthLimits, StatementDistributionSubsystem,
+};
 
 /// Arguments passed for overseer construction.
 pub struct OverseerGenArgs<'a, Spawner, RuntimeClient>
@@ -108,6 +111,8 @@ where
 	pub peerset_protocol_names: PeerSetProtocolNames,
 	/// Notification services for validation/collation protocols.
 	pub notification_services: HashMap<PeerSet, Box<dyn NotificationService>>,
+	/// Configuration of the watchdog detecting stalled subsystems.
+	pub watchdog_config: WatchdogConfig,
 }
 
 pub struct ExtendedOverseerGenArgs {
@@ -127,6 +132,8 @@ pub struct ExtendedOverseerGenArgs {
 	pub chunk_req_v2_receiver: IncomingRequestReceiver<request_v2::ChunkFetchingRequest>,
 	/// Receiver for incoming candidate requests.
 	pub candidate_req_v2_receiver: IncomingRequestReceiver<request_v2::AttestedCandidateRequest>,
+	/// Per-peer limits on the bandwidth spent answering candidate requests.
+	pub statement_response_limits: ResponseBandwidthLimits,
 	/// Configuration for the approval voting subsystem.
 	pub approval_voting_config: ApprovalVotingConfig,
 	/// Receiver for incoming disputes.
@@ -158,6 +165,7 @@ pub fn validator_overseer_builder<Spawner, RuntimeClient>(
 		req_protocol_names,
 		peerset_protocol_names,
 		notification_services,
+		watchdog_config,
 	}: OverseerGenArgs<Spawner, RuntimeClient>,
 	ExtendedOverseerGenArgs {
 		keystore,
@@ -168,6 +176,7 @@ pub fn validator_overseer_builder<Spawner, RuntimeClient>(
 		chunk_req_v1_receiver,
 		chunk_req_v2_receiver,
 		candidate_req_v2_receiver,
+		statement_response_limits,
 		approval_voting_config,
 		dispute_req_receiver,
 		dispute_coordinator_config,
@@ -289,11 +298,22 @@ where
 					return Err(Error::Overseer(SubsystemError::Context(
 						"build validator overseer for parachain node".to_owned(),
 					))),
+				#[cfg(not(feature = "experimental-collator-protocol"))]
 				IsParachainNode::No => ProtocolSide::Validator {
 					keystore: keystore.clone(),
 					eviction_policy: Default::default(),
 					metrics: Metrics::register(registry)?,
 				},
+				#[cfg(feature = "experimental-collator-protocol")]
+				IsParachainNode::No => ProtocolSide::ValidatorExperimental {
+					keystore: keystore.clone(),
+					eviction_policy: Default::default(),
+					metrics: Metrics::register(registry)?,
+					reputation_metrics: Metrics::register(registry)?,
+					db: parachains_db.clone(),
+					col_reputation_data: crate::parachains_db::REAL_COLUMNS
+						.col_collator_reputation_data,
+				},
 			};
 			CollatorProtocolSubsystem::new(side)
 		})
@@ -303,11 +323,14 @@ where
 			Metrics::register(registry)?,
 			spawner.clone(),
 		))
-		.statement_distribution(StatementDistributionSubsystem::new(
-			keystore.clone(),
-			candidate_req_v2_receiver,
-			Metrics::register(registry)?,
-		))
+		.statement_distribution(
+			StatementDistributionSubsystem::new(
+				keystore.clone(),
+				candidate_req_v2_receiver,
+				Metrics::register(registry)?,
+			)
+			.with_response_limits(statement_response_limits),
+		)
 		.approval_distribution(DummySubsystem)
 		.approval_voting(DummySubsystem)
 		.approval_voting_parallel(ApprovalVotingParallelSubsystem::with_config(
@@ -340,6 +363,7 @@ where
 		.prospective_parachains(ProspectiveParachainsSubsystem::new(Metrics::register(registry)?))
 		.activation_external_listeners(Default::default())
 		.active_leaves(Default::default())
+		.watchdog(watchdog_config)
 		.supports_parachains(runtime_client)
 		.metrics(metrics)
 		.spawner(spawner);
@@ -369,6 +393,7 @@ pub fn collator_overseer_builder<Spawner, RuntimeClient>(
 		req_protocol_names,
 		peerset_protocol_names,
 		notification_services,
+		watchdog_config,
 	}: OverseerGenArgs<Spawner, RuntimeClient>,
 ) -> Result<
 	InitializedOverseerBuilder<
@@ -485,6 +510,7 @@ where
 		.prospective_parachains(DummySubsystem)
 		.activation_external_listeners(Default::default())
 		.active_leaves(Default::default())
+		.watchdog(watchdog_config)
 		.supports_parachains(runtime_client)
 		.metrics(Metrics::register(registry)?)
 		.spawner(spawner);
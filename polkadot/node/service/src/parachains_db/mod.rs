@@ -15,7 +15,11 @@
 
 #[cfg(feature = "full-node")]
 use {
-	polkadot_node_subsystem_util::database::Database, std::io, std::path::PathBuf, std::sync::Arc,
+	kvdb::{DBKeyValue, DBTransaction, DBValue, IoStats, IoStatsKind, KeyValueDB},
+	polkadot_node_subsystem_util::database::Database,
+	std::io,
+	std::path::PathBuf,
+	std::sync::Arc,
 };
 
 #[cfg(feature = "full-node")]
@@ -73,6 +77,11 @@ pub struct ColumnsConfig {
 	pub col_chain_selection_data: u32,
 	/// The column used by dispute coordinator for data.
 	pub col_dispute_coordinator_data: u32,
+	/// The column used by the collator protocol for collator reputations.
+	///
+	/// The reputations are stored under their own key prefix, so this may be the column of
+	/// another subsystem.
+	pub col_collator_reputation_data: u32,
 }
 
 /// The real columns used by the parachains DB.
@@ -83,6 +92,7 @@ pub const REAL_COLUMNS: ColumnsConfig = ColumnsConfig {
 	col_approval_data: columns::v4::COL_APPROVAL_DATA,
 	col_chain_selection_data: columns::v4::COL_CHAIN_SELECTION_DATA,
 	col_dispute_coordinator_data: columns::v4::COL_DISPUTE_COORDINATOR_DATA,
+	col_collator_reputation_data: columns::v4::COL_DISPUTE_COORDINATOR_DATA,
 };
 
 #[derive(PartialEq, Copy, Clone)]
@@ -173,3 +183,134 @@ pub fn open_creating_paritydb(
 	);
 	Ok(Arc::new(db))
 }
+
+/// Open the `RocksDB` database on disk read-only, as a secondary instance.
+///
+/// The database isn't created nor migrated, and may be opened while the node is running.
+#[cfg(feature = "full-node")]
+pub fn open_read_only_rocksdb(root: PathBuf) -> io::Result<Arc<dyn Database>> {
+	use kvdb_rocksdb::{Database, DatabaseConfig};
+
+	let path = root.join("parachains").join("db");
+	let path_str = path
+		.to_str()
+		.ok_or_else(|| other_io_error(format!("Bad database path: {:?}", path)))?;
+
+	upgrade::ensure_current_version(&path, DatabaseKind::RocksDB)?;
+	// A secondary instance keeps its own info logs apart from the primary one.
+	let secondary = SecondaryDir::new();
+	let db_config = DatabaseConfig {
+		secondary: Some(secondary.0.clone()),
+		create_if_missing: false,
+		..DatabaseConfig::with_columns(columns::v4::NUM_COLUMNS)
+	};
+	let db = SecondaryRocksDb { db: Database::open(&db_config, &path_str)?, _secondary: secondary };
+	let db = polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter::new(
+		db,
+		columns::v4::ORDERED_COL,
+	);
+
+	Ok(Arc::new(db))
+}
+
+/// The directory of a secondary `RocksDB` instance, which is removed when dropped.
+#[cfg(feature = "full-node")]
+struct SecondaryDir(PathBuf);
+
+#[cfg(feature = "full-node")]
+impl SecondaryDir {
+	fn new() -> Self {
+		use std::sync::atomic::{AtomicUsize, Ordering};
+
+		// Tells apart the instances opened by the same process.
+		static INSTANCES: AtomicUsize = AtomicUsize::new(0);
+		let instance = INSTANCES.fetch_add(1, Ordering::Relaxed);
+		SecondaryDir(std::env::temp_dir().join(format!(
+			"polkadot-parachains-db-secondary-{}-{}",
+			std::process::id(),
+			instance,
+		)))
+	}
+}
+
+#[cfg(feature = "full-node")]
+impl Drop for SecondaryDir {
+	fn drop(&mut self) {
+		if let Err(err) = std::fs::remove_dir_all(&self.0) {
+			if err.kind() != io::ErrorKind::NotFound {
+				gum::warn!(
+					target: LOG_TARGET,
+					?err,
+					path = ?self.0,
+					"Failed to remove the directory of the secondary database",
+				);
+			}
+		}
+	}
+}
+
+/// A secondary `RocksDB` instance, whose directory is removed once the database is closed.
+#[cfg(feature = "full-node")]
+struct SecondaryRocksDb {
+	// Fields are dropped in declaration order, so the database is closed first.
+	db: kvdb_rocksdb::Database,
+	_secondary: SecondaryDir,
+}
+
+#[cfg(feature = "full-node")]
+impl KeyValueDB for SecondaryRocksDb {
+	fn get(&self, col: u32, key: &[u8]) -> io::Result<Option<DBValue>> {
+		self.db.get(col, key)
+	}
+
+	fn get_by_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<Option<DBValue>> {
+		self.db.get_by_prefix(col, prefix)
+	}
+
+	fn write(&self, transaction: DBTransaction) -> io::Result<()> {
+		self.db.write(transaction)
+	}
+
+	fn iter<'a>(&'a self, col: u32) -> Box<dyn Iterator<Item = io::Result<DBKeyValue>> + 'a> {
+		self.db.iter(col)
+	}
+
+	fn iter_with_prefix<'a>(
+		&'a self,
+		col: u32,
+		prefix: &'a [u8],
+	) -> Box<dyn Iterator<Item = io::Result<DBKeyValue>> + 'a> {
+		self.db.iter_with_prefix(col, prefix)
+	}
+
+	fn io_stats(&self, kind: IoStatsKind) -> IoStats {
+		self.db.io_stats(kind)
+	}
+
+	fn has_key(&self, col: u32, key: &[u8]) -> io::Result<bool> {
+		self.db.has_key(col, key)
+	}
+
+	fn has_prefix(&self, col: u32, prefix: &[u8]) -> io::Result<bool> {
+		self.db.has_prefix(col, prefix)
+	}
+}
+
+/// Open a parity db database read-only.
+///
+/// The database isn't created nor migrated, and may be opened while the node is running.
+#[cfg(feature = "full-node")]
+pub fn open_read_only_paritydb(root: PathBuf) -> io::Result<Arc<dyn Database>> {
+	let path = root.join("parachains");
+
+	upgrade::ensure_current_version(&path, DatabaseKind::ParityDB)?;
+
+	let db = parity_db::Db::open_read_only(&upgrade::paritydb_version_3_config(&path))
+		.map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{:?}", err)))?;
+
+	let db = polkadot_node_subsystem_util::database::paritydb_impl::DbAdapter::new(
+		db,
+		columns::v4::ORDERED_COL,
+	);
+	Ok(Arc::new(db))
+}
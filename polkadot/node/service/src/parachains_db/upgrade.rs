@@ -50,6 +50,8 @@ pub enum Error {
 	CorruptedVersionFile,
 	#[error("Parachains DB has a future version (expected {current:?}, found {got:?})")]
 	FutureVersion { current: Version, got: Version },
+	#[error("Parachains DB needs a migration (expected {current:?}, found {got:?})")]
+	OutdatedVersion { current: Version, got: Option<Version> },
 	#[error("Parachain DB migration failed")]
 	MigrationFailed,
 	#[error("Parachain DB migration would take forever")]
@@ -131,6 +133,18 @@ pub(crate) fn try_upgrade_db_to_next_version(
 	Ok(new_version)
 }
 
+/// Check that the database at the given path is at the current version, without migrating it.
+pub(crate) fn ensure_current_version(db_path: &Path, db_kind: DatabaseKind) -> Result<(), Error> {
+	match get_db_version(db_path)? {
+		Some(CURRENT_VERSION) => Ok(()),
+		// `RocksDB` did not previously have a version defined either, see above.
+		None if db_kind == DatabaseKind::RocksDB => Ok(()),
+		Some(v) if v > CURRENT_VERSION =>
+			Err(Error::FutureVersion { current: CURRENT_VERSION, got: v }),
+		got => Err(Error::OutdatedVersion { current: CURRENT_VERSION, got }),
+	}
+}
+
 /// Reads current database version from the file at given path.
 /// If the file does not exist returns `None`, otherwise the version stored in the file.
 fn get_db_version(path: &Path) -> Result<Option<Version>, Error> {
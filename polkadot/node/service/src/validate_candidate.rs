@@ -0,0 +1,383 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Offline re-validation of parachain candidates.
+//!
+//! Reproduces the validation of a candidate from the data the node stored locally: the candidate
+//! receipt is looked up in the candidate events of the relay chain, the PoV and the persisted
+//! validation data are read from the availability store and the validation code and executor
+//! parameters are fetched from the runtime. The candidate is then validated by the same prepare and
+//! execute workers the node uses, as an approval checker would.
+//!
+//! The parachains database is opened read-only and never migrated. The chain database is opened
+//! by the client as usual, so the node must not be running.
+
+use crate::{open_database_read_only, parachains_db, workers, Error, FullClient};
+use futures::future::Either;
+use polkadot_node_core_av_store::{
+	AvailabilityStoreSubsystem, Config as AvailabilityConfig, Metrics as AvailabilityMetrics,
+};
+use polkadot_node_core_candidate_validation::validate_candidate_offline;
+use polkadot_node_primitives::{AvailableData, ValidationResult};
+use polkadot_node_subsystem::messages::ValidationFailed;
+use polkadot_node_subsystem_util::{database::Database, metrics::Metrics as _};
+pub use polkadot_primitives::CandidateHash;
+use polkadot_primitives::{
+	runtime_api::ParachainHost,
+	vstaging::{CandidateEvent, CandidateReceiptV2 as CandidateReceipt},
+	BlockNumber, Hash, SessionIndex,
+};
+use prometheus_endpoint::Registry;
+use sc_service::Configuration;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use std::{
+	path::PathBuf,
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+/// The prefix of the metrics reported in [`ValidateCandidateReport::stats`].
+const PVF_METRICS_PREFIX: &str = "polkadot_pvf_";
+
+/// Parameters of [`validate_candidate`].
+#[derive(Debug, Clone)]
+pub struct ValidateCandidateParams {
+	/// The hash of the candidate to validate.
+	pub candidate_hash: CandidateHash,
+	/// The block to start searching for the candidate at. Defaults to the best block.
+	pub at: Option<Hash>,
+	/// The number of ancestors of `at` to search for the candidate.
+	pub search_depth: u32,
+	/// Path to the directory where the worker binaries reside.
+	pub workers_path: Option<PathBuf>,
+	/// The names of the prepare and execute worker binaries.
+	pub workers_names: Option<(String, String)>,
+	/// The version of the node. `None` skips the version check of the workers.
+	pub node_version: Option<String>,
+}
+
+/// The outcome of re-validating a candidate.
+#[derive(Debug)]
+pub struct ValidateCandidateReport {
+	/// The receipt of the validated candidate.
+	pub candidate_receipt: CandidateReceipt,
+	/// The relay chain block whose candidate events contain the candidate.
+	pub found_at: Hash,
+	/// The session whose executor parameters were used.
+	pub session_index: SessionIndex,
+	/// The result of the validation.
+	pub result: Result<ValidationResult, ValidationFailed>,
+	/// The wall clock time of the validation, including the preparation.
+	pub elapsed: Duration,
+	/// Timing and memory statistics reported by the validation host, by metric name.
+	pub stats: Vec<(String, f64)>,
+}
+
+/// Re-validate the candidate described by `params`, see the [module docs](self).
+pub async fn validate_candidate(
+	client: Arc<FullClient>,
+	config: &Configuration,
+	params: ValidateCandidateParams,
+) -> Result<ValidateCandidateReport, Error> {
+	let ValidateCandidateParams {
+		candidate_hash,
+		at,
+		search_depth,
+		workers_path,
+		workers_names,
+		node_version,
+	} = params;
+
+	let (candidate_receipt, found_at) = find_candidate(
+		&client,
+		candidate_hash,
+		at.unwrap_or(client.info().best_hash),
+		search_depth,
+	)?
+	.ok_or_else(|| {
+		Error::CandidateRevalidation(format!(
+			"candidate {candidate_hash:?} not found in the searched blocks"
+		))
+	})?;
+
+	let available_data =
+		load_available_data(open_database_read_only(&config.database)?, candidate_hash)?;
+
+	let api = client.runtime_api();
+	let relay_parent = candidate_receipt.descriptor.relay_parent();
+	let session_index = match candidate_receipt.descriptor.session_index() {
+		Some(session_index) => session_index,
+		None => api.session_index_for_child(relay_parent).map_err(sp_blockchain::Error::from)?,
+	};
+	let validation_code_hash = candidate_receipt.descriptor.validation_code_hash();
+	let validation_code = api
+		.validation_code_by_hash(found_at, validation_code_hash)
+		.map_err(sp_blockchain::Error::from)?
+		.ok_or_else(|| {
+			Error::CandidateRevalidation(format!(
+				"validation code {validation_code_hash:?} not found"
+			))
+		})?;
+	let executor_params = api
+		.session_executor_params(found_at, session_index)
+		.map_err(sp_blockchain::Error::from)?
+		.ok_or_else(|| {
+			Error::CandidateRevalidation(format!(
+				"executor parameters of session {session_index} not found"
+			))
+		})?;
+	#[allow(deprecated)]
+	let validation_code_bomb_limit = api
+		.validation_code_bomb_limit(found_at)
+		.unwrap_or(polkadot_node_primitives::VALIDATION_CODE_BOMB_LIMIT as u32);
+
+	// Use a dedicated cache, cleared before every run, so that the preparation is always measured.
+	let artifacts_cache_path = config
+		.database
+		.path()
+		.ok_or(Error::DatabasePathRequired)?
+		.join("pvf-artifacts-offline");
+	let _ = std::fs::remove_dir_all(&artifacts_cache_path);
+
+	let (prep_worker_path, exec_worker_path) =
+		workers::determine_workers_paths(workers_path, workers_names, node_version.clone())?;
+	let registry = Registry::new();
+	let (validation_host, host_task) = polkadot_node_core_pvf::start(
+		polkadot_node_core_pvf::Config::new(
+			artifacts_cache_path,
+			node_version,
+			false,
+			prep_worker_path,
+			exec_worker_path,
+			1,
+			1,
+			1,
+		),
+		polkadot_node_core_pvf::Metrics::register(Some(&registry))?,
+	)
+	.await?;
+
+	let started = Instant::now();
+	let validation = validate_candidate_offline(
+		validation_host,
+		available_data.validation_data,
+		validation_code,
+		candidate_receipt.clone(),
+		available_data.pov,
+		executor_params,
+		validation_code_bomb_limit,
+	);
+	futures::pin_mut!(validation, host_task);
+	let result = match futures::future::select(validation, host_task).await {
+		Either::Left((result, _)) => result,
+		Either::Right(((), _)) =>
+			return Err(Error::CandidateRevalidation(
+				"the validation host terminated unexpectedly".into(),
+			)),
+	};
+	let elapsed = started.elapsed();
+
+	Ok(ValidateCandidateReport {
+		candidate_receipt,
+		found_at,
+		session_index,
+		result,
+		elapsed,
+		stats: observed_stats(&registry),
+	})
+}
+
+/// Search the candidate events of `at` and its ancestors for the given candidate.
+fn find_candidate(
+	client: &FullClient,
+	candidate_hash: CandidateHash,
+	at: Hash,
+	search_depth: u32,
+) -> Result<Option<(CandidateReceipt, Hash)>, Error> {
+	let api = client.runtime_api();
+	search_candidate(
+		candidate_hash,
+		at,
+		search_depth,
+		|at| Ok(api.candidate_events(at).map_err(sp_blockchain::Error::from)?),
+		|at| Ok(client.header(at)?.map(|header| (header.number, header.parent_hash))),
+	)
+}
+
+/// Search the candidate events of `at` and up to `search_depth` of its ancestors, stopping at
+/// genesis.
+///
+/// `parent` returns the number and the parent hash of a block.
+fn search_candidate(
+	candidate_hash: CandidateHash,
+	mut at: Hash,
+	search_depth: u32,
+	candidate_events: impl Fn(Hash) -> Result<Vec<CandidateEvent>, Error>,
+	parent: impl Fn(Hash) -> Result<Option<(BlockNumber, Hash)>, Error>,
+) -> Result<Option<(CandidateReceipt, Hash)>, Error> {
+	for _ in 0..=search_depth {
+		let events = candidate_events(at)?;
+		let found = events.into_iter().find_map(|event| match event {
+			CandidateEvent::CandidateBacked(receipt, ..) |
+			CandidateEvent::CandidateIncluded(receipt, ..) |
+			CandidateEvent::CandidateTimedOut(receipt, ..) =>
+				(receipt.hash() == candidate_hash).then_some(receipt),
+		});
+		if let Some(receipt) = found {
+			return Ok(Some((receipt, at)))
+		}
+
+		match parent(at)? {
+			Some((number, parent_hash)) if number > 0 => at = parent_hash,
+			_ => break,
+		}
+	}
+
+	Ok(None)
+}
+
+/// Load the available data of the candidate from the availability store in `db`.
+fn load_available_data(
+	db: Arc<dyn Database>,
+	candidate_hash: CandidateHash,
+) -> Result<AvailableData, Error> {
+	AvailabilityStoreSubsystem::new(
+		db,
+		AvailabilityConfig {
+			col_data: parachains_db::REAL_COLUMNS.col_availability_data,
+			col_meta: parachains_db::REAL_COLUMNS.col_availability_meta,
+			keep_finalized_for: 0,
+			keep_finalized_pov_for: None,
+			disk_quota: None,
+		},
+		Box::new(sp_consensus::NoNetwork),
+		AvailabilityMetrics::default(),
+	)
+	.available_data(&candidate_hash)?
+	.ok_or_else(|| {
+		Error::CandidateRevalidation(format!(
+			"the availability store holds no PoV of candidate {candidate_hash:?}"
+		))
+	})
+}
+
+/// The sums of the PVF histograms observed during the validation.
+fn observed_stats(registry: &Registry) -> Vec<(String, f64)> {
+	registry
+		.gather()
+		.iter()
+		.filter(|family| family.get_name().starts_with(PVF_METRICS_PREFIX))
+		.flat_map(|family| {
+			family.get_metric().iter().filter_map(|metric| {
+				let histogram = metric.get_histogram();
+				(histogram.get_sample_count() > 0)
+					.then(|| (family.get_name().to_owned(), histogram.get_sample_sum()))
+			})
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use polkadot_node_subsystem_util::database::kvdb_impl::DbAdapter;
+	use polkadot_primitives::{CoreIndex, GroupIndex, HeadData};
+	use polkadot_primitives_test_helpers::dummy_candidate_receipt_v2;
+	use prometheus_endpoint::{register, Histogram, HistogramOpts};
+	use std::collections::HashMap;
+
+	fn included(receipt: &CandidateReceipt) -> CandidateEvent {
+		CandidateEvent::CandidateIncluded(
+			receipt.clone(),
+			HeadData::default(),
+			CoreIndex(0),
+			GroupIndex(0),
+		)
+	}
+
+	// A chain of blocks `1..=4` whose events include `candidates[n]` at block `n`.
+	fn search(
+		candidates: &HashMap<u8, CandidateReceipt>,
+		candidate_hash: CandidateHash,
+		search_depth: u32,
+	) -> Option<(CandidateReceipt, Hash)> {
+		search_candidate(
+			candidate_hash,
+			Hash::repeat_byte(4),
+			search_depth,
+			|at| Ok(candidates.get(&at[0]).map(included).into_iter().collect()),
+			|at| Ok(Some((at[0] as BlockNumber, Hash::repeat_byte(at[0].saturating_sub(1))))),
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn candidates_are_found_in_ancestors_within_search_depth() {
+		let candidates: HashMap<_, _> = [2, 4]
+			.into_iter()
+			.map(|n| (n, dummy_candidate_receipt_v2(Hash::repeat_byte(0x10 + n))))
+			.collect();
+		let at_two = candidates[&2].hash();
+
+		assert_eq!(
+			search(&candidates, candidates[&4].hash(), 0),
+			Some((candidates[&4].clone(), Hash::repeat_byte(4)))
+		);
+		assert_eq!(search(&candidates, at_two, 1), None);
+		assert_eq!(
+			search(&candidates, at_two, 2),
+			Some((candidates[&2].clone(), Hash::repeat_byte(2)))
+		);
+		// The search stops at genesis.
+		assert_eq!(search(&candidates, CandidateHash(Hash::repeat_byte(0xff)), 100), None);
+	}
+
+	#[test]
+	fn missing_available_data_is_reported() {
+		let db = DbAdapter::new(
+			kvdb_memorydb::create(parachains_db::columns::v4::NUM_COLUMNS),
+			parachains_db::columns::v4::ORDERED_COL,
+		);
+		let candidate_hash = CandidateHash(Hash::repeat_byte(1));
+
+		assert!(matches!(
+			load_available_data(Arc::new(db), candidate_hash),
+			Err(Error::CandidateRevalidation(_))
+		));
+	}
+
+	#[test]
+	fn stats_report_observed_pvf_histograms_only() {
+		let registry = Registry::new();
+		let histogram = |name: &str| {
+			register(Histogram::with_opts(HistogramOpts::new(name, "help")).unwrap(), &registry)
+				.unwrap()
+		};
+		histogram("polkadot_pvf_execution_time").observe(0.5);
+		histogram("polkadot_pvf_execution_time_other").observe(0.25);
+		histogram("polkadot_pvf_preparation_time");
+		histogram("polkadot_parachain_other_time").observe(1.0);
+
+		assert_eq!(
+			observed_stats(&registry),
+			vec![
+				("polkadot_pvf_execution_time".to_owned(), 0.5),
+				("polkadot_pvf_execution_time_other".to_owned(), 0.25),
+			]
+		);
+	}
+}
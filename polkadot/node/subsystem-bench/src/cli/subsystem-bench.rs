@@ -20,7 +20,10 @@
 use clap::Parser;
 use color_eyre::eyre;
 use colored::Colorize;
-use polkadot_subsystem_bench::{approval, availability, configuration, disputes, statement};
+use polkadot_subsystem_bench::{
+	approval, availability, collator_protocol, configuration, disputes, prospective_parachains,
+	statement,
+};
 use pyroscope::PyroscopeAgent;
 use pyroscope_pprofrs::{pprof_backend, PprofConfig};
 use serde::{Deserialize, Serialize};
@@ -42,8 +45,14 @@ pub enum TestObjective {
 	ApprovalVoting(approval::ApprovalsOptions),
 	// Benchmark the statement-distribution subsystem
 	StatementDistribution,
+	/// Benchmark the statement-distribution subsystem with a peer spamming candidate requests
+	StatementRequestSpam(statement::RequestSpamOptions),
 	/// Benchmark the dispute-coordinator subsystem
 	DisputeCoordinator(disputes::DisputesOptions),
+	/// Benchmark the validator side of the collator-protocol subsystem
+	CollatorProtocol(collator_protocol::CollatorProtocolOptions),
+	/// Benchmark the prospective-parachains subsystem
+	ProspectiveParachains(prospective_parachains::ProspectiveParachainsOptions),
 }
 
 impl std::fmt::Display for TestObjective {
@@ -56,7 +65,10 @@ impl std::fmt::Display for TestObjective {
 				Self::DataAvailabilityWrite => "DataAvailabilityWrite",
 				Self::ApprovalVoting(_) => "ApprovalVoting",
 				Self::StatementDistribution => "StatementDistribution",
+				Self::StatementRequestSpam(_) => "StatementRequestSpam",
 				Self::DisputeCoordinator(_) => "DisputeCoordinator",
+				Self::CollatorProtocol(_) => "CollatorProtocol",
+				Self::ProspectiveParachains(_) => "ProspectiveParachains",
 			}
 		)
 	}
@@ -172,12 +184,32 @@ impl BenchCli {
 					env.runtime()
 						.block_on(statement::benchmark_statement_distribution(&mut env, &state))
 				},
+				TestObjective::StatementRequestSpam(ref options) => {
+					let state =
+						statement::TestState::new(&test_config).with_request_spam(options.clone());
+					let mut env = statement::prepare_test(&state, true);
+					env.runtime()
+						.block_on(statement::benchmark_statement_distribution(&mut env, &state))
+				},
 				TestObjective::DisputeCoordinator(ref options) => {
 					let state = disputes::TestState::new(&test_config, options);
 					let mut env = disputes::prepare_test(&state, true);
 					env.runtime()
 						.block_on(disputes::benchmark_dispute_coordinator(&mut env, &state))
 				},
+				TestObjective::CollatorProtocol(ref options) => {
+					let state = collator_protocol::TestState::new(&test_config, options);
+					let mut env = collator_protocol::prepare_test(&state, true);
+					env.runtime()
+						.block_on(collator_protocol::benchmark_collator_protocol(&mut env, &state))
+				},
+				TestObjective::ProspectiveParachains(ref options) => {
+					let state = prospective_parachains::TestState::new(&test_config, options);
+					let mut env = prospective_parachains::prepare_test(&state, true);
+					env.runtime().block_on(
+						prospective_parachains::benchmark_prospective_parachains(&mut env, &state),
+					)
+				},
 			};
 			println!("\n{}\n{}", benchmark_name.purple(), usage);
 		}
@@ -0,0 +1,327 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmark of the validator side of the collator protocol.
+//!
+//! The collators of the para assigned to the backing group of the node under test connect, declare
+//! themselves and advertise competing collations at every relay chain block. The node fetches the
+//! collations and seconds them, candidate validation is not emulated.
+
+use crate::{
+	dummy_builder,
+	environment::{TestEnvironment, TestEnvironmentDependencies},
+	mock::{
+		candidate_backing::MockCandidateBacking,
+		chain_api::{ChainApiState, MockChainApi},
+		network_bridge::{MockNetworkBridgeRx, MockNetworkBridgeTx},
+		prospective_parachains::MockProspectiveParachains,
+		runtime_api::{MockRuntimeApi, MockRuntimeApiCoreState},
+		AlwaysSupportsParachains,
+	},
+	network::{new_network, NetworkEmulatorHandle, NetworkInterface, NetworkInterfaceReceiver},
+	usage::BenchmarkUsage,
+	NODE_UNDER_TEST,
+};
+use colored::Colorize;
+use polkadot_collator_protocol::{CollatorProtocolSubsystem, ProtocolSide};
+use polkadot_node_network_protocol::{
+	peer_set::CollationVersion, v2 as protocol_v2, CollationProtocols, ObservedRole,
+};
+use polkadot_node_subsystem::messages::{AllMessages, CollatorProtocolMessage, NetworkBridgeEvent};
+use polkadot_node_subsystem_util::metrics::Metrics;
+use polkadot_overseer::{
+	Handle as OverseerHandle, Overseer, OverseerConnector, OverseerMetrics, SpawnGlue,
+};
+use polkadot_primitives::{AuthorityDiscoveryId, ValidatorId};
+use sc_keystore::LocalKeystore;
+use sc_network_types::PeerId;
+use sc_service::SpawnTaskHandle;
+use serde::{Deserialize, Serialize};
+use sp_core::Pair;
+use sp_keystore::{Keystore, KeystorePtr};
+use sp_runtime::RuntimeAppPublic;
+use std::{
+	sync::{atomic::Ordering, Arc},
+	time::{Duration, Instant},
+};
+pub use test_state::TestState;
+
+mod test_state;
+
+const LOG_TARGET: &str = "subsystem-bench::collator-protocol";
+
+#[derive(Debug, Clone, Serialize, Deserialize, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct CollatorProtocolOptions {
+	#[clap(long, default_value_t = 10)]
+	/// The number of collators of the para assigned to the node under test.
+	pub n_collators: usize,
+	#[clap(long, default_value_t = 1)]
+	/// The number of cores assigned to each para.
+	pub cores_per_para: usize,
+	#[clap(long, default_value_t = false)]
+	/// Benchmark the experimental validator side, which requires the
+	/// `experimental-collator-protocol` feature.
+	pub experimental: bool,
+}
+
+pub fn make_keystore() -> KeystorePtr {
+	let keystore: KeystorePtr = Arc::new(LocalKeystore::in_memory());
+	Keystore::sr25519_generate_new(&*keystore, ValidatorId::ID, Some("//Node0"))
+		.expect("Insert key into keystore");
+	Keystore::sr25519_generate_new(&*keystore, AuthorityDiscoveryId::ID, Some("//Node0"))
+		.expect("Insert key into keystore");
+	keystore
+}
+
+fn protocol_side(state: &TestState, dependencies: &TestEnvironmentDependencies) -> ProtocolSide {
+	let keystore = make_keystore();
+	if state.options.experimental {
+		#[cfg(feature = "experimental-collator-protocol")]
+		{
+			let (db, col_reputation_data) = polkadot_collator_protocol::in_memory_reputation_db();
+			return ProtocolSide::ValidatorExperimental {
+				keystore,
+				eviction_policy: Default::default(),
+				metrics: Metrics::try_register(&dependencies.registry).unwrap(),
+				reputation_metrics: Metrics::try_register(&dependencies.registry).unwrap(),
+				db,
+				col_reputation_data,
+			}
+		}
+		#[cfg(not(feature = "experimental-collator-protocol"))]
+		panic!(
+			"The experimental validator side requires the `experimental-collator-protocol` feature"
+		);
+	}
+
+	ProtocolSide::Validator {
+		keystore,
+		eviction_policy: Default::default(),
+		metrics: Metrics::try_register(&dependencies.registry).unwrap(),
+	}
+}
+
+fn build_overseer(
+	state: &TestState,
+	network: NetworkEmulatorHandle,
+	network_interface: NetworkInterface,
+	network_receiver: NetworkInterfaceReceiver,
+	dependencies: &TestEnvironmentDependencies,
+) -> (Overseer<SpawnGlue<SpawnTaskHandle>, AlwaysSupportsParachains>, OverseerHandle) {
+	let overseer_connector = OverseerConnector::with_event_capacity(64000);
+	let overseer_metrics = OverseerMetrics::try_register(&dependencies.registry).unwrap();
+	let spawn_task_handle = dependencies.task_manager.spawn_handle();
+	let mock_runtime_api = MockRuntimeApi::new(
+		state.config.clone(),
+		state.test_authorities.clone(),
+		state.candidate_receipts.clone(),
+		state.candidate_events.clone(),
+		Default::default(),
+		0,
+		MockRuntimeApiCoreState::Scheduled,
+	)
+	.with_claim_queue(state.claim_queue.clone());
+	let chain_api_state = ChainApiState { block_headers: state.block_headers.clone() };
+	let mut mock_chain_api = MockChainApi::new(chain_api_state);
+	if state.options.experimental {
+		// The experimental side rebuilds the reputations from the finalized chain on startup.
+		mock_chain_api = mock_chain_api.with_finalized_block_number(state.config.num_blocks as u32);
+	}
+	let mock_prospective_parachains = MockProspectiveParachains::new()
+		.with_persisted_validation_data(state.pvd.clone())
+		.with_minimum_relay_parents(state.minimum_relay_parents());
+	let mock_candidate_backing = MockCandidateBacking::new(
+		state.config.clone(),
+		state
+			.test_authorities
+			.validator_pairs
+			.get(NODE_UNDER_TEST as usize)
+			.unwrap()
+			.clone(),
+		state.pvd.clone(),
+		// Statements of the backing group are not emulated.
+		Default::default(),
+	)
+	.with_commitments(state.commitments());
+	let subsystem = CollatorProtocolSubsystem::new(protocol_side(state, dependencies));
+	let network_bridge_tx = MockNetworkBridgeTx::new(
+		network,
+		network_interface.subsystem_sender(),
+		state.network_authorities.clone(),
+	);
+	let network_bridge_rx = MockNetworkBridgeRx::new(network_receiver, None);
+
+	let dummy = dummy_builder!(spawn_task_handle, overseer_metrics)
+		.replace_runtime_api(|_| mock_runtime_api)
+		.replace_chain_api(|_| mock_chain_api)
+		.replace_prospective_parachains(|_| mock_prospective_parachains)
+		.replace_candidate_backing(|_| mock_candidate_backing)
+		.replace_collator_protocol(|_| subsystem)
+		.replace_network_bridge_tx(|_| network_bridge_tx)
+		.replace_network_bridge_rx(|_| network_bridge_rx);
+	let (overseer, raw_handle) = dummy.build_with_connector(overseer_connector).unwrap();
+	let overseer_handle = OverseerHandle::new(raw_handle);
+
+	(overseer, overseer_handle)
+}
+
+pub fn prepare_test(state: &TestState, with_prometheus_endpoint: bool) -> TestEnvironment {
+	let dependencies = TestEnvironmentDependencies::default();
+	let (network, network_interface, network_receiver) = new_network(
+		&state.network_config,
+		&dependencies,
+		&state.network_authorities,
+		vec![Arc::new(state.clone())],
+	);
+	let (overseer, overseer_handle) =
+		build_overseer(state, network.clone(), network_interface, network_receiver, &dependencies);
+
+	TestEnvironment::new(
+		dependencies,
+		state.config.clone(),
+		network,
+		overseer,
+		overseer_handle,
+		state.test_authorities.clone(),
+		with_prometheus_endpoint,
+	)
+}
+
+fn collator_message(peer_id: PeerId, message: protocol_v2::CollatorProtocolMessage) -> AllMessages {
+	AllMessages::CollatorProtocol(CollatorProtocolMessage::NetworkBridgeUpdate(
+		NetworkBridgeEvent::PeerMessage(peer_id, CollationProtocols::V2(message)),
+	))
+}
+
+/// Connect all collators to the node under test and declare them as collators of our para.
+async fn connect_collators(env: &mut TestEnvironment, state: &TestState) {
+	for (collator_index, collator) in state.collators.iter().enumerate() {
+		let peer_id = state.collator_peer_id(collator_index);
+		env.send_message(AllMessages::CollatorProtocol(
+			CollatorProtocolMessage::NetworkBridgeUpdate(NetworkBridgeEvent::PeerConnected(
+				peer_id,
+				ObservedRole::Full,
+				CollationVersion::V2.into(),
+				None,
+			)),
+		))
+		.await;
+		env.send_message(collator_message(
+			peer_id,
+			protocol_v2::CollatorProtocolMessage::Declare(
+				collator.public(),
+				state.para_id,
+				collator.sign(&protocol_v2::declare_signature_payload(&peer_id)),
+			),
+		))
+		.await;
+	}
+}
+
+pub async fn benchmark_collator_protocol(
+	env: &mut TestEnvironment,
+	state: &TestState,
+) -> BenchmarkUsage {
+	state.reset_trackers();
+
+	let config = env.config().clone();
+	env.metrics().set_n_validators(config.n_validators);
+	env.metrics().set_n_cores(config.n_cores);
+
+	if state.options.experimental {
+		return benchmark_experimental_startup(env, state).await
+	}
+
+	let test_start = Instant::now();
+	for block_info in state.block_infos.iter() {
+		let block_num = block_info.number as usize;
+		gum::info!(target: LOG_TARGET, "Current block {}/{} {:?}", block_num, config.num_blocks, block_info.hash);
+		env.metrics().set_current_block(block_num);
+
+		let block_start_ts = Instant::now();
+		env.import_block(block_info.clone()).await;
+		if block_num == 1 {
+			// Collators are only accepted once our para is assigned at an active leaf.
+			connect_collators(env, state).await;
+		}
+
+		let advertisements = state.advertisements.get(&block_info.hash).unwrap();
+		for (collator_index, candidate_hash) in advertisements.iter().enumerate() {
+			let message = collator_message(
+				state.collator_peer_id(collator_index),
+				protocol_v2::CollatorProtocolMessage::AdvertiseCollation {
+					relay_parent: block_info.hash,
+					candidate_hash: *candidate_hash,
+					parent_head_data_hash: state.pvd.parent_head.hash(),
+				},
+			);
+			env.send_message(message).await;
+		}
+
+		let fetches_tracker = state.fetches_tracker.get(&block_info.hash).unwrap();
+		loop {
+			let fetches = fetches_tracker.load(Ordering::SeqCst);
+			gum::debug!(target: LOG_TARGET, "{} collations fetched at {:?}", fetches, block_info.hash);
+			if fetches > 0 {
+				break
+			}
+			tokio::time::sleep(Duration::from_millis(50)).await;
+		}
+
+		let block_time = block_start_ts.elapsed().as_millis() as u64;
+		env.metrics().set_block_time(block_time);
+		gum::info!(target: LOG_TARGET, "All work for block completed in {}", format!("{block_time:?}ms").cyan());
+	}
+
+	let duration: u128 = test_start.elapsed().as_millis();
+	gum::info!(target: LOG_TARGET, "All blocks processed in {}", format!("{duration:?}ms").cyan());
+	gum::info!(target: LOG_TARGET,
+		"Avg block time: {}",
+		format!("{} ms", test_start.elapsed().as_millis() / env.config().num_blocks as u128).red()
+	);
+
+	env.stop().await;
+	env.collect_resource_usage(&["collator-protocol"], false)
+}
+
+/// The experimental validator side only maintains the collator reputations so far, so its startup
+/// is benchmarked: the candidates included in all test blocks are processed after the last block
+/// is imported.
+async fn benchmark_experimental_startup(
+	env: &mut TestEnvironment,
+	state: &TestState,
+) -> BenchmarkUsage {
+	let test_start = Instant::now();
+	let block_info = state.block_infos.last().unwrap().clone();
+	gum::info!(target: LOG_TARGET, "Importing block {} {:?}", block_info.number, block_info.hash);
+	env.import_block(block_info).await;
+
+	// The reputations are stored at once when the startup is done.
+	env.wait_until_metric(
+		"polkadot_parachain_collator_protocol_stored_reputations",
+		None,
+		|value| value > 0.0,
+	)
+	.await;
+
+	let duration: u128 = test_start.elapsed().as_millis();
+	gum::info!(target: LOG_TARGET, "Startup completed in {}", format!("{duration:?}ms").cyan());
+
+	env.stop().await;
+	env.collect_resource_usage(&["collator-protocol"], false)
+}
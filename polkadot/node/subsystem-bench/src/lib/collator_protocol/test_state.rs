@@ -0,0 +1,289 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	collator_protocol::CollatorProtocolOptions,
+	configuration::{TestAuthorities, TestConfiguration},
+	environment::GENESIS_HASH,
+	network::{HandleNetworkMessage, NetworkMessage},
+};
+use codec::Encode;
+use polkadot_node_network_protocol::request_response::{
+	v1::CollationFetchingResponse, ProtocolName, Requests,
+};
+use polkadot_node_primitives::{BlockData, PoV};
+use polkadot_node_subsystem_test_helpers::mock::new_block_import_info;
+use polkadot_overseer::BlockInfo;
+use polkadot_primitives::{
+	vstaging::{
+		CandidateEvent, CandidateReceiptV2 as CandidateReceipt,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, MutateDescriptorV2,
+	},
+	BlockNumber, CandidateCommitments, CandidateHash, CollatorPair, CoreIndex, GroupIndex, Hash,
+	HeadData, Header, Id as ParaId, PersistedValidationData, DEFAULT_SCHEDULING_LOOKAHEAD,
+};
+use polkadot_primitives_test_helpers::{
+	dummy_committed_candidate_receipt_v2, dummy_head_data, dummy_pvd,
+};
+use sc_network_types::PeerId;
+use sp_core::Pair;
+use std::{
+	collections::{BTreeMap, HashMap, VecDeque},
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Arc,
+	},
+};
+
+const SESSION_INDEX: u32 = 0;
+
+// The core assigned to the backing group of the node under test.
+const OWN_CORE: CoreIndex = CoreIndex(0);
+
+#[derive(Clone)]
+pub struct TestState {
+	// Full test config
+	pub config: TestConfiguration,
+	// Benchmark specific options
+	pub options: CollatorProtocolOptions,
+	// Authority keys of the validators
+	pub test_authorities: TestAuthorities,
+	// Config of the network emulation, the collators are emulated as peers after the validators
+	pub network_config: TestConfiguration,
+	// Keys and peer ids of all emulated peers, validators first
+	pub network_authorities: TestAuthorities,
+	// Collators of the para assigned to the node under test
+	pub collators: Vec<CollatorPair>,
+	// The para assigned to the node under test
+	pub para_id: ParaId,
+	// The claim queue, `cores_per_para` consecutive cores are assigned to each para
+	pub claim_queue: BTreeMap<CoreIndex, VecDeque<ParaId>>,
+	// Relay chain block infos
+	pub block_infos: Vec<BlockInfo>,
+	// Relay chain block headers
+	pub block_headers: HashMap<Hash, Header>,
+	// Candidates included in each block
+	pub candidate_receipts: HashMap<Hash, Vec<CandidateReceipt>>,
+	// Candidate events of each block
+	pub candidate_events: HashMap<Hash, Vec<CandidateEvent>>,
+	// PersistedValidationData, we use one for all collations
+	pub pvd: PersistedValidationData,
+	// Collation advertised by each collator at each relay parent
+	pub advertisements: HashMap<Hash, Vec<CandidateHash>>,
+	// All generated collations
+	pub collations: HashMap<CandidateHash, (CommittedCandidateReceipt, Arc<PoV>)>,
+	// Tracks the number of collations fetched at each relay parent
+	pub fetches_tracker: HashMap<Hash, Arc<AtomicUsize>>,
+}
+
+impl TestState {
+	pub fn new(config: &TestConfiguration, options: &CollatorProtocolOptions) -> Self {
+		assert!(options.n_collators > 0, "At least one collator is required");
+		assert!(options.cores_per_para > 0, "At least one core per para is required");
+
+		let test_authorities = config.generate_authorities();
+		// Collators are always connected, the connectivity only matters for validators which the
+		// validator side of the protocol doesn't talk to.
+		let network_config = TestConfiguration {
+			n_validators: config.n_validators + options.n_collators,
+			connectivity: 100,
+			..config.clone()
+		};
+		let network_authorities = network_config.generate_authorities();
+		let collators = (0..options.n_collators)
+			.map(|index| {
+				CollatorPair::from_string_with_seed(&format!("//Collator{index}"), None)
+					.expect("Valid seed")
+					.0
+			})
+			.collect();
+
+		let para_id = ParaId::new(1);
+		let claim_queue = (0..config.n_cores)
+			.map(|core| {
+				let para_id = ParaId::new((core / options.cores_per_para) as u32 + 1);
+				(
+					CoreIndex(core as u32),
+					std::iter::repeat(para_id)
+						.take(DEFAULT_SCHEDULING_LOOKAHEAD as usize)
+						.collect(),
+				)
+			})
+			.collect();
+
+		let block_infos = (1..=config.num_blocks).map(generate_block_info).collect::<Vec<_>>();
+		let block_headers = std::iter::once(generate_genesis_header())
+			.chain(block_infos.iter().map(generate_block_header))
+			.collect();
+
+		let mut state = Self {
+			config: config.clone(),
+			options: options.clone(),
+			test_authorities,
+			network_config,
+			network_authorities,
+			collators,
+			para_id,
+			claim_queue,
+			block_infos,
+			block_headers,
+			candidate_receipts: Default::default(),
+			candidate_events: Default::default(),
+			pvd: dummy_pvd(dummy_head_data(), 0),
+			advertisements: Default::default(),
+			collations: Default::default(),
+			fetches_tracker: Default::default(),
+		};
+
+		let pov_sizes = config.pov_sizes();
+		for block_info in state.block_infos.iter() {
+			for collator_index in 0..options.n_collators {
+				let pov_size = pov_sizes[collator_index % pov_sizes.len()];
+				let pov = Arc::new(PoV { block_data: BlockData(vec![0; pov_size]) });
+				// Every collator builds a competing collation on top of the same parent.
+				let commitments = CandidateCommitments {
+					head_data: HeadData((block_info.number, collator_index as u32).encode()),
+					..dummy_committed_candidate_receipt_v2(block_info.hash).commitments
+				};
+				let mut receipt = dummy_committed_candidate_receipt_v2(block_info.hash);
+				receipt.descriptor.set_para_id(para_id);
+				receipt.descriptor.set_core_index(OWN_CORE);
+				receipt.descriptor.set_session_index(SESSION_INDEX);
+				receipt.descriptor.set_persisted_validation_data_hash(state.pvd.hash());
+				receipt.descriptor.set_pov_hash(pov.hash());
+				receipt.commitments = commitments;
+
+				let candidate_hash = receipt.hash();
+				if collator_index == 0 {
+					state
+						.candidate_receipts
+						.entry(block_info.hash)
+						.or_default()
+						.push(receipt.to_plain());
+					state.candidate_events.entry(block_info.hash).or_default().push(
+						CandidateEvent::CandidateIncluded(
+							receipt.to_plain(),
+							receipt.commitments.head_data.clone(),
+							OWN_CORE,
+							GroupIndex(0),
+						),
+					);
+				}
+				state.advertisements.entry(block_info.hash).or_default().push(candidate_hash);
+				state.collations.insert(candidate_hash, (receipt, pov));
+			}
+			state.fetches_tracker.insert(block_info.hash, Arc::new(AtomicUsize::new(0)));
+		}
+
+		state
+	}
+
+	/// The peer id of the collator with the given index.
+	pub fn collator_peer_id(&self, collator_index: usize) -> PeerId {
+		self.network_authorities.peer_ids[self.config.n_validators + collator_index]
+	}
+
+	/// The minimum relay parent of the para at each block, as reported by prospective-parachains.
+	pub fn minimum_relay_parents(&self) -> HashMap<Hash, Vec<(ParaId, BlockNumber)>> {
+		self.block_infos
+			.iter()
+			.map(|info| {
+				let min_relay_parent =
+					info.number.saturating_sub(self.config.allowed_ancestry_len).max(1);
+				(info.hash, vec![(self.para_id, min_relay_parent)])
+			})
+			.collect()
+	}
+
+	/// The commitments of all collations, needed to second them.
+	pub fn commitments(&self) -> HashMap<CandidateHash, CandidateCommitments> {
+		self.collations
+			.iter()
+			.map(|(candidate_hash, (receipt, _))| (*candidate_hash, receipt.commitments.clone()))
+			.collect()
+	}
+
+	pub fn reset_trackers(&self) {
+		self.fetches_tracker.values().for_each(|v| v.store(0, Ordering::SeqCst));
+	}
+}
+
+fn generate_block_info(block_num: usize) -> BlockInfo {
+	let parent_hash =
+		if block_num == 1 { GENESIS_HASH } else { Hash::repeat_byte(block_num as u8 - 1) };
+	let mut info =
+		new_block_import_info(Hash::repeat_byte(block_num as u8), block_num as BlockNumber);
+	info.parent_hash = parent_hash;
+	info
+}
+
+fn generate_genesis_header() -> (Hash, Header) {
+	(
+		GENESIS_HASH,
+		Header {
+			digest: Default::default(),
+			number: 0,
+			parent_hash: Default::default(),
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
+
+fn generate_block_header(info: &BlockInfo) -> (Hash, Header) {
+	(
+		info.hash,
+		Header {
+			digest: Default::default(),
+			number: info.number,
+			parent_hash: info.parent_hash,
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
+
+#[async_trait::async_trait]
+impl HandleNetworkMessage for TestState {
+	async fn handle(
+		&self,
+		message: NetworkMessage,
+		_node_sender: &mut futures::channel::mpsc::UnboundedSender<NetworkMessage>,
+	) -> Option<NetworkMessage> {
+		match message {
+			NetworkMessage::RequestFromNode(authority_id, requests) => match *requests {
+				Requests::CollationFetchingV2(req) => {
+					let (receipt, pov) = self
+						.collations
+						.get(&req.payload.candidate_hash)
+						.expect("Only advertised collations are requested");
+					self.fetches_tracker
+						.get(&req.payload.relay_parent)
+						.expect("Collations are advertised at known relay parents")
+						.fetch_add(1, Ordering::SeqCst);
+
+					let response =
+						CollationFetchingResponse::Collation(receipt.to_plain(), (**pov).clone());
+					let _ =
+						req.pending_response.send(Ok((response.encode(), ProtocolName::from(""))));
+					None
+				},
+				requests => Some(NetworkMessage::RequestFromNode(authority_id, Box::new(requests))),
+			},
+			_ => Some(message),
+		}
+	}
+}
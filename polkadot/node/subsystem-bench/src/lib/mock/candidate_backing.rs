@@ -18,13 +18,21 @@
 
 use crate::{configuration::TestConfiguration, NODE_UNDER_TEST};
 use futures::FutureExt;
-use polkadot_node_primitives::{SignedFullStatementWithPVD, Statement, StatementWithPVD};
+use polkadot_node_primitives::{
+	SignedFullStatement, SignedFullStatementWithPVD, Statement, StatementWithPVD,
+};
 use polkadot_node_subsystem::{
-	messages::CandidateBackingMessage, overseer, SpawnedSubsystem, SubsystemError,
+	messages::{CandidateBackingMessage, CollatorProtocolMessage},
+	overseer, SpawnedSubsystem, SubsystemError,
 };
 use polkadot_node_subsystem_types::OverseerSignal;
 use polkadot_primitives::{
-	CandidateHash, Hash, PersistedValidationData, SigningContext, ValidatorIndex, ValidatorPair,
+	vstaging::{
+		CandidateReceiptV2 as CandidateReceipt,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt,
+	},
+	CandidateCommitments, CandidateHash, Hash, PersistedValidationData, SigningContext,
+	ValidatorIndex, ValidatorPair,
 };
 use sp_core::Pair;
 use std::collections::HashMap;
@@ -35,6 +43,8 @@ struct MockCandidateBackingState {
 	pair: ValidatorPair,
 	pvd: PersistedValidationData,
 	own_backing_group: Vec<ValidatorIndex>,
+	// Commitments of the candidates the node is asked to second
+	commitments: HashMap<CandidateHash, CandidateCommitments>,
 }
 
 pub struct MockCandidateBacking {
@@ -49,7 +59,57 @@ impl MockCandidateBacking {
 		pvd: PersistedValidationData,
 		own_backing_group: Vec<ValidatorIndex>,
 	) -> Self {
-		Self { config, state: MockCandidateBackingState { pair, pvd, own_backing_group } }
+		Self {
+			config,
+			state: MockCandidateBackingState {
+				pair,
+				pvd,
+				own_backing_group,
+				commitments: Default::default(),
+			},
+		}
+	}
+
+	/// Sets the commitments of the candidates the node is asked to second, which are needed to
+	/// sign the `Seconded` statements.
+	pub fn with_commitments(
+		mut self,
+		commitments: HashMap<CandidateHash, CandidateCommitments>,
+	) -> Self {
+		self.state.commitments = commitments;
+		self
+	}
+
+	fn handle_second(
+		&self,
+		relay_parent: Hash,
+		receipt: CandidateReceipt,
+	) -> CollatorProtocolMessage {
+		let candidate_hash = receipt.hash();
+		let commitments = self
+			.state
+			.commitments
+			.get(&candidate_hash)
+			.cloned()
+			.expect("Commitments of the seconded candidates are known");
+		let statement = Statement::Seconded(CommittedCandidateReceipt {
+			descriptor: receipt.descriptor,
+			commitments,
+		});
+		let context = SigningContext { parent_hash: relay_parent, session_index: 0 };
+		let payload = statement.to_compact().signing_payload(&context);
+
+		CollatorProtocolMessage::Seconded(
+			relay_parent,
+			SignedFullStatement::new(
+				statement,
+				ValidatorIndex(NODE_UNDER_TEST),
+				self.state.pair.sign(&payload[..]),
+				&context,
+				&self.state.pair.public(),
+			)
+			.unwrap(),
+		)
 	}
 
 	fn handle_statement(
@@ -160,6 +220,14 @@ impl MockCandidateBacking {
 								ctx.send_message(message).await;
 							}
 						},
+						CandidateBackingMessage::CanSecond(_request, tx) => {
+							tx.send(true).unwrap();
+						},
+						CandidateBackingMessage::Second(relay_parent, receipt, _pvd, _pov) => {
+							// Validation is not emulated, all candidates are valid.
+							let message = self.handle_second(relay_parent, receipt);
+							ctx.send_message(message).await;
+						},
 						_ => {
 							unimplemented!("Unexpected candidate-backing message")
 						},
@@ -22,7 +22,7 @@ use polkadot_node_subsystem::{
 	messages::ChainApiMessage, overseer, SpawnedSubsystem, SubsystemError,
 };
 use polkadot_node_subsystem_types::OverseerSignal;
-use polkadot_primitives::Header;
+use polkadot_primitives::{BlockNumber, Header};
 use sp_core::H256;
 use std::collections::HashMap;
 
@@ -35,17 +35,27 @@ pub struct ChainApiState {
 
 pub struct MockChainApi {
 	state: ChainApiState,
+	finalized_block_number: BlockNumber,
 }
 
 impl ChainApiState {
-	fn get_header_by_number(&self, requested_number: u32) -> Option<&Header> {
-		self.block_headers.values().find(|header| header.number == requested_number)
+	fn get_hash_by_number(&self, requested_number: u32) -> Option<H256> {
+		self.block_headers
+			.iter()
+			.find(|(_, header)| header.number == requested_number)
+			.map(|(hash, _)| *hash)
 	}
 }
 
 impl MockChainApi {
 	pub fn new(state: ChainApiState) -> MockChainApi {
-		Self { state }
+		Self { state, finalized_block_number: 0 }
+	}
+
+	/// Sets the number of the finalized block, the genesis by default.
+	pub fn with_finalized_block_number(mut self, finalized_block_number: BlockNumber) -> Self {
+		self.finalized_block_number = finalized_block_number;
+		self
 	}
 }
 
@@ -83,14 +93,13 @@ impl MockChainApi {
 							)));
 						},
 						ChainApiMessage::FinalizedBlockNumber(val) => {
-							val.send(Ok(0)).unwrap();
+							val.send(Ok(self.finalized_block_number)).unwrap();
 						},
 						ChainApiMessage::FinalizedBlockHash(requested_number, sender) => {
 							let hash = self
 								.state
-								.get_header_by_number(requested_number)
-								.expect("Unknown block number")
-								.hash();
+								.get_hash_by_number(requested_number)
+								.expect("Unknown block number");
 							sender.send(Ok(Some(hash))).unwrap();
 						},
 						ChainApiMessage::BlockNumber(requested_hash, sender) => {
@@ -104,7 +113,7 @@ impl MockChainApi {
 								)))
 								.unwrap();
 						},
-						ChainApiMessage::Ancestors { hash, k: _, response_channel } => {
+						ChainApiMessage::Ancestors { hash, k, response_channel } => {
 							let block_number = self
 								.state
 								.block_headers
@@ -116,7 +125,9 @@ impl MockChainApi {
 								.block_headers
 								.iter()
 								.filter(|(_, header)| header.number < block_number)
-								.sorted_by(|a, b| a.1.number.cmp(&b.1.number))
+								// Ancestors are returned parent first.
+								.sorted_by(|a, b| b.1.number.cmp(&a.1.number))
+								.take(k)
 								.map(|(hash, _)| *hash)
 								.collect_vec();
 							response_channel.send(Ok(ancestors)).unwrap();
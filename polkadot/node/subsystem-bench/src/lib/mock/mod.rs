@@ -73,6 +73,7 @@ macro_rules! dummy_builder {
 			.prospective_parachains(MockProspectiveParachains {})
 			.activation_external_listeners(Default::default())
 			.active_leaves(Default::default())
+			.watchdog(Default::default())
 			.metrics($metrics)
 			.supports_parachains(AlwaysSupportsParachains {})
 			.spawner(SpawnGlue($spawn_task_handle))
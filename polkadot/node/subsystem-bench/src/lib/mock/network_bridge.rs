@@ -168,6 +168,13 @@ impl MockNetworkBridgeTx {
 							}
 						}
 					},
+					NetworkBridgeTxMessage::SendCollationMessage(..) |
+					NetworkBridgeTxMessage::SendCollationMessages(..) => {
+						// Emulated collators don't react to collation messages.
+					},
+					NetworkBridgeTxMessage::DisconnectPeers(..) => {
+						// Emulated peers stay connected for the whole test.
+					},
 					message => unimplemented!("Unexpected network bridge message {:?}", message),
 				},
 			}
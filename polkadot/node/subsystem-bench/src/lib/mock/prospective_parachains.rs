@@ -21,13 +21,32 @@ use polkadot_node_subsystem::{
 	messages::ProspectiveParachainsMessage, overseer, SpawnedSubsystem, SubsystemError,
 };
 use polkadot_node_subsystem_types::OverseerSignal;
-use polkadot_primitives::Hash;
+use polkadot_primitives::{BlockNumber, Hash, Id as ParaId, PersistedValidationData};
+use std::collections::HashMap;
 
-pub struct MockProspectiveParachains {}
+pub struct MockProspectiveParachains {
+	pvd: Option<PersistedValidationData>,
+	minimum_relay_parents: HashMap<Hash, Vec<(ParaId, BlockNumber)>>,
+}
 
 impl MockProspectiveParachains {
 	pub fn new() -> Self {
-		Self {}
+		Self { pvd: None, minimum_relay_parents: Default::default() }
+	}
+
+	/// Sets the minimum relay parents of the parachains at each relay chain block.
+	pub fn with_minimum_relay_parents(
+		mut self,
+		minimum_relay_parents: HashMap<Hash, Vec<(ParaId, BlockNumber)>>,
+	) -> Self {
+		self.minimum_relay_parents = minimum_relay_parents;
+		self
+	}
+
+	/// Sets the persisted validation data returned for all prospective candidates.
+	pub fn with_persisted_validation_data(mut self, pvd: PersistedValidationData) -> Self {
+		self.pvd = Some(pvd);
+		self
 	}
 }
 
@@ -51,8 +70,14 @@ impl MockProspectiveParachains {
 						return
 					},
 				orchestra::FromOrchestra::Communication { msg } => match msg {
-					ProspectiveParachainsMessage::GetMinimumRelayParents(_relay_parent, tx) => {
-						tx.send(vec![]).unwrap();
+					ProspectiveParachainsMessage::GetMinimumRelayParents(relay_parent, tx) => {
+						tx.send(
+							self.minimum_relay_parents
+								.get(&relay_parent)
+								.cloned()
+								.unwrap_or_default(),
+						)
+						.unwrap();
 					},
 					ProspectiveParachainsMessage::GetHypotheticalMembership(req, tx) => {
 						tx.send(
@@ -64,6 +89,9 @@ impl MockProspectiveParachains {
 						)
 						.unwrap();
 					},
+					ProspectiveParachainsMessage::GetProspectiveValidationData(_req, tx) => {
+						tx.send(self.pvd.clone()).unwrap();
+					},
 					_ => {
 						unimplemented!("Unexpected chain-api message")
 					},
@@ -27,10 +27,13 @@ use polkadot_node_subsystem::{
 use polkadot_node_subsystem_types::OverseerSignal;
 use polkadot_primitives::{
 	node_features,
-	vstaging::{CandidateEvent, CandidateReceiptV2 as CandidateReceipt, CoreState, OccupiedCore},
+	vstaging::{
+		async_backing::Constraints, CandidateEvent, CandidateReceiptV2 as CandidateReceipt,
+		CoreState, OccupiedCore,
+	},
 	ApprovalVotingParams, AsyncBackingParams, CoreIndex, GroupIndex, GroupRotationInfo,
 	Id as ParaId, IndexedVec, NodeFeatures, ScheduledCore, SessionIndex, SessionInfo,
-	ValidationCode, ValidatorIndex,
+	ValidationCode, ValidatorIndex, DEFAULT_SCHEDULING_LOOKAHEAD,
 };
 use sp_consensus_babe::Epoch as BabeEpoch;
 use sp_core::H256;
@@ -54,6 +57,8 @@ pub struct RuntimeApiState {
 	session_index: SessionIndex,
 	// The claim queue
 	claim_queue: BTreeMap<CoreIndex, VecDeque<ParaId>>,
+	// Backing constraints of the parachains per block
+	backing_constraints: HashMap<H256, HashMap<ParaId, Constraints>>,
 }
 
 #[derive(Clone)]
@@ -112,12 +117,29 @@ impl MockRuntimeApi {
 				session_index,
 				node_features,
 				claim_queue,
+				backing_constraints: Default::default(),
 			},
 			config,
 			core_state,
 		}
 	}
 
+	/// Replaces the claim queue, which by default assigns one core to the para of each candidate
+	/// of the first block.
+	pub fn with_claim_queue(mut self, claim_queue: BTreeMap<CoreIndex, VecDeque<ParaId>>) -> Self {
+		self.state.claim_queue = claim_queue;
+		self
+	}
+
+	/// Sets the backing constraints of the parachains at each relay chain block.
+	pub fn with_backing_constraints(
+		mut self,
+		backing_constraints: HashMap<H256, HashMap<ParaId, Constraints>>,
+	) -> Self {
+		self.state.backing_constraints = backing_constraints;
+		self
+	}
+
 	fn session_info(&self) -> SessionInfo {
 		session_info_for_peers(&self.config, &self.state.authorities)
 	}
@@ -343,6 +365,31 @@ impl MockRuntimeApi {
 						) => {
 							tx.send(Ok(vec![])).unwrap();
 						},
+						RuntimeApiMessage::Request(
+							_parent,
+							RuntimeApiRequest::SchedulingLookahead(_session_index, tx),
+						) => {
+							tx.send(Ok(DEFAULT_SCHEDULING_LOOKAHEAD)).unwrap();
+						},
+						RuntimeApiMessage::Request(
+							block_hash,
+							RuntimeApiRequest::BackingConstraints(para_id, tx),
+						) => {
+							let constraints = self
+								.state
+								.backing_constraints
+								.get(&block_hash)
+								.and_then(|constraints| constraints.get(&para_id))
+								.cloned();
+							tx.send(Ok(constraints)).unwrap();
+						},
+						RuntimeApiMessage::Request(
+							_parent,
+							RuntimeApiRequest::CandidatesPendingAvailability(_para_id, tx),
+						) => {
+							// Candidates are never pending availability in the benchmarks.
+							tx.send(Ok(vec![])).unwrap();
+						},
 						// Long term TODO: implement more as needed.
 						message => {
 							unimplemented!("Unexpected runtime-api message: {:?}", message)
@@ -0,0 +1,218 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	configuration::{TestAuthorities, TestConfiguration},
+	environment::GENESIS_HASH,
+	prospective_parachains::ProspectiveParachainsOptions,
+};
+use codec::Encode;
+use polkadot_node_subsystem_test_helpers::mock::new_block_import_info;
+use polkadot_overseer::BlockInfo;
+use polkadot_primitives::{
+	async_backing::InboundHrmpLimitations,
+	vstaging::{
+		async_backing::Constraints, CandidateReceiptV2 as CandidateReceipt,
+		CommittedCandidateReceiptV2 as CommittedCandidateReceipt,
+	},
+	BlockNumber, CoreIndex, Hash, HeadData, Header, Id as ParaId, PersistedValidationData,
+	ValidationCode, ValidationCodeHash, DEFAULT_SCHEDULING_LOOKAHEAD,
+};
+use polkadot_primitives_test_helpers::{dummy_head_data, make_candidate_v2};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+const MAX_POV_SIZE: u32 = 1_000_000;
+
+#[derive(Clone)]
+pub struct TestState {
+	// Full test config
+	pub config: TestConfiguration,
+	// Benchmark specific options
+	pub options: ProspectiveParachainsOptions,
+	// Authority keys for the network emulation
+	pub test_authorities: TestAuthorities,
+	// The claim queue, `cores_per_para` consecutive cores are assigned to each para
+	pub claim_queue: BTreeMap<CoreIndex, VecDeque<ParaId>>,
+	// Relay chain block infos
+	pub block_infos: Vec<BlockInfo>,
+	// Relay chain block headers
+	pub block_headers: HashMap<Hash, Header>,
+	// Backing constraints of each para at each block
+	pub backing_constraints: HashMap<Hash, HashMap<ParaId, Constraints>>,
+	// The candidates of the main chains at each block, one per core
+	pub candidate_receipts: HashMap<Hash, Vec<CandidateReceipt>>,
+	// All candidates of each para at each block, parents before children
+	pub candidates:
+		HashMap<Hash, BTreeMap<ParaId, Vec<(CommittedCandidateReceipt, PersistedValidationData)>>>,
+}
+
+impl TestState {
+	pub fn new(config: &TestConfiguration, options: &ProspectiveParachainsOptions) -> Self {
+		assert!(options.cores_per_para > 0, "At least one core per para is required");
+		assert!(options.fork_factor > 0, "At least one candidate per core is required");
+		assert_eq!(
+			config.n_cores % options.cores_per_para,
+			0,
+			"The number of cores must be a multiple of the number of cores per para"
+		);
+
+		let n_paras = config.n_cores / options.cores_per_para;
+		let claim_queue = (0..config.n_cores)
+			.map(|core| {
+				let para_id = ParaId::new((core / options.cores_per_para) as u32 + 1);
+				(
+					CoreIndex(core as u32),
+					std::iter::repeat(para_id)
+						.take(DEFAULT_SCHEDULING_LOOKAHEAD as usize)
+						.collect(),
+				)
+			})
+			.collect();
+
+		let block_infos = (1..=config.num_blocks).map(generate_block_info).collect::<Vec<_>>();
+		let block_headers = std::iter::once(generate_genesis_header())
+			.chain(block_infos.iter().map(generate_block_header))
+			.collect();
+
+		let mut state = Self {
+			config: config.clone(),
+			options: options.clone(),
+			test_authorities: config.generate_authorities(),
+			claim_queue,
+			block_infos,
+			block_headers,
+			backing_constraints: Default::default(),
+			candidate_receipts: Default::default(),
+			candidates: Default::default(),
+		};
+
+		let validation_code_hash = ValidationCode(vec![1, 2, 3]).hash();
+		// The head of each para included by the previous block.
+		let mut required_parents = vec![dummy_head_data(); n_paras];
+		for block_info in state.block_infos.iter() {
+			let mut constraints = HashMap::new();
+			let mut candidates = BTreeMap::new();
+			let mut candidate_receipts = Vec::new();
+			for (para_index, required_parent) in required_parents.iter_mut().enumerate() {
+				let para_id = ParaId::new(para_index as u32 + 1);
+				constraints.insert(
+					para_id,
+					dummy_constraints(
+						block_info.number.saturating_sub(config.allowed_ancestry_len),
+						required_parent.clone(),
+						validation_code_hash,
+					),
+				);
+
+				let mut para_candidates = Vec::new();
+				let mut parent_head = required_parent.clone();
+				for position in 0..options.cores_per_para {
+					// The first candidate at each position builds the main chain, the others are
+					// forks competing with it.
+					for fork in 0..options.fork_factor {
+						let head_data = HeadData(
+							(block_info.number, para_id, position as u32, fork as u32).encode(),
+						);
+						let (candidate, pvd) = make_candidate_v2(
+							block_info.hash,
+							block_info.number,
+							para_id,
+							parent_head.clone(),
+							head_data,
+							validation_code_hash,
+						);
+						if fork == 0 {
+							candidate_receipts.push(candidate.to_plain());
+						}
+						para_candidates.push((candidate, pvd));
+					}
+					parent_head = para_candidates[para_candidates.len() - options.fork_factor]
+						.0
+						.commitments
+						.head_data
+						.clone();
+				}
+				// The main chain is included before the next block.
+				*required_parent = parent_head;
+				candidates.insert(para_id, para_candidates);
+			}
+			state.backing_constraints.insert(block_info.hash, constraints);
+			state.candidate_receipts.insert(block_info.hash, candidate_receipts);
+			state.candidates.insert(block_info.hash, candidates);
+		}
+
+		state
+	}
+}
+
+fn dummy_constraints(
+	min_relay_parent_number: BlockNumber,
+	required_parent: HeadData,
+	validation_code_hash: ValidationCodeHash,
+) -> Constraints {
+	Constraints {
+		min_relay_parent_number,
+		max_pov_size: MAX_POV_SIZE,
+		max_head_data_size: 20480,
+		max_code_size: 1_000_000,
+		ump_remaining: 10,
+		ump_remaining_bytes: 1_000,
+		max_ump_num_per_candidate: 10,
+		dmp_remaining_messages: vec![],
+		hrmp_inbound: InboundHrmpLimitations { valid_watermarks: vec![] },
+		hrmp_channels_out: vec![],
+		max_hrmp_num_per_candidate: 0,
+		required_parent,
+		validation_code_hash,
+		upgrade_restriction: None,
+		future_validation_code: None,
+	}
+}
+
+fn generate_block_info(block_num: usize) -> BlockInfo {
+	let parent_hash =
+		if block_num == 1 { GENESIS_HASH } else { Hash::repeat_byte(block_num as u8 - 1) };
+	let mut info =
+		new_block_import_info(Hash::repeat_byte(block_num as u8), block_num as BlockNumber);
+	info.parent_hash = parent_hash;
+	info
+}
+
+fn generate_genesis_header() -> (Hash, Header) {
+	(
+		GENESIS_HASH,
+		Header {
+			digest: Default::default(),
+			number: 0,
+			parent_hash: Default::default(),
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
+
+fn generate_block_header(info: &BlockInfo) -> (Hash, Header) {
+	(
+		info.hash,
+		Header {
+			digest: Default::default(),
+			number: info.number,
+			parent_hash: info.parent_hash,
+			extrinsics_root: Default::default(),
+			state_root: Default::default(),
+		},
+	)
+}
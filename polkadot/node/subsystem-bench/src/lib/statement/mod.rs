@@ -31,12 +31,13 @@ use crate::{
 	NODE_UNDER_TEST,
 };
 use bitvec::vec::BitVec;
+use codec::Encode;
 use colored::Colorize;
 use itertools::Itertools;
 use polkadot_node_metrics::metrics::Metrics;
 use polkadot_node_network_protocol::{
 	grid_topology::{SessionGridTopology, TopologyPeerInfo},
-	request_response::{IncomingRequest, ReqProtocolNames},
+	request_response::{v2::AttestedCandidateRequest, IncomingRequest, ReqProtocolNames},
 	v3::{self, BackedCandidateManifest, StatementFilter},
 	view, ValidationProtocols, View,
 };
@@ -48,12 +49,14 @@ use polkadot_overseer::{
 	Handle as OverseerHandle, Overseer, OverseerConnector, OverseerMetrics, SpawnGlue,
 };
 use polkadot_primitives::{
-	AuthorityDiscoveryId, Block, GroupIndex, Hash, Id, ValidatorId, ValidatorIndex,
+	AuthorityDiscoveryId, Block, CandidateHash, GroupIndex, Hash, Id, ValidatorId, ValidatorIndex,
 };
-use polkadot_statement_distribution::StatementDistributionSubsystem;
+use polkadot_statement_distribution::{ResponseBandwidthLimits, StatementDistributionSubsystem};
 use sc_keystore::LocalKeystore;
+use sc_network::request_responses::{IncomingRequest as RawIncomingRequest, OutgoingResponse};
 use sc_network_types::PeerId;
 use sc_service::SpawnTaskHandle;
+use serde::{Deserialize, Serialize};
 use sp_keystore::{Keystore, KeystorePtr};
 use sp_runtime::RuntimeAppPublic;
 use std::{
@@ -66,6 +69,30 @@ mod test_state;
 
 const LOG_TARGET: &str = "subsystem-bench::statement";
 
+/// Parameters of the candidate request spam benchmark
+#[derive(Debug, Clone, Serialize, Deserialize, clap::Parser)]
+#[clap(rename_all = "kebab-case")]
+#[allow(missing_docs)]
+pub struct RequestSpamOptions {
+	#[clap(long, default_value_t = 100)]
+	/// The number of candidate requests a peer sends on every block.
+	pub spam_requests: usize,
+	#[clap(long, default_value_t = 16 * 1024)]
+	/// The rate at which the response budget of a peer is refilled, in bytes per second.
+	pub response_rate: u32,
+	#[clap(long, default_value_t = 64 * 1024)]
+	/// The response budget of a peer, in bytes.
+	pub response_burst: u32,
+}
+
+/// Outcomes of the spammed candidate requests.
+#[derive(Debug, Default)]
+struct RequestSpamStats {
+	served: usize,
+	refused: usize,
+	dropped: usize,
+}
+
 pub fn make_keystore() -> KeystorePtr {
 	let keystore: KeystorePtr = Arc::new(LocalKeystore::in_memory());
 	Keystore::sr25519_generate_new(&*keystore, ValidatorId::ID, Some("//Node0"))
@@ -113,11 +140,17 @@ fn build_overseer(
 			&ReqProtocolNames::new(GENESIS_HASH, None),
 		);
 	let keystore = make_keystore();
-	let subsystem = StatementDistributionSubsystem::new(
+	let mut subsystem = StatementDistributionSubsystem::new(
 		keystore.clone(),
 		candidate_req_receiver,
 		Metrics::try_register(&dependencies.registry).unwrap(),
 	);
+	if let Some(ref options) = state.request_spam {
+		subsystem = subsystem.with_response_limits(ResponseBandwidthLimits {
+			rate: options.response_rate,
+			burst: options.response_burst,
+		});
+	}
 	let network_bridge_tx = MockNetworkBridgeTx::new(
 		network,
 		network_interface.subsystem_sender(),
@@ -181,6 +214,58 @@ pub fn generate_new_session_topology(
 	))]
 }
 
+/// Makes a peer that received a manifest for our candidate re-request it `spam_requests` times.
+async fn spam_candidate_requests(
+	env: &TestEnvironment,
+	state: &TestState,
+	options: &RequestSpamOptions,
+	candidate_hash: CandidateHash,
+	stats: &mut RequestSpamStats,
+) {
+	let authority_id = loop {
+		let requester = state
+			.manifest_requesters
+			.lock()
+			.unwrap()
+			.get(&candidate_hash)
+			.and_then(|requesters| requesters.first().cloned());
+		if let Some(requester) = requester {
+			break requester
+		}
+		gum::debug!(target: LOG_TARGET, ?candidate_hash, "Waiting for a peer to request the candidate");
+		tokio::time::sleep(Duration::from_millis(50)).await;
+	};
+	let peer_index = state
+		.test_authorities
+		.validator_authority_id
+		.iter()
+		.position(|id| id == &authority_id)
+		.unwrap();
+	let peer_id = *state.test_authorities.peer_ids.get(peer_index).unwrap();
+
+	for _ in 0..options.spam_requests {
+		let (pending_response, response_receiver) = futures::channel::oneshot::channel();
+		let request = RawIncomingRequest {
+			peer: peer_id,
+			payload: AttestedCandidateRequest {
+				candidate_hash,
+				mask: StatementFilter::blank(state.own_backing_group.len()),
+			}
+			.encode(),
+			pending_response,
+		};
+		env.network().send_request_from_peer(&authority_id, request).unwrap();
+
+		match response_receiver.await {
+			Ok(OutgoingResponse { result: Ok(_), .. }) => stats.served += 1,
+			Ok(OutgoingResponse { result: Err(()), .. }) => stats.refused += 1,
+			// The request was dropped without an answer, e.g. because the previous one is still
+			// being served.
+			Err(_) => stats.dropped += 1,
+		}
+	}
+}
+
 /// Generates a topology to be used for this benchmark.
 pub fn generate_topology(test_authorities: &TestAuthorities) -> SessionGridTopology {
 	let keyrings = test_authorities
@@ -247,6 +332,7 @@ pub async fn benchmark_statement_distribution(
 
 	let test_start = Instant::now();
 	let mut candidates_advertised = 0;
+	let mut spam_stats = RequestSpamStats::default();
 	for block_info in state.block_infos.iter() {
 		let block_num = block_info.number as usize;
 		gum::info!(target: LOG_TARGET, "Current block {}/{} {:?}", block_num, config.num_blocks, block_info.hash);
@@ -417,6 +503,17 @@ pub async fn benchmark_statement_distribution(
 			}
 			tokio::time::sleep(Duration::from_millis(50)).await;
 		}
+
+		if let Some(ref options) = state.request_spam {
+			let candidate_hash = state
+				.candidate_receipts
+				.get(&block_info.hash)
+				.unwrap()
+				.get(own_backing_group_index)
+				.unwrap()
+				.hash();
+			spam_candidate_requests(env, state, options, candidate_hash, &mut spam_stats).await;
+		}
 	}
 
 	let duration: u128 = test_start.elapsed().as_millis();
@@ -425,6 +522,14 @@ pub async fn benchmark_statement_distribution(
 		"Avg block time: {}",
 		format!("{} ms", test_start.elapsed().as_millis() / env.config().num_blocks as u128).red()
 	);
+	if state.request_spam.is_some() {
+		gum::info!(target: LOG_TARGET,
+			"Spammed candidate requests: {} served, {} refused over bandwidth budget, {} dropped",
+			format!("{}", spam_stats.served).green(),
+			format!("{}", spam_stats.refused).red(),
+			spam_stats.dropped,
+		);
+	}
 
 	env.stop().await;
 	env.collect_resource_usage(&["statement-distribution"], false)
@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
 
+use super::RequestSpamOptions;
 use crate::{
 	configuration::{TestAuthorities, TestConfiguration},
 	mock::runtime_api::session_info_for_peers,
@@ -45,8 +46,8 @@ use polkadot_primitives::{
 		CandidateReceiptV2 as CandidateReceipt,
 		CommittedCandidateReceiptV2 as CommittedCandidateReceipt, MutateDescriptorV2,
 	},
-	BlockNumber, CandidateHash, CompactStatement, CoreIndex, Hash, Header, Id,
-	PersistedValidationData, SessionInfo, SignedStatement, SigningContext, UncheckedSigned,
+	AuthorityDiscoveryId, BlockNumber, CandidateHash, CompactStatement, CoreIndex, Hash, Header,
+	Id, PersistedValidationData, SessionInfo, SignedStatement, SigningContext, UncheckedSigned,
 	ValidatorIndex, ValidatorPair,
 };
 use polkadot_primitives_test_helpers::{
@@ -58,7 +59,7 @@ use std::{
 	collections::HashMap,
 	sync::{
 		atomic::{AtomicBool, Ordering},
-		Arc,
+		Arc, Mutex,
 	},
 };
 
@@ -90,6 +91,10 @@ pub struct TestState {
 	pub statements_tracker: HashMap<CandidateHash, Vec<Arc<AtomicBool>>>,
 	// Tracks if manifest exchange happened
 	pub manifests_tracker: HashMap<CandidateHash, Arc<AtomicBool>>,
+	// Map from candidate hash to authorities that requested it after receiving a manifest
+	pub manifest_requesters: Arc<Mutex<HashMap<CandidateHash, Vec<AuthorityDiscoveryId>>>>,
+	// Peers spamming the node with candidate requests, if enabled
+	pub request_spam: Option<RequestSpamOptions>,
 }
 
 impl TestState {
@@ -112,6 +117,8 @@ impl TestState {
 			block_headers: Default::default(),
 			statements_tracker: Default::default(),
 			manifests_tracker: Default::default(),
+			manifest_requesters: Default::default(),
+			request_spam: None,
 			session_info,
 			own_backing_group,
 			statements: Default::default(),
@@ -180,6 +187,12 @@ impl TestState {
 		state
 	}
 
+	/// Make a peer spam the node with candidate requests on every block.
+	pub fn with_request_spam(mut self, options: RequestSpamOptions) -> Self {
+		self.request_spam = Some(options);
+		self
+	}
+
 	pub fn reset_trackers(&self) {
 		self.statements_tracker.values().for_each(|v| {
 			v.iter()
@@ -189,6 +202,7 @@ impl TestState {
 		self.manifests_tracker
 			.values()
 			.for_each(|v| v.as_ref().store(false, Ordering::SeqCst));
+		self.manifest_requesters.lock().unwrap().clear();
 	}
 }
 
@@ -391,6 +405,12 @@ impl HandleNetworkMessage for TestState {
 					let response =
 						AttestedCandidateResponse::decode(&mut response.result.unwrap().as_ref())
 							.unwrap();
+					self.manifest_requesters
+						.lock()
+						.unwrap()
+						.entry(manifest.candidate_hash)
+						.or_default()
+						.push(authority_id);
 
 					for statement in response.statements {
 						let validator_index = statement.unchecked_validator_index();
@@ -33,6 +33,7 @@ use polkadot_node_network_protocol::{
 };
 use polkadot_node_primitives::{
 	approval::{
+		time::Tick,
 		v1::{BlockApprovalMeta, DelayTranche},
 		v2::{CandidateBitfield, IndirectAssignmentCertV2, IndirectSignedApprovalVoteV2},
 	},
@@ -262,6 +263,28 @@ pub enum CollatorProtocolMessage {
 	///
 	/// The hash is the relay parent.
 	Seconded(Hash, SignedFullStatement),
+	/// Get the reputations the validator side keeps for collators, optionally only those of the
+	/// given para, ordered by para and descending score.
+	///
+	/// Only answered when the validator side keeps the collator reputations, which requires the
+	/// `experimental-collator-protocol` feature. The sender is dropped otherwise.
+	QueryReputations(Option<ParaId>, oneshot::Sender<Vec<CollatorReputation>>),
+}
+
+/// The reputation of a collator for a para, as returned by
+/// `CollatorProtocolMessage::QueryReputations`.
+///
+/// Collators with a lower score are deprioritized when the validator runs out of connection slots.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollatorReputation {
+	/// The para the collator collates for.
+	pub para_id: ParaId,
+	/// The peer id of the collator.
+	pub peer_id: PeerId,
+	/// The reputation score.
+	pub score: u16,
+	/// The time of the last reputation bump, in milliseconds since the UNIX epoch.
+	pub last_bumped: u64,
 }
 
 impl Default for CollatorProtocolMessage {
@@ -346,6 +369,27 @@ pub enum DisputeCoordinatorMessage {
 		/// The block to vote on, might be base in case there is no better.
 		tx: oneshot::Sender<(BlockNumber, Hash)>,
 	},
+	/// Get a snapshot of the in-memory state of the dispute coordinator: the participation queues
+	/// and the occupied spam slots.
+	QueryState(oneshot::Sender<DisputeCoordinatorState>),
+}
+
+/// The in-memory state of the dispute coordinator, as returned by
+/// `DisputeCoordinatorMessage::QueryState`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DisputeCoordinatorState {
+	/// Candidates whose disputes we are currently participating in.
+	pub running_participations: Vec<CandidateHash>,
+	/// Disputes queued for participation with priority, in the order of participation.
+	pub priority_queue: Vec<(SessionIndex, CandidateHash)>,
+	/// Disputes queued for best-effort participation, in the order of participation.
+	pub best_effort_queue: Vec<(SessionIndex, CandidateHash)>,
+	/// Unconfirmed disputes with the validators which voted against the candidate.
+	pub unconfirmed_disputes: Vec<(SessionIndex, CandidateHash, Vec<ValidatorIndex>)>,
+	/// Occupied spam slots, the number of unconfirmed disputes raised by a validator in a session.
+	pub spam_slots: Vec<(SessionIndex, ValidatorIndex, u32)>,
+	/// The number of spam slots available to each validator per session.
+	pub max_spam_slots: u32,
 }
 
 /// The result of `DisputeCoordinatorMessage::ImportStatements`.
@@ -1014,6 +1058,8 @@ pub enum ApprovalVotingParallelMessage {
 		CandidateHash,
 		oneshot::Sender<HashMap<ValidatorIndex, (Vec<CandidateHash>, ValidatorSignature)>>,
 	),
+	/// Gets mapped into `ApprovalVotingMessage::InspectBlock`
+	InspectBlock(Hash, Option<Tick>, oneshot::Sender<Option<BlockApprovalState>>),
 	/// Gets mapped into `ApprovalDistributionMessage::NewBlocks`
 	NewBlocks(Vec<BlockApprovalMeta>),
 	/// Gets mapped into `ApprovalDistributionMessage::DistributeAssignment`
@@ -1043,6 +1089,8 @@ impl TryFrom<ApprovalVotingParallelMessage> for ApprovalVotingMessage {
 				Ok(ApprovalVotingMessage::ApprovedAncestor(hash, number, tx)),
 			ApprovalVotingParallelMessage::GetApprovalSignaturesForCandidate(candidate, tx) =>
 				Ok(ApprovalVotingMessage::GetApprovalSignaturesForCandidate(candidate, tx)),
+			ApprovalVotingParallelMessage::InspectBlock(block_hash, tick, tx) =>
+				Ok(ApprovalVotingMessage::InspectBlock(block_hash, tick, tx)),
 			_ => Err(()),
 		}
 	}
@@ -1182,6 +1230,102 @@ pub enum ApprovalVotingMessage {
 		CandidateHash,
 		oneshot::Sender<HashMap<ValidatorIndex, (Vec<CandidateHash>, ValidatorSignature)>>,
 	),
+
+	/// Get the assignments and approvals recorded for the candidates of a relay chain block, with
+	/// the outcome of the approval check at the given tick, or the current one.
+	///
+	/// Returns `None` if the block is unknown to approval-voting.
+	InspectBlock(Hash, Option<Tick>, oneshot::Sender<Option<BlockApprovalState>>),
+}
+
+/// The approval state of a relay chain block, as returned by `ApprovalVotingMessage::InspectBlock`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockApprovalState {
+	/// The hash of the block.
+	pub block_hash: Hash,
+	/// The number of the block.
+	pub block_number: BlockNumber,
+	/// The session of the block.
+	pub session: SessionIndex,
+	/// The slot of the block.
+	pub slot: u64,
+	/// Whether all candidates of the block are approved.
+	pub approved: bool,
+	/// The candidates included by the block.
+	pub candidates: Vec<CandidateApprovalState>,
+}
+
+/// The approval state of a candidate under a relay chain block, see [`BlockApprovalState`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateApprovalState {
+	/// The hash of the candidate.
+	pub candidate_hash: CandidateHash,
+	/// The para of the candidate.
+	pub para_id: ParaId,
+	/// The core the candidate occupies.
+	pub core_index: CoreIndex,
+	/// The group which backed the candidate.
+	pub backing_group: GroupIndex,
+	/// Whether the candidate is approved under the block.
+	pub approved: bool,
+	/// The assignments received for the candidate, by tranche, with the tick they were received
+	/// at. Empty tranches are omitted.
+	pub tranches: Vec<(DelayTranche, Vec<(ValidatorIndex, Tick)>)>,
+	/// The validators which approved the candidate.
+	pub approvals: Vec<ValidatorIndex>,
+	/// The tranche of our own assignment, if any.
+	pub our_assignment: Option<DelayTranche>,
+	/// The outcome of the approval check, if the session of the block is known.
+	pub check: Option<ApprovalCheckState>,
+}
+
+/// The outcome of running the approval check of a candidate at a given tick.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalCheckState {
+	/// The tick the check was run at.
+	pub tick: Tick,
+	/// The tranche corresponding to `tick`.
+	pub tranche_now: DelayTranche,
+	/// The assignments required for approval.
+	pub required: RequiredAssignments,
+	/// The validators considered no-shows.
+	pub no_shows: Vec<ValidatorIndex>,
+	/// The outcome of the check.
+	pub outcome: ApprovalCheckOutcome,
+}
+
+/// The assignments required to approve a candidate, see [`ApprovalCheckState`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequiredAssignments {
+	/// All validators are required, there are too many no-shows.
+	All,
+	/// More assignments are needed, the tranches up to `considered` were taken into account.
+	Pending {
+		/// The highest considered tranche.
+		considered: DelayTranche,
+		/// The tick at which the next no-show would occur.
+		next_no_show: Option<Tick>,
+	},
+	/// Enough validators are assigned, the assignments up to the tranche `needed` must approve.
+	Exact {
+		/// The tranche to inspect up to.
+		needed: DelayTranche,
+		/// The number of missing approvals tolerated.
+		tolerated_missing: usize,
+		/// The tick at which the next no-show would occur.
+		next_no_show: Option<Tick>,
+	},
+}
+
+/// The outcome of the approval check of a candidate, see [`ApprovalCheckState`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ApprovalCheckOutcome {
+	/// The candidate is not approved.
+	Unapproved,
+	/// The candidate is approved despite the given number of no-shows.
+	Approved(usize),
+	/// The candidate is approved by more than a third of the validators.
+	ApprovedOneThird,
 }
 
 /// Message to the Approval Distribution subsystem.
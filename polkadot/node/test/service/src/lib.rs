@@ -103,6 +103,10 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 					prepare_workers_hard_max_num: None,
 					prepare_workers_soft_max_num: None,
 					keep_finalized_for: None,
+					keep_finalized_pov_for: None,
+					availability_disk_quota: None,
+					overseer_watchdog: Default::default(),
+					statement_response_limits: Default::default(),
 				},
 			),
 		sc_network::config::NetworkBackendType::Litep2p =>
@@ -125,6 +129,10 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 					prepare_workers_hard_max_num: None,
 					prepare_workers_soft_max_num: None,
 					keep_finalized_for: None,
+					keep_finalized_pov_for: None,
+					availability_disk_quota: None,
+					overseer_watchdog: Default::default(),
+					statement_response_limits: Default::default(),
 				},
 			),
 	}
@@ -98,6 +98,10 @@ fn main() -> Result<()> {
 						prepare_workers_hard_max_num: None,
 						prepare_workers_soft_max_num: None,
 						keep_finalized_for: None,
+						keep_finalized_pov_for: None,
+						availability_disk_quota: None,
+						overseer_watchdog: Default::default(),
+						statement_response_limits: Default::default(),
 					},
 				)
 				.map_err(|e| e.to_string())?;
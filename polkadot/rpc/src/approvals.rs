@@ -0,0 +1,257 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC methods to inspect the approval state of relay chain blocks known to approval-voting.
+//!
+//! All methods are unsafe, they expose the internal state of the node to its operator.
+
+use futures::channel::oneshot;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+	Extensions,
+};
+use polkadot_node_subsystem_types::messages::{
+	ApprovalCheckOutcome, ApprovalCheckState, ApprovalVotingParallelMessage, BlockApprovalState,
+	CandidateApprovalState, RequiredAssignments,
+};
+use polkadot_overseer::Handle;
+use polkadot_primitives::{BlockNumber, Hash, Id as ParaId, SessionIndex};
+use serde::{Deserialize, Serialize};
+
+/// The approval state of a relay chain block, see [`BlockApprovalState`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockApprovals {
+	/// The hash of the block.
+	pub block_hash: Hash,
+	/// The number of the block.
+	pub block_number: BlockNumber,
+	/// The session of the block.
+	pub session: SessionIndex,
+	/// The slot of the block.
+	pub slot: u64,
+	/// Whether all candidates of the block are approved.
+	pub approved: bool,
+	/// The candidates included by the block.
+	pub candidates: Vec<CandidateApprovals>,
+}
+
+/// The approval state of a candidate, see [`CandidateApprovalState`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CandidateApprovals {
+	/// The hash of the candidate.
+	pub candidate_hash: Hash,
+	/// The para of the candidate.
+	pub para_id: ParaId,
+	/// The core the candidate occupies.
+	pub core_index: u32,
+	/// The group which backed the candidate.
+	pub backing_group: u32,
+	/// Whether the candidate is approved under the block.
+	pub approved: bool,
+	/// The received assignments, by tranche.
+	pub tranches: Vec<Tranche>,
+	/// The indices of the validators which approved the candidate.
+	pub approvals: Vec<u32>,
+	/// The tranche of the assignment of the node, if any.
+	pub our_assignment: Option<u32>,
+	/// The outcome of the approval check, if the session of the block is known.
+	pub check: Option<Check>,
+}
+
+/// The assignments received in a tranche.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tranche {
+	/// The delay tranche.
+	pub tranche: u32,
+	/// The assigned validator indices, with the tick the assignment was received at.
+	pub assignments: Vec<(u32, u64)>,
+}
+
+/// The outcome of the approval check of a candidate, see [`ApprovalCheckState`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Check {
+	/// The tick the check was run at.
+	pub tick: u64,
+	/// The tranche corresponding to `tick`.
+	pub tranche_now: u32,
+	/// The assignments required for approval.
+	pub required: Required,
+	/// The indices of the validators considered no-shows.
+	pub no_shows: Vec<u32>,
+	/// The outcome of the check.
+	pub outcome: Outcome,
+}
+
+/// The assignments required for approval, see [`RequiredAssignments`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Required {
+	/// All validators are required.
+	All,
+	/// More assignments are needed.
+	#[serde(rename_all = "camelCase")]
+	Pending { considered: u32, next_no_show: Option<u64> },
+	/// The assignments up to the tranche `needed` must approve.
+	#[serde(rename_all = "camelCase")]
+	Exact { needed: u32, tolerated_missing: usize, next_no_show: Option<u64> },
+}
+
+/// The outcome of an approval check, see [`ApprovalCheckOutcome`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Outcome {
+	/// The candidate is not approved.
+	Unapproved,
+	/// The candidate is approved despite the given number of no-shows.
+	Approved(usize),
+	/// The candidate is approved by more than a third of the validators.
+	ApprovedOneThird,
+}
+
+impl From<BlockApprovalState> for BlockApprovals {
+	fn from(state: BlockApprovalState) -> Self {
+		Self {
+			block_hash: state.block_hash,
+			block_number: state.block_number,
+			session: state.session,
+			slot: state.slot,
+			approved: state.approved,
+			candidates: state.candidates.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+impl From<CandidateApprovalState> for CandidateApprovals {
+	fn from(state: CandidateApprovalState) -> Self {
+		Self {
+			candidate_hash: state.candidate_hash.0,
+			para_id: state.para_id,
+			core_index: state.core_index.0,
+			backing_group: state.backing_group.0,
+			approved: state.approved,
+			tranches: state
+				.tranches
+				.into_iter()
+				.map(|(tranche, assignments)| Tranche {
+					tranche,
+					assignments: assignments
+						.into_iter()
+						.map(|(validator, tick)| (validator.0, tick))
+						.collect(),
+				})
+				.collect(),
+			approvals: state.approvals.into_iter().map(|v| v.0).collect(),
+			our_assignment: state.our_assignment,
+			check: state.check.map(Into::into),
+		}
+	}
+}
+
+impl From<ApprovalCheckState> for Check {
+	fn from(state: ApprovalCheckState) -> Self {
+		Self {
+			tick: state.tick,
+			tranche_now: state.tranche_now,
+			required: match state.required {
+				RequiredAssignments::All => Required::All,
+				RequiredAssignments::Pending { considered, next_no_show } =>
+					Required::Pending { considered, next_no_show },
+				RequiredAssignments::Exact { needed, tolerated_missing, next_no_show } =>
+					Required::Exact { needed, tolerated_missing, next_no_show },
+			},
+			no_shows: state.no_shows.into_iter().map(|v| v.0).collect(),
+			outcome: match state.outcome {
+				ApprovalCheckOutcome::Unapproved => Outcome::Unapproved,
+				ApprovalCheckOutcome::Approved(no_shows) => Outcome::Approved(no_shows),
+				ApprovalCheckOutcome::ApprovedOneThird => Outcome::ApprovedOneThird,
+			},
+		}
+	}
+}
+
+#[rpc(client, server)]
+pub trait ApprovalsApi {
+	/// The assignments and approvals of the candidates of the given block, with the outcome of
+	/// the approval check at the given tick, or the current one.
+	#[method(name = "approvals_inspectBlock", with_extensions)]
+	async fn inspect_block(
+		&self,
+		block_hash: Hash,
+		tick: Option<u64>,
+	) -> RpcResult<Option<BlockApprovals>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// Approval-voting did not answer, e.g. because the node is not a validator.
+	Unavailable,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::Unavailable => 1,
+		}
+	}
+}
+
+/// Provides RPC methods to inspect the approval state of relay chain blocks.
+pub struct Approvals {
+	overseer_handle: Handle,
+}
+
+impl Approvals {
+	/// Creates a new instance of the Approvals Rpc helper.
+	pub fn new(overseer_handle: Handle) -> Self {
+		Self { overseer_handle }
+	}
+}
+
+#[async_trait]
+impl ApprovalsApiServer for Approvals {
+	async fn inspect_block(
+		&self,
+		ext: &Extensions,
+		block_hash: Hash,
+		tick: Option<u64>,
+	) -> RpcResult<Option<BlockApprovals>> {
+		sc_rpc_api::check_if_safe(ext)?;
+
+		let (tx, rx) = oneshot::channel();
+		self.overseer_handle
+			.clone()
+			.send_msg(
+				ApprovalVotingParallelMessage::InspectBlock(block_hash, tick, tx),
+				"ApprovalsRpc",
+			)
+			.await;
+		let state = rx.await.map_err(|_| {
+			ErrorObject::owned(
+				Error::Unavailable.into(),
+				"Approval-voting is not available.",
+				None::<()>,
+			)
+		})?;
+
+		Ok(state.map(Into::into))
+	}
+}
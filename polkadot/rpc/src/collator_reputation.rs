@@ -0,0 +1,122 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC methods to audit the collator reputations kept by the collator protocol of a validator.
+//!
+//! All methods are unsafe, they expose the internal state of the node to its operator.
+
+use futures::channel::oneshot;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+	Extensions,
+};
+use polkadot_node_subsystem_types::messages::{
+	CollatorProtocolMessage, CollatorReputation as CollatorReputationState,
+};
+use polkadot_overseer::Handle;
+use polkadot_primitives::Id as ParaId;
+use serde::{Deserialize, Serialize};
+
+/// The reputation of a collator for a para, see [`CollatorReputationState`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CollatorReputation {
+	/// The para the collator collates for.
+	pub para_id: ParaId,
+	/// The peer id of the collator.
+	pub peer_id: String,
+	/// The reputation score. Collators with a lower score are deprioritized.
+	pub score: u16,
+	/// The time of the last reputation bump, in milliseconds since the UNIX epoch.
+	pub last_bumped: u64,
+}
+
+impl From<CollatorReputationState> for CollatorReputation {
+	fn from(state: CollatorReputationState) -> Self {
+		Self {
+			para_id: state.para_id,
+			peer_id: state.peer_id.to_string(),
+			score: state.score,
+			last_bumped: state.last_bumped,
+		}
+	}
+}
+
+#[rpc(client, server)]
+pub trait CollatorReputationApi {
+	/// The reputations of the collators, optionally only those of the given para, ordered by para
+	/// and descending score.
+	#[method(name = "collatorReputation_list", with_extensions)]
+	async fn list(&self, para_id: Option<ParaId>) -> RpcResult<Vec<CollatorReputation>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The collator protocol did not answer, e.g. because the node does not keep the collator
+	/// reputations.
+	Unavailable,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::Unavailable => 1,
+		}
+	}
+}
+
+/// Provides RPC methods to audit the collator reputations.
+pub struct CollatorReputations {
+	overseer_handle: Handle,
+}
+
+impl CollatorReputations {
+	/// Creates a new instance of the CollatorReputations Rpc helper.
+	pub fn new(overseer_handle: Handle) -> Self {
+		Self { overseer_handle }
+	}
+}
+
+#[async_trait]
+impl CollatorReputationApiServer for CollatorReputations {
+	async fn list(
+		&self,
+		ext: &Extensions,
+		para_id: Option<ParaId>,
+	) -> RpcResult<Vec<CollatorReputation>> {
+		sc_rpc_api::check_if_safe(ext)?;
+
+		let (tx, rx) = oneshot::channel();
+		self.overseer_handle
+			.clone()
+			.send_msg(
+				CollatorProtocolMessage::QueryReputations(para_id, tx),
+				"CollatorReputationRpc",
+			)
+			.await;
+		let reputations = rx.await.map_err(|_| {
+			ErrorObject::owned(
+				Error::Unavailable.into(),
+				"Collator reputations are not available.",
+				None::<()>,
+			)
+		})?;
+
+		Ok(reputations.into_iter().map(Into::into).collect())
+	}
+}
@@ -0,0 +1,299 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! RPC methods to inspect the disputes known to the dispute coordinator of a validator.
+//!
+//! All methods are unsafe, they expose the internal state of the node to its operator.
+
+use futures::channel::oneshot;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::ErrorObject,
+	Extensions,
+};
+use polkadot_node_primitives::DisputeStatus;
+use polkadot_node_subsystem_types::messages::{DisputeCoordinatorMessage, DisputeCoordinatorState};
+use polkadot_overseer::Handle;
+use polkadot_primitives::{
+	CandidateHash, Hash, Id as ParaId, InvalidDisputeStatementKind, SessionIndex,
+	ValidDisputeStatementKind,
+};
+use serde::{Deserialize, Serialize};
+
+/// A dispute and its status.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Dispute {
+	/// The session the disputed candidate was included in.
+	pub session: SessionIndex,
+	/// The hash of the disputed candidate.
+	pub candidate_hash: Hash,
+	/// The status of the dispute.
+	pub status: Status,
+}
+
+/// The status of a dispute, see [`DisputeStatus`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Status {
+	/// The dispute is active and unconcluded.
+	Active,
+	/// More than a byzantine threshold of validators participated or the candidate was seen
+	/// included.
+	Confirmed,
+	/// Concluded in favor of the candidate at the given UNIX timestamp, in seconds.
+	ConcludedFor(u64),
+	/// Concluded against the candidate at the given UNIX timestamp, in seconds.
+	ConcludedAgainst(u64),
+}
+
+impl From<DisputeStatus> for Status {
+	fn from(status: DisputeStatus) -> Self {
+		match status {
+			DisputeStatus::Active => Self::Active,
+			DisputeStatus::Confirmed => Self::Confirmed,
+			DisputeStatus::ConcludedFor(since) => Self::ConcludedFor(since),
+			DisputeStatus::ConcludedAgainst(since) => Self::ConcludedAgainst(since),
+		}
+	}
+}
+
+/// The votes cast on a disputed candidate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisputeVotes {
+	/// The para of the candidate.
+	pub para_id: ParaId,
+	/// The relay parent of the candidate.
+	pub relay_parent: Hash,
+	/// The votes for the validity of the candidate, by validator index.
+	pub valid: Vec<(u32, StatementKind)>,
+	/// The votes against the validity of the candidate, by validator index.
+	pub invalid: Vec<(u32, StatementKind)>,
+}
+
+/// The kind of statement a vote was cast with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StatementKind {
+	/// An explicit dispute statement.
+	Explicit,
+	/// A seconded statement of a backer, at the given relay parent.
+	BackingSeconded(Hash),
+	/// A valid statement of a backer, at the given relay parent.
+	BackingValid(Hash),
+	/// An approval vote.
+	ApprovalChecking,
+	/// An approval vote covering the given candidates.
+	ApprovalCheckingMultipleCandidates(Vec<Hash>),
+}
+
+impl From<ValidDisputeStatementKind> for StatementKind {
+	fn from(kind: ValidDisputeStatementKind) -> Self {
+		match kind {
+			ValidDisputeStatementKind::Explicit => Self::Explicit,
+			ValidDisputeStatementKind::BackingSeconded(relay_parent) =>
+				Self::BackingSeconded(relay_parent),
+			ValidDisputeStatementKind::BackingValid(relay_parent) =>
+				Self::BackingValid(relay_parent),
+			ValidDisputeStatementKind::ApprovalChecking => Self::ApprovalChecking,
+			ValidDisputeStatementKind::ApprovalCheckingMultipleCandidates(candidates) =>
+				Self::ApprovalCheckingMultipleCandidates(
+					candidates.into_iter().map(|candidate| candidate.0).collect(),
+				),
+		}
+	}
+}
+
+impl From<InvalidDisputeStatementKind> for StatementKind {
+	fn from(kind: InvalidDisputeStatementKind) -> Self {
+		match kind {
+			InvalidDisputeStatementKind::Explicit => Self::Explicit,
+		}
+	}
+}
+
+/// The in-memory state of the dispute coordinator, see [`DisputeCoordinatorState`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoordinatorState {
+	/// Candidates whose disputes the node is currently participating in.
+	pub running_participations: Vec<Hash>,
+	/// Disputes queued for participation with priority, in the order of participation.
+	pub priority_queue: Vec<(SessionIndex, Hash)>,
+	/// Disputes queued for best-effort participation, in the order of participation.
+	pub best_effort_queue: Vec<(SessionIndex, Hash)>,
+	/// Unconfirmed disputes with the indices of the validators which voted invalid.
+	pub unconfirmed_disputes: Vec<(SessionIndex, Hash, Vec<u32>)>,
+	/// Occupied spam slots as `(session, validator index, count)`.
+	pub spam_slots: Vec<(SessionIndex, u32, u32)>,
+	/// The number of spam slots available to each validator per session.
+	pub max_spam_slots: u32,
+}
+
+impl From<DisputeCoordinatorState> for CoordinatorState {
+	fn from(state: DisputeCoordinatorState) -> Self {
+		let queue = |queue: Vec<(SessionIndex, CandidateHash)>| {
+			queue
+				.into_iter()
+				.map(|(session, candidate_hash)| (session, candidate_hash.0))
+				.collect()
+		};
+		Self {
+			running_participations: state.running_participations.into_iter().map(|c| c.0).collect(),
+			priority_queue: queue(state.priority_queue),
+			best_effort_queue: queue(state.best_effort_queue),
+			unconfirmed_disputes: state
+				.unconfirmed_disputes
+				.into_iter()
+				.map(|(session, candidate_hash, validators)| {
+					(session, candidate_hash.0, validators.into_iter().map(|v| v.0).collect())
+				})
+				.collect(),
+			spam_slots: state
+				.spam_slots
+				.into_iter()
+				.map(|(session, validator, count)| (session, validator.0, count))
+				.collect(),
+			max_spam_slots: state.max_spam_slots,
+		}
+	}
+}
+
+#[rpc(client, server)]
+pub trait DisputesApi {
+	/// The disputes of the recent sessions, only the active ones if `active_only` is set.
+	#[method(name = "disputes_list", with_extensions)]
+	async fn list(&self, active_only: Option<bool>) -> RpcResult<Vec<Dispute>>;
+
+	/// The votes cast on the given candidate.
+	#[method(name = "disputes_votes", with_extensions)]
+	async fn votes(
+		&self,
+		session: SessionIndex,
+		candidate_hash: Hash,
+	) -> RpcResult<Option<DisputeVotes>>;
+
+	/// The participation queues and the occupied spam slots of the dispute coordinator.
+	#[method(name = "disputes_coordinatorState", with_extensions)]
+	async fn coordinator_state(&self) -> RpcResult<CoordinatorState>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The dispute coordinator did not answer, e.g. because it is not initialized yet or the node
+	/// is not a validator.
+	Unavailable,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::Unavailable => 1,
+		}
+	}
+}
+
+/// Provides RPC methods to inspect the disputes known to the dispute coordinator.
+pub struct Disputes {
+	overseer_handle: Handle,
+}
+
+impl Disputes {
+	/// Creates a new instance of the Disputes Rpc helper.
+	pub fn new(overseer_handle: Handle) -> Self {
+		Self { overseer_handle }
+	}
+
+	/// Send a request to the dispute coordinator and wait for the response.
+	async fn request<T>(
+		&self,
+		make_msg: impl FnOnce(oneshot::Sender<T>) -> DisputeCoordinatorMessage,
+	) -> RpcResult<T> {
+		let (tx, rx) = oneshot::channel();
+		self.overseer_handle.clone().send_msg(make_msg(tx), "DisputesRpc").await;
+		rx.await.map_err(|_| {
+			ErrorObject::owned(
+				Error::Unavailable.into(),
+				"The dispute coordinator is not available.",
+				None::<()>,
+			)
+		})
+	}
+}
+
+#[async_trait]
+impl DisputesApiServer for Disputes {
+	async fn list(&self, ext: &Extensions, active_only: Option<bool>) -> RpcResult<Vec<Dispute>> {
+		sc_rpc_api::check_if_safe(ext)?;
+
+		let disputes = if active_only.unwrap_or(false) {
+			self.request(DisputeCoordinatorMessage::ActiveDisputes).await?
+		} else {
+			self.request(DisputeCoordinatorMessage::RecentDisputes).await?
+		};
+		Ok(disputes
+			.into_iter()
+			.map(|(session, candidate_hash, status)| Dispute {
+				session,
+				candidate_hash: candidate_hash.0,
+				status: status.into(),
+			})
+			.collect())
+	}
+
+	async fn votes(
+		&self,
+		ext: &Extensions,
+		session: SessionIndex,
+		candidate_hash: Hash,
+	) -> RpcResult<Option<DisputeVotes>> {
+		sc_rpc_api::check_if_safe(ext)?;
+
+		let votes = self
+			.request(|tx| {
+				DisputeCoordinatorMessage::QueryCandidateVotes(
+					vec![(session, CandidateHash(candidate_hash))],
+					tx,
+				)
+			})
+			.await?;
+		Ok(votes.into_iter().next().map(|(_, _, votes)| {
+			let descriptor = &votes.candidate_receipt.descriptor;
+			DisputeVotes {
+				para_id: descriptor.para_id(),
+				relay_parent: descriptor.relay_parent(),
+				valid: votes
+					.valid
+					.into_iter()
+					.map(|(validator, (kind, _))| (validator.0, kind.into()))
+					.collect(),
+				invalid: votes
+					.invalid
+					.into_iter()
+					.map(|(validator, (kind, _))| (validator.0, kind.into()))
+					.collect(),
+			}
+		}))
+	}
+
+	async fn coordinator_state(&self, ext: &Extensions) -> RpcResult<CoordinatorState> {
+		sc_rpc_api::check_if_safe(ext)?;
+
+		Ok(self.request(DisputeCoordinatorMessage::QueryState).await?.into())
+	}
+}
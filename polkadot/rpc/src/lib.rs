@@ -20,7 +20,12 @@
 
 use std::sync::Arc;
 
+pub mod approvals;
+pub mod collator_reputation;
+pub mod disputes;
+
 use jsonrpsee::RpcModule;
+use polkadot_overseer::Handle as OverseerHandle;
 use polkadot_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Nonce};
 use sc_client_api::AuxStore;
 use sc_consensus_beefy::communication::notification::{
@@ -91,17 +96,23 @@ pub struct FullDeps<C, P, SC, B, AuthorityId: AuthorityIdBound> {
 	pub beefy: BeefyDeps<AuthorityId>,
 	/// Backend used by the node.
 	pub backend: Arc<B>,
+	/// Handle to the overseer, `None` if the node runs no parachain subsystems.
+	pub overseer_handle: Option<OverseerHandle>,
 }
 
 /// Instantiate all RPC extensions.
 pub fn create_full<C, P, SC, B, AuthorityId>(
-	FullDeps { client, pool, select_chain, chain_spec, babe, grandpa, beefy, backend }: FullDeps<
-		C,
-		P,
-		SC,
-		B,
-		AuthorityId,
-	>,
+	FullDeps {
+		client,
+		pool,
+		select_chain,
+		chain_spec,
+		babe,
+		grandpa,
+		beefy,
+		backend,
+		overseer_handle,
+	}: FullDeps<C, P, SC, B, AuthorityId>,
 ) -> Result<RpcExtension, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>
@@ -123,6 +134,9 @@ where
 	AuthorityId: AuthorityIdBound,
 	<AuthorityId as RuntimeAppPublic>::Signature: Send + Sync,
 {
+	use approvals::{Approvals, ApprovalsApiServer};
+	use collator_reputation::{CollatorReputationApiServer, CollatorReputations};
+	use disputes::{Disputes, DisputesApiServer};
 	use mmr_rpc::{Mmr, MmrApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
@@ -180,5 +194,11 @@ where
 		.into_rpc(),
 	)?;
 
+	if let Some(overseer_handle) = overseer_handle {
+		io.merge(Approvals::new(overseer_handle.clone()).into_rpc())?;
+		io.merge(CollatorReputations::new(overseer_handle.clone()).into_rpc())?;
+		io.merge(Disputes::new(overseer_handle).into_rpc())?;
+	}
+
 	Ok(io)
 }
@@ -68,7 +68,9 @@ impl pallet_vesting::Config for Test {
 	type WeightInfo = ();
 	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
 	type BlockNumberProvider = System;
+	type TransferPolicyOrigin = frame_system::EnsureRoot<u64>;
 	const MAX_VESTING_SCHEDULES: u32 = 28;
+	const MAX_VESTING_PAGES: u32 = 4;
 }
 
 parameter_types! {
@@ -536,7 +536,9 @@ impl pallet_vesting::Config for Runtime {
 	type WeightInfo = ();
 	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
 	type BlockNumberProvider = System;
+	type TransferPolicyOrigin = EnsureRoot<AccountId>;
 	const MAX_VESTING_SCHEDULES: u32 = 28;
+	const MAX_VESTING_PAGES: u32 = 4;
 }
 
 impl pallet_sudo::Config for Runtime {
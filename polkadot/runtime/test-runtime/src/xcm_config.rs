@@ -188,4 +188,7 @@ impl pallet_xcm::Config for crate::Runtime {
 	type AdminOrigin = EnsureRoot<crate::AccountId>;
 	// Aliasing is disabled: xcm_executor::Config::Aliasers is set to `Nothing`.
 	type AuthorizedAliasConsideration = Disabled;
+	type AssetTrapConsideration = Disabled;
+	type AssetTrapExpiry = ();
+	type RecurringTransferConsideration = Disabled;
 }
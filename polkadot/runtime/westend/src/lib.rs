@@ -121,7 +121,9 @@ use xcm::{
 };
 use xcm_builder::PayOverXcm;
 use xcm_runtime_apis::{
-	dry_run::{CallDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects},
+	dry_run::{
+		CallDryRunEffects, Error as XcmDryRunApiError, TracedDryRunEffects, XcmDryRunEffects,
+	},
 	fees::Error as XcmPaymentApiError,
 };
 
@@ -1204,7 +1206,9 @@ impl pallet_vesting::Config for Runtime {
 	type WeightInfo = weights::pallet_vesting::WeightInfo<Runtime>;
 	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
 	type BlockNumberProvider = System;
+	type TransferPolicyOrigin = EnsureRoot<AccountId>;
 	const MAX_VESTING_SCHEDULES: u32 = 28;
+	const MAX_VESTING_PAGES: u32 = 4;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -1471,8 +1475,11 @@ impl pallet_message_queue::Config for Runtime {
 	type MaxStale = MessageQueueMaxStale;
 	type ServiceWeight = MessageQueueServiceWeight;
 	type IdleMaxServiceWeight = MessageQueueServiceWeight;
-	#[cfg(not(feature = "runtime-benchmarks"))]
+	#[cfg(not(any(feature = "runtime-benchmarks", feature = "try-runtime")))]
 	type MessageProcessor = MessageProcessor;
+	#[cfg(all(feature = "try-runtime", not(feature = "runtime-benchmarks")))]
+	type MessageProcessor =
+		xcm_builder::CheckConservation<MessageProcessor, xcm_config::TokenCirculatingSupply>;
 	#[cfg(feature = "runtime-benchmarks")]
 	type MessageProcessor =
 		pallet_message_queue::mock_helpers::NoopMessageProcessor<AggregateMessageOrigin>;
@@ -2718,6 +2725,7 @@ sp_api::impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(3)]
 	impl xcm_runtime_apis::dry_run::DryRunApi<Block, RuntimeCall, RuntimeEvent, OriginCaller> for Runtime {
 		fn dry_run_call(origin: OriginCaller, call: RuntimeCall, result_xcms_version: XcmVersion) -> Result<CallDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
 			XcmPallet::dry_run_call::<Runtime, xcm_config::XcmRouter, OriginCaller, RuntimeCall>(origin, call, result_xcms_version)
@@ -2726,6 +2734,34 @@ sp_api::impl_runtime_apis! {
 		fn dry_run_xcm(origin_location: VersionedLocation, xcm: VersionedXcm<RuntimeCall>) -> Result<XcmDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
 			XcmPallet::dry_run_xcm::<Runtime, xcm_config::XcmRouter, RuntimeCall, xcm_config::XcmConfig>(origin_location, xcm)
 		}
+
+		fn dry_run_call_with_trace(origin: OriginCaller, call: RuntimeCall, result_xcms_version: XcmVersion) -> Result<TracedDryRunEffects<CallDryRunEffects<RuntimeEvent>>, XcmDryRunApiError> {
+			XcmPallet::dry_run_call_with_trace::<Runtime, xcm_config::XcmRouter, OriginCaller, RuntimeCall>(origin, call, result_xcms_version)
+		}
+
+		fn dry_run_xcm_with_trace(origin_location: VersionedLocation, xcm: VersionedXcm<RuntimeCall>) -> Result<TracedDryRunEffects<XcmDryRunEffects<RuntimeEvent>>, XcmDryRunApiError> {
+			XcmPallet::dry_run_xcm_with_trace::<Runtime, xcm_config::XcmRouter, RuntimeCall, xcm_config::XcmConfig>(origin_location, xcm)
+		}
+	}
+
+	impl xcm_runtime_apis::transfer_fees::TransferFeesApi<Block, RuntimeCall, OriginCaller> for Runtime {
+		fn estimate_transfer_fees(
+			origin: OriginCaller,
+			call: RuntimeCall,
+			fee_asset: VersionedAssetId,
+			destination_fee_infos: Vec<(VersionedLocation, xcm_runtime_apis::transfer_fees::DestinationFeeInfo)>,
+			result_xcms_version: XcmVersion,
+		) -> Result<xcm_runtime_apis::transfer_fees::TransferFeeEstimate, xcm_runtime_apis::transfer_fees::Error> {
+			type Trader = <xcm_config::XcmConfig as xcm_executor::Config>::Trader;
+
+			XcmPallet::estimate_transfer_fees::<xcm_config::XcmRouter, OriginCaller, RuntimeCall, Trader>(
+				origin,
+				call,
+				fee_asset,
+				destination_fee_infos,
+				result_xcms_version,
+			)
+		}
 	}
 
 	impl xcm_runtime_apis::conversions::LocationToAccountApi<Block, AccountId> for Runtime {
@@ -3096,4 +3132,17 @@ sp_api::impl_runtime_apis! {
 			XcmPallet::is_trusted_teleporter(asset, location)
 		}
 	}
+
+	impl xcm_runtime_apis::trapped_assets::TrappedAssetsApi<Block, BlockNumber> for Runtime {
+		fn trapped_assets(
+			origin: VersionedLocation,
+			start_after: Option<H256>,
+			max: u32,
+		) -> Result<
+			Vec<xcm_runtime_apis::trapped_assets::TrappedAssets<BlockNumber>>,
+			xcm_runtime_apis::trapped_assets::Error
+		> {
+			XcmPallet::trapped_assets(origin, start_after, max)
+		}
+	}
 }
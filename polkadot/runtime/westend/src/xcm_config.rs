@@ -24,7 +24,7 @@ use super::{
 use crate::governance::pallet_custom_origins::Treasurer;
 use frame_support::{
 	parameter_types,
-	traits::{Contains, Disabled, Equals, Everything, Nothing},
+	traits::{fungible::Inspect, Contains, Disabled, Equals, Everything, Nothing},
 };
 use frame_system::EnsureRoot;
 use pallet_xcm::XcmPassthrough;
@@ -47,7 +47,7 @@ use xcm_builder::{
 	TrailingSetTopicAsId, UsingComponents, WeightInfoBounds, WithComputedOrigin, WithUniqueTopic,
 	XcmFeeManagerFromComponents,
 };
-use xcm_executor::XcmExecutor;
+use xcm_executor::{conservation::CirculatingSupply, XcmExecutor};
 
 parameter_types! {
 	pub const TokenLocation: Location = Here.into_location();
@@ -64,6 +64,17 @@ parameter_types! {
 	pub const BaseDeliveryFee: u128 = CENTS.saturating_mul(3);
 	// Fellows pluralistic body.
 	pub const FellowsBodyId: BodyId = BodyId::Technical;
+	/// The assets whose conservation is checked by `try-runtime` builds.
+	pub const TokenCirculatingSupply: CirculatingSupply = circulating_supply;
+}
+
+/// The supply of the native token outside of the teleport checking account.
+pub fn circulating_supply(id: &AssetId) -> Option<u128> {
+	if id.0 != TokenLocation::get() {
+		return None
+	}
+	let checked = Balances::free_balance(&CheckAccount::get());
+	Some(Balances::total_issuance().saturating_sub(checked))
 }
 
 pub type LocationConverter = (
@@ -323,4 +334,7 @@ impl pallet_xcm::Config for Runtime {
 	type AdminOrigin = EnsureRoot<AccountId>;
 	// Aliasing is disabled: xcm_executor::Config::Aliasers only allows `AliasChildLocation`.
 	type AuthorizedAliasConsideration = Disabled;
+	type AssetTrapConsideration = Disabled;
+	type AssetTrapExpiry = ();
+	type RecurringTransferConsideration = Disabled;
 }
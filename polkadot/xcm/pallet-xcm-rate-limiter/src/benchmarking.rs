@@ -0,0 +1,88 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Benchmarks for the XCM rate limiter pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::v2::*;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn set_rate_limit() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let location =
+			Location::new(1, [Parachain(1000), AccountId32 { network: None, id: [1; 32] }]);
+		let limit = RateLimit { max_messages: 10, max_weight: Weight::from_parts(1_000, 1_000) };
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Box::new(location.clone().into()), Some(limit));
+
+		assert_eq!(RateLimits::<T>::get(&location), Some(limit));
+		Ok(())
+	}
+
+	// The worst case is a throttled message from an origin with as many junctions as possible,
+	// limited by its root which already used up its budget.
+	#[benchmark]
+	fn try_consume() {
+		let origin = Location::new(
+			1,
+			[
+				Parachain(1000),
+				PalletInstance(50),
+				GeneralIndex(1),
+				GeneralIndex(2),
+				GeneralIndex(3),
+				GeneralIndex(4),
+				GeneralIndex(5),
+				AccountId32 { network: None, id: [1; 32] },
+			],
+		);
+		let limited_by = Location::parent();
+		RateLimits::<T>::insert(
+			&limited_by,
+			RateLimit { max_messages: 1, max_weight: Weight::from_parts(1_000, 1_000) },
+		);
+		let mut usage = Pallet::<T>::usage(&limited_by);
+		usage.messages = 1;
+		WindowUsage::<T>::insert(&limited_by, usage);
+		let mut message = Xcm::<()>(vec![ClearOrigin]);
+		let mut properties =
+			Properties { weight_credit: Weight::zero(), message_id: Some([1; 32]) };
+		let result;
+
+		#[block]
+		{
+			result = <Pallet<T> as CheckRateLimit>::try_consume(
+				&origin,
+				message.inner_mut(),
+				Weight::from_parts(10, 10),
+				&mut properties,
+			);
+		}
+
+		assert_eq!(result, Err(ProcessMessageError::Yield));
+		assert!(LastThrottled::<T>::contains_key(&limited_by));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
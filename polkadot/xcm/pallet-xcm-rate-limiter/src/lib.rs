@@ -0,0 +1,320 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # XCM Rate Limiter Pallet
+//!
+//! Per-origin budgets for incoming XCMs, enforced by `xcm_builder::RateLimitedBarrier`.
+//!
+//! ## Overview
+//!
+//! Governance configures a [`RateLimit`] (a maximum number of messages and a maximum amount of
+//! weight) for a location through [`Pallet::set_rate_limit`]. A limit applies to the location
+//! itself and to every location below it which doesn't have a more specific limit, so a limit
+//! for `../Parachain(1000)` throttles all origins on that parachain together. Budgets are
+//! replenished every [`Config::WindowLength`] blocks.
+//!
+//! The pallet implements [`CheckRateLimit`] and is meant to be plugged into the barrier of the
+//! XCM executor:
+//!
+//! ```ignore
+//! pub type Barrier = TrailingSetTopicAsId<
+//! 	RateLimitedBarrier<
+//! 		(TakeWeightCredit, AllowTopLevelPaidExecutionFrom<Everything>),
+//! 		XcmRateLimiter,
+//! 	>,
+//! >;
+//! ```
+//!
+//! Messages over the remaining budget are rejected with `ProcessMessageError::Yield` and reported
+//! with [`Event::MessageThrottled`], once per message. Messages which don't fit into the budget of
+//! a whole window would never be executed, so they are rejected for good instead: with
+//! `ProcessMessageError::Unsupported` if the limit allows no messages at all, or with
+//! `ProcessMessageError::Overweight` if their weight exceeds the limit. Origins without a
+//! configured limit are never throttled.
+//!
+//! The storage accesses done by the barrier are charged by wrapping the `Weigher` of the XCM
+//! executor into [`RateLimitedWeigher`]:
+//!
+//! ```ignore
+//! pub type Weigher = RateLimitedWeigher<
+//! 	Runtime,
+//! 	WeightInfoBounds<XcmWeight<RuntimeCall>, RuntimeCall, MaxInstructions>,
+//! >;
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+use alloc::boxed::Box;
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use frame_support::{pallet_prelude::*, traits::ProcessMessageError, weights::Weight};
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_runtime::{traits::Zero, RuntimeDebug, Saturating};
+use xcm::{latest::prelude::*, VersionedLocation};
+use xcm_executor::traits::{CheckRateLimit, Properties, WeightBounds};
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+const LOG_TARGET: &str = "xcm::rate-limiter";
+
+/// The budget of a location within a window.
+#[derive(
+	Clone,
+	Copy,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct RateLimit {
+	/// Maximum number of messages executed.
+	pub max_messages: u32,
+	/// Maximum total weight of the messages executed.
+	pub max_weight: Weight,
+}
+
+/// The budget a location consumed in the current window.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Usage<BlockNumber> {
+	/// The first block of the window the usage was accounted in.
+	pub window_start: BlockNumber,
+	/// Number of messages executed.
+	pub messages: u32,
+	/// Total weight of the messages executed.
+	pub weight: Weight,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		#[allow(deprecated)]
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Origin allowed to configure rate limits.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The number of blocks after which the budgets are replenished.
+		#[pallet::constant]
+		type WindowLength: Get<BlockNumberFor<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The configured limits, by location.
+	#[pallet::storage]
+	pub type RateLimits<T: Config> = StorageMap<_, Blake2_128Concat, Location, RateLimit>;
+
+	/// The budget consumed in the latest window, by location with a configured limit.
+	#[pallet::storage]
+	pub type WindowUsage<T: Config> =
+		StorageMap<_, Blake2_128Concat, Location, Usage<BlockNumberFor<T>>>;
+
+	/// The block and the identity of the latest message throttled, by location with a configured
+	/// limit.
+	///
+	/// Used to report a throttled message only once while it is retried.
+	#[pallet::storage]
+	pub type LastThrottled<T: Config> =
+		StorageMap<_, Blake2_128Concat, Location, (BlockNumberFor<T>, Option<XcmHash>)>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The rate limit of a location was set, or removed if `limit` is `None`.
+		RateLimitSet { location: Location, limit: Option<RateLimit> },
+		/// A message from `origin` was rejected because `limited_by` exceeded its budget.
+		///
+		/// Deposited once per message, or once per block for messages without an identity.
+		MessageThrottled { origin: Location, limited_by: Location, weight: Weight },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The location could not be converted to the latest XCM version.
+		BadVersion,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the rate limit of `location` and all locations below it which don't have their
+		/// own limit. Passing `None` removes the limit.
+		///
+		/// The budget consumed by `location` in the current window is reset.
+		///
+		/// The dispatch origin must be `AdminOrigin`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_rate_limit())]
+		pub fn set_rate_limit(
+			origin: OriginFor<T>,
+			location: Box<VersionedLocation>,
+			limit: Option<RateLimit>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let location: Location = (*location).try_into().map_err(|()| {
+				tracing::debug!(
+					target: LOG_TARGET,
+					"Failed to convert VersionedLocation for rate limit",
+				);
+				Error::<T>::BadVersion
+			})?;
+
+			RateLimits::<T>::set(&location, limit);
+			WindowUsage::<T>::remove(&location);
+			LastThrottled::<T>::remove(&location);
+
+			Self::deposit_event(Event::RateLimitSet { location, limit });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The most specific limit applying to `origin`, together with the location it is
+	/// configured for.
+	pub fn limit_for(origin: &Location) -> Option<(Location, RateLimit)> {
+		let mut location = origin.clone();
+		loop {
+			if let Some(limit) = RateLimits::<T>::get(&location) {
+				return Some((location, limit))
+			}
+			location.take_last()?;
+		}
+	}
+
+	/// The budget consumed by `location` in the current window.
+	pub fn usage(location: &Location) -> Usage<BlockNumberFor<T>> {
+		let window_start = Self::current_window_start();
+		WindowUsage::<T>::get(location)
+			.filter(|usage| usage.window_start == window_start)
+			.unwrap_or(Usage { window_start, messages: 0, weight: Weight::zero() })
+	}
+
+	/// Report a message throttled by `limited_by`, unless it was already reported.
+	fn throttled(
+		origin: &Location,
+		limited_by: Location,
+		weight: Weight,
+		message_id: Option<XcmHash>,
+	) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let reported = LastThrottled::<T>::get(&limited_by)
+			.is_some_and(|(block, id)| id == message_id && (message_id.is_some() || block == now));
+		if reported {
+			return
+		}
+
+		LastThrottled::<T>::insert(&limited_by, (now, message_id));
+		Self::deposit_event(Event::MessageThrottled { origin: origin.clone(), limited_by, weight });
+	}
+
+	fn current_window_start() -> BlockNumberFor<T> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let length = T::WindowLength::get();
+		if length.is_zero() {
+			now
+		} else {
+			now.saturating_sub(now % length)
+		}
+	}
+}
+
+impl<T: Config> CheckRateLimit for Pallet<T> {
+	fn try_consume<Call>(
+		origin: &Location,
+		_instructions: &mut [Instruction<Call>],
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		let Some((limited_by, limit)) = Self::limit_for(origin) else { return Ok(()) };
+
+		if limit.max_messages.is_zero() || max_weight.any_gt(limit.max_weight) {
+			tracing::debug!(
+				target: LOG_TARGET,
+				?origin,
+				?limited_by,
+				?max_weight,
+				?limit,
+				"Message exceeds the rate limit of a whole window",
+			);
+			return Err(if limit.max_messages.is_zero() {
+				ProcessMessageError::Unsupported
+			} else {
+				ProcessMessageError::Overweight(max_weight)
+			})
+		}
+
+		let mut usage = Self::usage(&limited_by);
+		let messages = usage.messages.saturating_add(1);
+		let weight = usage.weight.saturating_add(max_weight);
+		if messages > limit.max_messages || weight.any_gt(limit.max_weight) {
+			tracing::debug!(
+				target: LOG_TARGET,
+				?origin,
+				?limited_by,
+				?max_weight,
+				?usage,
+				"Rate limit exceeded",
+			);
+			Self::throttled(origin, limited_by, max_weight, properties.message_id);
+			return Err(ProcessMessageError::Yield)
+		}
+
+		usage.messages = messages;
+		usage.weight = weight;
+		WindowUsage::<T>::insert(&limited_by, usage);
+		Ok(())
+	}
+}
+
+/// Weigher adding the weight of the accounting done by [`Pallet`] in the barrier to the weight
+/// of every message weighed by `Inner`.
+pub struct RateLimitedWeigher<T, Inner>(PhantomData<(T, Inner)>);
+impl<T: Config, Inner: WeightBounds<Call>, Call> WeightBounds<Call>
+	for RateLimitedWeigher<T, Inner>
+{
+	fn weight(message: &mut Xcm<Call>, weight_limit: Weight) -> Result<Weight, InstructionError> {
+		let accounting = T::WeightInfo::try_consume();
+		let weight = Inner::weight(message, weight_limit.saturating_sub(accounting))?;
+		Ok(weight.saturating_add(accounting))
+	}
+
+	fn instr_weight(instruction: &mut Instruction<Call>) -> Result<Weight, XcmError> {
+		Inner::instr_weight(instruction)
+	}
+}
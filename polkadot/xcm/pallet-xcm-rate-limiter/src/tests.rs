@@ -0,0 +1,318 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{mock::*, *};
+use frame_support::{assert_noop, assert_ok, traits::ProcessMessageError};
+use sp_runtime::DispatchError;
+use xcm_builder::{AllowUnpaidExecutionFrom, FixedWeightBounds, RateLimitedBarrier};
+use xcm_executor::traits::{ShouldExecute, WeightBounds};
+
+type Barrier =
+	RateLimitedBarrier<AllowUnpaidExecutionFrom<frame_support::traits::Everything>, XcmRateLimiter>;
+
+fn sibling(para_id: u32) -> Location {
+	Location::new(1, [Parachain(para_id)])
+}
+
+fn sibling_account(para_id: u32, id: u8) -> Location {
+	Location::new(1, [Parachain(para_id), AccountId32 { network: None, id: [id; 32] }])
+}
+
+fn set_limit(location: Location, max_messages: u32, max_weight: u64) {
+	assert_ok!(XcmRateLimiter::set_rate_limit(
+		RuntimeOrigin::root(),
+		Box::new(location.into()),
+		Some(RateLimit { max_messages, max_weight: Weight::from_parts(max_weight, max_weight) }),
+	));
+}
+
+fn execute(origin: &Location, weight: u64) -> Result<(), ProcessMessageError> {
+	execute_with_id(origin, weight, None)
+}
+
+fn execute_with_id(
+	origin: &Location,
+	weight: u64,
+	message_id: Option<XcmHash>,
+) -> Result<(), ProcessMessageError> {
+	let mut message = Xcm::<()>(vec![ClearOrigin]);
+	Barrier::should_execute(
+		origin,
+		message.inner_mut(),
+		Weight::from_parts(weight, weight),
+		&mut Properties { weight_credit: Weight::zero(), message_id },
+	)
+}
+
+fn throttled_events() -> usize {
+	System::events()
+		.into_iter()
+		.filter(|record| {
+			matches!(record.event, RuntimeEvent::XcmRateLimiter(Event::MessageThrottled { .. }))
+		})
+		.count()
+}
+
+#[test]
+fn set_rate_limit_requires_admin_origin() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			XcmRateLimiter::set_rate_limit(
+				RuntimeOrigin::signed(1),
+				Box::new(sibling(1000).into()),
+				None,
+			),
+			DispatchError::BadOrigin,
+		);
+	});
+}
+
+#[test]
+fn set_rate_limit_works() {
+	new_test_ext().execute_with(|| {
+		let limit = RateLimit { max_messages: 2, max_weight: Weight::from_parts(100, 100) };
+		assert_ok!(XcmRateLimiter::set_rate_limit(
+			RuntimeOrigin::root(),
+			Box::new(sibling(1000).into()),
+			Some(limit),
+		));
+		assert_eq!(RateLimits::<Test>::get(sibling(1000)), Some(limit));
+		System::assert_last_event(
+			Event::RateLimitSet { location: sibling(1000), limit: Some(limit) }.into(),
+		);
+
+		assert_ok!(XcmRateLimiter::set_rate_limit(
+			RuntimeOrigin::root(),
+			Box::new(sibling(1000).into()),
+			None,
+		));
+		assert_eq!(RateLimits::<Test>::get(sibling(1000)), None);
+		System::assert_last_event(
+			Event::RateLimitSet { location: sibling(1000), limit: None }.into(),
+		);
+	});
+}
+
+#[test]
+fn unlimited_origins_are_not_throttled() {
+	new_test_ext().execute_with(|| {
+		set_limit(sibling(1000), 1, 100);
+
+		for _ in 0..10 {
+			assert_ok!(execute(&sibling(2000), 100));
+		}
+		assert_eq!(WindowUsage::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn message_count_is_limited() {
+	new_test_ext().execute_with(|| {
+		set_limit(sibling(1000), 2, 1_000);
+
+		assert_ok!(execute(&sibling(1000), 10));
+		assert_ok!(execute(&sibling(1000), 10));
+		assert_eq!(execute(&sibling(1000), 10), Err(ProcessMessageError::Yield));
+		System::assert_last_event(
+			Event::MessageThrottled {
+				origin: sibling(1000),
+				limited_by: sibling(1000),
+				weight: Weight::from_parts(10, 10),
+			}
+			.into(),
+		);
+
+		let usage = XcmRateLimiter::usage(&sibling(1000));
+		assert_eq!(usage.messages, 2);
+		assert_eq!(usage.weight, Weight::from_parts(20, 20));
+	});
+}
+
+#[test]
+fn weight_is_limited() {
+	new_test_ext().execute_with(|| {
+		set_limit(sibling(1000), 10, 100);
+
+		assert_ok!(execute(&sibling(1000), 60));
+		assert_eq!(execute(&sibling(1000), 60), Err(ProcessMessageError::Yield));
+		// A smaller message still fits into the remaining budget.
+		assert_ok!(execute(&sibling(1000), 40));
+		assert_eq!(execute(&sibling(1000), 1), Err(ProcessMessageError::Yield));
+	});
+}
+
+#[test]
+fn messages_over_the_whole_budget_are_rejected_for_good() {
+	new_test_ext().execute_with(|| {
+		set_limit(sibling(1000), 10, 100);
+		set_limit(sibling(2000), 0, 100);
+
+		// Would never fit, even into an empty window.
+		assert_eq!(
+			execute(&sibling(1000), 101),
+			Err(ProcessMessageError::Overweight(Weight::from_parts(101, 101)))
+		);
+		assert_eq!(execute(&sibling(2000), 1), Err(ProcessMessageError::Unsupported));
+		assert_eq!(throttled_events(), 0);
+		assert_eq!(WindowUsage::<Test>::iter().count(), 0);
+
+		// Messages which fit into a window are still accepted, then yield once over budget.
+		assert_ok!(execute(&sibling(1000), 100));
+		assert_eq!(execute(&sibling(1000), 1), Err(ProcessMessageError::Yield));
+	});
+}
+
+#[test]
+fn budget_is_replenished_every_window() {
+	new_test_ext().execute_with(|| {
+		set_limit(sibling(1000), 1, 100);
+
+		assert_ok!(execute(&sibling(1000), 10));
+		assert_eq!(execute(&sibling(1000), 10), Err(ProcessMessageError::Yield));
+
+		// Still within the window starting at block 0.
+		System::set_block_number(WINDOW_LENGTH - 1);
+		assert_eq!(execute(&sibling(1000), 10), Err(ProcessMessageError::Yield));
+
+		System::set_block_number(WINDOW_LENGTH);
+		assert_ok!(execute(&sibling(1000), 10));
+		assert_eq!(execute(&sibling(1000), 10), Err(ProcessMessageError::Yield));
+	});
+}
+
+#[test]
+fn prefix_limit_is_shared_and_most_specific_limit_wins() {
+	new_test_ext().execute_with(|| {
+		set_limit(sibling(1000), 2, 1_000);
+		set_limit(sibling_account(1000, 9), 5, 1_000);
+
+		// Accounts without their own limit share the budget of the parachain.
+		assert_ok!(execute(&sibling_account(1000, 1), 10));
+		assert_ok!(execute(&sibling_account(1000, 2), 10));
+		assert_eq!(execute(&sibling(1000), 10), Err(ProcessMessageError::Yield));
+		System::assert_last_event(
+			Event::MessageThrottled {
+				origin: sibling(1000),
+				limited_by: sibling(1000),
+				weight: Weight::from_parts(10, 10),
+			}
+			.into(),
+		);
+
+		// An account with its own limit has its own budget.
+		for _ in 0..5 {
+			assert_ok!(execute(&sibling_account(1000, 9), 10));
+		}
+		assert_eq!(execute(&sibling_account(1000, 9), 10), Err(ProcessMessageError::Yield));
+	});
+}
+
+#[test]
+fn resetting_limit_resets_usage() {
+	new_test_ext().execute_with(|| {
+		set_limit(sibling(1000), 1, 100);
+		assert_ok!(execute(&sibling(1000), 10));
+		assert_eq!(execute(&sibling(1000), 10), Err(ProcessMessageError::Yield));
+
+		set_limit(sibling(1000), 1, 100);
+		assert_ok!(execute(&sibling(1000), 10));
+	});
+}
+
+#[test]
+fn messages_rejected_by_inner_barrier_are_not_accounted() {
+	new_test_ext().execute_with(|| {
+		set_limit(sibling(1000), 1, 100);
+
+		type Strict = RateLimitedBarrier<
+			AllowUnpaidExecutionFrom<frame_support::traits::Nothing>,
+			XcmRateLimiter,
+		>;
+		let mut message = Xcm::<()>(vec![ClearOrigin]);
+		assert_eq!(
+			Strict::should_execute(
+				&sibling(1000),
+				message.inner_mut(),
+				Weight::from_parts(10, 10),
+				&mut Properties { weight_credit: Weight::zero(), message_id: None },
+			),
+			Err(ProcessMessageError::Unsupported),
+		);
+
+		assert_ok!(execute(&sibling(1000), 10));
+	});
+}
+
+#[test]
+fn throttled_message_is_reported_once() {
+	new_test_ext().execute_with(|| {
+		set_limit(sibling(1000), 1, 100);
+		assert_ok!(execute(&sibling(1000), 10));
+
+		// A message retried in the same and in later blocks is reported once.
+		for _ in 0..3 {
+			assert_eq!(
+				execute_with_id(&sibling(1000), 10, Some([1; 32])),
+				Err(ProcessMessageError::Yield)
+			);
+		}
+		System::set_block_number(2);
+		assert_eq!(
+			execute_with_id(&sibling(1000), 10, Some([1; 32])),
+			Err(ProcessMessageError::Yield)
+		);
+		assert_eq!(throttled_events(), 1);
+
+		// Another message is reported.
+		assert_eq!(
+			execute_with_id(&sibling_account(1000, 1), 10, Some([2; 32])),
+			Err(ProcessMessageError::Yield)
+		);
+		assert_eq!(throttled_events(), 2);
+
+		// Messages without an identity are reported once per block.
+		assert_eq!(execute(&sibling(1000), 10), Err(ProcessMessageError::Yield));
+		assert_eq!(execute(&sibling(1000), 10), Err(ProcessMessageError::Yield));
+		assert_eq!(throttled_events(), 3);
+		System::set_block_number(3);
+		assert_eq!(execute(&sibling(1000), 10), Err(ProcessMessageError::Yield));
+		assert_eq!(throttled_events(), 4);
+	});
+}
+
+#[test]
+fn rate_limited_weigher_charges_the_accounting() {
+	frame_support::parameter_types! {
+		pub const InstructionWeight: Weight = Weight::from_parts(1_000, 1_000);
+		pub const MaxInstructions: u32 = 100;
+	}
+	type Weigher = RateLimitedWeigher<
+		Test,
+		FixedWeightBounds<InstructionWeight, RuntimeCall, MaxInstructions>,
+	>;
+
+	let accounting = <() as WeightInfo>::try_consume();
+	let mut message = Xcm::<RuntimeCall>(vec![ClearOrigin, ClearOrigin]);
+	assert_eq!(
+		Weigher::weight(&mut message, Weight::MAX),
+		Ok(Weight::from_parts(2_000, 2_000).saturating_add(accounting)),
+	);
+
+	// The accounting counts against the limit.
+	let limit = Weight::from_parts(2_000, 2_000).saturating_add(accounting);
+	assert!(Weigher::weight(&mut message, limit).is_ok());
+	assert!(Weigher::weight(&mut message, limit.saturating_sub(Weight::from_parts(1, 1))).is_err());
+}
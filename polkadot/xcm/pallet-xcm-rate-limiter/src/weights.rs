@@ -0,0 +1,88 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Weights for `pallet_xcm_rate_limiter`.
+//!
+//! These are placeholder weights which have not been measured: the pallet is not part of any
+//! runtime yet. Runtimes using the pallet must benchmark it and use their own weights instead.
+//!
+//! The execution times are rough estimates, the storage accesses and proof sizes follow the
+//! worst case of each function.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_xcm_rate_limiter`.
+pub trait WeightInfo {
+	fn set_rate_limit() -> Weight;
+	fn try_consume() -> Weight;
+}
+
+/// Placeholder weights for `pallet_xcm_rate_limiter`, see the module documentation.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `XcmRateLimiter::WindowUsage` (r:0 w:1)
+	/// Proof: `XcmRateLimiter::WindowUsage` (`max_values`: None, `max_size`: Some(743), added: 3218, mode: `MaxEncodedLen`)
+	/// Storage: `XcmRateLimiter::RateLimits` (r:0 w:1)
+	/// Proof: `XcmRateLimiter::RateLimits` (`max_values`: None, `max_size`: Some(739), added: 3214, mode: `MaxEncodedLen`)
+	/// Storage: `XcmRateLimiter::LastThrottled` (r:0 w:1)
+	/// Proof: `XcmRateLimiter::LastThrottled` (`max_values`: None, `max_size`: Some(776), added: 3251, mode: `MaxEncodedLen`)
+	fn set_rate_limit() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `XcmRateLimiter::RateLimits` (r:9 w:0)
+	/// Proof: `XcmRateLimiter::RateLimits` (`max_values`: None, `max_size`: Some(739), added: 3214, mode: `MaxEncodedLen`)
+	/// Storage: `XcmRateLimiter::WindowUsage` (r:1 w:1)
+	/// Proof: `XcmRateLimiter::WindowUsage` (`max_values`: None, `max_size`: Some(743), added: 3218, mode: `MaxEncodedLen`)
+	/// Storage: `XcmRateLimiter::LastThrottled` (r:1 w:1)
+	/// Proof: `XcmRateLimiter::LastThrottled` (`max_values`: None, `max_size`: Some(776), added: 3251, mode: `MaxEncodedLen`)
+	fn try_consume() -> Weight {
+		Weight::from_parts(50_000_000, 35_395)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `XcmRateLimiter::WindowUsage` (r:0 w:1)
+	/// Proof: `XcmRateLimiter::WindowUsage` (`max_values`: None, `max_size`: Some(743), added: 3218, mode: `MaxEncodedLen`)
+	/// Storage: `XcmRateLimiter::RateLimits` (r:0 w:1)
+	/// Proof: `XcmRateLimiter::RateLimits` (`max_values`: None, `max_size`: Some(739), added: 3214, mode: `MaxEncodedLen`)
+	/// Storage: `XcmRateLimiter::LastThrottled` (r:0 w:1)
+	/// Proof: `XcmRateLimiter::LastThrottled` (`max_values`: None, `max_size`: Some(776), added: 3251, mode: `MaxEncodedLen`)
+	fn set_rate_limit() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `XcmRateLimiter::RateLimits` (r:9 w:0)
+	/// Proof: `XcmRateLimiter::RateLimits` (`max_values`: None, `max_size`: Some(739), added: 3214, mode: `MaxEncodedLen`)
+	/// Storage: `XcmRateLimiter::WindowUsage` (r:1 w:1)
+	/// Proof: `XcmRateLimiter::WindowUsage` (`max_values`: None, `max_size`: Some(743), added: 3218, mode: `MaxEncodedLen`)
+	/// Storage: `XcmRateLimiter::LastThrottled` (r:1 w:1)
+	/// Proof: `XcmRateLimiter::LastThrottled` (`max_values`: None, `max_size`: Some(776), added: 3251, mode: `MaxEncodedLen`)
+	fn try_consume() -> Weight {
+		Weight::from_parts(50_000_000, 35_395)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
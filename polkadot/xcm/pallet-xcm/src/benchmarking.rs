@@ -83,6 +83,58 @@ pub trait Config: crate::Config + pallet_balances::Config {
 	fn get_asset() -> Asset;
 }
 
+/// Schedules a recurring transfer of the assets of `T::set_up_complex_asset_transfer` from
+/// `caller`, returning its identifier and the function verifying that a transfer was executed.
+fn schedule_benchmark_recurring_transfer<T: Config>(
+	caller: &T::AccountId,
+	interval: BlockNumberFor<T>,
+) -> Result<(RecurringTransferId, Box<dyn FnOnce()>), BenchmarkError> {
+	let error = BenchmarkError::Override(BenchmarkResult::from_weight(Weight::MAX));
+	let (assets, _fee_index, destination, verify_fn) =
+		T::set_up_complex_asset_transfer().ok_or(error.clone())?;
+	// Ensure that origin can send to destination
+	// (e.g. setup delivery fees, ensure router setup, ...)
+	T::DeliveryHelper::ensure_successful_delivery(
+		&Default::default(),
+		&destination,
+		FeeReason::ChargeFees,
+	);
+	let dest = VersionedLocation::from(destination);
+	let beneficiary: VersionedLocation = AccountId32 { network: None, id: [0u8; 32] }.into();
+	let assets = VersionedAssets::from(assets);
+	RecurringTransferTicketOf::<T>::ensure_successful(
+		caller,
+		recurring_transfer_footprint(&dest, &beneficiary, &assets),
+	);
+	let id = RecurringTransferCounter::<T>::get();
+	crate::Pallet::<T>::schedule_recurring_transfer(
+		RawOrigin::Signed(caller.clone()).into(),
+		Box::new(dest),
+		Box::new(beneficiary),
+		Box::new(assets),
+		0,
+		WeightLimit::Unlimited,
+		interval,
+		2,
+	)
+	.map_err(|_| error)?;
+	Ok((id, verify_fn))
+}
+
+/// Fills the agendas of the blocks from `from`, so that a recurring transfer scheduled at `from`
+/// is delayed as much as possible.
+fn fill_recurring_transfer_agendas<T: Config>(from: BlockNumberFor<T>) {
+	let full = BoundedVec::truncate_from(vec![
+		RecurringTransferId::MAX;
+		MaxRecurringTransfersPerBlock::get() as usize
+	]);
+	let mut when = from;
+	for _ in 1..MAX_RECURRING_TRANSFER_DELAY {
+		RecurringTransferAgenda::<T>::insert(when, full.clone());
+		when += 1u32.into();
+	}
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
@@ -680,6 +732,190 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn set_destination_fee_info() {
+		let destination: VersionedLocation = Location::new(1, [Parachain(1000)]).into();
+		let info = DestinationFeeInfo {
+			xcm_weight: Weight::from_parts(1_000_000_000, 100_000),
+			fee_asset: AssetId(Location::parent()).into(),
+			fee: 1_000_000_000,
+		};
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, Box::new(destination), Some(info));
+	}
+
+	#[benchmark]
+	fn claim_trapped_assets() -> Result<(), BenchmarkError> {
+		let claim_origin = RawOrigin::Signed(whitelisted_caller());
+		let error = BenchmarkError::Override(BenchmarkResult::from_weight(Weight::MAX));
+		let claim_location = T::ExecuteXcmOrigin::try_origin(claim_origin.clone().into())
+			.map_err(|_| error.clone())?;
+		let asset: Asset = T::get_asset();
+		let versioned_assets = VersionedAssets::from(Assets::from(asset.clone()));
+		let depositor =
+			T::SovereignAccountOf::convert_location(&claim_location).ok_or(error.clone())?;
+		AssetTrapTicketOf::<T>::ensure_successful(
+			&depositor,
+			asset_trap_footprint(1, &versioned_assets),
+		);
+		// Trap assets for claiming later
+		crate::Pallet::<T>::drop_assets(
+			&claim_location,
+			asset.into(),
+			&XcmContext { origin: None, message_id: [0u8; 32], topic: None },
+		);
+		let hash = BlakeTwo256::hash_of(&(&claim_location, &versioned_assets));
+		if !AssetTrapRecords::<T>::contains_key(LatestVersionedLocation(&claim_location), hash) {
+			return Err(error)
+		}
+
+		#[extrinsic_call]
+		_(claim_origin, hash, Box::new(VersionedLocation::from(claim_location)));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn sweep_trapped_assets() -> Result<(), BenchmarkError> {
+		let error = BenchmarkError::Override(BenchmarkResult::from_weight(Weight::MAX));
+		let trap_location =
+			T::ExecuteXcmOrigin::try_origin(RawOrigin::Signed(whitelisted_caller()).into())
+				.map_err(|_| error.clone())?;
+		let expiry = T::AssetTrapExpiry::get().ok_or(error.clone())?;
+		let asset: Asset = T::get_asset();
+		let versioned_assets = VersionedAssets::from(Assets::from(asset.clone()));
+		let depositor =
+			T::SovereignAccountOf::convert_location(&trap_location).ok_or(error.clone())?;
+		AssetTrapTicketOf::<T>::ensure_successful(
+			&depositor,
+			asset_trap_footprint(1, &versioned_assets),
+		);
+		// Trap assets and let them expire
+		crate::Pallet::<T>::drop_assets(
+			&trap_location,
+			asset.into(),
+			&XcmContext { origin: None, message_id: [0u8; 32], topic: None },
+		);
+		let hash = BlakeTwo256::hash_of(&(&trap_location, &versioned_assets));
+		if !AssetTrapRecords::<T>::contains_key(LatestVersionedLocation(&trap_location), hash) {
+			return Err(error)
+		}
+		let now = frame_system::Pallet::<T>::current_block_number();
+		frame_system::Pallet::<T>::set_block_number(now + expiry + 1u32.into());
+		let beneficiary = VersionedLocation::from(trap_location.clone());
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, Box::new(trap_location.into()), hash, Box::new(beneficiary));
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn drop_assets() -> Result<(), BenchmarkError> {
+		let trap_location =
+			T::ExecuteXcmOrigin::try_origin(RawOrigin::Signed(whitelisted_caller()).into())
+				.map_err(|_| BenchmarkError::Weightless)?;
+		let asset: Asset = T::get_asset();
+		let versioned_assets = VersionedAssets::from(Assets::from(asset.clone()));
+		if let Some(depositor) = T::SovereignAccountOf::convert_location(&trap_location) {
+			AssetTrapTicketOf::<T>::ensure_successful(
+				&depositor,
+				asset_trap_footprint(2, &versioned_assets),
+			);
+		}
+		let context = XcmContext { origin: None, message_id: [0u8; 32], topic: None };
+		// Trap the same assets before, so that the record of the trap and its deposit are updated.
+		crate::Pallet::<T>::drop_assets(&trap_location, asset.clone().into(), &context);
+
+		#[block]
+		{
+			crate::Pallet::<T>::drop_assets(&trap_location, asset.into(), &context);
+		}
+
+		let hash = BlakeTwo256::hash_of(&(&trap_location, &versioned_assets));
+		assert_eq!(AssetTraps::<T>::get(hash), 2);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn schedule_recurring_transfer() -> Result<(), BenchmarkError> {
+		let error = BenchmarkError::Override(BenchmarkResult::from_weight(Weight::MAX));
+		let (assets, _fee_index, destination, _verify_fn) =
+			T::set_up_complex_asset_transfer().ok_or(error.clone())?;
+		let caller: T::AccountId = whitelisted_caller();
+		let versioned_dest: VersionedLocation = destination.into();
+		let versioned_beneficiary: VersionedLocation =
+			AccountId32 { network: None, id: [0u8; 32] }.into();
+		let versioned_assets: VersionedAssets = assets.into();
+		let footprint = recurring_transfer_footprint(
+			&versioned_dest,
+			&versioned_beneficiary,
+			&versioned_assets,
+		);
+		RecurringTransferTicketOf::<T>::ensure_successful(&caller, footprint);
+		// Recurring transfers may be disabled.
+		RecurringTransferTicketOf::<T>::new(&caller, footprint)
+			.and_then(|ticket| ticket.drop(&caller))
+			.map_err(|_| error.clone())?;
+		let interval: BlockNumberFor<T> = 1u32.into();
+		let now = frame_system::Pallet::<T>::current_block_number();
+		fill_recurring_transfer_agendas::<T>(now + interval);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			Box::new(versioned_dest),
+			Box::new(versioned_beneficiary),
+			Box::new(versioned_assets),
+			0,
+			WeightLimit::Unlimited,
+			interval,
+			2,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_recurring_transfer() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let interval: BlockNumberFor<T> = 1u32.into();
+		let now = frame_system::Pallet::<T>::current_block_number();
+		// Cancel the last transfer of a full agenda.
+		let others = MaxRecurringTransfersPerBlock::get() as usize - 1;
+		RecurringTransferAgenda::<T>::insert(
+			now + interval,
+			BoundedVec::truncate_from(vec![RecurringTransferId::MAX; others]),
+		);
+		let (id, _verify_fn) = schedule_benchmark_recurring_transfer::<T>(&caller, interval)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), id);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn execute_recurring_transfer() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let interval: BlockNumberFor<T> = 1u32.into();
+		let (id, verify_fn) = schedule_benchmark_recurring_transfer::<T>(&caller, interval)?;
+		let next = frame_system::Pallet::<T>::current_block_number() + interval;
+		// The next transfer is delayed by full agendas.
+		fill_recurring_transfer_agendas::<T>(next + interval);
+
+		#[block]
+		{
+			crate::Pallet::<T>::service_recurring_transfers(next);
+		}
+
+		assert!(RecurringTransfers::<T>::get(id).is_some_and(|transfer| transfer.remaining == 1));
+		// run provided verification function
+		verify_fn();
+		Ok(())
+	}
+
 	#[benchmark]
 	fn weigh_message() -> Result<(), BenchmarkError> {
 		let msg = Xcm(vec![ClearOrigin; MAX_INSTRUCTIONS_TO_DECODE.into()]);
@@ -58,7 +58,7 @@ use sp_runtime::{
 	},
 	Either, RuntimeDebug, SaturatedConversion,
 };
-use storage::{with_transaction, TransactionOutcome};
+use storage::{with_storage_layer, with_transaction, TransactionOutcome};
 use xcm::{latest::QueryResponseInfo, prelude::*};
 use xcm_builder::{
 	ExecuteController, ExecuteControllerWeightInfo, InspectMessageQueues, QueryController,
@@ -67,16 +67,23 @@ use xcm_builder::{
 use xcm_executor::{
 	traits::{
 		AssetTransferError, CheckSuspension, ClaimAssets, ConvertLocation, ConvertOrigin,
-		DropAssets, EventEmitter, FeeManager, FeeReason, MatchesFungible, OnResponse, Properties,
-		QueryHandler, QueryResponseStatus, RecordXcm, TransactAsset, TransferType,
-		VersionChangeNotifier, WeightBounds, XcmAssetTransfers,
+		DropAssets, EventEmitter, FeeManager, FeeReason, InstructionTrace, MatchesFungible,
+		OnResponse, Properties, QueryHandler, QueryResponseStatus, RecordXcm, TransactAsset,
+		TransferType, VersionChangeNotifier, WeightBounds, XcmAssetTransfers,
 	},
 	AssetsInHolding,
 };
 use xcm_runtime_apis::{
 	authorized_aliases::{Error as AuthorizedAliasersApiError, OriginAliaser},
-	dry_run::{CallDryRunEffects, Error as XcmDryRunApiError, XcmDryRunEffects},
+	dry_run::{
+		CallDryRunEffects, Error as XcmDryRunApiError, TracedDryRunEffects, XcmDryRunEffects,
+	},
 	fees::Error as XcmPaymentApiError,
+	transfer_fees::{
+		DestinationFeeInfo, Error as TransferFeesApiError, ForwardedXcm, RemoteExecution,
+		TransferFeeEstimate,
+	},
+	trapped_assets::{Error as TrappedAssetsApiError, TrappedAssets},
 	trusted_query::Error as TrustedQueryApiError,
 };
 
@@ -109,6 +116,13 @@ pub trait WeightInfo {
 	fn claim_assets() -> Weight;
 	fn add_authorized_alias() -> Weight;
 	fn remove_authorized_alias() -> Weight;
+	fn set_destination_fee_info() -> Weight;
+	fn claim_trapped_assets() -> Weight;
+	fn sweep_trapped_assets() -> Weight;
+	fn drop_assets() -> Weight;
+	fn schedule_recurring_transfer() -> Weight;
+	fn cancel_recurring_transfer() -> Weight;
+	fn execute_recurring_transfer() -> Weight;
 
 	fn weigh_message() -> Weight;
 }
@@ -204,6 +218,34 @@ impl WeightInfo for TestWeightInfo {
 		Weight::from_parts(100_000, 0)
 	}
 
+	fn set_destination_fee_info() -> Weight {
+		Weight::from_parts(100_000, 0)
+	}
+
+	fn claim_trapped_assets() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+	}
+
+	fn sweep_trapped_assets() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+	}
+
+	fn drop_assets() -> Weight {
+		Weight::zero()
+	}
+
+	fn schedule_recurring_transfer() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+	}
+
+	fn cancel_recurring_transfer() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+	}
+
+	fn execute_recurring_transfer() -> Weight {
+		Weight::from_parts(100_000_000, 0)
+	}
+
 	fn weigh_message() -> Weight {
 		Weight::from_parts(100_000, 0)
 	}
@@ -219,6 +261,63 @@ pub fn aliasers_footprint(aliasers_count: usize) -> Footprint {
 	Footprint::from_parts(aliasers_count, OriginAliaser::max_encoded_len())
 }
 
+/// The full contents of an asset trap, stored so that the trapped assets can be listed and
+/// claimed without knowing them in advance.
+#[derive(Clone, Debug, Encode, Decode, TypeInfo)]
+pub struct AssetTrapRecord<AccountId, Ticket, BlockNumber> {
+	/// The trapped assets, in the latest version when they were trapped.
+	pub assets: VersionedAssets,
+	/// The block after which `AdminOrigin` can sweep the assets, if any.
+	pub expires_at: Option<BlockNumber>,
+	/// The account the storage deposit was taken from.
+	pub depositor: AccountId,
+	/// The storage deposit.
+	pub ticket: Ticket,
+}
+
+pub fn asset_trap_footprint(count: u32, assets: &VersionedAssets) -> Footprint {
+	Footprint::from_parts(count as usize, assets.encoded_size())
+}
+
+/// The identifier of a recurring transfer.
+pub type RecurringTransferId = u64;
+
+/// A transfer of assets repeated every `interval` blocks, registered with
+/// `schedule_recurring_transfer`.
+#[derive(Clone, Debug, Encode, Decode, TypeInfo)]
+pub struct RecurringTransfer<AccountId, Ticket, BlockNumber> {
+	/// The origin the assets are transferred from.
+	pub origin: VersionedLocation,
+	/// The destination chain of the assets.
+	pub dest: VersionedLocation,
+	/// The beneficiary of the assets on `dest`.
+	pub beneficiary: VersionedLocation,
+	/// The assets transferred each time.
+	pub assets: VersionedAssets,
+	/// The index into `assets` of the item used to pay fees.
+	pub fee_asset_item: u32,
+	/// The remote-side weight limit for the XCM fee purchase.
+	pub weight_limit: WeightLimit,
+	/// The number of blocks between transfers.
+	pub interval: BlockNumber,
+	/// The number of transfers left.
+	pub remaining: u32,
+	/// The block of the next transfer.
+	pub next: BlockNumber,
+	/// The account which registered the transfer, able to cancel it.
+	pub owner: AccountId,
+	/// The storage deposit.
+	pub ticket: Ticket,
+}
+
+pub fn recurring_transfer_footprint(
+	dest: &VersionedLocation,
+	beneficiary: &VersionedLocation,
+	assets: &VersionedAssets,
+) -> Footprint {
+	Footprint::from_encodable((dest, beneficiary, assets))
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -238,6 +337,9 @@ pub mod pallet {
 		#[derive(Debug, TypeInfo)]
 		/// The maximum number of distinct locations allowed as authorized aliases for a local origin.
 		pub const MaxAuthorizedAliases: u32 = 10;
+
+		/// The maximum number of recurring transfers executed in a block.
+		pub const MaxRecurringTransfersPerBlock: u32 = 10;
 	}
 
 	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
@@ -250,6 +352,13 @@ pub mod pallet {
 	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 	pub type TicketOf<T> = <T as Config>::AuthorizedAliasConsideration;
+	pub type AssetTrapTicketOf<T> = <T as Config>::AssetTrapConsideration;
+	pub type RecurringTransferTicketOf<T> = <T as Config>::RecurringTransferConsideration;
+	pub type RecurringTransferOf<T> = RecurringTransfer<
+		<T as frame_system::Config>::AccountId,
+		RecurringTransferTicketOf<T>,
+		BlockNumberFor<T>,
+	>;
 
 	#[pallet::config]
 	/// The module configuration trait.
@@ -268,6 +377,23 @@ pub mod pallet {
 		/// A means of providing some cost while Authorized Aliasers data is stored on-chain.
 		type AuthorizedAliasConsideration: Consideration<Self::AccountId, Footprint>;
 
+		/// A means of providing some cost while the full contents of an asset trap are stored
+		/// on-chain. It is taken from the sovereign account of the trap's origin.
+		///
+		/// Use `Disabled` to only store the hashes of trapped assets.
+		type AssetTrapConsideration: Consideration<Self::AccountId, Footprint>;
+
+		/// The number of blocks after which the recorded trapped assets can be swept by
+		/// `AdminOrigin`. `None` means they never expire.
+		#[pallet::constant]
+		type AssetTrapExpiry: Get<Option<BlockNumberFor<Self>>>;
+
+		/// A means of providing some cost while a recurring transfer is stored on-chain. It is
+		/// taken from the account registering the transfer.
+		///
+		/// Use `Disabled` to disable recurring transfers.
+		type RecurringTransferConsideration: Consideration<Self::AccountId, Footprint>;
+
 		/// Required origin for sending XCM messages. If successful, it resolves to `Location`
 		/// which exists as an interior location within this chain's XCM context.
 		type SendXcmOrigin: EnsureOrigin<<Self as SysConfig>::RuntimeOrigin, Success = Location>;
@@ -633,6 +759,25 @@ pub mod pallet {
 		AliasAuthorizationRemoved { aliaser: Location, target: Location },
 		/// `target` removed all alias authorizations.
 		AliasesAuthorizationsRemoved { target: Location },
+		/// The cached fee information of `destination` was set, or removed if `info` is `None`.
+		DestinationFeeInfoSet { destination: Location, info: Option<DestinationFeeInfo> },
+		/// Expired assets trapped for `origin` were claimed by `AdminOrigin` for `beneficiary`.
+		AssetsSwept { hash: H256, origin: Location, beneficiary: Location },
+		/// A transfer of assets from `origin` to `destination` was scheduled to recur, starting
+		/// at block `next`.
+		RecurringTransferScheduled {
+			id: RecurringTransferId,
+			origin: Location,
+			destination: Location,
+			next: BlockNumberFor<T>,
+		},
+		/// A recurring transfer was executed, with `remaining` transfers left.
+		RecurringTransferExecuted { id: RecurringTransferId, remaining: u32 },
+		/// A recurring transfer failed with `error`, with `remaining` transfers left.
+		RecurringTransferFailed { id: RecurringTransferId, error: DispatchError, remaining: u32 },
+		/// A recurring transfer was removed, because it completed, was canceled by its owner or
+		/// couldn't be scheduled again.
+		RecurringTransferRemoved { id: RecurringTransferId },
 	}
 
 	#[pallet::origin]
@@ -664,6 +809,10 @@ pub mod pallet {
 	pub enum HoldReason {
 		/// The funds are held as storage deposit for an authorized alias.
 		AuthorizeAlias,
+		/// The funds are held as storage deposit for the contents of an asset trap.
+		AssetTrap,
+		/// The funds are held as storage deposit for a recurring transfer.
+		RecurringTransfer,
 	}
 
 	#[pallet::error]
@@ -737,6 +886,24 @@ pub mod pallet {
 		/// instruction that caused the error.
 		#[codec(index = 28)]
 		LocalExecutionIncompleteWithError { index: InstructionIndex, error: ExecutionError },
+		/// No assets trapped for the origin were recorded with the given hash.
+		#[codec(index = 29)]
+		AssetTrapNotFound,
+		/// The trapped assets can't be swept before they expire.
+		#[codec(index = 30)]
+		AssetTrapNotExpired,
+		/// The interval or the number of transfers of a recurring transfer is zero.
+		#[codec(index = 31)]
+		InvalidRecurringTransfer,
+		/// Too many recurring transfers are scheduled around the block of the next transfer.
+		#[codec(index = 32)]
+		RecurringTransferAgendaFull,
+		/// No recurring transfer exists with the given identifier.
+		#[codec(index = 33)]
+		RecurringTransferNotFound,
+		/// The recurring transfer was registered by another account.
+		#[codec(index = 34)]
+		NotRecurringTransferOwner,
 	}
 
 	impl<T: Config> From<SendError> for Error<T> {
@@ -819,6 +986,21 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type AssetTraps<T: Config> = StorageMap<_, Identity, H256, u32, ValueQuery>;
 
+	/// The full contents of the asset traps, keyed by the origin able to claim them and by the
+	/// hash of their `AssetTraps` entry.
+	///
+	/// Only traps for which `T::AssetTrapConsideration` could be taken are recorded.
+	#[pallet::storage]
+	pub(super) type AssetTrapRecords<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		VersionedLocation,
+		Identity,
+		H256,
+		AssetTrapRecord<T::AccountId, AssetTrapTicketOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
 	/// Default version to encode XCM when latest version of destination is unknown. If `None`,
 	/// then the destinations whose XCM version is unknown are considered unreachable.
 	#[pallet::storage]
@@ -954,6 +1136,22 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type RecordedXcm<T: Config> = StorageValue<_, Xcm<()>>;
 
+	/// Whether or not the XCM executor should trace the instructions it executes.
+	/// Like [`ShouldRecordXcm`], this is meant to be used in runtime APIs only.
+	///
+	/// Only relevant if this pallet is being used as the [`xcm_executor::traits::RecordXcm`]
+	/// implementation in the XCM executor configuration.
+	#[pallet::storage]
+	pub(crate) type ShouldTraceXcm<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// If [`ShouldTraceXcm`] is set to true, the instructions of all XCM programs executed
+	/// locally since it was set.
+	///
+	/// Only relevant if this pallet is being used as the [`xcm_executor::traits::RecordXcm`]
+	/// implementation in the XCM executor configuration.
+	#[pallet::storage]
+	pub(crate) type XcmTrace<T: Config> = StorageValue<_, Vec<InstructionTrace>, ValueQuery>;
+
 	/// Map of authorized aliasers of local origins. Each local location can authorize a list of
 	/// other locations to alias into it. Each aliaser is only valid until its inner `expiry`
 	/// block number.
@@ -966,6 +1164,32 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// How destinations charge for executing messages, used to estimate the remote fees of
+	/// transfers in the `xcm_runtime_apis::transfer_fees::TransferFeesApi` runtime API.
+	#[pallet::storage]
+	pub(super) type DestinationFeeInfos<T: Config> =
+		StorageMap<_, Blake2_128Concat, VersionedLocation, DestinationFeeInfo, OptionQuery>;
+
+	/// The identifier of the next recurring transfer.
+	#[pallet::storage]
+	pub(super) type RecurringTransferCounter<T: Config> =
+		StorageValue<_, RecurringTransferId, ValueQuery>;
+
+	/// The registered recurring transfers.
+	#[pallet::storage]
+	pub(super) type RecurringTransfers<T: Config> =
+		StorageMap<_, Twox64Concat, RecurringTransferId, RecurringTransferOf<T>, OptionQuery>;
+
+	/// The recurring transfers to execute at each block.
+	#[pallet::storage]
+	pub(super) type RecurringTransferAgenda<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<RecurringTransferId, MaxRecurringTransfersPerBlock>,
+		ValueQuery,
+	>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		#[serde(skip)]
@@ -1003,8 +1227,8 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
-			let mut weight_used = Weight::zero();
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			let mut weight_used = Self::service_recurring_transfers(n);
 			if let Some(migration) = CurrentMigration::<T>::get() {
 				// Consume 10% of block at most
 				let max_weight = T::BlockWeights::get().max_block / 10;
@@ -1540,16 +1764,6 @@ pub mod pallet {
 		) -> DispatchResult {
 			let origin_location = T::ExecuteXcmOrigin::ensure_origin(origin)?;
 			tracing::debug!(target: "xcm::pallet_xcm::claim_assets", ?origin_location, ?assets, ?beneficiary);
-			// Extract version from `assets`.
-			let assets_version = assets.identify_version();
-			let assets: Assets = (*assets).try_into().map_err(|()| {
-				tracing::debug!(
-					target: "xcm::pallet_xcm::claim_assets",
-					"Failed to convert input VersionedAssets",
-				);
-				Error::<T>::BadVersion
-			})?;
-			let number_of_assets = assets.len() as u32;
 			let beneficiary: Location = (*beneficiary).try_into().map_err(|()| {
 				tracing::debug!(
 					target: "xcm::pallet_xcm::claim_assets",
@@ -1557,28 +1771,7 @@ pub mod pallet {
 				);
 				Error::<T>::BadVersion
 			})?;
-			let ticket: Location = GeneralIndex(assets_version as u128).into();
-			let mut message = Xcm(vec![
-				ClaimAsset { assets, ticket },
-				DepositAsset { assets: AllCounted(number_of_assets).into(), beneficiary },
-			]);
-			let weight = T::Weigher::weight(&mut message, Weight::MAX).map_err(|error| {
-				tracing::debug!(target: "xcm::pallet_xcm::claim_assets", ?error, "Failed to calculate weight");
-				Error::<T>::UnweighableMessage
-			})?;
-			let mut hash = message.using_encoded(sp_io::hashing::blake2_256);
-			let outcome = T::XcmExecutor::prepare_and_execute(
-				origin_location,
-				message,
-				&mut hash,
-				weight,
-				weight,
-			);
-			outcome.ensure_complete().map_err(|error| {
-				tracing::error!(target: "xcm::pallet_xcm::claim_assets", ?error, "XCM execution failed with error");
-				Error::<T>::LocalExecutionIncompleteWithError { index: error.index, error: error.error.into()}
-			})?;
-			Ok(())
+			Self::do_claim_assets(origin_location, *assets, beneficiary)
 		}
 
 		/// Transfer assets from the local chain to the destination chain using explicit transfer
@@ -1874,12 +2067,244 @@ pub mod pallet {
 				Err(Error::<T>::AliasNotFound.into())
 			}
 		}
+
+		/// Set how `destination` charges for executing messages, used to estimate the fees of
+		/// transfers to it. Passing `None` removes the information.
+		///
+		/// - `origin`: Must be an origin specified by AdminOrigin.
+		/// - `destination`: The destination being described.
+		/// - `info`: The weight and price of execution on `destination`.
+		#[pallet::call_index(17)]
+		pub fn set_destination_fee_info(
+			origin: OriginFor<T>,
+			destination: Box<VersionedLocation>,
+			info: Option<DestinationFeeInfo>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let destination: Location = (*destination).try_into().map_err(|()| {
+				tracing::debug!(
+					target: "xcm::pallet_xcm::set_destination_fee_info",
+					"Failed to convert VersionedLocation for destination fee info",
+				);
+				Error::<T>::BadVersion
+			})?;
+			DestinationFeeInfos::<T>::set(
+				VersionedLocation::from(destination.clone()),
+				info.clone(),
+			);
+			Self::deposit_event(Event::DestinationFeeInfoSet { destination, info });
+			Ok(())
+		}
+
+		/// Claims assets trapped on this pallet, identified by the hash of their trap.
+		///
+		/// Unlike `claim_assets`, the trapped assets don't need to be known: they are read from the
+		/// trap's record, which is only kept when the trap's deposit could be taken. Trapped
+		/// assets can be listed with the `TrappedAssetsApi` runtime API.
+		///
+		/// - `origin`: Must be the origin the assets were trapped for.
+		/// - `hash`: The hash of the asset trap.
+		/// - `beneficiary`: The location/account where the claimed assets will be deposited.
+		#[pallet::call_index(18)]
+		pub fn claim_trapped_assets(
+			origin: OriginFor<T>,
+			hash: H256,
+			beneficiary: Box<VersionedLocation>,
+		) -> DispatchResult {
+			let origin_location = T::ExecuteXcmOrigin::ensure_origin(origin)?;
+			tracing::debug!(target: "xcm::pallet_xcm::claim_trapped_assets", ?origin_location, ?hash, ?beneficiary);
+			let beneficiary: Location = (*beneficiary).try_into().map_err(|()| {
+				tracing::debug!(
+					target: "xcm::pallet_xcm::claim_trapped_assets",
+					"Failed to convert beneficiary VersionedLocation",
+				);
+				Error::<T>::BadVersion
+			})?;
+			let record =
+				AssetTrapRecords::<T>::get(LatestVersionedLocation(&origin_location), hash)
+					.ok_or(Error::<T>::AssetTrapNotFound)?;
+			Self::do_claim_assets(origin_location, record.assets, beneficiary)
+		}
+
+		/// Claims expired assets trapped for `trap_origin` on its behalf.
+		///
+		/// Only one trap is claimed, even if the same assets were trapped several times for
+		/// `trap_origin`.
+		///
+		/// - `origin`: Must be an origin specified by AdminOrigin.
+		/// - `trap_origin`: The origin the assets were trapped for.
+		/// - `hash`: The hash of the asset trap.
+		/// - `beneficiary`: The location/account where the claimed assets will be deposited.
+		#[pallet::call_index(19)]
+		pub fn sweep_trapped_assets(
+			origin: OriginFor<T>,
+			trap_origin: Box<VersionedLocation>,
+			hash: H256,
+			beneficiary: Box<VersionedLocation>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let trap_origin: Location = (*trap_origin).try_into().map_err(|()| {
+				tracing::debug!(
+					target: "xcm::pallet_xcm::sweep_trapped_assets",
+					"Failed to convert trap origin VersionedLocation",
+				);
+				Error::<T>::BadVersion
+			})?;
+			let beneficiary: Location = (*beneficiary).try_into().map_err(|()| {
+				tracing::debug!(
+					target: "xcm::pallet_xcm::sweep_trapped_assets",
+					"Failed to convert beneficiary VersionedLocation",
+				);
+				Error::<T>::BadVersion
+			})?;
+			tracing::debug!(target: "xcm::pallet_xcm::sweep_trapped_assets", ?trap_origin, ?hash, ?beneficiary);
+			let record = AssetTrapRecords::<T>::get(LatestVersionedLocation(&trap_origin), hash)
+				.ok_or(Error::<T>::AssetTrapNotFound)?;
+			let now = frame_system::Pallet::<T>::current_block_number();
+			ensure!(
+				record.expires_at.is_some_and(|expires_at| now > expires_at),
+				Error::<T>::AssetTrapNotExpired
+			);
+			Self::do_claim_assets(trap_origin.clone(), record.assets, beneficiary.clone())?;
+			Self::deposit_event(Event::AssetsSwept { hash, origin: trap_origin, beneficiary });
+			Ok(())
+		}
+
+		/// Transfer some assets from the local chain to the destination chain every `interval`
+		/// blocks, `count` times. The first transfer happens `interval` blocks from now.
+		///
+		/// Each transfer is executed like `transfer_assets` for the origin of this call, so its
+		/// transfer types, delivery fees and XCM version of the destination are determined when
+		/// it's executed. A failed transfer is not retried, but the next ones are still executed.
+		/// A storage deposit is held from the caller until the last transfer or until it's
+		/// canceled with `cancel_recurring_transfer`.
+		///
+		/// - `origin`: Must be a signed origin capable of withdrawing the `assets` and executing
+		///   XCM.
+		/// - `dest`: Destination context for the assets.
+		/// - `beneficiary`: A beneficiary location for the assets in the context of `dest`.
+		/// - `assets`: The assets to be withdrawn for each transfer, including the fees.
+		/// - `fee_asset_item`: The index into `assets` of the item which should be used to pay
+		///   fees.
+		/// - `weight_limit`: The remote-side weight limit, if any, for the XCM fee purchase.
+		/// - `interval`: The number of blocks between transfers.
+		/// - `count`: The number of transfers.
+		#[pallet::call_index(20)]
+		pub fn schedule_recurring_transfer(
+			origin: OriginFor<T>,
+			dest: Box<VersionedLocation>,
+			beneficiary: Box<VersionedLocation>,
+			assets: Box<VersionedAssets>,
+			fee_asset_item: u32,
+			weight_limit: WeightLimit,
+			interval: BlockNumberFor<T>,
+			count: u32,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin.clone())?;
+			let origin = T::ExecuteXcmOrigin::ensure_origin(origin)?;
+			let dest: Location = (*dest).try_into().map_err(|()| {
+				tracing::debug!(
+					target: "xcm::pallet_xcm::schedule_recurring_transfer",
+					"Failed to convert destination VersionedLocation",
+				);
+				Error::<T>::BadVersion
+			})?;
+			let beneficiary: Location = (*beneficiary).try_into().map_err(|()| {
+				tracing::debug!(
+					target: "xcm::pallet_xcm::schedule_recurring_transfer",
+					"Failed to convert beneficiary VersionedLocation",
+				);
+				Error::<T>::BadVersion
+			})?;
+			let assets: Assets = (*assets).try_into().map_err(|()| {
+				tracing::debug!(
+					target: "xcm::pallet_xcm::schedule_recurring_transfer",
+					"Failed to convert VersionedAssets",
+				);
+				Error::<T>::BadVersion
+			})?;
+			tracing::debug!(
+				target: "xcm::pallet_xcm::schedule_recurring_transfer",
+				?origin, ?dest, ?beneficiary, ?assets, ?fee_asset_item, ?weight_limit, ?interval, ?count,
+			);
+
+			ensure!(!interval.is_zero() && count > 0, Error::<T>::InvalidRecurringTransfer);
+			ensure!(assets.len() <= MAX_ASSETS_FOR_TRANSFER, Error::<T>::TooManyAssets);
+			// Reject transfers which could never be executed.
+			Self::find_fee_and_assets_transfer_types(
+				assets.inner(),
+				fee_asset_item as usize,
+				&dest,
+			)?;
+
+			// storage entries are always latest version
+			let versioned_dest = VersionedLocation::from(dest.clone());
+			let beneficiary = VersionedLocation::from(beneficiary);
+			let assets = VersionedAssets::from(assets);
+			let footprint = recurring_transfer_footprint(&versioned_dest, &beneficiary, &assets);
+			let ticket = RecurringTransferTicketOf::<T>::new(&owner, footprint)?;
+			let id = RecurringTransferCounter::<T>::mutate(|counter| {
+				let id = *counter;
+				counter.saturating_inc();
+				id
+			});
+			let now = frame_system::Pallet::<T>::current_block_number();
+			let next = Self::add_to_recurring_transfer_agenda(id, now.saturating_add(interval))?;
+			RecurringTransfers::<T>::insert(
+				id,
+				RecurringTransfer {
+					origin: origin.clone().into(),
+					dest: versioned_dest,
+					beneficiary,
+					assets,
+					fee_asset_item,
+					weight_limit,
+					interval,
+					remaining: count,
+					next,
+					owner,
+					ticket,
+				},
+			);
+			Self::deposit_event(Event::RecurringTransferScheduled {
+				id,
+				origin,
+				destination: dest,
+				next,
+			});
+			Ok(())
+		}
+
+		/// Cancel the recurring transfer `id`, releasing its storage deposit.
+		///
+		/// - `origin`: Must be the account which scheduled the recurring transfer.
+		/// - `id`: The identifier of the recurring transfer.
+		#[pallet::call_index(21)]
+		pub fn cancel_recurring_transfer(
+			origin: OriginFor<T>,
+			id: RecurringTransferId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let transfer =
+				RecurringTransfers::<T>::get(id).ok_or(Error::<T>::RecurringTransferNotFound)?;
+			ensure!(transfer.owner == who, Error::<T>::NotRecurringTransferOwner);
+			tracing::debug!(target: "xcm::pallet_xcm::cancel_recurring_transfer", ?id);
+			RecurringTransferAgenda::<T>::mutate(transfer.next, |agenda| {
+				agenda.retain(|scheduled| *scheduled != id)
+			});
+			Self::remove_recurring_transfer(id, transfer);
+			Ok(())
+		}
 	}
 }
 
 /// The maximum number of distinct assets allowed to be transferred in a single helper extrinsic.
 const MAX_ASSETS_FOR_TRANSFER: usize = 2;
 
+/// The maximum number of blocks a recurring transfer is delayed by when too many transfers are
+/// scheduled in the same block.
+const MAX_RECURRING_TRANSFER_DELAY: u32 = 10;
+
 /// Specify how assets used for fees are handled during asset transfers.
 #[derive(Clone, PartialEq)]
 enum FeesHandling<T: Config> {
@@ -1999,6 +2424,241 @@ impl<T: Config> Pallet<T> {
 		AssetTraps::<T>::get(trap_id)
 	}
 
+	/// Returns up to `max` of the asset traps recorded for `origin`, starting after the trap with
+	/// hash `start_after`.
+	pub fn trapped_assets(
+		origin: VersionedLocation,
+		start_after: Option<H256>,
+		max: u32,
+	) -> Result<Vec<TrappedAssets<BlockNumberFor<T>>>, TrappedAssetsApiError> {
+		// storage entries are always latest version
+		let origin: VersionedLocation = origin.into_version(XCM_VERSION).map_err(|e| {
+			tracing::debug!(
+				target: "xcm::pallet_xcm::trapped_assets",
+				?e, "Failed to convert versioned location",
+			);
+			TrappedAssetsApiError::LocationVersionConversionFailed
+		})?;
+		let records = match start_after {
+			Some(hash) => AssetTrapRecords::<T>::iter_prefix_from(
+				&origin,
+				AssetTrapRecords::<T>::hashed_key_for(&origin, hash),
+			),
+			None => AssetTrapRecords::<T>::iter_prefix(&origin),
+		};
+		Ok(records
+			.take(max as usize)
+			.map(|(hash, record)| TrappedAssets {
+				hash,
+				assets: record.assets,
+				count: AssetTraps::<T>::get(hash),
+				expires_at: record.expires_at,
+			})
+			.collect())
+	}
+
+	/// Claims the `assets` trapped for `origin` and deposits them to `beneficiary`.
+	fn do_claim_assets(
+		origin: Location,
+		assets: VersionedAssets,
+		beneficiary: Location,
+	) -> DispatchResult {
+		// Extract version from `assets`.
+		let assets_version = assets.identify_version();
+		let assets: Assets = assets.try_into().map_err(|()| {
+			tracing::debug!(
+				target: "xcm::pallet_xcm::claim_assets",
+				"Failed to convert input VersionedAssets",
+			);
+			Error::<T>::BadVersion
+		})?;
+		let number_of_assets = assets.len() as u32;
+		let ticket: Location = GeneralIndex(assets_version as u128).into();
+		let mut message = Xcm(vec![
+			ClaimAsset { assets, ticket },
+			DepositAsset { assets: AllCounted(number_of_assets).into(), beneficiary },
+		]);
+		let weight = T::Weigher::weight(&mut message, Weight::MAX).map_err(|error| {
+			tracing::debug!(target: "xcm::pallet_xcm::claim_assets", ?error, "Failed to calculate weight");
+			Error::<T>::UnweighableMessage
+		})?;
+		let mut hash = message.using_encoded(sp_io::hashing::blake2_256);
+		let outcome =
+			T::XcmExecutor::prepare_and_execute(origin, message, &mut hash, weight, weight);
+		outcome.ensure_complete().map_err(|error| {
+			tracing::error!(target: "xcm::pallet_xcm::claim_assets", ?error, "XCM execution failed with error");
+			Error::<T>::LocalExecutionIncompleteWithError { index: error.index, error: error.error.into() }
+		})?;
+		Ok(())
+	}
+
+	/// Records the full contents of an asset trap of `origin`, if its deposit can be taken from
+	/// the sovereign account of `origin`.
+	///
+	/// The same assets trapped several times for the same origin share a record, whose expiry
+	/// is refreshed and whose deposit grows with each trap.
+	fn record_asset_trap(origin: &Location, hash: H256, assets: &VersionedAssets) {
+		let Some(depositor) = T::SovereignAccountOf::convert_location(origin) else {
+			tracing::trace!(target: "xcm::pallet_xcm::record_asset_trap", ?origin, "No sovereign account to take the deposit from");
+			return
+		};
+		let footprint = asset_trap_footprint(AssetTraps::<T>::get(hash), assets);
+		let expires_at = T::AssetTrapExpiry::get()
+			.map(|expiry| frame_system::Pallet::<T>::current_block_number().saturating_add(expiry));
+		let versioned_origin = LatestVersionedLocation(origin);
+		let result = with_transaction(|| {
+			let record = match AssetTrapRecords::<T>::take(versioned_origin, hash) {
+				Some(record) => record
+					.ticket
+					.update(&record.depositor, footprint)
+					.map(|ticket| AssetTrapRecord { ticket, expires_at, ..record }),
+				None => AssetTrapTicketOf::<T>::new(&depositor, footprint).map(|ticket| {
+					AssetTrapRecord { assets: assets.clone(), expires_at, depositor, ticket }
+				}),
+			};
+			match record {
+				Ok(record) => {
+					AssetTrapRecords::<T>::insert(versioned_origin, hash, record);
+					TransactionOutcome::Commit(Ok(()))
+				},
+				Err(error) => TransactionOutcome::Rollback(Err(error)),
+			}
+		});
+		if let Err(error) = result {
+			tracing::trace!(target: "xcm::pallet_xcm::record_asset_trap", ?origin, ?hash, ?error, "Asset trap not recorded");
+		}
+	}
+
+	/// Updates the record of an asset trap of `origin` after one of its traps was claimed,
+	/// releasing its deposit once all of them are.
+	///
+	/// The record is kept unchanged if its deposit can't be updated, so that a held deposit is
+	/// never left without a record.
+	fn release_asset_trap(origin: &Location, hash: H256) {
+		let versioned_origin = LatestVersionedLocation(origin);
+		let Some(record) = AssetTrapRecords::<T>::get(versioned_origin, hash) else { return };
+		let remaining = AssetTraps::<T>::get(hash);
+		let result = if remaining == 0 {
+			record
+				.ticket
+				.clone()
+				.drop(&record.depositor)
+				.map(|()| AssetTrapRecords::<T>::remove(versioned_origin, hash))
+		} else {
+			let footprint = asset_trap_footprint(remaining, &record.assets);
+			record.ticket.clone().update(&record.depositor, footprint).map(|ticket| {
+				AssetTrapRecords::<T>::insert(
+					versioned_origin,
+					hash,
+					AssetTrapRecord { ticket, ..record },
+				);
+			})
+		};
+		if let Err(error) = result {
+			tracing::error!(target: "xcm::pallet_xcm::release_asset_trap", ?origin, ?hash, ?error, "Failed to release asset trap deposit");
+		}
+	}
+
+	/// Schedules the recurring transfer `id` at block `when`, or at one of the following blocks if
+	/// too many transfers are already scheduled at `when`. Returns the block it was scheduled at.
+	fn add_to_recurring_transfer_agenda(
+		id: RecurringTransferId,
+		when: BlockNumberFor<T>,
+	) -> Result<BlockNumberFor<T>, Error<T>> {
+		let mut when = when;
+		for _ in 0..MAX_RECURRING_TRANSFER_DELAY {
+			if RecurringTransferAgenda::<T>::mutate(when, |agenda| agenda.try_push(id)).is_ok() {
+				return Ok(when)
+			}
+			when.saturating_inc();
+		}
+		Err(Error::<T>::RecurringTransferAgendaFull)
+	}
+
+	/// Removes the recurring transfer `id` and releases its deposit.
+	fn remove_recurring_transfer(id: RecurringTransferId, transfer: RecurringTransferOf<T>) {
+		RecurringTransfers::<T>::remove(id);
+		if let Err(error) = transfer.ticket.drop(&transfer.owner) {
+			tracing::error!(target: "xcm::pallet_xcm::remove_recurring_transfer", ?id, ?error, "Failed to release recurring transfer deposit");
+		}
+		Self::deposit_event(Event::RecurringTransferRemoved { id });
+	}
+
+	/// Executes the recurring transfers scheduled at block `now` and schedules their next
+	/// transfers.
+	fn service_recurring_transfers(now: BlockNumberFor<T>) -> Weight {
+		// Most blocks have nothing scheduled, and none do if recurring transfers are disabled, so
+		// the agenda is only taken if there is one.
+		if !RecurringTransferAgenda::<T>::contains_key(now) {
+			return T::DbWeight::get().reads(1)
+		}
+		let agenda = RecurringTransferAgenda::<T>::take(now);
+		let mut weight_used = T::DbWeight::get().reads_writes(1, 1);
+		for id in agenda {
+			weight_used.saturating_accrue(T::WeightInfo::execute_recurring_transfer());
+			let Some(mut transfer) = RecurringTransfers::<T>::get(id) else {
+				frame_support::defensive!("Scheduled recurring transfer not found");
+				continue
+			};
+			let result = with_storage_layer(|| Self::do_recurring_transfer(&transfer));
+			transfer.remaining.saturating_dec();
+			let remaining = transfer.remaining;
+			match result {
+				Ok(()) => Self::deposit_event(Event::RecurringTransferExecuted { id, remaining }),
+				Err(error) => {
+					tracing::debug!(target: "xcm::pallet_xcm::service_recurring_transfers", ?id, ?error, "Recurring transfer failed");
+					Self::deposit_event(Event::RecurringTransferFailed { id, error, remaining });
+				},
+			}
+			if remaining == 0 {
+				Self::remove_recurring_transfer(id, transfer);
+				continue
+			}
+			match Self::add_to_recurring_transfer_agenda(id, now.saturating_add(transfer.interval))
+			{
+				Ok(next) =>
+					RecurringTransfers::<T>::insert(id, RecurringTransfer { next, ..transfer }),
+				Err(error) => {
+					tracing::error!(target: "xcm::pallet_xcm::service_recurring_transfers", ?id, ?error, "Failed to schedule next recurring transfer");
+					Self::remove_recurring_transfer(id, transfer);
+				},
+			}
+		}
+		weight_used
+	}
+
+	/// Executes one transfer of a recurring transfer, like `transfer_assets`.
+	fn do_recurring_transfer(transfer: &RecurringTransferOf<T>) -> DispatchResult {
+		let origin: Location =
+			transfer.origin.clone().try_into().map_err(|()| Error::<T>::BadVersion)?;
+		let dest: Location =
+			transfer.dest.clone().try_into().map_err(|()| Error::<T>::BadVersion)?;
+		let beneficiary: Location =
+			transfer.beneficiary.clone().try_into().map_err(|()| Error::<T>::BadVersion)?;
+		let assets: Assets =
+			transfer.assets.clone().try_into().map_err(|()| Error::<T>::BadVersion)?;
+		let assets = assets.into_inner();
+		let fee_asset_item = transfer.fee_asset_item as usize;
+		let (fees_transfer_type, assets_transfer_type) =
+			Self::find_fee_and_assets_transfer_types(&assets, fee_asset_item, &dest)?;
+		Self::ensure_network_asset_reserve_transfer_allowed(
+			&assets,
+			fee_asset_item,
+			&assets_transfer_type,
+			&fees_transfer_type,
+		)?;
+		Self::do_transfer_assets(
+			origin,
+			dest,
+			Either::Left(beneficiary),
+			assets,
+			assets_transfer_type,
+			fee_asset_item,
+			fees_transfer_type,
+			transfer.weight_limit.clone(),
+		)
+	}
+
 	/// Find `TransferType`s for `assets` and fee identified through `fee_asset_item`, when
 	/// transferring to `dest`.
 	///
@@ -3149,6 +3809,90 @@ impl<T: Config> Pallet<T> {
 		Ok(XcmDryRunEffects { forwarded_xcms, emitted_events: events, execution_result: result })
 	}
 
+	/// Dry-runs `call` like [`Self::dry_run_call`], additionally returning the trace of the XCM
+	/// instructions executed locally.
+	///
+	/// Meant to be used in the `xcm_runtime_apis::dry_run::DryRunApi` runtime API.
+	pub fn dry_run_call_with_trace<Runtime, Router, OriginCaller, RuntimeCall>(
+		origin: OriginCaller,
+		call: RuntimeCall,
+		result_xcms_version: XcmVersion,
+	) -> Result<
+		TracedDryRunEffects<CallDryRunEffects<<Runtime as frame_system::Config>::RuntimeEvent>>,
+		XcmDryRunApiError,
+	>
+	where
+		Runtime: crate::Config,
+		Router: InspectMessageQueues,
+		RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>,
+		<RuntimeCall as Dispatchable>::RuntimeOrigin: From<OriginCaller>,
+	{
+		crate::Pallet::<Runtime>::set_trace_xcm(true);
+		let effects = Self::dry_run_call::<Runtime, Router, OriginCaller, RuntimeCall>(
+			origin,
+			call,
+			result_xcms_version,
+		);
+		let trace = crate::Pallet::<Runtime>::take_trace();
+		crate::Pallet::<Runtime>::set_trace_xcm(false);
+
+		Ok(TracedDryRunEffects {
+			effects: effects?,
+			trace: Self::convert_trace(result_xcms_version, trace)?,
+		})
+	}
+
+	/// Dry-runs `xcm` like [`Self::dry_run_xcm`], additionally returning the trace of the
+	/// instructions executed.
+	///
+	/// Meant to be used in the `xcm_runtime_apis::dry_run::DryRunApi` runtime API.
+	pub fn dry_run_xcm_with_trace<
+		Runtime,
+		Router,
+		RuntimeCall: Decode + GetDispatchInfo,
+		XcmConfig,
+	>(
+		origin_location: VersionedLocation,
+		xcm: VersionedXcm<RuntimeCall>,
+	) -> Result<
+		TracedDryRunEffects<XcmDryRunEffects<<Runtime as frame_system::Config>::RuntimeEvent>>,
+		XcmDryRunApiError,
+	>
+	where
+		Runtime: frame_system::Config,
+		Router: InspectMessageQueues,
+		XcmConfig: xcm_executor::Config<RuntimeCall = RuntimeCall>,
+	{
+		let xcm_version = xcm.identify_version();
+		XcmConfig::XcmRecorder::set_trace_xcm(true);
+		let effects =
+			Self::dry_run_xcm::<Runtime, Router, RuntimeCall, XcmConfig>(origin_location, xcm);
+		let trace = XcmConfig::XcmRecorder::take_trace();
+		XcmConfig::XcmRecorder::set_trace_xcm(false);
+
+		Ok(TracedDryRunEffects {
+			effects: effects?,
+			trace: Self::convert_trace(xcm_version, trace)?,
+		})
+	}
+
+	fn convert_trace(
+		xcm_version: XcmVersion,
+		trace: Vec<InstructionTrace>,
+	) -> Result<Vec<InstructionTrace>, XcmDryRunApiError> {
+		trace
+			.into_iter()
+			.map(|step| step.into_version(xcm_version))
+			.collect::<Result<Vec<_>, ()>>()
+			.map_err(|()| {
+				tracing::debug!(
+					target: "xcm::pallet_xcm::convert_trace",
+					"Failed to convert instruction trace to requested version",
+				);
+				XcmDryRunApiError::VersionedConversionFailed
+			})
+	}
+
 	fn convert_xcms(
 		xcm_version: XcmVersion,
 		xcms: Vec<VersionedXcm<()>>,
@@ -3202,10 +3946,13 @@ impl<T: Config> Pallet<T> {
 				XcmPaymentApiError::VersionedConversionFailed
 			})?;
 
-		T::Weigher::weight(&mut message.clone().into(), Weight::MAX).map_err(|error| {
-			tracing::debug!(target: "xcm::pallet_xcm::query_xcm_weight", ?error, ?message, "Error when querying XCM weight");
-			XcmPaymentApiError::WeightNotComputable
-		})
+		// Prepared by the executor so that the weight charged up front for trapping assets counts.
+		T::XcmExecutor::prepare(message.clone().into(), Weight::MAX)
+			.map(|prepared| prepared.weight_of())
+			.map_err(|error| {
+				tracing::debug!(target: "xcm::pallet_xcm::query_xcm_weight", ?error, ?message, "Error when querying XCM weight");
+				XcmPaymentApiError::WeightNotComputable
+			})
 	}
 
 	/// Computes the weight cost using the provided `WeightTrader`.
@@ -3299,6 +4046,115 @@ impl<T: Config> Pallet<T> {
 			})
 	}
 
+	/// Estimates all the fees of dispatching the transfer `call` with the given `origin`.
+	///
+	/// The call is dry-run and its effects are discarded, so that delivery fees are quoted on the
+	/// current state. The local XCM is weighed and its fee quoted in `fee_asset` with `Trader`,
+	/// which must be the trader of the XCM executor. Execution on the destinations is estimated
+	/// with their [`DestinationFeeInfo`], taken from `destination_fee_infos` or from
+	/// [`DestinationFeeInfos`] if not supplied.
+	///
+	/// Meant to be used in the `xcm_runtime_apis::transfer_fees::TransferFeesApi` runtime API.
+	pub fn estimate_transfer_fees<Router, OriginCaller, RuntimeCall, Trader>(
+		origin: OriginCaller,
+		call: RuntimeCall,
+		fee_asset: VersionedAssetId,
+		destination_fee_infos: Vec<(VersionedLocation, DestinationFeeInfo)>,
+		result_xcms_version: XcmVersion,
+	) -> Result<TransferFeeEstimate, TransferFeesApiError>
+	where
+		Router: InspectMessageQueues,
+		RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>,
+		<RuntimeCall as Dispatchable>::RuntimeOrigin: From<OriginCaller>,
+		Trader: xcm_executor::traits::WeightTrader,
+	{
+		let effects = with_transaction(|| -> TransactionOutcome<Result<_, DispatchError>> {
+			TransactionOutcome::Rollback(Ok(Self::dry_run_call::<T, Router, OriginCaller, RuntimeCall>(
+				origin,
+				call,
+				result_xcms_version,
+			)))
+		})
+		.map_err(|error| {
+			tracing::error!(target: "xcm::pallet_xcm::estimate_transfer_fees", ?error, "Dry-run transaction failed");
+			TransferFeesApiError::TransactionalLimitReached
+		})??;
+
+		let local_xcm_weight = effects.local_xcm.map(Self::query_xcm_weight).transpose()?;
+		let local_execution_fee = local_xcm_weight
+			.map(|weight| -> Result<_, TransferFeesApiError> {
+				let amount = Self::query_weight_to_asset_fee::<Trader>(weight, fee_asset.clone())?;
+				Self::versioned_fee(fee_asset.clone(), amount, result_xcms_version)
+			})
+			.transpose()?;
+
+		let forwarded_xcms = effects
+			.forwarded_xcms
+			.into_iter()
+			.flat_map(|(destination, messages)| {
+				messages.into_iter().map(move |message| (destination.clone(), message))
+			})
+			.map(|(destination, message)| -> Result<_, TransferFeesApiError> {
+				let delivery_fees =
+					Self::query_delivery_fees(destination.clone(), message.clone())?
+						.into_version(result_xcms_version)
+						.map_err(|()| TransferFeesApiError::VersionedConversionFailed)?;
+				let remote_execution =
+					Self::destination_fee_info(&destination, &destination_fee_infos)?
+						.map(|info| Self::remote_execution(info, result_xcms_version))
+						.transpose()?;
+				Ok(ForwardedXcm { destination, message, delivery_fees, remote_execution })
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(TransferFeeEstimate {
+			execution_result: effects.execution_result,
+			local_xcm_weight,
+			local_execution_fee,
+			forwarded_xcms,
+		})
+	}
+
+	/// The fee information of `destination`, preferring the supplied one over the cached one.
+	fn destination_fee_info(
+		destination: &VersionedLocation,
+		supplied: &[(VersionedLocation, DestinationFeeInfo)],
+	) -> Result<Option<DestinationFeeInfo>, TransferFeesApiError> {
+		let destination: Location = destination.clone().try_into().map_err(|()| {
+			tracing::debug!(target: "xcm::pallet_xcm::destination_fee_info", ?destination, "Failed to convert versioned destination");
+			TransferFeesApiError::VersionedConversionFailed
+		})?;
+		let supplied_info = supplied
+			.iter()
+			.find(|(location, _)| Location::try_from(location.clone()).as_ref() == Ok(&destination))
+			.map(|(_, info)| info.clone());
+		Ok(supplied_info
+			.or_else(|| DestinationFeeInfos::<T>::get(VersionedLocation::from(destination))))
+	}
+
+	/// The cost of executing a forwarded message on a destination, as reported by its `info`.
+	fn remote_execution(
+		info: DestinationFeeInfo,
+		result_xcms_version: XcmVersion,
+	) -> Result<RemoteExecution, TransferFeesApiError> {
+		let fee = Self::versioned_fee(info.fee_asset, info.fee, result_xcms_version)?;
+		Ok(RemoteExecution { weight: info.xcm_weight, fee })
+	}
+
+	fn versioned_fee(
+		asset_id: VersionedAssetId,
+		amount: u128,
+		result_xcms_version: XcmVersion,
+	) -> Result<VersionedAsset, TransferFeesApiError> {
+		let asset_id: AssetId = asset_id.try_into().map_err(|()| {
+			tracing::debug!(target: "xcm::pallet_xcm::versioned_fee", "Failed to convert versioned asset id");
+			TransferFeesApiError::VersionedConversionFailed
+		})?;
+		VersionedAsset::from(Asset::from((asset_id, amount)))
+			.into_version(result_xcms_version)
+			.map_err(|()| TransferFeesApiError::VersionedConversionFailed)
+	}
+
 	/// Given an Asset and a Location, returns if the provided location is a trusted reserve for the
 	/// given asset.
 	pub fn is_trusted_reserve(
@@ -3572,6 +4428,14 @@ impl<T: Config> Pallet<T> {
 			)
 		);
 
+		// check `RecurringTransfers`
+		ensure!(
+			RecurringTransfers::<T>::iter().all(|(id, transfer)| {
+				RecurringTransferAgenda::<T>::get(transfer.next).contains(&id)
+			}),
+			TryRuntimeError::Other("`RecurringTransfers` should be scheduled in their agenda!")
+		);
+
 		// if migration has been already scheduled, everything is ok and data will be eventually
 		// migrated
 		if CurrentMigration::<T>::exists() {
@@ -3882,13 +4746,17 @@ impl<T: Config> DropAssets for Pallet<T> {
 		let versioned = VersionedAssets::from(Assets::from(assets));
 		let hash = BlakeTwo256::hash_of(&(&origin, &versioned));
 		AssetTraps::<T>::mutate(hash, |n| *n += 1);
+		Self::record_asset_trap(origin, hash, &versioned);
 		Self::deposit_event(Event::AssetsTrapped {
 			hash,
 			origin: origin.clone(),
 			assets: versioned,
 		});
-		// TODO #3735: Put the real weight in there.
-		Weight::zero()
+		T::WeightInfo::drop_assets()
+	}
+
+	fn max_drop_assets_weight() -> Weight {
+		T::WeightInfo::drop_assets()
 	}
 }
 
@@ -3915,6 +4783,7 @@ impl<T: Config> ClaimAssets for Pallet<T> {
 			1 => AssetTraps::<T>::remove(hash),
 			n => AssetTraps::<T>::insert(hash, n - 1),
 		}
+		Self::release_asset_trap(origin, hash);
 		Self::deposit_event(Event::AssetsClaimed {
 			hash,
 			origin: origin.clone(),
@@ -4134,6 +5003,23 @@ impl<T: Config> RecordXcm for Pallet<T> {
 	fn record(xcm: Xcm<()>) {
 		RecordedXcm::<T>::put(xcm);
 	}
+
+	fn should_trace() -> bool {
+		ShouldTraceXcm::<T>::get()
+	}
+
+	fn set_trace_xcm(enabled: bool) {
+		ShouldTraceXcm::<T>::put(enabled);
+		XcmTrace::<T>::kill();
+	}
+
+	fn take_trace() -> Vec<InstructionTrace> {
+		XcmTrace::<T>::take()
+	}
+
+	fn record_trace(step: InstructionTrace) {
+		XcmTrace::<T>::append(step);
+	}
 }
 
 /// Ensure that the origin `o` represents an XCM (`Transact`) origin.
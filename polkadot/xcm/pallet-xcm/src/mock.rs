@@ -564,6 +564,9 @@ pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, A
 parameter_types! {
 	pub static AdvertisedXcmVersion: pallet_xcm::XcmVersion = 4;
 	pub const AuthorizeAliasHoldReason: RuntimeHoldReason = RuntimeHoldReason::XcmPallet(pallet_xcm::HoldReason::AuthorizeAlias);
+	pub const AssetTrapHoldReason: RuntimeHoldReason = RuntimeHoldReason::XcmPallet(pallet_xcm::HoldReason::AssetTrap);
+	pub const AssetTrapExpiry: Option<u64> = Some(100);
+	pub const RecurringTransferHoldReason: RuntimeHoldReason = RuntimeHoldReason::XcmPallet(pallet_xcm::HoldReason::RecurringTransfer);
 }
 
 pub struct ConvertDeposit;
@@ -607,6 +610,11 @@ impl pallet_xcm::Config for Test {
 	type WeightInfo = TestWeightInfo;
 	type AuthorizedAliasConsideration =
 		HoldConsideration<AccountId, Balances, AuthorizeAliasHoldReason, ConvertDeposit>;
+	type AssetTrapConsideration =
+		HoldConsideration<AccountId, Balances, AssetTrapHoldReason, ConvertDeposit>;
+	type AssetTrapExpiry = AssetTrapExpiry;
+	type RecurringTransferConsideration =
+		HoldConsideration<AccountId, Balances, RecurringTransferHoldReason, ConvertDeposit>;
 }
 
 impl origin::Config for Test {}
@@ -24,7 +24,7 @@ use crate::{
 };
 use frame_support::{
 	assert_err, assert_ok,
-	traits::{tokens::fungibles::Inspect, Currency},
+	traits::{tokens::fungibles::Inspect, Currency, Hooks},
 	weights::Weight,
 };
 use polkadot_parachain_primitives::primitives::Id as ParaId;
@@ -2631,3 +2631,135 @@ fn limited_reserve_transfer_assets_with_remote_asset_reserve_and_remote_asset_fe
 		expected_result,
 	);
 }
+
+/// Test `schedule_recurring_transfer`
+///
+/// Asserts that the assets are transferred every `interval` blocks, `count` times, and that the
+/// storage deposit is released after the last transfer.
+#[test]
+fn recurring_transfer_works() {
+	let balances = vec![
+		(ALICE, INITIAL_BALANCE),
+		(ParaId::from(OTHER_PARA_ID).into_account_truncating(), INITIAL_BALANCE),
+	];
+	let beneficiary: Location = AccountId32 { network: None, id: BOB.into() }.into();
+	new_test_ext_with_balances(balances).execute_with(|| {
+		assert_ok!(XcmPallet::schedule_recurring_transfer(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(RelayLocation::get().into()),
+			Box::new(beneficiary.into()),
+			Box::new((Here, SEND_AMOUNT).into()),
+			0,
+			WeightLimit::Unlimited,
+			5,
+			2,
+		));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::RecurringTransferScheduled {
+				id: 0,
+				origin: AccountId32 { network: None, id: ALICE.into() }.into(),
+				destination: RelayLocation::get(),
+				next: 6,
+			})
+		);
+		// The storage deposit is held.
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE);
+		assert!(Balances::free_balance(&ALICE) < INITIAL_BALANCE);
+
+		XcmPallet::on_initialize(5);
+		assert!(sent_xcm().is_empty());
+
+		XcmPallet::on_initialize(6);
+		assert_eq!(sent_xcm().len(), 1);
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - SEND_AMOUNT);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::RecurringTransferExecuted {
+				id: 0,
+				remaining: 1
+			})
+		);
+
+		XcmPallet::on_initialize(11);
+		assert_eq!(sent_xcm().len(), 2);
+		assert_eq!(
+			last_events(2),
+			vec![
+				RuntimeEvent::XcmPallet(crate::Event::RecurringTransferExecuted {
+					id: 0,
+					remaining: 0
+				}),
+				RuntimeEvent::XcmPallet(crate::Event::RecurringTransferRemoved { id: 0 }),
+			]
+		);
+		// The storage deposit is released.
+		assert_eq!(Balances::free_balance(&ALICE), INITIAL_BALANCE - 2 * SEND_AMOUNT);
+
+		XcmPallet::on_initialize(16);
+		assert_eq!(sent_xcm().len(), 2);
+	});
+}
+
+/// Test `schedule_recurring_transfer` with transfers which fail and `cancel_recurring_transfer`.
+///
+/// Asserts that failed transfers are reported and don't prevent the next ones, and that only
+/// the owner can cancel the recurring transfer.
+#[test]
+fn recurring_transfer_failures_and_cancel_work() {
+	let balances = vec![
+		(ALICE, INITIAL_BALANCE),
+		(ParaId::from(OTHER_PARA_ID).into_account_truncating(), INITIAL_BALANCE),
+	];
+	let beneficiary: Location = AccountId32 { network: None, id: BOB.into() }.into();
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let schedule = |interval, count| {
+			XcmPallet::schedule_recurring_transfer(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(RelayLocation::get().into()),
+				Box::new(beneficiary.clone().into()),
+				// More than the free balance of `ALICE`.
+				Box::new((Here, INITIAL_BALANCE).into()),
+				0,
+				WeightLimit::Unlimited,
+				interval,
+				count,
+			)
+		};
+		assert_err!(schedule(0, 3), crate::Error::<Test>::InvalidRecurringTransfer);
+		assert_err!(schedule(5, 0), crate::Error::<Test>::InvalidRecurringTransfer);
+		assert_ok!(schedule(5, 3));
+
+		XcmPallet::on_initialize(6);
+		assert!(sent_xcm().is_empty());
+		assert!(matches!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::RecurringTransferFailed {
+				id: 0,
+				remaining: 2,
+				..
+			})
+		));
+
+		assert_err!(
+			XcmPallet::cancel_recurring_transfer(RuntimeOrigin::signed(BOB), 0),
+			crate::Error::<Test>::NotRecurringTransferOwner
+		);
+		assert_ok!(XcmPallet::cancel_recurring_transfer(RuntimeOrigin::signed(ALICE), 0));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::RecurringTransferRemoved { id: 0 })
+		);
+		assert_err!(
+			XcmPallet::cancel_recurring_transfer(RuntimeOrigin::signed(ALICE), 0),
+			crate::Error::<Test>::RecurringTransferNotFound
+		);
+		// The storage deposit is released and the next transfer is not executed.
+		assert_eq!(Balances::free_balance(&ALICE), INITIAL_BALANCE);
+		XcmPallet::on_initialize(11);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::RecurringTransferRemoved { id: 0 })
+		);
+	});
+}
@@ -22,12 +22,12 @@ use crate::{
 	aliasers_footprint,
 	migration::data::NeedsMigration,
 	mock::*,
-	pallet::{LockedFungibles, RemoteLockedFungibles, SupportedVersion},
+	pallet::{DestinationFeeInfos, LockedFungibles, RemoteLockedFungibles, SupportedVersion},
 	xcm_helpers::find_xcm_sent_message_id,
-	AssetTraps, AuthorizedAliasers, Config, CurrentMigration, Error, ExecuteControllerWeightInfo,
-	LatestVersionedLocation, MaxAuthorizedAliases, Pallet, Queries, QueryStatus, RecordedXcm,
-	RemoteLockedFungibleRecord, ShouldRecordXcm, VersionDiscoveryQueue, VersionMigrationStage,
-	VersionNotifiers, VersionNotifyTargets, WeightInfo,
+	AssetTrapRecords, AssetTraps, AuthorizedAliasers, Config, CurrentMigration, Error,
+	ExecuteControllerWeightInfo, LatestVersionedLocation, MaxAuthorizedAliases, Pallet, Queries,
+	QueryStatus, RecordedXcm, RemoteLockedFungibleRecord, ShouldRecordXcm, VersionDiscoveryQueue,
+	VersionMigrationStage, VersionNotifiers, VersionNotifyTargets, WeightInfo,
 };
 use bounded_collections::BoundedVec;
 use frame_support::{
@@ -36,9 +36,10 @@ use frame_support::{
 	weights::Weight,
 };
 use polkadot_parachain_primitives::primitives::Id as ParaId;
+use sp_core::H256;
 use sp_runtime::{
 	traits::{AccountIdConversion, BlakeTwo256, BlockNumberProvider, Hash},
-	SaturatedConversion, TokenError,
+	DispatchError, SaturatedConversion, TokenError,
 };
 use xcm::{latest::QueryResponseInfo, prelude::*};
 use xcm_builder::AllowKnownQueryResponses;
@@ -46,6 +47,7 @@ use xcm_executor::{
 	traits::{Properties, QueryHandler, QueryResponseStatus, ShouldExecute},
 	XcmExecutor,
 };
+use xcm_runtime_apis::{transfer_fees::DestinationFeeInfo, trapped_assets::TrappedAssets};
 use xcm_simulator::fake_message_hash;
 
 const ALICE: AccountId = AccountId::new([0u8; 32]);
@@ -718,6 +720,186 @@ fn claim_assets_works() {
 	});
 }
 
+/// Traps `amount` of the native asset for `who`, returning the hash of the trap.
+fn trap_native_assets(who: AccountId, amount: u128) -> H256 {
+	let trapping_program =
+		Xcm::<RuntimeCall>::builder_unsafe().withdraw_asset((Here, amount)).build();
+	assert_ok!(XcmPallet::execute(
+		RuntimeOrigin::signed(who.clone()),
+		Box::new(VersionedXcm::from(trapping_program)),
+		BaseXcmWeight::get() * 2,
+	));
+	let source: Location = Junction::AccountId32 { network: None, id: who.into() }.into();
+	let versioned_assets = VersionedAssets::from(Assets::from((Here, amount)));
+	BlakeTwo256::hash_of(&(source, versioned_assets))
+}
+
+#[test]
+fn trapped_assets_can_be_listed_and_claimed_by_hash() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let source: Location = Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let first = trap_native_assets(ALICE, SEND_AMOUNT);
+		let second = trap_native_assets(ALICE, SEND_AMOUNT * 2);
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - SEND_AMOUNT * 3);
+		// The deposits of the records are held.
+		let deposits = INITIAL_BALANCE - SEND_AMOUNT * 3 - Balances::free_balance(&ALICE);
+		assert!(deposits > 0);
+
+		// Traps are listed page by page.
+		let expires_at = Some(System::block_number() + AssetTrapExpiry::get().unwrap());
+		let all = XcmPallet::trapped_assets(source.clone().into(), None, 10).unwrap();
+		assert_eq!(all.len(), 2);
+		for (hash, amount) in [(first, SEND_AMOUNT), (second, SEND_AMOUNT * 2)] {
+			assert!(all.contains(&TrappedAssets {
+				hash,
+				assets: VersionedAssets::from(Assets::from((Here, amount))),
+				count: 1,
+				expires_at,
+			}));
+		}
+		let page = XcmPallet::trapped_assets(source.clone().into(), None, 1).unwrap();
+		assert_eq!(page, all[..1]);
+		let page = XcmPallet::trapped_assets(source.clone().into(), Some(all[0].hash), 1).unwrap();
+		assert_eq!(page, all[1..]);
+		let bob: Location = Junction::AccountId32 { network: None, id: BOB.into() }.into();
+		assert_eq!(XcmPallet::trapped_assets(bob.clone().into(), None, 10).unwrap(), vec![]);
+
+		// Only the origin of the trap can claim it, for any beneficiary.
+		assert_noop!(
+			XcmPallet::claim_trapped_assets(
+				RuntimeOrigin::signed(BOB),
+				first,
+				Box::new(bob.clone().into()),
+			),
+			Error::<Test>::AssetTrapNotFound
+		);
+		assert_ok!(XcmPallet::claim_trapped_assets(
+			RuntimeOrigin::signed(ALICE),
+			first,
+			Box::new(bob.clone().into()),
+		));
+		assert_eq!(Balances::total_balance(&BOB), INITIAL_BALANCE + SEND_AMOUNT);
+		assert_eq!(AssetTraps::<Test>::get(first), 0);
+		assert!(!AssetTrapRecords::<Test>::contains_key(
+			VersionedLocation::from(source.clone()),
+			first
+		));
+		assert_noop!(
+			XcmPallet::claim_trapped_assets(
+				RuntimeOrigin::signed(ALICE),
+				first,
+				Box::new(bob.clone().into()),
+			),
+			Error::<Test>::AssetTrapNotFound
+		);
+
+		// Claiming with `claim_assets` also removes the record.
+		assert_ok!(XcmPallet::claim_assets(
+			RuntimeOrigin::signed(ALICE),
+			Box::new(VersionedAssets::from(Assets::from((Here, SEND_AMOUNT * 2)))),
+			Box::new(bob.into()),
+		));
+		assert_eq!(XcmPallet::trapped_assets(source.into(), None, 10).unwrap(), vec![]);
+		// All deposits are released.
+		assert_eq!(Balances::free_balance(&ALICE), INITIAL_BALANCE - SEND_AMOUNT * 3);
+	});
+}
+
+#[test]
+fn same_assets_trapped_twice_share_a_record() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let source: Location = Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let hash = trap_native_assets(ALICE, SEND_AMOUNT);
+		let single_deposit = INITIAL_BALANCE - SEND_AMOUNT - Balances::free_balance(&ALICE);
+		System::set_block_number(10);
+		assert_eq!(trap_native_assets(ALICE, SEND_AMOUNT), hash);
+
+		let traps = XcmPallet::trapped_assets(source.clone().into(), None, 10).unwrap();
+		assert_eq!(traps.len(), 1);
+		assert_eq!(traps[0].count, 2);
+		// The expiry is refreshed and the deposit grows.
+		assert_eq!(traps[0].expires_at, Some(10 + AssetTrapExpiry::get().unwrap()));
+		assert!(
+			INITIAL_BALANCE - SEND_AMOUNT * 2 - Balances::free_balance(&ALICE) > single_deposit
+		);
+
+		let bob: Location = Junction::AccountId32 { network: None, id: BOB.into() }.into();
+		assert_ok!(XcmPallet::claim_trapped_assets(
+			RuntimeOrigin::signed(ALICE),
+			hash,
+			Box::new(bob.clone().into()),
+		));
+		let traps = XcmPallet::trapped_assets(source.clone().into(), None, 10).unwrap();
+		assert_eq!(traps[0].count, 1);
+		assert_eq!(
+			INITIAL_BALANCE - SEND_AMOUNT * 2 - Balances::free_balance(&ALICE),
+			single_deposit
+		);
+
+		assert_ok!(XcmPallet::claim_trapped_assets(
+			RuntimeOrigin::signed(ALICE),
+			hash,
+			Box::new(bob.into()),
+		));
+		assert_eq!(XcmPallet::trapped_assets(source.into(), None, 10).unwrap(), vec![]);
+		assert_eq!(Balances::free_balance(&ALICE), INITIAL_BALANCE - SEND_AMOUNT * 2);
+		assert_eq!(Balances::total_balance(&BOB), INITIAL_BALANCE + SEND_AMOUNT * 2);
+	});
+}
+
+#[test]
+fn expired_trapped_assets_can_be_swept() {
+	let balances = vec![(ALICE, INITIAL_BALANCE), (BOB, INITIAL_BALANCE)];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let source: Location = Junction::AccountId32 { network: None, id: ALICE.into() }.into();
+		let bob: Location = Junction::AccountId32 { network: None, id: BOB.into() }.into();
+		let hash = trap_native_assets(ALICE, SEND_AMOUNT);
+		let expires_at = System::block_number() + AssetTrapExpiry::get().unwrap();
+
+		assert_noop!(
+			XcmPallet::sweep_trapped_assets(
+				RuntimeOrigin::signed(BOB),
+				Box::new(source.clone().into()),
+				hash,
+				Box::new(bob.clone().into()),
+			),
+			DispatchError::BadOrigin
+		);
+		System::set_block_number(expires_at);
+		assert_noop!(
+			XcmPallet::sweep_trapped_assets(
+				RuntimeOrigin::root(),
+				Box::new(source.clone().into()),
+				hash,
+				Box::new(bob.clone().into()),
+			),
+			Error::<Test>::AssetTrapNotExpired
+		);
+
+		System::set_block_number(expires_at + 1);
+		assert_ok!(XcmPallet::sweep_trapped_assets(
+			RuntimeOrigin::root(),
+			Box::new(source.clone().into()),
+			hash,
+			Box::new(bob.clone().into()),
+		));
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::AssetsSwept {
+				hash,
+				origin: source.clone(),
+				beneficiary: bob,
+			})
+		);
+		assert_eq!(Balances::total_balance(&BOB), INITIAL_BALANCE + SEND_AMOUNT);
+		assert_eq!(AssetTraps::<Test>::get(hash), 0);
+		assert_eq!(XcmPallet::trapped_assets(source.into(), None, 10).unwrap(), vec![]);
+		assert_eq!(Balances::free_balance(&ALICE), INITIAL_BALANCE - SEND_AMOUNT);
+	});
+}
+
 /// Test failure to complete execution reverts intermediate side-effects.
 ///
 /// XCM program will withdraw and deposit some assets, then fail execution of a further withdraw.
@@ -1785,3 +1967,58 @@ fn query_weight_to_asset_fee_noop() {
 		.unwrap());
 	})
 }
+
+#[test]
+fn set_destination_fee_info_works() {
+	new_test_ext_with_balances(vec![]).execute_with(|| {
+		let destination = Location::new(1, [Parachain(OTHER_PARA_ID)]);
+		let info = DestinationFeeInfo {
+			xcm_weight: Weight::from_parts(1_000_000, 10_000),
+			fee_asset: AssetId(Location::parent()).into(),
+			fee: 1_000_000,
+		};
+
+		assert_noop!(
+			XcmPallet::set_destination_fee_info(
+				RuntimeOrigin::signed(ALICE),
+				Box::new(destination.clone().into()),
+				Some(info.clone()),
+			),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(XcmPallet::set_destination_fee_info(
+			RuntimeOrigin::root(),
+			Box::new(destination.clone().into()),
+			Some(info.clone()),
+		));
+		assert_eq!(
+			DestinationFeeInfos::<Test>::get(VersionedLocation::from(destination.clone())),
+			Some(info.clone())
+		);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::DestinationFeeInfoSet {
+				destination: destination.clone(),
+				info: Some(info),
+			})
+		);
+
+		assert_ok!(XcmPallet::set_destination_fee_info(
+			RuntimeOrigin::root(),
+			Box::new(destination.clone().into()),
+			None,
+		));
+		assert_eq!(
+			DestinationFeeInfos::<Test>::get(VersionedLocation::from(destination.clone())),
+			None
+		);
+		assert_eq!(
+			last_event(),
+			RuntimeEvent::XcmPallet(crate::Event::DestinationFeeInfoSet {
+				destination,
+				info: None
+			})
+		);
+	})
+}
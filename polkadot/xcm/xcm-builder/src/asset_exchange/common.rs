@@ -0,0 +1,182 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Code shared by the exchange adapters over [`pallet_asset_conversion`].
+
+extern crate alloc;
+use alloc::vec::Vec;
+use frame_support::traits::tokens::fungibles;
+use pallet_asset_conversion::SwapCredit;
+use xcm::prelude::*;
+use xcm_executor::{traits::MatchesFungibles, AssetsInHolding};
+
+const LOG_TARGET: &str = "xcm::asset_exchange";
+
+/// An exchange of exactly one fungible asset for another, with both assets matched to the asset
+/// ids the pools handle.
+pub(super) struct SingleAssetExchange<AssetId> {
+	pub give_asset: Asset,
+	pub give_asset_id: AssetId,
+	pub give_amount: u128,
+	pub want_asset: Asset,
+	pub want_asset_id: AssetId,
+	pub want_amount: u128,
+}
+
+impl<AssetId> SingleAssetExchange<AssetId> {
+	/// Match the exchange of the assets in holding `give` for `want`.
+	pub fn from_holding<Matcher: MatchesFungibles<AssetId, u128>>(
+		give: &AssetsInHolding,
+		want: &Assets,
+	) -> Option<Self> {
+		let mut give_iter = give.fungible_assets_iter();
+		let Some(give_asset) = give_iter.next() else {
+			tracing::trace!(target: LOG_TARGET, ?give, "No fungible asset was in `give`.");
+			return None;
+		};
+		// We only support 1 asset in `give`, and don't allow non-fungible assets.
+		if give_iter.next().is_some() || give.non_fungible_assets_iter().next().is_some() {
+			return None;
+		}
+		// We only support 1 asset in `want`.
+		if want.len() != 1 {
+			return None;
+		}
+		Self::matching::<Matcher>(give_asset, want.get(0)?.clone())
+	}
+
+	/// Match the exchange of `give` for `want`, as quoted.
+	pub fn from_assets<Matcher: MatchesFungibles<AssetId, u128>>(
+		give: &Assets,
+		want: &Assets,
+	) -> Option<Self> {
+		// We only support 1 asset in `give` or `want`.
+		if give.len() != 1 || want.len() != 1 {
+			return None;
+		}
+		Self::matching::<Matcher>(give.get(0)?.clone(), want.get(0)?.clone())
+	}
+
+	fn matching<Matcher: MatchesFungibles<AssetId, u128>>(
+		give_asset: Asset,
+		want_asset: Asset,
+	) -> Option<Self> {
+		let (give_asset_id, give_amount) = Matcher::matches_fungibles(&give_asset)
+			.map_err(|error| {
+				tracing::trace!(
+					target: LOG_TARGET,
+					?give_asset,
+					?error,
+					"Could not map XCM asset give to FRAME asset.",
+				);
+			})
+			.ok()?;
+		let (want_asset_id, want_amount) = Matcher::matches_fungibles(&want_asset)
+			.map_err(|error| {
+				tracing::trace!(
+					target: LOG_TARGET,
+					?want_asset,
+					?error,
+					"Could not map XCM asset want to FRAME asset.",
+				);
+			})
+			.ok()?;
+		Some(Self {
+			give_asset,
+			give_asset_id,
+			give_amount,
+			want_asset,
+			want_asset_id,
+			want_amount,
+		})
+	}
+}
+
+/// Swap the given asset along `path`, returning the wanted asset and the leftover of the given
+/// one, if any.
+///
+/// If `maximal`, all of the given asset is swapped for as much as possible of the wanted one, with
+/// a minimum of the wanted amount. Otherwise, as little as possible of the given asset is swapped
+/// for exactly the wanted amount.
+pub(super) fn swap<AssetConversion, Fungibles, AccountId>(
+	exchange: SingleAssetExchange<Fungibles::AssetId>,
+	path: Vec<Fungibles::AssetId>,
+	maximal: bool,
+) -> Option<AssetsInHolding>
+where
+	AssetConversion: SwapCredit<
+		AccountId,
+		Balance = u128,
+		AssetKind = Fungibles::AssetId,
+		Credit = fungibles::Credit<AccountId, Fungibles>,
+	>,
+	Fungibles: fungibles::Balanced<AccountId, Balance = u128>,
+{
+	let SingleAssetExchange {
+		give_asset, give_asset_id, give_amount, want_asset, want_amount, ..
+	} = exchange;
+
+	// We have to do this to convert the XCM assets into credit the pool can use.
+	let credit_in = Fungibles::issue(give_asset_id, give_amount);
+
+	// Do the swap.
+	let (credit_out, maybe_credit_change) = if maximal {
+		// If `maximal`, then we swap exactly `credit_in` to get as much of `want_asset_id` as
+		// we can, with a minimum of `want_amount`.
+		let credit_out = <AssetConversion as SwapCredit<_>>::swap_exact_tokens_for_tokens(
+			path,
+			credit_in,
+			Some(want_amount),
+		)
+		.map_err(|(credit_in, error)| {
+			tracing::debug!(target: LOG_TARGET, ?error, "Could not perform the swap");
+			drop(credit_in);
+		})
+		.ok()?;
+
+		// We don't have leftover assets if exchange was maximal.
+		(credit_out, None)
+	} else {
+		// If `minimal`, then we swap as little of `credit_in` as we can to get exactly
+		// `want_amount` of `want_asset_id`.
+		let (credit_out, credit_change) =
+			<AssetConversion as SwapCredit<_>>::swap_tokens_for_exact_tokens(
+				path,
+				credit_in,
+				want_amount,
+			)
+			.map_err(|(credit_in, error)| {
+				tracing::debug!(target: LOG_TARGET, ?error, "Could not perform the swap");
+				drop(credit_in);
+			})
+			.ok()?;
+
+		(credit_out, if credit_change.peek() > 0 { Some(credit_change) } else { None })
+	};
+
+	// We create an `AssetsInHolding` instance by putting in the resulting asset
+	// of the exchange.
+	let resulting_asset: Asset = (want_asset.id, credit_out.peek()).into();
+	let mut result: AssetsInHolding = resulting_asset.into();
+
+	// If we have some leftover assets from the exchange, also put them in the result.
+	if let Some(credit_change) = maybe_credit_change {
+		let leftover_asset: Asset = (give_asset.id, credit_change.peek()).into();
+		result.subsume(leftover_asset);
+	}
+
+	Some(result)
+}
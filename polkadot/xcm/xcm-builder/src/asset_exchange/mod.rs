@@ -18,5 +18,13 @@
 //!
 //! E.g. types that implement the [`xcm_executor::traits::AssetExchange`] trait.
 
+mod multi_hop_adapter;
+pub use multi_hop_adapter::MultiHopExchangeAdapter;
+
 mod single_asset_adapter;
 pub use single_asset_adapter::SingleAssetExchangeAdapter;
+
+mod common;
+
+#[cfg(test)]
+mod mock;
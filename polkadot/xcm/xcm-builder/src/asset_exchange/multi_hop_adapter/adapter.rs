@@ -0,0 +1,236 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Multi-hop asset exchange adapter.
+
+extern crate alloc;
+use alloc::{vec, vec::Vec};
+use core::marker::PhantomData;
+use frame_support::{
+	ensure,
+	traits::{tokens::fungibles, Get},
+};
+use pallet_asset_conversion::{QuotePrice, SwapCredit};
+use xcm::prelude::*;
+use xcm_executor::{
+	traits::{AssetExchange, MatchesFungibles},
+	AssetsInHolding,
+};
+
+use crate::asset_exchange::common::{swap, SingleAssetExchange};
+
+/// An adapter from [`pallet_asset_conversion::SwapCredit`] and
+/// [`pallet_asset_conversion::QuotePrice`] to [`xcm_executor::traits::AssetExchange`] which
+/// swaps through intermediate pools when there's no pool between the two assets, or when an
+/// indirect route gives a better price.
+///
+/// Paths are made of `give`, up to `MaxHops - 1` distinct assets out of `Intermediates` and
+/// `want`, and are bounded by [`SwapCredit::max_path_len`] as well. Every such path is quoted
+/// with [`QuotePrice`], including pool fees, and the best one is used:
+/// - if `maximal`, the path yielding the most of `want` for all of `give`, which must be at least
+///   the amount in `want`;
+/// - otherwise, the path requiring the least of `give` to obtain exactly the amount in `want`.
+///
+/// Among equally good paths, the shortest one is preferred. Quoting `n` intermediates costs up to
+/// `n^(MaxHops - 1)` quotes, so both should be kept small, e.g. the native token and a couple of
+/// stablecoins with deep liquidity and at most 2 or 3 hops.
+///
+/// Like [`crate::SingleAssetExchangeAdapter`], this adapter only supports exactly one fungible
+/// asset in both `give` and `want`.
+pub struct MultiHopExchangeAdapter<
+	AssetConversion,
+	Fungibles,
+	Matcher,
+	Intermediates,
+	MaxHops,
+	AccountId,
+>(PhantomData<(AssetConversion, Fungibles, Matcher, Intermediates, MaxHops, AccountId)>);
+
+impl<AssetConversion, Fungibles, Matcher, Intermediates, MaxHops, AccountId>
+	MultiHopExchangeAdapter<AssetConversion, Fungibles, Matcher, Intermediates, MaxHops, AccountId>
+where
+	AssetConversion: SwapCredit<AccountId, Balance = u128, AssetKind = Fungibles::AssetId>
+		+ QuotePrice<Balance = u128, AssetKind = Fungibles::AssetId>,
+	Fungibles: fungibles::Inspect<AccountId, Balance = u128>,
+	Intermediates: Get<Vec<Fungibles::AssetId>>,
+	MaxHops: Get<u32>,
+{
+	/// The best path from `give` to `want`, with the amount of `want` obtained for `amount` of
+	/// `give` if `maximal`, or the amount of `give` needed to obtain `amount` of `want` otherwise.
+	pub fn best_path(
+		give: Fungibles::AssetId,
+		want: Fungibles::AssetId,
+		amount: u128,
+		maximal: bool,
+	) -> Option<(Vec<Fungibles::AssetId>, u128)> {
+		// The number of pools along a path is one less than the number of assets.
+		let max_path_len = <AssetConversion as SwapCredit<AccountId>>::max_path_len();
+		let max_hops = MaxHops::get().min(max_path_len.saturating_sub(1)) as usize;
+		if max_hops == 0 || give == want {
+			return None;
+		}
+		let intermediates: Vec<_> = Intermediates::get()
+			.into_iter()
+			.filter(|asset| *asset != give && *asset != want)
+			.collect();
+
+		let mut best: Option<(Vec<Fungibles::AssetId>, u128)> = None;
+		let mut path = vec![give];
+		// Paths are visited by increasing length, so that shorter paths win ties.
+		for intermediate_count in 0..max_hops {
+			Self::visit_paths(
+				&mut path,
+				&intermediates,
+				intermediate_count,
+				&mut |path: &[_]| {
+					let mut path = path.to_vec();
+					path.push(want.clone());
+					let Some(quoted) = Self::quote_path(&path, amount, maximal) else { return };
+					let better = match &best {
+						None => true,
+						Some((_, best)) if maximal => quoted > *best,
+						Some((_, best)) => quoted < *best,
+					};
+					if better {
+						best = Some((path, quoted));
+					}
+				},
+			);
+		}
+		best
+	}
+
+	/// Call `f` with every extension of `path` by `remaining` distinct `intermediates`.
+	fn visit_paths(
+		path: &mut Vec<Fungibles::AssetId>,
+		intermediates: &[Fungibles::AssetId],
+		remaining: usize,
+		f: &mut impl FnMut(&[Fungibles::AssetId]),
+	) {
+		if remaining == 0 {
+			return f(path);
+		}
+		for intermediate in intermediates {
+			if path.contains(intermediate) {
+				continue;
+			}
+			path.push(intermediate.clone());
+			Self::visit_paths(path, intermediates, remaining - 1, f);
+			path.pop();
+		}
+	}
+
+	/// Quote swapping along `path`, hop by hop, forwards if `maximal` and backwards otherwise.
+	fn quote_path(path: &[Fungibles::AssetId], amount: u128, maximal: bool) -> Option<u128> {
+		if maximal {
+			path.windows(2).try_fold(amount, |amount, pair| {
+				AssetConversion::quote_price_exact_tokens_for_tokens(
+					pair[0].clone(),
+					pair[1].clone(),
+					amount,
+					true, // Include fee.
+				)
+			})
+		} else {
+			path.windows(2).rev().try_fold(amount, |amount, pair| {
+				AssetConversion::quote_price_tokens_for_exact_tokens(
+					pair[0].clone(),
+					pair[1].clone(),
+					amount,
+					true, // Include fee.
+				)
+			})
+		}
+	}
+}
+
+impl<AssetConversion, Fungibles, Matcher, Intermediates, MaxHops, AccountId> AssetExchange
+	for MultiHopExchangeAdapter<AssetConversion, Fungibles, Matcher, Intermediates, MaxHops, AccountId>
+where
+	AssetConversion: SwapCredit<
+			AccountId,
+			Balance = u128,
+			AssetKind = Fungibles::AssetId,
+			Credit = fungibles::Credit<AccountId, Fungibles>,
+		> + QuotePrice<Balance = u128, AssetKind = Fungibles::AssetId>,
+	Fungibles: fungibles::Balanced<AccountId, Balance = u128>,
+	Matcher: MatchesFungibles<Fungibles::AssetId, Fungibles::Balance>,
+	Intermediates: Get<Vec<Fungibles::AssetId>>,
+	MaxHops: Get<u32>,
+{
+	fn exchange_asset(
+		_: Option<&Location>,
+		give: AssetsInHolding,
+		want: &Assets,
+		maximal: bool,
+	) -> Result<AssetsInHolding, AssetsInHolding> {
+		let Some(exchange) = SingleAssetExchange::from_holding::<Matcher>(&give, want) else {
+			return Err(give)
+		};
+
+		let amount = if maximal { exchange.give_amount } else { exchange.want_amount };
+		let Some((path, quoted)) = Self::best_path(
+			exchange.give_asset_id.clone(),
+			exchange.want_asset_id.clone(),
+			amount,
+			maximal,
+		) else {
+			tracing::debug!(
+				target: "xcm::MultiHopExchangeAdapter::exchange_asset",
+				give_asset = ?exchange.give_asset,
+				want_asset = ?exchange.want_asset,
+				"No path between the assets",
+			);
+			return Err(give)
+		};
+		// Fail early instead of issuing credit for a swap which can't succeed.
+		let sufficient =
+			if maximal { quoted >= exchange.want_amount } else { quoted <= exchange.give_amount };
+		ensure!(sufficient, give);
+		tracing::trace!(
+			target: "xcm::MultiHopExchangeAdapter::exchange_asset",
+			?path, ?quoted, "Swapping along the best path",
+		);
+
+		swap::<AssetConversion, Fungibles, AccountId>(exchange, path, maximal).ok_or(give)
+	}
+
+	fn quote_exchange_price(give: &Assets, want: &Assets, maximal: bool) -> Option<Assets> {
+		let SingleAssetExchange {
+			give_asset,
+			give_asset_id,
+			give_amount,
+			want_asset,
+			want_asset_id,
+			want_amount,
+		} = SingleAssetExchange::from_assets::<Matcher>(give, want)?;
+		// We quote the price along the best path. When not `maximal`, the amount in `give` is
+		// ignored, the executor quotes delivery fees with an amount of zero.
+		if maximal {
+			// The amount of `want` resulting from swapping `give`.
+			let (_, resulting_want) =
+				Self::best_path(give_asset_id, want_asset_id, give_amount, true)?;
+
+			Some((want_asset.id, resulting_want).into())
+		} else {
+			// The `give` amount required to obtain `want`.
+			let (_, necessary_give) =
+				Self::best_path(give_asset_id, want_asset_id, want_amount, false)?;
+
+			Some((give_asset.id, necessary_give).into())
+		}
+	}
+}
@@ -0,0 +1,73 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Mock to test [`MultiHopExchangeAdapter`].
+
+pub use crate::asset_exchange::mock::*;
+use crate::MultiHopExchangeAdapter;
+use frame_support::{parameter_types, traits::fungible::NativeOrWithId};
+use sp_core::ConstU32;
+use xcm::prelude::*;
+
+parameter_types! {
+	pub Intermediates: Vec<NativeOrWithId<u32>> =
+		vec![NativeOrWithId::Native, NativeOrWithId::WithId(3)];
+}
+
+/// Exchanger swapping through at most 3 pools.
+pub type MultiHopExchanger = MultiHopExchangeAdapter<
+	AssetConversion,
+	NativeAndAssets,
+	Matcher,
+	Intermediates,
+	ConstU32<3>,
+	AccountId,
+>;
+
+/// Exchanger limited to direct swaps.
+pub type DirectExchanger = MultiHopExchangeAdapter<
+	AssetConversion,
+	NativeAndAssets,
+	Matcher,
+	Intermediates,
+	ConstU32<1>,
+	AccountId,
+>;
+
+/// Location of the trust backed asset with the given id.
+pub fn asset(id: u32) -> Location {
+	Location::new(0, [PalletInstance(TrustBackedAssetsPalletIndex::get()), GeneralIndex(id.into())])
+}
+
+/// Assets 1 and 2 both have a pool with the native token and with asset 3, which is more
+/// liquid. There's no pool between assets 1 and 2, nor between asset 3 and the native token.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let (native, asset1, asset2, asset3) = (
+		NativeOrWithId::Native,
+		NativeOrWithId::WithId(1),
+		NativeOrWithId::WithId(2),
+		NativeOrWithId::WithId(3),
+	);
+	new_test_ext_with_pools(
+		3,
+		vec![
+			(native.clone(), asset1.clone(), 50_000_000, 100_000_000),
+			(native, asset2.clone(), 50_000_000, 100_000_000),
+			(asset1, asset3.clone(), 500_000_000, 500_000_000),
+			(asset2, asset3, 400_000_000, 400_000_000),
+		],
+	)
+}
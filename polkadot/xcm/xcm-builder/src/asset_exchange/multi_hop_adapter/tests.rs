@@ -0,0 +1,181 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests for the [`MultiHopExchangeAdapter`] type.
+
+use super::mock::*;
+use frame_support::traits::fungible::NativeOrWithId::{self, Native, WithId};
+use xcm::prelude::*;
+use xcm_executor::{traits::AssetExchange, AssetsInHolding};
+
+/// The amount of the last asset of `path` obtained by swapping `amount` of the first one.
+fn quote_out(path: &[NativeOrWithId<u32>], amount: u128) -> u128 {
+	path.windows(2).fold(amount, |amount, pair| {
+		AssetConversion::quote_price_exact_tokens_for_tokens(
+			pair[0].clone(),
+			pair[1].clone(),
+			amount,
+			true,
+		)
+		.unwrap()
+	})
+}
+
+#[test]
+fn best_path_prefers_the_most_liquid_route() {
+	new_test_ext().execute_with(|| {
+		let via_native = quote_out(&[WithId(1), Native, WithId(2)], 10_000_000);
+		let via_asset_3 = quote_out(&[WithId(1), WithId(3), WithId(2)], 10_000_000);
+		assert!(via_asset_3 > via_native);
+
+		assert_eq!(
+			MultiHopExchanger::best_path(WithId(1), WithId(2), 10_000_000, true),
+			Some((vec![WithId(1), WithId(3), WithId(2)], via_asset_3))
+		);
+		// A direct pool is used when it's the only route.
+		assert_eq!(
+			MultiHopExchanger::best_path(Native, WithId(1), 10_000_000, true),
+			Some((vec![Native, WithId(1)], quote_out(&[Native, WithId(1)], 10_000_000)))
+		);
+		// Without intermediate hops, there's no route between assets 1 and 2.
+		assert_eq!(DirectExchanger::best_path(WithId(1), WithId(2), 10_000_000, true), None);
+	});
+}
+
+#[test]
+fn maximal_exchange_through_intermediate_pool() {
+	new_test_ext().execute_with(|| {
+		let expected = quote_out(&[WithId(1), WithId(3), WithId(2)], 10_000_000);
+		let assets = MultiHopExchanger::exchange_asset(
+			None,
+			vec![(asset(1), 10_000_000).into()].into(),
+			&vec![(asset(2), 9_000_000).into()].into(),
+			true, // Maximal
+		)
+		.unwrap();
+		assert_eq!(fungibles(&assets), vec![(asset(2), expected)]);
+	});
+}
+
+#[test]
+fn minimal_exchange_through_intermediate_pool() {
+	new_test_ext().execute_with(|| {
+		let (path, necessary) =
+			MultiHopExchanger::best_path(WithId(1), WithId(2), 2_000_000, false).unwrap();
+		assert_eq!(path, vec![WithId(1), WithId(3), WithId(2)]);
+
+		let assets = MultiHopExchanger::exchange_asset(
+			None,
+			vec![(asset(1), 10_000_000).into()].into(),
+			&vec![(asset(2), 2_000_000).into()].into(),
+			false, // Minimal
+		)
+		.unwrap();
+		let mut expected = vec![(asset(1), 10_000_000 - necessary), (asset(2), 2_000_000)];
+		expected.sort();
+		assert_eq!(fungibles(&assets), expected);
+	});
+}
+
+#[test]
+fn quotes_use_the_best_path() {
+	new_test_ext().execute_with(|| {
+		let (_, resulting) =
+			MultiHopExchanger::best_path(WithId(1), WithId(2), 10_000_000, true).unwrap();
+		assert_eq!(
+			MultiHopExchanger::quote_exchange_price(
+				&(asset(1), 10_000_000).into(),
+				&(asset(2), 1).into(),
+				true,
+			),
+			Some((asset(2), resulting).into())
+		);
+
+		// The executor quotes delivery fees without an amount to give.
+		let (_, necessary) =
+			MultiHopExchanger::best_path(WithId(1), WithId(2), 2_000_000, false).unwrap();
+		assert_eq!(
+			MultiHopExchanger::quote_exchange_price(
+				&(asset(1), 0).into(),
+				&(asset(2), 2_000_000).into(),
+				false,
+			),
+			Some((asset(1), necessary).into())
+		);
+		assert_eq!(
+			DirectExchanger::quote_exchange_price(
+				&(asset(1), 0).into(),
+				&(asset(2), 2_000_000).into(),
+				false,
+			),
+			None
+		);
+	});
+}
+
+#[test]
+fn exchange_fails_below_minimum_out() {
+	new_test_ext().execute_with(|| {
+		let give: AssetsInHolding = vec![(asset(1), 10_000_000).into()].into();
+		let result = MultiHopExchanger::exchange_asset(
+			None,
+			give.clone(),
+			&vec![(asset(2), 10_000_000).into()].into(),
+			true, // Maximal
+		);
+		assert_eq!(result.unwrap_err(), give);
+	});
+}
+
+#[test]
+fn exchange_fails_when_give_is_not_enough() {
+	new_test_ext().execute_with(|| {
+		let give: AssetsInHolding = vec![(asset(1), 1_000_000).into()].into();
+		let result = MultiHopExchanger::exchange_asset(
+			None,
+			give.clone(),
+			&vec![(asset(2), 2_000_000).into()].into(),
+			false, // Minimal
+		);
+		assert_eq!(result.unwrap_err(), give);
+	});
+}
+
+#[test]
+fn exchange_fails_without_route() {
+	new_test_ext().execute_with(|| {
+		let give: AssetsInHolding = vec![(asset(1), 10_000_000).into()].into();
+		let result = DirectExchanger::exchange_asset(
+			None,
+			give.clone(),
+			&vec![(asset(2), 1).into()].into(),
+			true, // Maximal
+		);
+		assert_eq!(result.unwrap_err(), give);
+	});
+}
+
+fn fungibles(assets: &AssetsInHolding) -> Vec<(Location, u128)> {
+	let mut fungibles: Vec<_> = assets
+		.fungible_assets_iter()
+		.map(|asset| match asset {
+			Asset { id: AssetId(location), fun: Fungible(amount) } => (location, amount),
+			_ => unreachable!("fungible_assets_iter only returns fungible assets"),
+		})
+		.collect();
+	fungibles.sort();
+	fungibles
+}
@@ -19,7 +19,7 @@
 extern crate alloc;
 use alloc::vec;
 use core::marker::PhantomData;
-use frame_support::{ensure, traits::tokens::fungibles};
+use frame_support::traits::tokens::fungibles;
 use pallet_asset_conversion::{QuotePrice, SwapCredit};
 use xcm::prelude::*;
 use xcm_executor::{
@@ -27,6 +27,8 @@ use xcm_executor::{
 	AssetsInHolding,
 };
 
+use crate::asset_exchange::common::{swap, SingleAssetExchange};
+
 /// An adapter from [`pallet_asset_conversion::SwapCredit`] and
 /// [`pallet_asset_conversion::QuotePrice`] to [`xcm_executor::traits::AssetExchange`].
 ///
@@ -59,129 +61,22 @@ where
 		want: &Assets,
 		maximal: bool,
 	) -> Result<AssetsInHolding, AssetsInHolding> {
-		let mut give_iter = give.fungible_assets_iter();
-		let give_asset = give_iter.next().ok_or_else(|| {
-			tracing::trace!(
-				target: "xcm::SingleAssetExchangeAdapter::exchange_asset",
-				?give, "No fungible asset was in `give`.",
-			);
-			give.clone()
-		})?;
-		ensure!(give_iter.next().is_none(), give.clone()); // We only support 1 asset in `give`.
-		ensure!(give.non_fungible_assets_iter().next().is_none(), give.clone()); // We don't allow non-fungible assets.
-		ensure!(want.len() == 1, give.clone()); // We only support 1 asset in `want`.
-		let want_asset = want.get(0).ok_or_else(|| give.clone())?;
-		let (give_asset_id, give_amount) =
-			Matcher::matches_fungibles(&give_asset).map_err(|error| {
-				tracing::trace!(
-					target: "xcm::SingleAssetExchangeAdapter::exchange_asset",
-					?give_asset,
-					?error,
-					"Could not map XCM asset give to FRAME asset.",
-				);
-				give.clone()
-			})?;
-		let (want_asset_id, want_amount) =
-			Matcher::matches_fungibles(&want_asset).map_err(|error| {
-				tracing::trace!(
-					target: "xcm::SingleAssetExchangeAdapter::exchange_asset",
-					?want_asset,
-					?error,
-					"Could not map XCM asset want to FRAME asset."
-				);
-				give.clone()
-			})?;
-
-		// We have to do this to convert the XCM assets into credit the pool can use.
-		let swap_asset = give_asset_id.clone().into();
-		let credit_in = Fungibles::issue(give_asset_id, give_amount);
-
-		// Do the swap.
-		let (credit_out, maybe_credit_change) = if maximal {
-			// If `maximal`, then we swap exactly `credit_in` to get as much of `want_asset_id` as
-			// we can, with a minimum of `want_amount`.
-			let credit_out = <AssetConversion as SwapCredit<_>>::swap_exact_tokens_for_tokens(
-				vec![swap_asset, want_asset_id],
-				credit_in,
-				Some(want_amount),
-			)
-			.map_err(|(credit_in, error)| {
-				tracing::debug!(
-					target: "xcm::SingleAssetExchangeAdapter::exchange_asset",
-					?error,
-					"Could not perform the swap"
-				);
-				drop(credit_in);
-				give.clone()
-			})?;
-
-			// We don't have leftover assets if exchange was maximal.
-			(credit_out, None)
-		} else {
-			// If `minimal`, then we swap as little of `credit_in` as we can to get exactly
-			// `want_amount` of `want_asset_id`.
-			let (credit_out, credit_change) =
-				<AssetConversion as SwapCredit<_>>::swap_tokens_for_exact_tokens(
-					vec![swap_asset, want_asset_id],
-					credit_in,
-					want_amount,
-				)
-				.map_err(|(credit_in, error)| {
-					tracing::debug!(
-						target: "xcm::SingleAssetExchangeAdapter::exchange_asset",
-						?error,
-						"Could not perform the swap",
-					);
-					drop(credit_in);
-					give.clone()
-				})?;
-
-			(credit_out, if credit_change.peek() > 0 { Some(credit_change) } else { None })
+		let Some(exchange) = SingleAssetExchange::from_holding::<Matcher>(&give, want) else {
+			return Err(give)
 		};
-
-		// We create an `AssetsInHolding` instance by putting in the resulting asset
-		// of the exchange.
-		let resulting_asset: Asset = (want_asset.id.clone(), credit_out.peek()).into();
-		let mut result: AssetsInHolding = resulting_asset.into();
-
-		// If we have some leftover assets from the exchange, also put them in the result.
-		if let Some(credit_change) = maybe_credit_change {
-			let leftover_asset: Asset = (give_asset.id.clone(), credit_change.peek()).into();
-			result.subsume(leftover_asset);
-		}
-
-		Ok(result.into())
+		let path = vec![exchange.give_asset_id.clone(), exchange.want_asset_id.clone()];
+		swap::<AssetConversion, Fungibles, AccountId>(exchange, path, maximal).ok_or(give)
 	}
 
 	fn quote_exchange_price(give: &Assets, want: &Assets, maximal: bool) -> Option<Assets> {
-		if give.len() != 1 || want.len() != 1 {
-			return None;
-		} // We only support 1 asset in `give` or `want`.
-		let give_asset = give.get(0)?;
-		let want_asset = want.get(0)?;
-		// We first match both XCM assets to the asset ID types `AssetConversion` can handle.
-		let (give_asset_id, give_amount) = Matcher::matches_fungibles(give_asset)
-			.map_err(|error| {
-				tracing::trace!(
-					target: "xcm::SingleAssetExchangeAdapter::quote_exchange_price",
-					?give_asset,
-					?error,
-					"Could not map XCM asset to FRAME asset."
-				);
-				()
-			})
-			.ok()?;
-		let (want_asset_id, want_amount) = Matcher::matches_fungibles(want_asset)
-			.map_err(|error| {
-				tracing::trace!(
-					target: "xcm::SingleAssetExchangeAdapter::quote_exchange_price",
-					?want_asset,
-					?error,
-					"Could not map XCM asset to FRAME asset"
-				);
-				()
-			})
-			.ok()?;
+		let SingleAssetExchange {
+			give_asset,
+			give_asset_id,
+			give_amount,
+			want_asset,
+			want_asset_id,
+			want_amount,
+		} = SingleAssetExchange::from_assets::<Matcher>(give, want)?;
 		// We quote the price.
 		if maximal {
 			// The amount of `want` resulting from swapping `give`.
@@ -193,7 +88,7 @@ where
 					true, // Include fee.
 				)?;
 
-			Some((want_asset.id.clone(), resulting_want).into())
+			Some((want_asset.id, resulting_want).into())
 		} else {
 			// The `give` amount required to obtain `want`.
 			let necessary_give =
@@ -204,7 +99,7 @@ where
 					true, // Include fee.
 				)?;
 
-			Some((give_asset.id.clone(), necessary_give).into())
+			Some((give_asset.id, necessary_give).into())
 		}
 	}
 }
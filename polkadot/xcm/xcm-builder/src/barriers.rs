@@ -24,7 +24,9 @@ use frame_support::{
 };
 use polkadot_parachain_primitives::primitives::IsSystem;
 use xcm::prelude::*;
-use xcm_executor::traits::{CheckSuspension, DenyExecution, OnResponse, Properties, ShouldExecute};
+use xcm_executor::traits::{
+	CheckRateLimit, CheckSuspension, DenyExecution, OnResponse, Properties, ShouldExecute,
+};
 
 /// Execution barrier that just takes `max_weight` from `properties.weight_credit`.
 ///
@@ -279,6 +281,38 @@ where
 	}
 }
 
+/// Barrier condition that throttles the XCMs allowed by `Inner` according to the per-origin
+/// budgets kept by `Limits`.
+///
+/// Messages over the remaining budget are rejected with `ProcessMessageError::Yield`, so that
+/// queues which support it can retry them once the budget of the origin has been replenished.
+/// Messages which exceed the whole budget are rejected with the permanent error of `Limits`.
+pub struct RateLimitedBarrier<Inner, Limits>(PhantomData<(Inner, Limits)>);
+impl<Inner, Limits> ShouldExecute for RateLimitedBarrier<Inner, Limits>
+where
+	Inner: ShouldExecute,
+	Limits: CheckRateLimit,
+{
+	fn should_execute<Call>(
+		origin: &Location,
+		instructions: &mut [Instruction<Call>],
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		Inner::should_execute(origin, instructions, max_weight, properties)?;
+
+		Limits::try_consume(origin, instructions, max_weight, properties).inspect_err(|error| {
+			tracing::trace!(
+				target: "xcm::barriers",
+				?origin,
+				?max_weight,
+				?error,
+				"RateLimitedBarrier: origin exceeded its execution budget",
+			);
+		})
+	}
+}
+
 /// Allows execution from any origin that is contained in `T` (i.e. `T::Contains(origin)`).
 ///
 /// Use only for executions from completely trusted origins, from which no permissionless messages
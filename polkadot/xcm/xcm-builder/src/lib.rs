@@ -37,15 +37,15 @@ pub use asset_conversion::{
 };
 
 mod asset_exchange;
-pub use asset_exchange::SingleAssetExchangeAdapter;
+pub use asset_exchange::{MultiHopExchangeAdapter, SingleAssetExchangeAdapter};
 
 mod barriers;
 pub use barriers::{
 	AllowExplicitUnpaidExecutionFrom, AllowHrmpNotificationsFromRelayChain,
 	AllowKnownQueryResponses, AllowSubscriptionsFrom, AllowTopLevelPaidExecutionFrom,
 	AllowUnpaidExecutionFrom, DenyRecursively, DenyReserveTransferToRelayChain, DenyThenTry,
-	IsChildSystemParachain, IsParentsOnly, IsSiblingSystemParachain, RespectSuspension,
-	TakeWeightCredit, TrailingSetTopicAsId, WithComputedOrigin,
+	IsChildSystemParachain, IsParentsOnly, IsSiblingSystemParachain, RateLimitedBarrier,
+	RespectSuspension, TakeWeightCredit, TrailingSetTopicAsId, WithComputedOrigin,
 };
 
 mod controller;
@@ -124,7 +124,7 @@ mod pay;
 pub use pay::{FixedLocation, LocatableAssetId, PayAccountId32OnChainOverXcm, PayOverXcm};
 
 mod process_xcm_message;
-pub use process_xcm_message::ProcessXcmMessage;
+pub use process_xcm_message::{CheckConservation, ProcessXcmMessage};
 
 mod routing;
 pub use routing::{
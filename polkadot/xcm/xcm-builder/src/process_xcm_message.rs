@@ -20,11 +20,12 @@ use codec::{Decode, DecodeLimit, FullCodec, MaxEncodedLen};
 use core::{fmt::Debug, marker::PhantomData};
 use frame_support::{
 	dispatch::GetDispatchInfo,
-	traits::{ProcessMessage, ProcessMessageError},
+	traits::{Get, ProcessMessage, ProcessMessageError},
 };
 use scale_info::TypeInfo;
 use sp_weights::{Weight, WeightMeter};
 use xcm::{prelude::*, MAX_XCM_DECODE_DEPTH};
+use xcm_executor::conservation::{check_conservation, CirculatingSupply};
 
 const LOG_TARGET: &str = "xcm::process-message";
 
@@ -128,6 +129,34 @@ impl<
 	}
 }
 
+/// A message processor checking the conservation of the assets moved by the messages processed
+/// by `Inner`.
+///
+/// Meant to be used as the `MessageProcessor` of `try-runtime` builds, so that `execute-block`
+/// and `follow-chain` report the instructions minting or burning assets by mistake. Violations are
+/// logged by the executor and raised as defensive failures, the outcome of `Inner` is unchanged.
+pub struct CheckConservation<Inner, Circulating>(PhantomData<(Inner, Circulating)>);
+impl<Inner: ProcessMessage, Circulating: Get<CirculatingSupply>> ProcessMessage
+	for CheckConservation<Inner, Circulating>
+{
+	type Origin = Inner::Origin;
+
+	fn process_message(
+		message: &[u8],
+		origin: Self::Origin,
+		meter: &mut WeightMeter,
+		id: &mut XcmHash,
+	) -> Result<bool, ProcessMessageError> {
+		let (result, violations) = check_conservation(Circulating::get(), || {
+			Inner::process_message(message, origin, meter, id)
+		});
+		if !violations.is_empty() {
+			frame_support::defensive!("Assets not conserved by XCM message", violations);
+		}
+		result
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -163,6 +192,31 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn check_conservation_keeps_the_outcome() {
+		fn not_checked(_: &AssetId) -> Option<u128> {
+			None
+		}
+		frame_support::parameter_types! {
+			pub const NotChecked: CirculatingSupply = not_checked;
+		}
+		type Checked = CheckConservation<Processor, NotChecked>;
+
+		sp_io::TestExternalities::default().execute_with(|| {
+			for (msg, outcome) in [(v5_xcm(true), true), (v5_xcm(false), false)] {
+				let raw = msg.encode();
+				assert_eq!(
+					Checked::process_message(&raw, ORIGIN, &mut WeightMeter::new(), &mut [0; 32]),
+					Ok(outcome),
+				);
+			}
+			assert_err!(
+				Checked::process_message(&[], ORIGIN, &mut WeightMeter::new(), &mut [0; 32]),
+				Corrupt
+			);
+		});
+	}
+
 	#[test]
 	fn process_message_corrupted_fails() {
 		let msgs: &[&[u8]] = &[&[], &[55, 66], &[123, 222, 233]];
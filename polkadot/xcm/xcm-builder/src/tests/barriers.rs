@@ -741,6 +741,38 @@ fn suspension_should_work() {
 	assert_eq!(r, Ok(()));
 }
 
+#[test]
+fn rate_limited_barrier_should_work() {
+	TestRateLimiter::set_budget(Weight::from_parts(25, 25));
+	AllowUnpaidFrom::set(vec![Parent.into()]);
+
+	type Barrier =
+		RateLimitedBarrier<AllowUnpaidExecutionFrom<IsInVec<AllowUnpaidFrom>>, TestRateLimiter>;
+	let should_execute = |origin: Location| {
+		let mut message = Xcm::<()>(vec![TransferAsset {
+			assets: (Parent, 100).into(),
+			beneficiary: Here.into(),
+		}]);
+		Barrier::should_execute(
+			&origin,
+			message.inner_mut(),
+			Weight::from_parts(10, 10),
+			&mut props(Weight::zero()),
+		)
+	};
+
+	assert_eq!(should_execute(Parent.into()), Ok(()));
+	assert_eq!(should_execute(Parent.into()), Ok(()));
+	// Budget exhausted, the message should be retried later.
+	assert_eq!(should_execute(Parent.into()), Err(ProcessMessageError::Yield));
+	assert_eq!(TestRateLimiter::budget(), Weight::from_parts(5, 5));
+
+	// Messages rejected by the inner barrier are not accounted.
+	TestRateLimiter::set_budget(Weight::from_parts(25, 25));
+	assert_eq!(should_execute(Parachain(1).into()), Err(ProcessMessageError::Unsupported));
+	assert_eq!(TestRateLimiter::budget(), Weight::from_parts(25, 25));
+}
+
 #[test]
 fn allow_subscriptions_from_should_work() {
 	// allow only parent
@@ -33,7 +33,7 @@ pub use core::{
 	fmt::Debug,
 	ops::ControlFlow,
 };
-use frame_support::traits::{ContainsPair, Everything};
+use frame_support::traits::{ContainsPair, Everything, ProcessMessageError};
 pub use frame_support::{
 	dispatch::{DispatchInfo, DispatchResultWithPostInfo, GetDispatchInfo, PostDispatchInfo},
 	ensure, parameter_types,
@@ -43,8 +43,8 @@ pub use frame_support::{
 pub use xcm::latest::{prelude::*, QueryId, Weight};
 pub use xcm_executor::{
 	traits::{
-		AssetExchange, AssetLock, CheckSuspension, ConvertOrigin, DenyExecution, Enact, ExportXcm,
-		FeeManager, FeeReason, LockError, OnResponse, Properties, QueryHandler,
+		AssetExchange, AssetLock, CheckRateLimit, CheckSuspension, ConvertOrigin, DenyExecution,
+		Enact, ExportXcm, FeeManager, FeeReason, LockError, OnResponse, Properties, QueryHandler,
 		QueryResponseStatus, TransactAsset,
 	},
 	AssetsInHolding, Config,
@@ -137,6 +137,7 @@ thread_local! {
 	)>> = RefCell::new(None);
 	pub static SEND_PRICE: RefCell<Assets> = RefCell::new(Assets::new());
 	pub static SUSPENDED: Cell<bool> = Cell::new(false);
+	pub static RATE_LIMIT_BUDGET: Cell<Weight> = Cell::new(Weight::MAX);
 }
 pub fn sent_xcm() -> Vec<(Location, opaque::Xcm, XcmHash)> {
 	SENT_XCM.with(|q| (*q.borrow()).clone())
@@ -507,6 +508,34 @@ impl TestSuspender {
 	}
 }
 
+pub struct TestRateLimiter;
+impl CheckRateLimit for TestRateLimiter {
+	fn try_consume<Call>(
+		_origin: &Location,
+		_instructions: &mut [Instruction<Call>],
+		max_weight: Weight,
+		_properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		RATE_LIMIT_BUDGET.with(|b| match b.get().checked_sub(&max_weight) {
+			Some(remaining) => {
+				b.set(remaining);
+				Ok(())
+			},
+			None => Err(ProcessMessageError::Yield),
+		})
+	}
+}
+
+impl TestRateLimiter {
+	pub fn set_budget(budget: Weight) {
+		RATE_LIMIT_BUDGET.with(|b| b.set(budget));
+	}
+
+	pub fn budget() -> Weight {
+		RATE_LIMIT_BUDGET.with(|b| b.get())
+	}
+}
+
 pub type TestBarrier = (
 	TakeWeightCredit,
 	AllowKnownQueryResponses<TestResponseHandler>,
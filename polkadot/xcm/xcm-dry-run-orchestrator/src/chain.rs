@@ -0,0 +1,316 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A chain loaded from a state snapshot, and the runtime APIs the orchestrator calls on it.
+
+use crate::{EmittedEvent, Error, EventDecoder};
+use codec::{Decode, Encode, Input};
+use frame_metadata::RuntimeMetadataPrefixed;
+use frame_support::dispatch::DispatchResultWithPostInfo;
+use remote_externalities::{Builder, Mode, OfflineConfig, SnapshotConfig};
+use sc_executor::WasmExecutor;
+use sp_api::RuntimeApiInfo;
+use sp_core::{
+	storage::well_known_keys,
+	traits::{CallContext, ReadRuntimeVersionExt},
+};
+use sp_externalities::Extensions;
+use sp_runtime::{generic, traits::BlakeTwo256, OpaqueExtrinsic};
+use sp_state_machine::{
+	backend::BackendRuntimeCode, InMemoryBackend, OverlayedChanges, StateMachine,
+};
+use sp_version::RuntimeVersion;
+use std::path::PathBuf;
+use xcm::prelude::*;
+use xcm_runtime_apis::{
+	dry_run::{
+		CallDryRunEffects, DryRunApi, Error as DryRunError, InstructionTrace, XcmDryRunEffects,
+	},
+	fees::Error as FeesError,
+};
+
+/// The block type of the loaded chains. Only its header matters to load the snapshot.
+type Block = generic::Block<generic::Header<u32, BlakeTwo256>, OpaqueExtrinsic>;
+
+/// Host functions are provided by the node; the ones a runtime needs for block production only
+/// (e.g. the proof size extension of parachains) are allowed to be missing.
+type Executor = WasmExecutor<sp_io::SubstrateHostFunctions>;
+
+/// The first version of `DryRunApi` taking the version of the returned XCMs.
+const DRY_RUN_API_MIN_VERSION: u32 = 2;
+
+/// The version of `DryRunApi` returning execution traces.
+const DRY_RUN_API_TRACE_VERSION: u32 = 3;
+
+/// Where to load a chain from.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ChainConfig {
+	/// Name of the chain, used to refer to it and in reports.
+	pub name: String,
+	/// Location of the chain in the global consensus, e.g.
+	/// `{ "X2": [{ "GlobalConsensus": "Polkadot" }, { "Parachain": 1000 }] }`.
+	///
+	/// Forwarded messages are routed to the chain at the location they are sent to.
+	pub universal_location: InteriorLocation,
+	/// State snapshot of the chain, as created by `remote-externalities`.
+	pub snapshot: PathBuf,
+	/// Runtime to use instead of the one in the snapshot.
+	#[serde(default)]
+	pub wasm: Option<PathBuf>,
+}
+
+/// Effects of dry-running an extrinsic.
+pub type CallEffects = CallDryRunEffects<EmittedEvent>;
+
+/// Effects of dry-running an XCM program.
+pub type XcmEffects = XcmDryRunEffects<EmittedEvent>;
+
+/// A chain on which runtime APIs can be called.
+///
+/// Every call is executed on the state of the snapshot, changes made by previous calls are
+/// discarded.
+pub struct Chain {
+	config: ChainConfig,
+	backend: InMemoryBackend<BlakeTwo256>,
+	executor: Executor,
+	events: EventDecoder,
+	dry_run_api_version: u32,
+}
+
+impl Chain {
+	/// Load the chain from its snapshot.
+	pub async fn load(config: ChainConfig) -> Result<Self, Error> {
+		let mut ext = Builder::<Block>::new()
+			.mode(Mode::Offline(OfflineConfig {
+				state_snapshot: SnapshotConfig::new(&config.snapshot),
+			}))
+			.build()
+			.await
+			.map_err(|error| Error::Snapshot { chain: config.name.clone(), error })?;
+		if let Some(wasm) = &config.wasm {
+			let code =
+				std::fs::read(wasm).map_err(|error| Error::Io { path: wasm.clone(), error })?;
+			ext.insert(well_known_keys::CODE.to_vec(), code);
+		}
+		let backend = ext.as_backend();
+		let executor = Executor::builder().with_allow_missing_host_functions(true).build();
+
+		let version: RuntimeVersion = call(&config.name, &backend, &executor, "Core_version", &[])?;
+		let dry_run_api_version = version
+			.api_version(&<dyn DryRunApi<Block, (), (), ()> as RuntimeApiInfo>::ID)
+			.filter(|version| *version >= DRY_RUN_API_MIN_VERSION)
+			.ok_or_else(|| Error::MissingApi { chain: config.name.clone() })?;
+
+		let metadata: Option<Vec<u8>> = call(
+			&config.name,
+			&backend,
+			&executor,
+			"Metadata_metadata_at_version",
+			&15u32.encode(),
+		)?;
+		let events = metadata
+			.and_then(|metadata| RuntimeMetadataPrefixed::decode(&mut &metadata[..]).ok())
+			.and_then(EventDecoder::from_metadata)
+			.ok_or_else(|| Error::Metadata { chain: config.name.clone() })?;
+
+		log::info!(
+			target: crate::LOG_TARGET,
+			"Loaded {} ({} v{}) at {:?}",
+			config.name,
+			version.spec_name,
+			version.spec_version,
+			config.universal_location,
+		);
+
+		Ok(Self { config, backend, executor, events, dry_run_api_version })
+	}
+
+	/// Name of the chain.
+	pub fn name(&self) -> &str {
+		&self.config.name
+	}
+
+	/// Location of the chain in the global consensus.
+	pub fn universal_location(&self) -> &InteriorLocation {
+		&self.config.universal_location
+	}
+
+	/// Whether the runtime can return execution traces.
+	pub fn supports_trace(&self) -> bool {
+		self.dry_run_api_version >= DRY_RUN_API_TRACE_VERSION
+	}
+
+	/// Dry-run the encoded `call` with the encoded `OriginCaller` of the runtime.
+	///
+	/// Returns the trace as well if `trace` is set and the runtime supports it.
+	pub fn dry_run_call(
+		&self,
+		origin: &[u8],
+		call: &[u8],
+		xcm_version: XcmVersion,
+		trace: bool,
+	) -> Result<(CallEffects, Vec<InstructionTrace>), Error> {
+		let trace = trace && self.supports_trace();
+		let method =
+			if trace { "DryRunApi_dry_run_call_with_trace" } else { "DryRunApi_dry_run_call" };
+		let data = [origin, call, &xcm_version.encode()].concat();
+		let result = self.call_raw(method, &data)?;
+
+		self.decode_dry_run(method, &result, trace, |input| {
+			Ok(CallDryRunEffects {
+				execution_result: DispatchResultWithPostInfo::decode(input)?,
+				emitted_events: self.events.decode_events(input)?,
+				local_xcm: Decode::decode(input)?,
+				forwarded_xcms: Decode::decode(input)?,
+			})
+		})
+	}
+
+	/// Dry-run `xcm` sent by `origin`.
+	///
+	/// Returns the trace as well if `trace` is set and the runtime supports it.
+	pub fn dry_run_xcm(
+		&self,
+		origin: VersionedLocation,
+		xcm: VersionedXcm<()>,
+		trace: bool,
+	) -> Result<(XcmEffects, Vec<InstructionTrace>), Error> {
+		let trace = trace && self.supports_trace();
+		let method =
+			if trace { "DryRunApi_dry_run_xcm_with_trace" } else { "DryRunApi_dry_run_xcm" };
+		// `Xcm<()>` is encoded like `Xcm<RuntimeCall>`, calls are opaque within messages.
+		let result = self.call_raw(method, &(origin, xcm).encode())?;
+
+		self.decode_dry_run(method, &result, trace, |input| {
+			Ok(XcmDryRunEffects {
+				execution_result: Decode::decode(input)?,
+				emitted_events: self.events.decode_events(input)?,
+				forwarded_xcms: Decode::decode(input)?,
+			})
+		})
+	}
+
+	/// The weight of executing `xcm` on this chain.
+	pub fn query_xcm_weight(&self, xcm: VersionedXcm<()>) -> Result<Weight, Error> {
+		self.call_fees_api("XcmPaymentApi_query_xcm_weight", &xcm.encode())
+	}
+
+	/// The assets accepted for paying execution fees.
+	pub fn query_acceptable_payment_assets(
+		&self,
+		xcm_version: XcmVersion,
+	) -> Result<Vec<VersionedAssetId>, Error> {
+		self.call_fees_api("XcmPaymentApi_query_acceptable_payment_assets", &xcm_version.encode())
+	}
+
+	/// The fee for `weight`, in `asset`.
+	pub fn query_weight_to_asset_fee(
+		&self,
+		weight: Weight,
+		asset: VersionedAssetId,
+	) -> Result<u128, Error> {
+		self.call_fees_api("XcmPaymentApi_query_weight_to_asset_fee", &(weight, asset).encode())
+	}
+
+	/// The fees for delivering `xcm` from this chain to `destination`.
+	pub fn query_delivery_fees(
+		&self,
+		destination: VersionedLocation,
+		xcm: VersionedXcm<()>,
+	) -> Result<VersionedAssets, Error> {
+		self.call_fees_api("XcmPaymentApi_query_delivery_fees", &(destination, xcm).encode())
+	}
+
+	fn call_fees_api<R: Decode>(&self, method: &'static str, data: &[u8]) -> Result<R, Error> {
+		call::<Result<R, FeesError>>(self.name(), &self.backend, &self.executor, method, data)?
+			.map_err(|error| Error::Fees { chain: self.config.name.clone(), method, error })
+	}
+
+	fn call_raw(&self, method: &'static str, data: &[u8]) -> Result<Vec<u8>, Error> {
+		call_raw(self.name(), &self.backend, &self.executor, method, data)
+	}
+
+	/// Decode the `Result` returned by a dry-run API, followed by the trace if `traced`.
+	fn decode_dry_run<Effects>(
+		&self,
+		method: &'static str,
+		mut input: &[u8],
+		traced: bool,
+		decode_effects: impl FnOnce(&mut &[u8]) -> Result<Effects, codec::Error>,
+	) -> Result<(Effects, Vec<InstructionTrace>), Error> {
+		let decode_error = |error| Error::Decode { chain: self.config.name.clone(), method, error };
+		match input.read_byte().map_err(decode_error)? {
+			0 => {
+				let effects = decode_effects(&mut input).map_err(decode_error)?;
+				let trace = if traced {
+					Decode::decode(&mut input).map_err(decode_error)?
+				} else {
+					Vec::new()
+				};
+				Ok((effects, trace))
+			},
+			1 => {
+				let error = DryRunError::decode(&mut input).map_err(decode_error)?;
+				Err(Error::DryRun { chain: self.config.name.clone(), method, error })
+			},
+			_ => Err(decode_error("Invalid result variant".into())),
+		}
+	}
+}
+
+fn call<R: Decode>(
+	chain: &str,
+	backend: &InMemoryBackend<BlakeTwo256>,
+	executor: &Executor,
+	method: &'static str,
+	data: &[u8],
+) -> Result<R, Error> {
+	let result = call_raw(chain, backend, executor, method, data)?;
+	R::decode(&mut &result[..]).map_err(|error| Error::Decode {
+		chain: chain.to_string(),
+		method,
+		error,
+	})
+}
+
+fn call_raw(
+	chain: &str,
+	backend: &InMemoryBackend<BlakeTwo256>,
+	executor: &Executor,
+	method: &'static str,
+	data: &[u8],
+) -> Result<Vec<u8>, Error> {
+	let call_error = |error: String| Error::Call { chain: chain.to_string(), method, error };
+	let runtime_code = BackendRuntimeCode::new(backend);
+	let runtime_code = runtime_code.runtime_code().map_err(|error| call_error(error.into()))?;
+
+	let mut extensions = Extensions::default();
+	extensions.register(ReadRuntimeVersionExt::new(executor.clone()));
+
+	StateMachine::new(
+		backend,
+		&mut OverlayedChanges::default(),
+		executor,
+		method,
+		data,
+		&mut extensions,
+		&runtime_code,
+		CallContext::Offchain,
+	)
+	.execute()
+	.map_err(|error| call_error(error.to_string()))
+}
@@ -0,0 +1,248 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Decoding of runtime events without compile-time knowledge of the runtime.
+//!
+//! The dry-run APIs return the runtime's `RuntimeEvent`s, which are only known through the
+//! runtime metadata. Events are split according to the type information in the metadata and
+//! reported by pallet and event name, together with their encoding.
+
+use codec::{Compact, Decode, Input};
+use frame_metadata::{v15::RuntimeMetadataV15, RuntimeMetadata, RuntimeMetadataPrefixed};
+use scale_info::{form::PortableForm, PortableRegistry, TypeDef, TypeDefPrimitive, Variant};
+use std::fmt;
+
+/// An event emitted during a dry-run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmittedEvent {
+	/// Name of the pallet which emitted the event.
+	pub pallet: String,
+	/// Name of the event.
+	pub name: String,
+	/// The encoded `RuntimeEvent`.
+	pub encoded: Vec<u8>,
+}
+
+impl fmt::Display for EmittedEvent {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}.{} {}", self.pallet, self.name, array_bytes::bytes2hex("0x", &self.encoded))
+	}
+}
+
+/// Splits encoded `RuntimeEvent`s using the type information of a runtime.
+pub struct EventDecoder {
+	types: PortableRegistry,
+	event_ty: u32,
+}
+
+impl EventDecoder {
+	/// Create a decoder for the runtime with the given (V15) metadata.
+	pub fn from_metadata(metadata: RuntimeMetadataPrefixed) -> Option<Self> {
+		match metadata.1 {
+			RuntimeMetadata::V15(RuntimeMetadataV15 { types, outer_enums, .. }) =>
+				Some(Self::new(types, outer_enums.event_enum_ty.id)),
+			_ => None,
+		}
+	}
+
+	pub(crate) fn new(types: PortableRegistry, event_ty: u32) -> Self {
+		Self { types, event_ty }
+	}
+
+	/// Decode a vector of `RuntimeEvent`s.
+	pub fn decode_events(&self, input: &mut &[u8]) -> Result<Vec<EmittedEvent>, codec::Error> {
+		let len = Compact::<u32>::decode(input)?.0;
+		(0..len).map(|_| self.decode_event(input)).collect()
+	}
+
+	/// Decode a single `RuntimeEvent`.
+	pub fn decode_event(&self, input: &mut &[u8]) -> Result<EmittedEvent, codec::Error> {
+		let start = *input;
+		let pallet = self.variant(self.event_ty, start.first().copied())?;
+		// Pallet events are wrapped in a variant of the outer enum with a single field.
+		let name = match pallet.fields.first() {
+			Some(field) => self.variant(field.ty.id, start.get(1).copied())?.name.clone(),
+			None => String::new(),
+		};
+		let pallet = pallet.name.clone();
+
+		self.skip(self.event_ty, input)?;
+		let encoded = start[..start.len() - input.len()].to_vec();
+		Ok(EmittedEvent { pallet, name, encoded })
+	}
+
+	fn variant(&self, ty: u32, index: Option<u8>) -> Result<&Variant<PortableForm>, codec::Error> {
+		let index = index.ok_or("Not enough data to read variant index")?;
+		match self.types.resolve(ty).map(|ty| &ty.type_def) {
+			Some(TypeDef::Variant(def)) => def
+				.variants
+				.iter()
+				.find(|variant| variant.index == index)
+				.ok_or_else(|| "Unknown variant index".into()),
+			_ => Err("Expected an enum type".into()),
+		}
+	}
+
+	/// Advance `input` past a value of type `ty`.
+	fn skip(&self, ty: u32, input: &mut &[u8]) -> Result<(), codec::Error> {
+		let ty = self.types.resolve(ty).ok_or("Unknown type id")?;
+		match &ty.type_def {
+			TypeDef::Composite(def) =>
+				def.fields.iter().try_for_each(|field| self.skip(field.ty.id, input)),
+			TypeDef::Variant(def) => {
+				let index = input.read_byte()?;
+				let variant = def
+					.variants
+					.iter()
+					.find(|variant| variant.index == index)
+					.ok_or("Unknown variant index")?;
+				variant.fields.iter().try_for_each(|field| self.skip(field.ty.id, input))
+			},
+			TypeDef::Sequence(def) => {
+				let len = Compact::<u32>::decode(input)?.0;
+				(0..len).try_for_each(|_| self.skip(def.type_param.id, input))
+			},
+			TypeDef::Array(def) =>
+				(0..def.len).try_for_each(|_| self.skip(def.type_param.id, input)),
+			TypeDef::Tuple(def) =>
+				def.fields.iter().try_for_each(|field| self.skip(field.id, input)),
+			TypeDef::Primitive(TypeDefPrimitive::Str) => {
+				let len = Compact::<u32>::decode(input)?.0;
+				advance(input, len as usize)
+			},
+			TypeDef::Primitive(primitive) => advance(input, primitive_size(primitive)),
+			TypeDef::Compact(_) => Compact::<u128>::decode(input).map(drop),
+			TypeDef::BitSequence(def) => {
+				let bits = Compact::<u32>::decode(input)?.0 as usize;
+				let store = match self.types.resolve(def.bit_store_type.id).map(|ty| &ty.type_def) {
+					Some(TypeDef::Primitive(primitive)) => primitive_size(primitive),
+					_ => return Err("Invalid bit store type".into()),
+				};
+				advance(input, bits.div_ceil(store * 8) * store)
+			},
+		}
+	}
+}
+
+/// Encoded size of a fixed-width primitive.
+fn primitive_size(primitive: &TypeDefPrimitive) -> usize {
+	match primitive {
+		TypeDefPrimitive::Bool | TypeDefPrimitive::U8 | TypeDefPrimitive::I8 => 1,
+		TypeDefPrimitive::U16 | TypeDefPrimitive::I16 => 2,
+		TypeDefPrimitive::Char | TypeDefPrimitive::U32 | TypeDefPrimitive::I32 => 4,
+		TypeDefPrimitive::U64 | TypeDefPrimitive::I64 => 8,
+		TypeDefPrimitive::U128 | TypeDefPrimitive::I128 => 16,
+		TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => 32,
+		// Variable length, handled by the caller.
+		TypeDefPrimitive::Str => 0,
+	}
+}
+
+fn advance(input: &mut &[u8], len: usize) -> Result<(), codec::Error> {
+	if input.len() < len {
+		return Err("Not enough data".into())
+	}
+	*input = &input[len..];
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use codec::Encode;
+	use scale_info::{meta_type, Registry, TypeInfo};
+
+	#[derive(Encode, TypeInfo)]
+	enum BalancesEvent {
+		Transfer {
+			from: [u8; 32],
+			to: [u8; 32],
+			amount: u128,
+		},
+		#[codec(index = 5)]
+		Burned {
+			who: [u8; 32],
+			#[codec(compact)]
+			amount: u64,
+		},
+	}
+
+	#[derive(Encode, TypeInfo)]
+	enum SystemEvent {
+		Remarked { remark: Vec<u8>, note: Option<String> },
+	}
+
+	#[derive(Encode, TypeInfo)]
+	enum RuntimeEvent {
+		#[codec(index = 0)]
+		System(SystemEvent),
+		#[codec(index = 10)]
+		Balances(BalancesEvent),
+	}
+
+	fn decoder() -> EventDecoder {
+		let mut registry = Registry::new();
+		let event_ty = registry.register_type(&meta_type::<RuntimeEvent>()).id;
+		EventDecoder::new(registry.into(), event_ty)
+	}
+
+	#[test]
+	fn splits_events_by_type_information() {
+		let events = vec![
+			RuntimeEvent::Balances(BalancesEvent::Transfer {
+				from: [1; 32],
+				to: [2; 32],
+				amount: 3,
+			}),
+			RuntimeEvent::System(SystemEvent::Remarked {
+				remark: vec![1, 2, 3],
+				note: Some("hello".into()),
+			}),
+			RuntimeEvent::Balances(BalancesEvent::Burned { who: [4; 32], amount: u64::MAX }),
+		];
+		let encoded = events.encode();
+
+		// Trailing data is left alone.
+		let mut input = &[encoded.clone(), vec![42]].concat()[..];
+		let decoded = decoder().decode_events(&mut input).unwrap();
+		assert_eq!(input, &[42]);
+
+		let names: Vec<_> = decoded
+			.iter()
+			.map(|event| (event.pallet.as_str(), event.name.as_str()))
+			.collect();
+		assert_eq!(
+			names,
+			vec![("Balances", "Transfer"), ("System", "Remarked"), ("Balances", "Burned")]
+		);
+		for (decoded, event) in decoded.iter().zip(events.iter()) {
+			assert_eq!(decoded.encoded, event.encode());
+		}
+	}
+
+	#[test]
+	fn truncated_or_unknown_events_are_rejected() {
+		let encoded =
+			vec![RuntimeEvent::Balances(BalancesEvent::Burned { who: [4; 32], amount: 1 })]
+				.encode();
+		assert!(decoder().decode_events(&mut &encoded[..encoded.len() - 1]).is_err());
+
+		// No pallet at index 1.
+		let mut unknown = encoded.clone();
+		unknown[1] = 1;
+		assert!(decoder().decode_events(&mut &unknown[..]).is_err());
+	}
+}
@@ -0,0 +1,293 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # XCM Dry-Run Orchestrator
+//!
+//! Predicts the outcome of a cross-chain transfer by dry-running it on every chain involved.
+//!
+//! `DryRunApi` only simulates a single chain: it returns the messages an extrinsic or an XCM
+//! program sends, but not what they do on their destinations. The [`Orchestrator`] loads
+//! several chains from state snapshots (as created by `remote-externalities`), dry-runs an
+//! extrinsic or a message on one of them and feeds every forwarded message into `dry_run_xcm`
+//! of its destination, until no more messages are sent or the maximum number of hops is
+//! reached.
+//!
+//! The resulting [`Report`] contains the outcome and events of every execution, the delivery
+//! fees charged by the sender of every message and the execution fees on every chain, as
+//! quoted by `XcmPaymentApi`.
+//!
+//! Every dry-run is executed on the state of its chain's snapshot, so effects of earlier hops
+//! on the same chain (e.g. the outbound leg of a round trip) are not visible to later ones.
+//!
+//! The `xcm-dry-run` binary exposes the orchestrator on the command line.
+
+mod chain;
+mod events;
+mod report;
+
+pub use chain::{CallEffects, Chain, ChainConfig, XcmEffects};
+pub use events::{EmittedEvent, EventDecoder};
+pub use report::{Execution, ExecutionResult, Fee, Hop, HopStatus, Report};
+
+use std::{collections::VecDeque, path::PathBuf};
+use xcm::prelude::*;
+use xcm_runtime_apis::{dry_run::Error as DryRunError, fees::Error as FeesError};
+
+const LOG_TARGET: &str = "xcm::dry-run-orchestrator";
+
+/// The default maximum number of hops followed from the origin.
+pub const DEFAULT_MAX_HOPS: u32 = 4;
+
+/// Errors of the orchestrator.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	#[error("Failed to load the snapshot of {chain}: {error}")]
+	Snapshot { chain: String, error: &'static str },
+	#[error("Failed to read {path:?}: {error}")]
+	Io { path: PathBuf, error: std::io::Error },
+	#[error("Unknown chain {0}")]
+	UnknownChain(String),
+	#[error("{chain}: runtime doesn't implement `DryRunApi` version 2 or later")]
+	MissingApi { chain: String },
+	#[error("{chain}: runtime doesn't provide V15 metadata to decode events")]
+	Metadata { chain: String },
+	#[error("{chain}: calling {method} failed: {error}")]
+	Call { chain: String, method: &'static str, error: String },
+	#[error("{chain}: failed to decode the result of {method}: {error}")]
+	Decode { chain: String, method: &'static str, error: codec::Error },
+	#[error("{chain}: {method} failed: {error:?}")]
+	DryRun { chain: String, method: &'static str, error: DryRunError },
+	#[error("{chain}: {method} failed: {error:?}")]
+	Fees { chain: String, method: &'static str, error: FeesError },
+}
+
+/// Dry-runs extrinsics and messages across the loaded chains.
+pub struct Orchestrator {
+	chains: Vec<Chain>,
+	max_hops: u32,
+	xcm_version: XcmVersion,
+	trace: bool,
+}
+
+impl Orchestrator {
+	/// Create an orchestrator for the given chains.
+	pub fn new(chains: Vec<Chain>) -> Self {
+		Self { chains, max_hops: DEFAULT_MAX_HOPS, xcm_version: XCM_VERSION, trace: false }
+	}
+
+	/// Follow at most `max_hops` messages from the origin.
+	pub fn with_max_hops(mut self, max_hops: u32) -> Self {
+		self.max_hops = max_hops;
+		self
+	}
+
+	/// The XCM version of the messages returned by the origin chain, and the version fee assets
+	/// are queried in.
+	pub fn with_xcm_version(mut self, xcm_version: XcmVersion) -> Self {
+		self.xcm_version = xcm_version;
+		self
+	}
+
+	/// Trace the instructions executed on every chain supporting it.
+	pub fn with_trace(mut self, trace: bool) -> Self {
+		self.trace = trace;
+		self
+	}
+
+	/// The chain with the given name.
+	pub fn chain(&self, name: &str) -> Result<&Chain, Error> {
+		self.chains
+			.iter()
+			.find(|chain| chain.name() == name)
+			.ok_or_else(|| Error::UnknownChain(name.into()))
+	}
+
+	/// Dry-run the encoded `call` on `chain`, dispatched by the encoded `OriginCaller`, and all
+	/// messages resulting from it.
+	pub fn dry_run_call(&self, chain: &str, origin: &[u8], call: &[u8]) -> Result<Report, Error> {
+		let chain = self.chain(chain)?;
+		let (effects, trace) = chain.dry_run_call(origin, call, self.xcm_version, self.trace)?;
+		let (weight, fee) = match effects.local_xcm {
+			Some(xcm) => self.execution_fee(chain, xcm),
+			None => (None, None),
+		};
+		let origin = Execution {
+			chain: chain.name().into(),
+			result: ExecutionResult::Call(effects.execution_result),
+			events: effects.emitted_events,
+			trace,
+			weight,
+			fee,
+		};
+		let hops = self.follow(chain, effects.forwarded_xcms);
+
+		Ok(Report { origin, hops })
+	}
+
+	/// Dry-run `xcm` on `chain`, sent by `origin`, and all messages resulting from it.
+	pub fn dry_run_xcm(
+		&self,
+		chain: &str,
+		origin: VersionedLocation,
+		xcm: VersionedXcm<()>,
+	) -> Result<Report, Error> {
+		let chain = self.chain(chain)?;
+		let (execution, forwarded_xcms) = self.execute(chain, origin, xcm)?;
+		let hops = self.follow(chain, forwarded_xcms);
+
+		Ok(Report { origin: execution, hops })
+	}
+
+	/// Dry-run the forwarded messages on their destinations, breadth first.
+	fn follow(
+		&self,
+		origin: &Chain,
+		forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
+	) -> Vec<Hop> {
+		let mut queue = VecDeque::new();
+		enqueue(&mut queue, origin, 1, forwarded_xcms);
+
+		let mut hops = Vec::new();
+		while let Some((sender, depth, destination, message)) = queue.pop_front() {
+			let delivery_fees =
+				ok_or_warn(sender.query_delivery_fees(destination.clone(), message.clone()));
+			let receiver = self.route(sender, &destination);
+			let status = match &receiver {
+				None => HopStatus::NotLoaded,
+				Some(_) if depth > self.max_hops => HopStatus::TooManyHops,
+				Some((receiver, origin)) =>
+					match self.execute(receiver, origin.clone().into(), message.clone()) {
+						Ok((execution, forwarded_xcms)) => {
+							enqueue(&mut queue, *receiver, depth + 1, forwarded_xcms);
+							HopStatus::Executed(execution)
+						},
+						Err(error) => HopStatus::Failed(error.to_string()),
+					},
+			};
+			hops.push(Hop {
+				depth,
+				from: sender.name().into(),
+				to: receiver.map(|(receiver, _)| receiver.name().into()),
+				destination,
+				message,
+				delivery_fees,
+				status,
+			});
+		}
+		hops
+	}
+
+	/// The chain `destination` refers to from `sender`, and the location of `sender` as seen
+	/// by it.
+	fn route(&self, sender: &Chain, destination: &VersionedLocation) -> Option<(&Chain, Location)> {
+		let (universal, origin) = resolve(sender.universal_location(), destination)?;
+		let receiver = self.chains.iter().find(|chain| *chain.universal_location() == universal)?;
+		Some((receiver, origin))
+	}
+
+	fn execute(
+		&self,
+		chain: &Chain,
+		origin: VersionedLocation,
+		xcm: VersionedXcm<()>,
+	) -> Result<(Execution, Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>), Error> {
+		let (weight, fee) = self.execution_fee(chain, xcm.clone());
+		let (effects, trace) = chain.dry_run_xcm(origin, xcm, self.trace)?;
+		let execution = Execution {
+			chain: chain.name().into(),
+			result: ExecutionResult::Xcm(effects.execution_result),
+			events: effects.emitted_events,
+			trace,
+			weight,
+			fee,
+		};
+		Ok((execution, effects.forwarded_xcms))
+	}
+
+	/// The weight of `xcm` on `chain`, and its cost in the first asset the chain accepts.
+	fn execution_fee(&self, chain: &Chain, xcm: VersionedXcm<()>) -> (Option<Weight>, Option<Fee>) {
+		let Some(weight) = ok_or_warn(chain.query_xcm_weight(xcm)) else { return (None, None) };
+		let fee = ok_or_warn(chain.query_acceptable_payment_assets(self.xcm_version))
+			.and_then(|assets| assets.into_iter().next())
+			.and_then(|asset| {
+				let amount = ok_or_warn(chain.query_weight_to_asset_fee(weight, asset.clone()))?;
+				Some(Fee { asset, amount })
+			});
+		(Some(weight), fee)
+	}
+}
+
+type Queue<'a> = VecDeque<(&'a Chain, u32, VersionedLocation, VersionedXcm<()>)>;
+
+fn enqueue<'a>(
+	queue: &mut Queue<'a>,
+	sender: &'a Chain,
+	depth: u32,
+	forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
+) {
+	for (destination, messages) in forwarded_xcms {
+		for message in messages {
+			queue.push_back((sender, depth, destination.clone(), message));
+		}
+	}
+}
+
+/// The universal location of `destination` as seen from `sender`, and the location of `sender`
+/// as seen from `destination`.
+fn resolve(
+	sender: &InteriorLocation,
+	destination: &VersionedLocation,
+) -> Option<(InteriorLocation, Location)> {
+	let destination = Location::try_from(destination.clone()).ok()?;
+	let universal = sender.clone().within_global(destination).ok()?;
+	let origin = sender.clone().relative_to(&universal);
+	Some((universal, origin))
+}
+
+/// Fees are best effort, failing to quote them doesn't fail the dry-run.
+fn ok_or_warn<T>(result: Result<T, Error>) -> Option<T> {
+	result.inspect_err(|error| log::warn!(target: LOG_TARGET, "{}", error)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn destinations_resolve_to_universal_locations() {
+		let relay: InteriorLocation = [GlobalConsensus(Polkadot)].into();
+		let asset_hub: InteriorLocation = [GlobalConsensus(Polkadot), Parachain(1000)].into();
+		let para: InteriorLocation = [GlobalConsensus(Polkadot), Parachain(2000)].into();
+
+		// Up to the relay chain.
+		assert_eq!(
+			resolve(&asset_hub, &Location::parent().into()),
+			Some((relay.clone(), Location::new(0, [Parachain(1000)])))
+		);
+		// Down to a parachain.
+		assert_eq!(
+			resolve(&relay, &Location::new(0, [Parachain(2000)]).into()),
+			Some((para.clone(), Location::parent()))
+		);
+		// Across to a sibling.
+		assert_eq!(
+			resolve(&asset_hub, &Location::new(1, [Parachain(2000)]).into()),
+			Some((para, Location::new(1, [Parachain(1000)])))
+		);
+		// Out of the global consensus.
+		assert_eq!(resolve(&relay, &Location::new(2, [GlobalConsensus(Kusama)]).into()), None);
+	}
+}
@@ -0,0 +1,125 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Command line interface of the XCM dry-run orchestrator.
+
+use clap::{Parser, Subcommand};
+use codec::Decode;
+use std::path::PathBuf;
+use xcm::prelude::*;
+use xcm_dry_run_orchestrator::{Chain, ChainConfig, Orchestrator, DEFAULT_MAX_HOPS};
+
+/// Dry-run an extrinsic or an XCM program across several chains loaded from state snapshots.
+#[derive(Debug, Parser)]
+struct Cli {
+	/// JSON file with the list of chains to load, e.g.
+	/// `[{ "name": "asset-hub", "universalLocation": ..., "snapshot": "asset-hub.snap" }]`.
+	#[arg(long)]
+	chains: PathBuf,
+
+	/// Name of the chain to start the dry-run on.
+	#[arg(long)]
+	origin_chain: String,
+
+	/// Maximum number of hops followed from the origin chain.
+	#[arg(long, default_value_t = DEFAULT_MAX_HOPS)]
+	max_hops: u32,
+
+	/// XCM version of the messages sent by the origin chain.
+	#[arg(long, default_value_t = XCM_VERSION)]
+	xcm_version: XcmVersion,
+
+	/// Trace the instructions executed on every chain supporting it.
+	#[arg(long)]
+	trace: bool,
+
+	#[command(subcommand)]
+	command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+	/// Dry-run an extrinsic.
+	Call {
+		/// Hex-encoded `OriginCaller` of the origin chain's runtime.
+		#[arg(long)]
+		origin: String,
+		/// Hex-encoded `RuntimeCall` of the origin chain's runtime.
+		#[arg(long)]
+		call: String,
+	},
+	/// Dry-run an XCM program.
+	Xcm {
+		/// Hex-encoded `VersionedLocation` sending the program.
+		#[arg(long)]
+		origin: String,
+		/// Hex-encoded `VersionedXcm` to execute.
+		#[arg(long)]
+		xcm: String,
+	},
+}
+
+fn hex(name: &str, value: &str) -> Result<Vec<u8>, String> {
+	array_bytes::hex2bytes(value).map_err(|error| format!("Invalid hex in --{}: {:?}", name, error))
+}
+
+fn decode<T: Decode>(name: &str, value: &str) -> Result<T, String> {
+	T::decode(&mut &hex(name, value)?[..])
+		.map_err(|error| format!("Failed to decode --{}: {}", name, error))
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+	let configs = std::fs::read(&cli.chains)
+		.map_err(|error| format!("Failed to read {:?}: {}", cli.chains, error))?;
+	let configs: Vec<ChainConfig> = serde_json::from_slice(&configs)
+		.map_err(|error| format!("Invalid chains file {:?}: {}", cli.chains, error))?;
+
+	let mut chains = Vec::with_capacity(configs.len());
+	for config in configs {
+		chains.push(Chain::load(config).await.map_err(|error| error.to_string())?);
+	}
+	let orchestrator = Orchestrator::new(chains)
+		.with_max_hops(cli.max_hops)
+		.with_xcm_version(cli.xcm_version)
+		.with_trace(cli.trace);
+
+	let report = match cli.command {
+		Command::Call { origin, call } => orchestrator.dry_run_call(
+			&cli.origin_chain,
+			&hex("origin", &origin)?,
+			&hex("call", &call)?,
+		),
+		Command::Xcm { origin, xcm } => orchestrator.dry_run_xcm(
+			&cli.origin_chain,
+			decode("origin", &origin)?,
+			decode("xcm", &xcm)?,
+		),
+	}
+	.map_err(|error| error.to_string())?;
+
+	print!("{}", report);
+	Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+	sp_tracing::try_init_simple();
+
+	if let Err(error) = run(Cli::parse()).await {
+		eprintln!("Error: {}", error);
+		std::process::exit(1);
+	}
+}
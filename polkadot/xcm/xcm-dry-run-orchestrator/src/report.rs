@@ -0,0 +1,177 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! The cross-chain effects of a dry-run.
+
+use crate::EmittedEvent;
+use frame_support::dispatch::DispatchResultWithPostInfo;
+use std::fmt;
+use xcm::prelude::*;
+use xcm_runtime_apis::dry_run::InstructionTrace;
+
+/// The effects of a dry-run on every chain the resulting messages reached.
+#[derive(Debug)]
+pub struct Report {
+	/// Execution on the chain the dry-run started on.
+	pub origin: Execution,
+	/// Every message sent, in the order they were dry-run.
+	pub hops: Vec<Hop>,
+}
+
+/// The result of executing an extrinsic or a message.
+#[derive(Debug)]
+pub enum ExecutionResult {
+	/// The result of dispatching an extrinsic.
+	Call(DispatchResultWithPostInfo),
+	/// The outcome of executing a message.
+	Xcm(Outcome),
+}
+
+/// An execution fee.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fee {
+	/// The asset the fee is paid in, the first asset accepted by the chain.
+	pub asset: VersionedAssetId,
+	/// The amount of `asset`.
+	pub amount: u128,
+}
+
+/// Execution of an extrinsic or a message on a single chain.
+#[derive(Debug)]
+pub struct Execution {
+	/// Name of the chain.
+	pub chain: String,
+	/// The result of the execution.
+	pub result: ExecutionResult,
+	/// Events emitted during execution.
+	pub events: Vec<EmittedEvent>,
+	/// The instructions executed locally, if tracing was requested and is supported.
+	pub trace: Vec<InstructionTrace>,
+	/// The weight of the XCM executed locally, if any.
+	pub weight: Option<Weight>,
+	/// The fee for executing the XCM executed locally, if any.
+	pub fee: Option<Fee>,
+}
+
+/// What happened to a sent message.
+#[derive(Debug)]
+pub enum HopStatus {
+	/// The message was dry-run on its destination.
+	Executed(Execution),
+	/// Dry-running the message on its destination failed.
+	Failed(String),
+	/// The destination is not one of the loaded chains.
+	NotLoaded,
+	/// The message was not dry-run, since the maximum number of hops was reached.
+	TooManyHops,
+}
+
+/// A message sent from one chain to another.
+#[derive(Debug)]
+pub struct Hop {
+	/// Number of messages between the origin and this one, starting at `1`.
+	pub depth: u32,
+	/// Name of the sending chain.
+	pub from: String,
+	/// Name of the receiving chain, if it is loaded.
+	pub to: Option<String>,
+	/// The destination, as seen by the sender.
+	pub destination: VersionedLocation,
+	/// The message.
+	pub message: VersionedXcm<()>,
+	/// The fees the sender charges for delivering the message.
+	pub delivery_fees: Option<VersionedAssets>,
+	/// What happened to the message on its destination.
+	pub status: HopStatus,
+}
+
+impl Report {
+	/// The execution fees of every chain with a known fee, in order of execution.
+	pub fn execution_fees(&self) -> Vec<(&str, &Fee)> {
+		let executed = self.hops.iter().filter_map(|hop| match &hop.status {
+			HopStatus::Executed(execution) => Some(execution),
+			_ => None,
+		});
+		core::iter::once(&self.origin)
+			.chain(executed)
+			.filter_map(|execution| Some((execution.chain.as_str(), execution.fee.as_ref()?)))
+			.collect()
+	}
+}
+
+impl fmt::Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		writeln!(f, "Origin: {}", self.origin.chain)?;
+		write_execution(f, &self.origin)?;
+		for hop in &self.hops {
+			let to = hop.to.as_deref().unwrap_or("<not loaded>");
+			writeln!(f, "Hop {}: {} -> {} ({:?})", hop.depth, hop.from, to, hop.destination)?;
+			writeln!(f, "  message: {:?}", hop.message)?;
+			match &hop.delivery_fees {
+				Some(fees) => writeln!(f, "  delivery fees: {:?}", fees)?,
+				None => writeln!(f, "  delivery fees: unknown")?,
+			}
+			match &hop.status {
+				HopStatus::Executed(execution) => write_execution(f, execution)?,
+				HopStatus::Failed(error) => writeln!(f, "  dry-run failed: {}", error)?,
+				HopStatus::NotLoaded => writeln!(f, "  not executed: destination not loaded")?,
+				HopStatus::TooManyHops => writeln!(f, "  not executed: too many hops")?,
+			}
+		}
+		Ok(())
+	}
+}
+
+fn write_execution(f: &mut fmt::Formatter, execution: &Execution) -> fmt::Result {
+	match &execution.result {
+		ExecutionResult::Call(result) => writeln!(f, "  result: {:?}", result)?,
+		ExecutionResult::Xcm(outcome) => writeln!(f, "  outcome: {:?}", outcome)?,
+	}
+	if let Some(weight) = execution.weight {
+		writeln!(f, "  weight: {:?}", weight)?;
+	}
+	if let Some(fee) = &execution.fee {
+		writeln!(f, "  execution fee: {} of {:?}", fee.amount, fee.asset)?;
+	}
+	if !execution.events.is_empty() {
+		writeln!(f, "  events:")?;
+		for event in &execution.events {
+			writeln!(f, "    {}", event)?;
+		}
+	}
+	if !execution.trace.is_empty() {
+		writeln!(f, "  trace:")?;
+		for step in &execution.trace {
+			let indent = "  ".repeat(step.depth as usize);
+			write!(
+				f,
+				"    {}{} {} weight: {:?}, holding: {:?}, fees: {:?}, origin: {:?}",
+				indent,
+				step.index,
+				step.instruction,
+				step.weight_used,
+				step.holding,
+				step.fees,
+				step.origin,
+			)?;
+			match step.error {
+				Some(error) => writeln!(f, ", error: {:?}", error)?,
+				None => writeln!(f)?,
+			}
+		}
+	}
+	Ok(())
+}
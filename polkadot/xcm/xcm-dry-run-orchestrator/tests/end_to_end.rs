@@ -0,0 +1,153 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Dry-runs a transfer from the Westend asset hub to the Westend relay chain, both loaded from
+//! snapshots of their development genesis state.
+
+use codec::{Compact, Encode};
+use cumulus_primitives_core::{relay_chain::AsyncBackingParams, AbridgedHostConfiguration};
+use sc_chain_spec::GenesisConfigBuilderRuntimeCaller;
+use sp_core::{
+	storage::{well_known_keys, Storage},
+	H256,
+};
+use sp_keyring::Sr25519Keyring;
+use sp_runtime::{
+	generic::Header,
+	traits::{BlakeTwo256, Header as _},
+	StateVersion,
+};
+use std::path::{Path, PathBuf};
+use xcm::latest::prelude::*;
+use xcm_dry_run_orchestrator::{Chain, ChainConfig, ExecutionResult, HopStatus, Orchestrator};
+
+/// Version of the `remote-externalities` snapshot format written by [`write_snapshot`].
+const SNAPSHOT_VERSION: Compact<u16> = Compact(4);
+
+type HostConfiguration =
+	cumulus_pallet_parachain_system::HostConfiguration<asset_hub_westend_runtime::Runtime>;
+
+/// Development genesis storage of the runtime in `code`, including the code itself.
+fn genesis_storage(code: &[u8]) -> Storage {
+	let mut storage = GenesisConfigBuilderRuntimeCaller::<()>::new(code)
+		.get_storage_for_named_preset(Some(&sp_genesis_builder::DEV_RUNTIME_PRESET.to_string()))
+		.expect("development preset builds");
+	storage.top.insert(well_known_keys::CODE.to_vec(), code.to_vec());
+	storage
+}
+
+/// Write `storage` to `dir` in the snapshot format `remote-externalities` loads.
+fn write_snapshot(dir: &Path, name: &str, storage: Storage) -> PathBuf {
+	let (raw_storage, storage_root) =
+		sp_io::TestExternalities::new_with_state_version(storage, StateVersion::V1)
+			.into_raw_snapshot();
+	let header = Header::<u32, BlakeTwo256>::new(
+		0,
+		Default::default(),
+		storage_root,
+		Default::default(),
+		Default::default(),
+	);
+	let path = dir.join(format!("{name}.snap"));
+	let snapshot: (_, _, _, H256, _) =
+		(SNAPSHOT_VERSION, StateVersion::V1, raw_storage, storage_root, header);
+	std::fs::write(&path, snapshot.encode()).expect("snapshot is written");
+	path
+}
+
+async fn load(name: &str, universal_location: InteriorLocation, snapshot: PathBuf) -> Chain {
+	Chain::load(ChainConfig { name: name.into(), universal_location, snapshot, wasm: None })
+		.await
+		.expect("chain loads from its snapshot")
+}
+
+#[tokio::test]
+async fn transfer_to_relay_chain_is_dry_run_on_both_chains() {
+	let dir = tempfile::tempdir().unwrap();
+	let westend = NetworkId::ByGenesis(WESTEND_GENESIS_HASH);
+
+	let mut storage = genesis_storage(asset_hub_westend_runtime::WASM_BINARY.unwrap());
+	// Upward messages can only be sent once the relay chain configuration is known, which is
+	// set by the first block rather than at genesis.
+	storage.top.insert(
+		HostConfiguration::hashed_key().to_vec(),
+		AbridgedHostConfiguration {
+			max_code_size: 3 * 1024 * 1024,
+			max_head_data_size: 32 * 1024,
+			max_upward_queue_count: 8,
+			max_upward_queue_size: 1024 * 1024,
+			max_upward_message_size: 64 * 1024,
+			max_upward_message_num_per_candidate: 2,
+			hrmp_max_message_num_per_candidate: 2,
+			validation_upgrade_cooldown: 2,
+			validation_upgrade_delay: 2,
+			async_backing_params: AsyncBackingParams {
+				allowed_ancestry_len: 0,
+				max_candidate_depth: 0,
+			},
+		}
+		.encode(),
+	);
+	let asset_hub = load(
+		"asset-hub",
+		[GlobalConsensus(westend), Parachain(1000)].into(),
+		write_snapshot(dir.path(), "asset-hub", storage),
+	)
+	.await;
+	let relay = load(
+		"westend",
+		[GlobalConsensus(westend)].into(),
+		write_snapshot(
+			dir.path(),
+			"westend",
+			genesis_storage(westend_runtime::WASM_BINARY.unwrap()),
+		),
+	)
+	.await;
+
+	let origin = asset_hub_westend_runtime::OriginCaller::system(frame_system::RawOrigin::Signed(
+		Sr25519Keyring::Alice.to_account_id(),
+	));
+	let call =
+		asset_hub_westend_runtime::RuntimeCall::PolkadotXcm(pallet_xcm::Call::transfer_assets {
+			dest: Box::new(Location::parent().into()),
+			beneficiary: Box::new(
+				Location::new(0, [AccountId32 { network: None, id: Sr25519Keyring::Alice.into() }])
+					.into(),
+			),
+			assets: Box::new((Location::parent(), 1_000_000_000_000u128).into()),
+			fee_asset_item: 0,
+			weight_limit: Unlimited,
+		});
+	let report = Orchestrator::new(vec![asset_hub, relay])
+		.dry_run_call("asset-hub", &origin.encode(), &call.encode())
+		.unwrap();
+
+	assert_eq!(report.origin.chain, "asset-hub");
+	assert!(matches!(report.origin.result, ExecutionResult::Call(Ok(_))));
+	assert_eq!(report.hops.len(), 1);
+	let hop = &report.hops[0];
+	assert_eq!(
+		(hop.depth, hop.from.as_str(), hop.to.as_deref()),
+		(1, "asset-hub", Some("westend"))
+	);
+	assert!(hop.delivery_fees.is_some());
+	let HopStatus::Executed(execution) = &hop.status else {
+		panic!("message should be executed on the relay chain, got {:?}", hop.status);
+	};
+	assert_eq!(execution.chain, "westend");
+	assert!(matches!(execution.result, ExecutionResult::Xcm(_)));
+}
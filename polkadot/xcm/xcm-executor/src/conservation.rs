@@ -0,0 +1,291 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Asset conservation checks for the XCM executor.
+//!
+//! Inside of [`check_conservation`], the executor accounts for every asset moved by the
+//! instructions it executes. For each asset, the total of its circulating supply and of the
+//! amounts in the holding and fees registers must only change by the assets which explicitly
+//! entered or left the chain: reserve deposits, teleports, claims, burns and trapped assets.
+//! Instructions which fail must leave the totals untouched if the executor is transactional.
+//!
+//! Any difference is reported as a [`ConservationViolation`] naming the offending instruction,
+//! which points at `TransactAsset` adapters minting or burning assets by mistake.
+//!
+//! The movements of the trader, the asset exchanger, the fee manager and dispatched calls are
+//! not known to the executor, so the instructions relying on them are not checked and delivery
+//! fees are accounted for by their effect on the totals.
+
+use crate::AssetsInHolding;
+use alloc::{collections::btree_map::BTreeMap, string::String, vec::Vec};
+use xcm::latest::prelude::*;
+
+/// The circulating supply of an asset, or `None` if it's not checked.
+///
+/// Assets in the teleport checking accounts must not be counted as circulating, since teleports
+/// are accounted for as assets leaving and entering the chain.
+pub type CirculatingSupply = fn(&AssetId) -> Option<u128>;
+
+/// An asset whose total changed by a different amount than the assets which entered or left the
+/// chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConservationViolation {
+	/// The index of the offending instruction in its program, or `None` for the assets trapped
+	/// at the end of execution.
+	pub index: Option<u32>,
+	/// The name of the offending instruction.
+	pub instruction: String,
+	/// The asset which was not conserved.
+	pub asset: AssetId,
+	/// The change of the total which was expected.
+	pub expected: i128,
+	/// The change of the total which happened.
+	pub actual: i128,
+}
+
+/// Run `f`, checking the conservation of the assets moved by the XCM executor.
+///
+/// Returns the result of `f` along with all the violations found. This is meant to be called from
+/// `try-runtime` checks and XCM emulator tests around the code executing messages.
+pub fn check_conservation<R>(
+	circulating: CirculatingSupply,
+	f: impl FnOnce() -> R,
+) -> (R, Vec<ConservationViolation>) {
+	let mut state = Checks { circulating, frames: Vec::new(), violations: Vec::new() };
+	let result = checks::using(&mut state, f);
+	(result, state.violations)
+}
+
+/// The totals of the checked assets.
+pub(crate) type Totals = BTreeMap<AssetId, i128>;
+
+/// How the effects of an instruction ended up.
+pub(crate) enum Effects {
+	/// The instruction succeeded.
+	Applied,
+	/// The instruction failed and its effects were rolled back.
+	Reverted,
+	/// The instruction failed and some of its effects may remain.
+	Unknown,
+}
+
+struct Frame {
+	checked: bool,
+	before: Totals,
+	flows: Totals,
+	/// Whether some flows were only known by their effect on the totals, in which case they may
+	/// or may not have been rolled back along with a failed instruction.
+	unaccounted: bool,
+}
+
+struct Checks {
+	circulating: CirculatingSupply,
+	frames: Vec<Frame>,
+	violations: Vec<ConservationViolation>,
+}
+
+impl Checks {
+	fn total(
+		&self,
+		id: &AssetId,
+		holding: &AssetsInHolding,
+		fees: &AssetsInHolding,
+	) -> Option<i128> {
+		let circulating = (self.circulating)(id)?;
+		let registers = amount(holding, id).saturating_add(amount(fees, id));
+		Some((circulating as i128).saturating_add(registers))
+	}
+
+	fn totals<'a>(
+		&self,
+		ids: impl IntoIterator<Item = &'a AssetId>,
+		holding: &AssetsInHolding,
+		fees: &AssetsInHolding,
+	) -> Totals {
+		ids.into_iter()
+			.filter_map(|id| Some((id.clone(), self.total(id, holding, fees)?)))
+			.collect()
+	}
+
+	fn note(&mut self, assets: &AssetsInHolding, sign: i128) {
+		let Some(frame) = self.frames.last_mut() else { return };
+		for id in asset_ids(assets) {
+			let flow = frame.flows.entry(id.clone()).or_default();
+			*flow = flow.saturating_add(amount(assets, &id).saturating_mul(sign));
+		}
+	}
+}
+
+environmental::environmental!(checks: Checks);
+
+/// Whether the conservation of assets is being checked.
+pub(crate) fn is_enabled() -> bool {
+	checks::with(|_| ()).is_some()
+}
+
+/// Start accounting for an instruction moving `assets`, as well as all the assets in the holding
+/// and fees registers.
+pub(crate) fn begin(
+	checked: bool,
+	assets: impl IntoIterator<Item = AssetId>,
+	holding: &AssetsInHolding,
+	fees: &AssetsInHolding,
+) {
+	checks::with(|checks| {
+		let ids: Vec<_> =
+			assets.into_iter().chain(asset_ids(holding)).chain(asset_ids(fees)).collect();
+		let before = checks.totals(&ids, holding, fees);
+		checks
+			.frames
+			.push(Frame { checked, before, flows: Totals::new(), unaccounted: false });
+	});
+}
+
+/// Finish accounting for the instruction at `index`, reporting the assets which were not
+/// conserved.
+pub(crate) fn end(
+	index: Option<u32>,
+	instruction: &str,
+	effects: Effects,
+	holding: &AssetsInHolding,
+	fees: &AssetsInHolding,
+) {
+	checks::with(|checks| {
+		let Some(frame) = checks.frames.pop() else { return };
+		let no_flows = Totals::new();
+		let expected_flows = match effects {
+			_ if !frame.checked => None,
+			Effects::Applied => Some(&frame.flows),
+			Effects::Reverted if !frame.unaccounted => Some(&no_flows),
+			Effects::Reverted | Effects::Unknown => None,
+		};
+		if let Some(flows) = expected_flows {
+			let after = checks.totals(frame.before.keys(), holding, fees);
+			for (asset, before) in &frame.before {
+				let actual = after.get(asset).copied().unwrap_or_default().saturating_sub(*before);
+				let expected = flows.get(asset).copied().unwrap_or_default();
+				if actual != expected {
+					tracing::error!(
+						target: "xcm::conservation",
+						?index, ?instruction, ?asset, ?expected, ?actual,
+						"Asset not conserved",
+					);
+					checks.violations.push(ConservationViolation {
+						index,
+						instruction: instruction.into(),
+						asset: asset.clone(),
+						expected,
+						actual,
+					});
+				}
+			}
+		}
+		// The flows of nested instructions are flows of the enclosing instruction too.
+		if let (Effects::Applied, Some(parent)) = (&effects, checks.frames.last_mut()) {
+			parent.unaccounted |= frame.unaccounted;
+			for (asset, flow) in frame.flows {
+				let parent_flow = parent.flows.entry(asset).or_default();
+				*parent_flow = parent_flow.saturating_add(flow);
+			}
+		}
+	});
+}
+
+/// The current totals of the assets checked for the instruction being executed.
+pub(crate) fn totals(holding: &AssetsInHolding, fees: &AssetsInHolding) -> Option<Totals> {
+	checks::with(|checks| {
+		let frame = checks.frames.last()?;
+		Some(checks.totals(frame.before.keys(), holding, fees))
+	})
+	.flatten()
+}
+
+/// Account for the change of the totals since `before` as a flow of the instruction being
+/// executed, for the movements of assets the executor can't account for, like paying fees.
+pub(crate) fn note_unaccounted(
+	before: Option<Totals>,
+	holding: &AssetsInHolding,
+	fees: &AssetsInHolding,
+) {
+	let Some(before) = before else { return };
+	checks::with(|checks| {
+		let after = checks.totals(before.keys(), holding, fees);
+		let Some(frame) = checks.frames.last_mut() else { return };
+		frame.unaccounted = true;
+		for (asset, before) in before {
+			let change = after.get(&asset).copied().unwrap_or_default().saturating_sub(before);
+			let flow = frame.flows.entry(asset).or_default();
+			*flow = flow.saturating_add(change);
+		}
+	});
+}
+
+/// Account for `assets` entering the chain.
+pub(crate) fn note_inflow(assets: &AssetsInHolding) {
+	checks::with(|checks| checks.note(assets, 1));
+}
+
+/// Account for `assets` leaving the chain.
+pub(crate) fn note_outflow(assets: &AssetsInHolding) {
+	checks::with(|checks| checks.note(assets, -1));
+}
+
+/// Whether the assets moved by `instr` can be accounted for by the executor.
+pub(crate) fn is_checked<Call>(instr: &Instruction<Call>) -> bool {
+	!matches!(
+		instr,
+		BuyExecution { .. } |
+			PayFees { .. } |
+			RefundSurplus |
+			Transact { .. } |
+			QueryResponse { .. } |
+			ExchangeAsset { .. }
+	)
+}
+
+/// The assets named by `instr` which are not necessarily in the holding register.
+pub(crate) fn instruction_assets<Call>(instr: &Instruction<Call>) -> Vec<AssetId> {
+	let assets = match instr {
+		WithdrawAsset(assets) |
+		ReserveAssetDeposited(assets) |
+		ReceiveTeleportedAsset(assets) |
+		TransferAsset { assets, .. } |
+		TransferReserveAsset { assets, .. } |
+		ClaimAsset { assets, .. } |
+		BurnAsset(assets) => assets.inner(),
+		LockAsset { asset, .. } |
+		UnlockAsset { asset, .. } |
+		NoteUnlockable { asset, .. } |
+		RequestUnlock { asset, .. } => core::slice::from_ref(asset),
+		_ => &[],
+	};
+	assets.iter().map(|asset| asset.id.clone()).collect()
+}
+
+fn asset_ids(assets: &AssetsInHolding) -> impl Iterator<Item = AssetId> + '_ {
+	assets
+		.fungible
+		.keys()
+		.cloned()
+		.chain(assets.non_fungible.iter().map(|(id, _)| id.clone()))
+}
+
+/// The amount of `id` in `assets`, counting each instance of a non-fungible asset as one.
+fn amount(assets: &AssetsInHolding, id: &AssetId) -> i128 {
+	let fungible = assets.fungible.get(id).copied().unwrap_or_default();
+	let instances = assets.non_fungible.iter().filter(|(asset, _)| asset == id).count();
+	(fungible as i128).saturating_add(instances as i128)
+}
@@ -18,9 +18,12 @@
 
 extern crate alloc;
 
-use alloc::{vec, vec::Vec};
+use alloc::{string::String, vec, vec::Vec};
 use codec::{Decode, Encode};
-use core::{fmt::Debug, marker::PhantomData};
+use core::{
+	fmt::{self, Debug},
+	marker::PhantomData,
+};
 use frame_support::{
 	dispatch::GetDispatchInfo,
 	ensure,
@@ -35,9 +38,9 @@ pub mod traits;
 use traits::{
 	validate_export, AssetExchange, AssetLock, CallDispatcher, ClaimAssets, ConvertOrigin,
 	DropAssets, Enact, EventEmitter, ExportXcm, FeeManager, FeeReason, HandleHrmpChannelAccepted,
-	HandleHrmpChannelClosing, HandleHrmpNewChannelOpenRequest, OnResponse, ProcessTransaction,
-	Properties, ShouldExecute, TransactAsset, VersionChangeNotifier, WeightBounds, WeightTrader,
-	XcmAssetTransfers,
+	HandleHrmpChannelClosing, HandleHrmpNewChannelOpenRequest, InstructionTrace, OnResponse,
+	ProcessTransaction, Properties, ShouldExecute, TransactAsset, VersionChangeNotifier,
+	WeightBounds, WeightTrader, XcmAssetTransfers,
 };
 
 pub use traits::RecordXcm;
@@ -46,6 +49,7 @@ mod assets;
 pub use assets::AssetsInHolding;
 mod config;
 pub use config::Config;
+pub mod conservation;
 
 #[cfg(test)]
 mod tests;
@@ -100,6 +104,11 @@ pub struct XcmExecutor<Config: config::Config> {
 	message_weight: Weight,
 	asset_claimer: Option<Location>,
 	already_paid_fees: bool,
+	/// Whether executed instructions are recorded through `Config::XcmRecorder::record_trace`.
+	tracing: bool,
+	/// Whether the conservation of the assets moved by executed instructions is checked, see
+	/// [`conservation::check_conservation`].
+	checking_conservation: bool,
 	_config: PhantomData<Config>,
 }
 
@@ -212,6 +221,27 @@ impl<Config: config::Config> XcmExecutor<Config> {
 	}
 }
 
+/// The name of the variant of `instr`, taken from its `Debug` representation.
+fn instruction_name<Call>(instr: &Instruction<Call>) -> String {
+	/// Collects the leading identifier written to it and rejects everything after.
+	struct Name(String);
+	impl fmt::Write for Name {
+		fn write_str(&mut self, s: &str) -> fmt::Result {
+			let end = s.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(s.len());
+			self.0.push_str(&s[..end]);
+			if end < s.len() {
+				Err(fmt::Error)
+			} else {
+				Ok(())
+			}
+		}
+	}
+
+	let mut name = Name(String::new());
+	let _ = fmt::write(&mut name, format_args!("{:?}", instr));
+	name.0
+}
+
 pub struct WeighedMessage<Call>(Weight, Xcm<Call>);
 impl<C> PreparedMessage for WeighedMessage<C> {
 	fn weight_of(&self) -> Weight {
@@ -232,7 +262,17 @@ impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Con
 		mut message: Xcm<Config::RuntimeCall>,
 		weight_limit: Weight,
 	) -> Result<Self::Prepared, InstructionError> {
-		match Config::Weigher::weight(&mut message, weight_limit) {
+		// Whatever is left in holding is trapped after execution, so the weight of trapping it is
+		// charged up front and refunded in `post_process` when nothing is trapped.
+		let weight = Config::Weigher::weight(&mut message, weight_limit).and_then(|weight| {
+			let weight = weight.saturating_add(Config::AssetTrap::max_drop_assets_weight());
+			if weight.any_gt(weight_limit) {
+				let index = message.len().saturating_sub(1).try_into().unwrap_or(u8::MAX);
+				return Err(InstructionError { index, error: XcmError::WeightLimitReached(weight) })
+			}
+			Ok(weight)
+		});
+		match weight {
 			Ok(weight) => Ok(WeighedMessage(weight, message)),
 			Err(error) => {
 				tracing::debug!(
@@ -293,6 +333,8 @@ impl<Config: config::Config> ExecuteXcm<Config::RuntimeCall> for XcmExecutor<Con
 
 		let mut vm = Self::new(origin, *id);
 		vm.message_weight = xcm_weight;
+		vm.tracing = Config::XcmRecorder::should_trace();
+		vm.checking_conservation = conservation::is_enabled();
 
 		while !message.0.is_empty() {
 			let result = vm.process(message);
@@ -381,6 +423,8 @@ impl<Config: config::Config> XcmExecutor<Config> {
 			message_weight: Weight::zero(),
 			asset_claimer: None,
 			already_paid_fees: false,
+			tracing: false,
+			checking_conservation: false,
 			_config: PhantomData,
 		}
 	}
@@ -394,7 +438,10 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		let _ = self.refund_surplus();
 		drop(self.trader);
 
-		let mut weight_used = xcm_weight.saturating_sub(self.total_surplus);
+		// The weight of trapping assets was charged up front, only what was used is kept.
+		let mut weight_used = xcm_weight
+			.saturating_sub(self.total_surplus)
+			.saturating_sub(Config::AssetTrap::max_drop_assets_weight());
 
 		if !self.holding.is_empty() {
 			tracing::trace!(
@@ -409,8 +456,20 @@ impl<Config: config::Config> XcmExecutor<Config> {
 			} else {
 				self.context.origin.as_ref().unwrap_or(&self.original_origin)
 			};
-			let trap_weight = Config::AssetTrap::drop_assets(claimer, self.holding, &self.context);
+			if self.checking_conservation {
+				conservation::begin(true, [], &self.holding, &self.fees);
+				conservation::note_outflow(&self.holding);
+			}
+			let trap_weight = Config::AssetTrap::drop_assets(
+				claimer,
+				core::mem::take(&mut self.holding),
+				&self.context,
+			);
 			weight_used.saturating_accrue(trap_weight);
+			if self.checking_conservation {
+				let effects = conservation::Effects::Applied;
+				conservation::end(None, "AssetTrap", effects, &self.holding, &self.fees);
+			}
 		};
 
 		match self.error {
@@ -572,6 +631,17 @@ impl<Config: config::Config> XcmExecutor<Config> {
 	}
 
 	fn take_fee(&mut self, fees: Assets, reason: FeeReason) -> XcmResult {
+		if !self.checking_conservation {
+			return self.do_take_fee(fees, reason);
+		}
+		// The fees end up wherever the fee manager and the asset exchanger put them.
+		let before = conservation::totals(&self.holding, &self.fees);
+		let result = self.do_take_fee(fees, reason);
+		conservation::note_unaccounted(before, &self.holding, &self.fees);
+		result
+	}
+
+	fn do_take_fee(&mut self, fees: Assets, reason: FeeReason) -> XcmResult {
 		if Config::FeeManager::is_waived(self.origin_ref(), reason.clone()) {
 			return Ok(());
 		}
@@ -762,9 +832,13 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				XcmError::UntrustedReserveLocation
 			);
 		}
+		conservation::note_outflow(&assets);
 		// Note that here we are able to place any assets which could not be
 		// reanchored back into Holding.
-		let reanchored_assets = Self::reanchored(assets, reserve, Some(failed_bin));
+		let mut failed = AssetsInHolding::new();
+		let reanchored_assets = Self::reanchored(assets, reserve, Some(&mut failed));
+		conservation::note_inflow(&failed);
+		failed_bin.subsume_assets(failed);
 		remote_xcm.push(WithdrawAsset(reanchored_assets.clone()));
 
 		Ok(reanchored_assets)
@@ -792,6 +866,7 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		for asset in assets.assets_iter() {
 			Config::AssetTransactor::check_out(dest, &asset, context);
 		}
+		conservation::note_outflow(&assets);
 		// Note that we pass `None` as `maybe_failed_bin` and drop any assets which
 		// cannot be reanchored, because we have already checked all assets out.
 		let reanchored_assets = Self::reanchored(assets, dest, None);
@@ -846,6 +921,20 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		for (i, mut instr) in xcm.0.into_iter().enumerate() {
 			match &mut result {
 				r @ Ok(()) => {
+					// Taken before the instruction is consumed, so it can be traced afterwards.
+					let traced = self.tracing.then(|| {
+						let depth = recursion_count::with(|count| count.saturating_sub(1));
+						let weight = Config::Weigher::instr_weight(&mut instr).unwrap_or_default();
+						(instruction_name(&instr), depth.unwrap_or(0), weight, self.total_surplus)
+					});
+					let checked = self.checking_conservation.then(|| {
+						let assets = conservation::instruction_assets(&instr)
+							.into_iter()
+							.chain(self.asset_used_in_buy_execution.clone());
+						let is_checked = conservation::is_checked(&instr);
+						conservation::begin(is_checked, assets, &self.holding, &self.fees);
+						instruction_name(&instr)
+					});
 					// Initialize the recursion count only the first time we hit this code in our
 					// potential recursive execution.
 					let inst_res = recursion_count::using_once(&mut 1, || {
@@ -869,6 +958,31 @@ impl<Config: config::Config> XcmExecutor<Config> {
 
 						self.process_instruction(instr)
 					});
+					if let Some(name) = checked {
+						let effects = match inst_res {
+							Ok(()) => conservation::Effects::Applied,
+							Err(_) if Config::TransactionalProcessor::IS_TRANSACTIONAL =>
+								conservation::Effects::Reverted,
+							Err(_) => conservation::Effects::Unknown,
+						};
+						conservation::end(
+							Some(i as u32),
+							&name,
+							effects,
+							&self.holding,
+							&self.fees,
+						);
+					}
+					if let Some((name, depth, weight, surplus_before)) = traced {
+						let refunded = self.total_surplus.saturating_sub(surplus_before);
+						self.trace_step(
+							i as u32,
+							depth,
+							name,
+							weight.saturating_sub(refunded),
+							inst_res.err(),
+						);
+					}
 					if let Err(error) = inst_res {
 						tracing::debug!(
 							target: "xcm::process",
@@ -895,6 +1009,27 @@ impl<Config: config::Config> XcmExecutor<Config> {
 		result
 	}
 
+	/// Record the state of the registers after the instruction at `index` was executed.
+	fn trace_step(
+		&self,
+		index: u32,
+		depth: u8,
+		instruction: String,
+		weight_used: Weight,
+		error: Option<XcmError>,
+	) {
+		Config::XcmRecorder::record_trace(InstructionTrace {
+			index,
+			depth,
+			instruction,
+			weight_used,
+			holding: Assets::from(self.holding.clone()).into(),
+			fees: Assets::from(self.fees.clone()).into(),
+			origin: self.context.origin.clone().map(Into::into),
+			error,
+		});
+	}
+
 	/// Process a single XCM instruction, mutating the state of the XCM virtual machine.
 	fn process_instruction(
 		&mut self,
@@ -943,7 +1078,9 @@ impl<Config: config::Config> XcmExecutor<Config> {
 						XcmError::UntrustedReserveLocation
 					);
 				}
-				self.holding.subsume_assets(assets.into());
+				let assets = assets.into();
+				conservation::note_inflow(&assets);
+				self.holding.subsume_assets(assets);
 				Ok(())
 			},
 			TransferAsset { assets, beneficiary } => {
@@ -1024,7 +1161,9 @@ impl<Config: config::Config> XcmExecutor<Config> {
 					Ok(())
 				})
 				.and_then(|_| {
-					self.holding.subsume_assets(assets.into());
+					let assets = assets.into();
+					conservation::note_inflow(&assets);
+					self.holding.subsume_assets(assets);
 					Ok(())
 				})
 			},
@@ -1537,7 +1676,9 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				self.ensure_can_subsume_assets(assets.len())?;
 				let ok = Config::AssetClaims::claim_assets(origin, &ticket, &assets, &self.context);
 				ensure!(ok, XcmError::UnknownClaim);
-				self.holding.subsume_assets(assets.into());
+				let assets = assets.into();
+				conservation::note_inflow(&assets);
+				self.holding.subsume_assets(assets);
 				Ok(())
 			},
 			Trap(code) => Err(XcmError::Trap(code)),
@@ -1559,7 +1700,8 @@ impl<Config: config::Config> XcmExecutor<Config> {
 				Config::SubscriptionService::stop(origin, &self.context)
 			},
 			BurnAsset(assets) => {
-				self.holding.saturating_take(assets.into());
+				let burnt = self.holding.saturating_take(assets.into());
+				conservation::note_outflow(&burnt);
 				Ok(())
 			},
 			ExpectAsset(assets) =>
@@ -0,0 +1,81 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Unit tests related to charging the weight of trapping the assets left in holding.
+
+use codec::Encode;
+use xcm::prelude::*;
+
+use super::mock::*;
+use crate::XcmExecutor;
+
+const SENDER: [u8; 32] = [0; 32];
+
+fn execute(xcm: Xcm<TestCall>) -> Outcome {
+	let mut hash = xcm.using_encoded(sp_io::hashing::blake2_256);
+	XcmExecutor::<XcmConfig>::prepare_and_execute(
+		SENDER,
+		xcm,
+		&mut hash,
+		Weight::MAX,
+		Weight::zero(),
+	)
+}
+
+#[test]
+fn asset_trap_weight_is_charged_up_front() {
+	TrapWeight::set(Weight::from_parts(5, 5));
+	let xcm = Xcm::<TestCall>(vec![WithdrawAsset((Here, 100u128).into())]);
+
+	let weight = XcmExecutor::<XcmConfig>::prepare(xcm.clone(), Weight::MAX)
+		.map(|prepared| prepared.weight_of());
+	assert_eq!(weight, Ok(Weight::from_parts(7, 7)));
+	// A limit covering the instructions but not the trap is not enough.
+	let weight = XcmExecutor::<XcmConfig>::prepare(xcm, Weight::from_parts(2, 2))
+		.map(|prepared| prepared.weight_of());
+	assert_eq!(
+		weight,
+		Err(InstructionError {
+			index: 0,
+			error: XcmError::WeightLimitReached(Weight::from_parts(7, 7))
+		})
+	);
+}
+
+#[test]
+fn asset_trap_weight_is_used_when_assets_are_trapped() {
+	TrapWeight::set(Weight::from_parts(5, 5));
+	add_asset(SENDER, (Here, 100u128));
+
+	let outcome = execute(Xcm(vec![WithdrawAsset((Here, 100u128).into())]));
+
+	assert_eq!(outcome, Outcome::Complete { used: Weight::from_parts(7, 7) });
+	assert_eq!(asset_list(TRAPPED_ASSETS), [(Here, 100u128).into()]);
+}
+
+#[test]
+fn asset_trap_weight_is_refunded_when_nothing_is_trapped() {
+	TrapWeight::set(Weight::from_parts(5, 5));
+	add_asset(SENDER, (Here, 100u128));
+
+	let outcome = execute(Xcm(vec![
+		WithdrawAsset((Here, 100u128).into()),
+		DepositAsset { assets: Wild(All), beneficiary: SENDER.into() },
+	]));
+
+	assert_eq!(outcome, Outcome::Complete { used: Weight::from_parts(2, 2) });
+	assert!(asset_list(TRAPPED_ASSETS).is_empty());
+}
@@ -0,0 +1,209 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Unit tests related to checking the conservation of assets moved by the executor.
+
+use codec::Encode;
+use xcm::prelude::*;
+
+use super::mock::*;
+use crate::{
+	conservation::{check_conservation, ConservationViolation},
+	XcmExecutor,
+};
+
+const SENDER: [u8; 32] = [0; 32];
+const RECIPIENT: [u8; 32] = [1; 32];
+
+/// The supply of `id` in all accounts but the trapped assets one, counting each instance of a
+/// non-fungible asset as one.
+fn circulating(id: &AssetId) -> Option<u128> {
+	let trapped = Location::from(TRAPPED_ASSETS);
+	let supply = ASSETS.with(|assets| {
+		assets
+			.borrow()
+			.iter()
+			.filter(|(who, _)| **who != trapped)
+			.map(|(_, assets)| {
+				let instances = assets.non_fungible.iter().filter(|(asset, _)| asset == id).count();
+				assets.fungible.get(id).copied().unwrap_or_default() + instances as u128
+			})
+			.sum()
+	});
+	Some(supply)
+}
+
+/// The supply of `id` in all accounts but the trapped assets one and the recipient, so that
+/// depositing to the recipient looks like a transactor burning the assets.
+fn circulating_without_recipient(id: &AssetId) -> Option<u128> {
+	let recipient = asset_list(RECIPIENT)
+		.into_iter()
+		.filter_map(|asset| match asset {
+			Asset { id: asset_id, fun: Fungible(amount) } if asset_id == *id => Some(amount),
+			Asset { id: asset_id, fun: NonFungible(_) } if asset_id == *id => Some(1),
+			_ => None,
+		})
+		.sum::<u128>();
+	circulating(id).map(|supply| supply - recipient)
+}
+
+fn execute(xcm: Xcm<TestCall>) -> Outcome {
+	let mut hash = xcm.using_encoded(sp_io::hashing::blake2_256);
+	XcmExecutor::<XcmConfig>::prepare_and_execute(
+		SENDER,
+		xcm,
+		&mut hash,
+		Weight::MAX,
+		Weight::zero(),
+	)
+}
+
+#[test]
+fn conserved_assets_are_not_reported() {
+	add_asset(SENDER, (Here, 100u128));
+
+	let xcm = Xcm::<TestCall>(vec![
+		WithdrawAsset((Here, 100u128).into()),
+		PayFees { asset: (Here, 10u128).into() },
+		BurnAsset((Here, 5u128).into()),
+		// Pays the delivery fee from the fees register.
+		InitiateReserveWithdraw {
+			assets: Definite((Here, 20u128).into()),
+			reserve: Parent.into(),
+			xcm: Xcm(vec![]),
+		},
+		DepositAsset { assets: Definite((Here, 60u128).into()), beneficiary: RECIPIENT.into() },
+	]);
+	let (outcome, violations) = check_conservation(circulating, || execute(xcm));
+
+	assert!(outcome.ensure_complete().is_ok());
+	assert_eq!(violations, vec![]);
+	assert_eq!(asset_list(RECIPIENT), [(Here, 60u128).into()]);
+	// The rest of the holding register and the unused fees are trapped.
+	assert!(!asset_list(TRAPPED_ASSETS).is_empty());
+}
+
+#[test]
+fn assets_not_conserved_are_reported_with_the_instruction() {
+	add_asset(SENDER, (Here, 100u128));
+
+	let xcm = Xcm::<TestCall>(vec![
+		WithdrawAsset((Here, 100u128).into()),
+		PayFees { asset: (Here, 10u128).into() },
+		DepositAsset { assets: Wild(All), beneficiary: RECIPIENT.into() },
+	]);
+	let (outcome, violations) = check_conservation(circulating_without_recipient, || execute(xcm));
+
+	assert!(outcome.ensure_complete().is_ok());
+	assert_eq!(
+		violations,
+		vec![ConservationViolation {
+			index: Some(2),
+			instruction: "DepositAsset".into(),
+			asset: Here.into(),
+			expected: 0,
+			actual: -90,
+		}]
+	);
+}
+
+#[test]
+fn teleported_assets_are_conserved() {
+	let xcm = Xcm::<TestCall>(vec![
+		ReceiveTeleportedAsset((Here, 100u128).into()),
+		PayFees { asset: (Here, 10u128).into() },
+		InitiateTeleport {
+			assets: Definite((Here, 40u128).into()),
+			dest: Parent.into(),
+			xcm: Xcm(vec![]),
+		},
+		DepositAsset { assets: Wild(All), beneficiary: RECIPIENT.into() },
+	]);
+	let (outcome, violations) = check_conservation(circulating, || execute(xcm));
+
+	assert!(outcome.ensure_complete().is_ok());
+	assert_eq!(violations, vec![]);
+	assert_eq!(asset_list(RECIPIENT), [(Here, 50u128).into()]);
+}
+
+#[test]
+fn non_fungible_assets_are_checked() {
+	let nft: Asset = (GeneralIndex(1), Index(7)).into();
+	add_asset(SENDER, nft.clone());
+
+	let xcm = Xcm::<TestCall>(vec![
+		WithdrawAsset(nft.clone().into()),
+		DepositAsset { assets: Wild(All), beneficiary: RECIPIENT.into() },
+	]);
+	let (outcome, violations) = check_conservation(circulating, || execute(xcm));
+
+	assert!(outcome.ensure_complete().is_ok());
+	assert_eq!(violations, vec![]);
+	assert_eq!(asset_list(RECIPIENT), [nft.clone()]);
+
+	let other: Asset = (GeneralIndex(1), Index(8)).into();
+	add_asset(SENDER, other.clone());
+
+	let xcm = Xcm::<TestCall>(vec![
+		WithdrawAsset(other.into()),
+		DepositAsset { assets: Wild(All), beneficiary: RECIPIENT.into() },
+	]);
+	let (outcome, violations) = check_conservation(circulating_without_recipient, || execute(xcm));
+
+	assert!(outcome.ensure_complete().is_ok());
+	assert_eq!(
+		violations,
+		vec![ConservationViolation {
+			index: Some(1),
+			instruction: "DepositAsset".into(),
+			asset: nft.id,
+			expected: 0,
+			actual: -1,
+		}]
+	);
+}
+
+#[test]
+fn reverted_instructions_are_expected_to_move_nothing() {
+	add_asset(SENDER, (Here, 100u128));
+
+	// Nothing is withdrawn when the sender doesn't have enough.
+	let xcm = Xcm::<TestCall>(vec![WithdrawAsset((Here, 1000u128).into())]);
+	let (outcome, violations) = check_conservation(circulating, || execute(xcm));
+
+	assert!(outcome.ensure_complete().is_err());
+	assert_eq!(violations, vec![]);
+	assert_eq!(asset_list(SENDER), [(Here, 100u128).into()]);
+
+	// The test transactional processor doesn't roll back the storage, so the partial withdrawal
+	// remains even though the failed instruction is reported as reverted.
+	let xcm = Xcm::<TestCall>(vec![WithdrawAsset(
+		vec![(Here, 100u128).into(), (Parent, 50u128).into()].into(),
+	)]);
+	let (outcome, violations) = check_conservation(circulating, || execute(xcm));
+
+	assert!(outcome.ensure_complete().is_err());
+	assert_eq!(
+		violations,
+		vec![ConservationViolation {
+			index: Some(0),
+			instruction: "WithdrawAsset".into(),
+			asset: Here.into(),
+			expected: 0,
+			actual: -100,
+		}]
+	);
+}
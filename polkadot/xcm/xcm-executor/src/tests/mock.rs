@@ -30,8 +30,8 @@ use xcm::prelude::*;
 
 use crate::{
 	traits::{
-		DropAssets, FeeManager, ProcessTransaction, Properties, ShouldExecute, TransactAsset,
-		WeightBounds, WeightTrader,
+		DropAssets, FeeManager, InstructionTrace, ProcessTransaction, Properties, RecordXcm,
+		ShouldExecute, TransactAsset, WeightBounds, WeightTrader,
 	},
 	AssetsInHolding, Config, FeeReason, XcmExecutor,
 };
@@ -58,6 +58,8 @@ parameter_types! {
 	pub UniversalLocation: InteriorLocation = [GlobalConsensus(ByGenesis([0; 32])), Parachain(1000)].into();
 	/// Simulate the chain’s existential deposit.
 	pub const ExistentialDeposit: u128 = 2;
+	/// Weight of trapping the assets left in holding.
+	pub static TrapWeight: Weight = Weight::zero();
 }
 
 /// Test origin.
@@ -230,7 +232,11 @@ impl DropAssets for TestAssetTrap {
 				.or_insert(AssetsInHolding::new())
 				.subsume_assets(assets)
 		});
-		Weight::zero()
+		TrapWeight::get()
+	}
+
+	fn max_drop_assets_weight() -> Weight {
+		TrapWeight::get()
 	}
 }
 
@@ -296,6 +302,46 @@ impl ProcessTransaction for TestTransactionalProcessor {
 }
 
 /// Test XcmConfig that uses all the test implementations in this file.
+thread_local! {
+	pub static TRACE: RefCell<Option<Vec<InstructionTrace>>> = RefCell::new(None);
+}
+
+/// Test recorder that only supports tracing, keeping the trace in memory.
+pub struct TestRecorder;
+impl RecordXcm for TestRecorder {
+	fn should_record() -> bool {
+		false
+	}
+
+	fn set_record_xcm(_: bool) {}
+
+	fn recorded_xcm() -> Option<Xcm<()>> {
+		None
+	}
+
+	fn record(_: Xcm<()>) {}
+
+	fn should_trace() -> bool {
+		TRACE.with(|trace| trace.borrow().is_some())
+	}
+
+	fn set_trace_xcm(enabled: bool) {
+		TRACE.with(|trace| *trace.borrow_mut() = enabled.then(Vec::new));
+	}
+
+	fn take_trace() -> Vec<InstructionTrace> {
+		TRACE.with(|trace| trace.borrow_mut().as_mut().map(core::mem::take).unwrap_or_default())
+	}
+
+	fn record_trace(step: InstructionTrace) {
+		TRACE.with(|trace| {
+			if let Some(trace) = trace.borrow_mut().as_mut() {
+				trace.push(step);
+			}
+		});
+	}
+}
+
 pub struct XcmConfig;
 impl Config for XcmConfig {
 	type RuntimeCall = TestCall;
@@ -304,7 +350,8 @@ impl Config for XcmConfig {
 	type AssetTransactor = TestAssetTransactor;
 	type OriginConverter = ();
 	type IsReserve = ();
-	type IsTeleporter = ();
+	// Trust every origin to teleport any asset, so that teleports can be tested.
+	type IsTeleporter = Everything;
 	type UniversalLocation = UniversalLocation;
 	type Barrier = TestBarrier;
 	type Weigher = TestWeigher;
@@ -327,5 +374,5 @@ impl Config for XcmConfig {
 	type HrmpNewChannelOpenRequestHandler = ();
 	type HrmpChannelAcceptedHandler = ();
 	type HrmpChannelClosingHandler = ();
-	type XcmRecorder = ();
+	type XcmRecorder = TestRecorder;
 }
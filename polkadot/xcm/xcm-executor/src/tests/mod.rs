@@ -20,7 +20,10 @@
 //! `xcm-emulator` based tests in the cumulus folder.
 //! These tests deal with internal state changes of the XCVM.
 
+mod asset_trap;
+mod conservation;
 mod initiate_transfer;
 mod mock;
 mod pay_fees;
 mod set_asset_claimer;
+mod trace;
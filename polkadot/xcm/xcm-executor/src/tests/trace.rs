@@ -0,0 +1,123 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Unit tests related to tracing executed instructions through `RecordXcm`.
+
+use codec::Encode;
+use xcm::prelude::*;
+
+use super::mock::*;
+use crate::{
+	traits::{InstructionTrace, RecordXcm},
+	XcmExecutor,
+};
+
+const SENDER: [u8; 32] = [0; 32];
+const RECIPIENT: [u8; 32] = [1; 32];
+
+fn step(
+	index: u32,
+	instruction: &str,
+	holding: u128,
+	fees: u128,
+	origin: Option<Location>,
+) -> InstructionTrace {
+	let register = |amount: u128| -> VersionedAssets {
+		let assets = if amount == 0 { Assets::new() } else { (Here, amount).into() };
+		assets.into()
+	};
+	InstructionTrace {
+		index,
+		depth: 0,
+		instruction: instruction.into(),
+		weight_used: Weight::from_parts(2, 2),
+		holding: register(holding),
+		fees: register(fees),
+		origin: origin.map(Into::into),
+		error: None,
+	}
+}
+
+#[test]
+fn traces_every_executed_instruction() {
+	add_asset(SENDER, (Here, 100u128));
+	TestRecorder::set_trace_xcm(true);
+
+	let xcm = Xcm::<TestCall>::builder_unsafe()
+		.set_appendix(Xcm::builder_unsafe().deposit_asset(All, RECIPIENT).build())
+		.withdraw_asset((Here, 100u128))
+		.pay_fees((Here, 10u128))
+		.clear_origin()
+		// Fails, there's no origin to withdraw from anymore.
+		.withdraw_asset((Here, 1u128))
+		.clear_topic()
+		.build();
+	let mut hash = xcm.using_encoded(sp_io::hashing::blake2_256);
+	let outcome = XcmExecutor::<XcmConfig>::prepare_and_execute(
+		SENDER,
+		xcm,
+		&mut hash,
+		Weight::MAX,
+		Weight::zero(),
+	);
+	assert!(matches!(
+		outcome,
+		Outcome::Incomplete {
+			error: InstructionError { index: 4, error: XcmError::BadOrigin },
+			..
+		}
+	));
+
+	let sender = Some(Location::from(SENDER));
+	assert_eq!(
+		TestRecorder::take_trace(),
+		vec![
+			step(0, "SetAppendix", 0, 0, sender.clone()),
+			step(1, "WithdrawAsset", 100, 0, sender.clone()),
+			// Execution costs 4, the rest of the fees is kept in the fees register.
+			step(2, "PayFees", 90, 6, sender),
+			step(3, "ClearOrigin", 90, 6, None),
+			InstructionTrace {
+				error: Some(XcmError::BadOrigin),
+				..step(4, "WithdrawAsset", 90, 6, None)
+			},
+			// `ClearTopic` is skipped, but the appendix still runs.
+			step(0, "DepositAsset", 0, 6, None),
+		]
+	);
+	assert_eq!(asset_list(RECIPIENT), [(Here, 90u128).into()]);
+}
+
+#[test]
+fn nothing_is_traced_when_disabled() {
+	add_asset(SENDER, (Here, 100u128));
+	TestRecorder::set_trace_xcm(false);
+
+	let xcm = Xcm::<TestCall>::builder_unsafe()
+		.withdraw_asset((Here, 100u128))
+		.deposit_asset(All, RECIPIENT)
+		.build();
+	let mut hash = xcm.using_encoded(sp_io::hashing::blake2_256);
+	let outcome = XcmExecutor::<XcmConfig>::prepare_and_execute(
+		SENDER,
+		xcm,
+		&mut hash,
+		Weight::MAX,
+		Weight::zero(),
+	);
+	assert!(outcome.ensure_complete().is_ok());
+	assert!(TestRecorder::take_trace().is_empty());
+}
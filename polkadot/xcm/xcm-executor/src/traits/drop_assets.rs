@@ -23,6 +23,12 @@ use xcm::latest::{Assets, Location, Weight, XcmContext};
 pub trait DropAssets {
 	/// Handler for receiving dropped assets. Returns the weight consumed by this operation.
 	fn drop_assets(origin: &Location, assets: AssetsInHolding, context: &XcmContext) -> Weight;
+
+	/// The most weight `drop_assets` may consume. It is charged up front for every message and
+	/// refunded when no assets are left to drop.
+	fn max_drop_assets_weight() -> Weight {
+		Weight::zero()
+	}
 }
 impl DropAssets for () {
 	fn drop_assets(_origin: &Location, _assets: AssetsInHolding, _context: &XcmContext) -> Weight {
@@ -42,6 +48,10 @@ impl<D: DropAssets, A: Contains<AssetsInHolding>> DropAssets for FilterAssets<D,
 			Weight::zero()
 		}
 	}
+
+	fn max_drop_assets_weight() -> Weight {
+		D::max_drop_assets_weight()
+	}
 }
 
 /// Morph a given `DropAssets` implementation into one which can filter based on origin. This can
@@ -57,6 +67,10 @@ impl<D: DropAssets, O: Contains<Location>> DropAssets for FilterOrigin<D, O> {
 			Weight::zero()
 		}
 	}
+
+	fn max_drop_assets_weight() -> Weight {
+		D::max_drop_assets_weight()
+	}
 }
 
 /// Define any handlers for the `AssetClaim` instruction.
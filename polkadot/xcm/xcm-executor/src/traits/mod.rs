@@ -42,7 +42,9 @@ pub use on_response::{OnResponse, QueryHandler, QueryResponseStatus, VersionChan
 mod process_transaction;
 pub use process_transaction::ProcessTransaction;
 mod should_execute;
-pub use should_execute::{CheckSuspension, DenyExecution, Properties, ShouldExecute};
+pub use should_execute::{
+	CheckRateLimit, CheckSuspension, DenyExecution, Properties, ShouldExecute,
+};
 mod transact_asset;
 pub use transact_asset::TransactAsset;
 mod hrmp;
@@ -54,7 +56,7 @@ mod record_xcm;
 mod weight;
 pub use event_emitter::EventEmitter;
 
-pub use record_xcm::RecordXcm;
+pub use record_xcm::{InstructionTrace, RecordXcm};
 #[deprecated = "Use `sp_runtime::traits::` instead"]
 pub use sp_runtime::traits::{Identity, TryConvertInto as JustTry};
 pub use weight::{WeightBounds, WeightTrader};
@@ -16,7 +16,52 @@
 
 //! Trait for recording XCMs and a dummy implementation.
 
-use xcm::latest::Xcm;
+use alloc::{string::String, vec::Vec};
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_weights::Weight;
+use xcm::{
+	latest::{Error as XcmError, Xcm},
+	IntoVersion, Version, VersionedAssets, VersionedLocation,
+};
+
+/// The state of the executor after executing a single instruction.
+///
+/// Recorded for every instruction the executor processes while tracing is enabled, see
+/// [`RecordXcm::should_trace`].
+#[derive(Clone, Encode, Decode, Debug, PartialEq, Eq, TypeInfo)]
+pub struct InstructionTrace {
+	/// Index of the instruction within the program it belongs to.
+	pub index: u32,
+	/// How deeply the program is nested, `0` for the message itself (and its error handler and
+	/// appendix), `1` for messages executed by its `Transact` instructions and so on.
+	pub depth: u8,
+	/// Name of the instruction.
+	pub instruction: String,
+	/// The weight used by the instruction, after any refund. Includes the weight of nested
+	/// messages.
+	pub weight_used: Weight,
+	/// The holding register after the instruction was executed.
+	pub holding: VersionedAssets,
+	/// The fees register after the instruction was executed.
+	pub fees: VersionedAssets,
+	/// The origin after the instruction was executed.
+	pub origin: Option<VersionedLocation>,
+	/// The error the instruction failed with, if any.
+	pub error: Option<XcmError>,
+}
+
+impl InstructionTrace {
+	/// Convert the versioned registers and origin of the step to `version`.
+	pub fn into_version(self, version: Version) -> Result<Self, ()> {
+		Ok(Self {
+			holding: self.holding.into_version(version)?,
+			fees: self.fees.into_version(version)?,
+			origin: self.origin.map(|origin| origin.into_version(version)).transpose()?,
+			..self
+		})
+	}
+}
 
 /// Trait for recording XCMs.
 pub trait RecordXcm {
@@ -29,6 +74,25 @@ pub trait RecordXcm {
 	fn recorded_xcm() -> Option<Xcm<()>>;
 	/// Record `xcm`.
 	fn record(xcm: Xcm<()>);
+
+	/// Whether or not we should record a trace of the executed instructions.
+	///
+	/// Like recording, this is meant for dry-running only and disabled by default.
+	fn should_trace() -> bool {
+		false
+	}
+	/// Enable or disable tracing. Any previously recorded trace is cleared.
+	fn set_trace_xcm(_enabled: bool) {}
+	/// Take the steps recorded since tracing was enabled.
+	/// Returns an empty trace if no message was executed, or if tracing was off.
+	fn take_trace() -> Vec<InstructionTrace> {
+		Vec::new()
+	}
+	/// Record an executed instruction.
+	///
+	/// Steps are recorded once their instruction completes, so the steps of a nested message
+	/// precede the instruction which executed it.
+	fn record_trace(_step: InstructionTrace) {}
 }
 
 impl RecordXcm for () {
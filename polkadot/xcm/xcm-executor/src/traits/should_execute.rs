@@ -128,6 +128,32 @@ impl CheckSuspension for Tuple {
 	}
 }
 
+/// Trait to account the XCMs of an origin against an execution budget.
+///
+/// The trait method is given the same parameters as `ShouldExecute::should_execute`. If the given
+/// XCM fits into the remaining budget of `origin`, it is accounted and `Ok(())` is returned.
+/// Otherwise nothing must be accounted, and `Err(ProcessMessageError::Yield)` is returned if the
+/// XCM would fit once the budget is replenished, or a permanent error if it never would.
+pub trait CheckRateLimit {
+	fn try_consume<Call>(
+		origin: &Location,
+		instructions: &mut [Instruction<Call>],
+		max_weight: Weight,
+		properties: &mut Properties,
+	) -> Result<(), ProcessMessageError>;
+}
+
+impl CheckRateLimit for () {
+	fn try_consume<Call>(
+		_origin: &Location,
+		_instructions: &mut [Instruction<Call>],
+		_max_weight: Weight,
+		_properties: &mut Properties,
+	) -> Result<(), ProcessMessageError> {
+		Ok(())
+	}
+}
+
 /// Trait to determine whether the execution engine should not execute a given XCM.
 ///
 /// Can be amalgamated into a tuple to have multiple traits. If any of the tuple elements returns
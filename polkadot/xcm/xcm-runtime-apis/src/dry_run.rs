@@ -23,6 +23,8 @@ use codec::{Decode, Encode};
 use frame_support::pallet_prelude::{DispatchResultWithPostInfo, TypeInfo};
 use xcm::prelude::*;
 
+pub use xcm_executor::traits::InstructionTrace;
+
 /// Effects of dry-running an extrinsic.
 #[derive(Encode, Decode, Debug, TypeInfo)]
 pub struct CallDryRunEffects<Event> {
@@ -47,6 +49,18 @@ pub struct XcmDryRunEffects<Event> {
 	pub forwarded_xcms: Vec<(VersionedLocation, Vec<VersionedXcm<()>>)>,
 }
 
+/// Effects of a dry-run together with a trace of the instructions executed locally.
+#[derive(Encode, Decode, Debug, TypeInfo)]
+pub struct TracedDryRunEffects<Effects> {
+	/// The effects of the dry-run.
+	pub effects: Effects,
+	/// The state of the XCM executor after every instruction executed during the dry-run, in
+	/// execution order.
+	///
+	/// Empty if the runtime's XCM executor doesn't record traces.
+	pub trace: Vec<InstructionTrace>,
+}
+
 sp_api::decl_runtime_apis! {
 	/// API for dry-running extrinsics and XCM programs to get the programs that need to be passed to the fees API.
 	///
@@ -73,6 +87,18 @@ sp_api::decl_runtime_apis! {
 
 		/// Dry run XCM program
 		fn dry_run_xcm(origin_location: VersionedLocation, xcm: VersionedXcm<Call>) -> Result<XcmDryRunEffects<Event>, Error>;
+
+		/// Dry run call, tracing every XCM instruction executed locally.
+		///
+		/// The trace uses `result_xcms_version`, like the returned XCMs.
+		#[api_version(3)]
+		fn dry_run_call_with_trace(origin: OriginCaller, call: Call, result_xcms_version: XcmVersion) -> Result<TracedDryRunEffects<CallDryRunEffects<Event>>, Error>;
+
+		/// Dry run XCM program, tracing every instruction executed.
+		///
+		/// The trace uses the version of `xcm`.
+		#[api_version(3)]
+		fn dry_run_xcm_with_trace(origin_location: VersionedLocation, xcm: VersionedXcm<Call>) -> Result<TracedDryRunEffects<XcmDryRunEffects<Event>>, Error>;
 	}
 }
 
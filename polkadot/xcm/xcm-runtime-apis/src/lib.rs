@@ -30,6 +30,12 @@ pub mod dry_run;
 /// Fee estimation API.
 /// Given an XCM program, it will return the fees needed to execute it properly or send it.
 pub mod fees;
+/// Transfer fee estimation API.
+/// Given a transfer extrinsic, it returns its fees on the local chain and on its destinations.
+pub mod transfer_fees;
+/// Trapped assets API.
+/// Given an origin, it returns the assets trapped for it and claimable by it.
+pub mod trapped_assets;
 /// Exposes runtime API for querying whether a Location is trusted as a reserve or teleporter for a
 /// given Asset.
 pub mod trusted_query;
@@ -0,0 +1,142 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for estimating all the fees of a cross-chain transfer at once.
+//!
+//! Quoting a transfer otherwise requires dry-running it with the `DryRunApi`, then querying the
+//! `XcmPaymentApi` of the local chain for the execution and delivery fees and the `XcmPaymentApi`
+//! of every destination for the execution fees of the forwarded messages.
+
+use crate::{dry_run::Error as DryRunError, fees::Error as FeesError};
+use alloc::vec::Vec;
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use frame_support::pallet_prelude::{DispatchResultWithPostInfo, TypeInfo};
+use sp_weights::Weight;
+use xcm::prelude::*;
+
+/// What a destination charges for executing the message of a transfer, as reported by the
+/// destination's `XcmPaymentApi`.
+///
+/// The local chain can't weigh or price messages the way the destination does, so these are the
+/// destination's own results. Callers get the forwarded messages from a first estimate, query
+/// the destination for them and supply the results to [`TransferFeesApi::estimate_transfer_fees`].
+/// Governance can cache the results for a typical transfer message to the destination on chain.
+#[derive(Clone, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, Debug, TypeInfo)]
+pub struct DestinationFeeInfo {
+	/// The weight of the message, as returned by `XcmPaymentApi::query_xcm_weight`.
+	pub xcm_weight: Weight,
+	/// The asset execution fees are paid in, as seen by the destination.
+	pub fee_asset: VersionedAssetId,
+	/// The fee for `xcm_weight` in `fee_asset`, as returned by
+	/// `XcmPaymentApi::query_weight_to_asset_fee`.
+	pub fee: u128,
+}
+
+/// The estimated cost of executing a forwarded message on its destination.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub struct RemoteExecution {
+	/// The weight of the message on the destination.
+	pub weight: Weight,
+	/// The fee for `weight`, in the destination's fee asset.
+	pub fee: VersionedAsset,
+}
+
+/// A message sent by the transfer, with its fees.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub struct ForwardedXcm {
+	/// The destination of the message.
+	pub destination: VersionedLocation,
+	/// The message the destination will execute.
+	pub message: VersionedXcm<()>,
+	/// The fees charged locally for delivering the message.
+	pub delivery_fees: VersionedAssets,
+	/// The cost of executing the message on `destination`, if its [`DestinationFeeInfo`] was
+	/// supplied or is cached.
+	pub remote_execution: Option<RemoteExecution>,
+}
+
+/// All the fees of a transfer.
+#[derive(Encode, Decode, Debug, TypeInfo)]
+pub struct TransferFeeEstimate {
+	/// The result of dispatching the transfer.
+	pub execution_result: DispatchResultWithPostInfo,
+	/// The weight of the XCM executed locally, if any.
+	pub local_xcm_weight: Option<Weight>,
+	/// The fee for `local_xcm_weight`, in the requested asset.
+	pub local_execution_fee: Option<VersionedAsset>,
+	/// Every message sent by the transfer.
+	pub forwarded_xcms: Vec<ForwardedXcm>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API for estimating the fees of a transfer extrinsic on the local chain and on the chains
+	/// it sends messages to.
+	pub trait TransferFeesApi<Call, OriginCaller>
+	where
+		Call: Encode,
+		OriginCaller: Encode
+	{
+		/// Estimate the fees of dispatching `call` with `origin`.
+		///
+		/// # Arguments
+		///
+		/// * `origin`: The origin dispatching the transfer.
+		/// * `call`: The transfer, e.g. `PolkadotXcm::transfer_assets`.
+		/// * `fee_asset`: The asset the local execution fee is quoted in.
+		/// * `destination_fee_infos`: What destinations charge for executing the forwarded
+		///   messages, from their `XcmPaymentApi`. Destinations not in this list use the
+		///   information cached on chain, if any.
+		/// * `result_xcms_version`: The XCM version of the returned messages and assets.
+		fn estimate_transfer_fees(
+			origin: OriginCaller,
+			call: Call,
+			fee_asset: VersionedAssetId,
+			destination_fee_infos: Vec<(VersionedLocation, DestinationFeeInfo)>,
+			result_xcms_version: XcmVersion,
+		) -> Result<TransferFeeEstimate, Error>;
+	}
+}
+
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Error {
+	/// Dry-running the transfer failed.
+	#[codec(index = 0)]
+	DryRun(DryRunError),
+
+	/// Quoting local execution or delivery fees failed.
+	#[codec(index = 1)]
+	Fees(FeesError),
+
+	/// Converting a versioned data structure from one version to another failed.
+	#[codec(index = 2)]
+	VersionedConversionFailed,
+
+	/// The transfer could not be dry-run in a storage transaction, as too many are nested.
+	#[codec(index = 3)]
+	TransactionalLimitReached,
+}
+
+impl From<DryRunError> for Error {
+	fn from(error: DryRunError) -> Self {
+		Self::DryRun(error)
+	}
+}
+
+impl From<FeesError> for Error {
+	fn from(error: FeesError) -> Self {
+		Self::Fees(error)
+	}
+}
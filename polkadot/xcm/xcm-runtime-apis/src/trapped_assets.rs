@@ -0,0 +1,72 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for listing the assets trapped on a chain.
+//!
+//! Assets left in the holding register at the end of an XCM execution are trapped. Claiming them
+//! requires the exact trapped assets, which this API returns for every origin that can claim them.
+//!
+//! Traps are listed by the origin they were trapped for rather than by beneficiary: assets are
+//! trapped precisely because the program never deposited them, so there is no beneficiary to list
+//! them by. The origin is the only location able to claim them, and it picks the beneficiary when
+//! it does. To find the assets trapped for an account on another chain, query the location of
+//! that account as seen from this chain, e.g. `../Parachain(2000)/AccountId32(..)` for messages
+//! sent by an account of parachain 2000, or `../Parachain(2000)` for messages sent by the chain
+//! itself.
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use frame_support::{pallet_prelude::TypeInfo, sp_runtime::H256};
+use xcm::{VersionedAssets, VersionedLocation};
+
+/// Assets trapped for an origin.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub struct TrappedAssets<BlockNumber> {
+	/// The hash identifying the trap, which is the blake2 256 hash of the (origin, assets) pair.
+	pub hash: H256,
+	/// The trapped assets, in the version they were trapped with.
+	pub assets: VersionedAssets,
+	/// The number of times these exact assets were trapped for the origin.
+	pub count: u32,
+	/// The block after which governance can sweep the assets, if they expire.
+	pub expires_at: Option<BlockNumber>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API for listing the assets trapped for an origin.
+	pub trait TrappedAssetsApi<BlockNumber>
+	where
+		BlockNumber: Encode + Decode
+	{
+		/// Returns up to `max` traps of `origin`, starting after the trap with hash `start_after`.
+		///
+		/// Pass the hash of the last trap returned to get the next page. Only the traps recorded
+		/// while asset trap recovery is enabled are returned.
+		fn trapped_assets(
+			origin: VersionedLocation,
+			start_after: Option<H256>,
+			max: u32,
+		) -> Result<Vec<TrappedAssets<BlockNumber>>, Error>;
+	}
+}
+
+/// `TrappedAssetsApi` Runtime APIs errors.
+#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, Debug, TypeInfo)]
+pub enum Error {
+	/// Converting a location from one version to another failed.
+	#[codec(index = 0)]
+	LocationVersionConversionFailed,
+}
@@ -16,19 +16,22 @@
 
 //! Tests for using both the XCM fee payment API and the dry-run API.
 
-use frame_support::sp_runtime::testing::H256;
+use frame_support::{assert_ok, sp_runtime::testing::H256};
 use frame_system::RawOrigin;
 use sp_api::ProvideRuntimeApi;
 use xcm::prelude::*;
+use xcm_executor::traits::RecordXcm;
 use xcm_runtime_apis::{
-	dry_run::{CallDryRunEffects, DryRunApi},
+	dry_run::{CallDryRunEffects, DryRunApi, InstructionTrace},
 	fees::XcmPaymentApi,
+	transfer_fees::{DestinationFeeInfo, ForwardedXcm, RemoteExecution, TransferFeesApi},
 };
 
 mod mock;
 use mock::{
-	new_test_ext_with_balances, new_test_ext_with_balances_and_assets, DeliveryFees,
-	ExistentialDeposit, HereLocation, OriginCaller, RuntimeCall, RuntimeEvent, TestClient,
+	new_test_ext_with_balances, new_test_ext_with_balances_and_assets, BaseXcmWeight, DeliveryFees,
+	ExistentialDeposit, HereLocation, OriginCaller, RuntimeCall, RuntimeEvent, RuntimeOrigin,
+	TestClient, XcmPallet,
 };
 use xcm_simulator::fake_message_hash;
 
@@ -443,6 +446,69 @@ fn dry_run_xcm_versions() {
 	}
 }
 
+// The program fails after buying execution because it tries to withdraw again with a cleared
+// origin. The trace shows which instruction failed and the state of the executor at every step.
+#[test]
+fn dry_run_xcm_with_trace_works() {
+	sp_tracing::init_for_tests();
+	let who = 1; // AccountId = u64.
+	let transfer_amount = 100u128;
+	let xcm = Xcm::<RuntimeCall>::builder_unsafe()
+		.withdraw_asset((Here, transfer_amount))
+		.clear_origin()
+		.buy_execution((Here, transfer_amount), Unlimited)
+		.withdraw_asset((Here, 1u128))
+		.build();
+	let origin: Location = [AccountIndex64 { index: who, network: None }].into();
+	let balances = vec![(who, transfer_amount + ExistentialDeposit::get())];
+	new_test_ext_with_balances(balances).execute_with(|| {
+		let client = TestClient;
+		let runtime_api = client.runtime_api();
+		let traced = runtime_api
+			.dry_run_xcm_with_trace(
+				H256::zero(),
+				VersionedLocation::from(origin.clone()),
+				VersionedXcm::from(xcm),
+			)
+			.unwrap()
+			.unwrap();
+		assert_eq!(
+			traced.effects.execution_result,
+			Outcome::Incomplete {
+				used: BaseXcmWeight::get() * 4,
+				error: InstructionError { index: 3, error: XcmError::BadOrigin },
+			}
+		);
+
+		let step = |index: u32, instruction: &str, origin: Option<Location>| InstructionTrace {
+			index,
+			depth: 0,
+			instruction: instruction.into(),
+			weight_used: BaseXcmWeight::get(),
+			holding: Assets::from((Here, transfer_amount)).into(),
+			fees: Assets::new().into(),
+			origin: origin.map(Into::into),
+			error: None,
+		};
+		assert_eq!(
+			traced.trace,
+			vec![
+				step(0, "WithdrawAsset", Some(origin)),
+				step(1, "ClearOrigin", None),
+				step(2, "BuyExecution", None),
+				InstructionTrace {
+					error: Some(XcmError::BadOrigin),
+					..step(3, "WithdrawAsset", None)
+				},
+			]
+		);
+
+		// Tracing is only enabled for the duration of the dry-run.
+		assert!(!XcmPallet::should_trace());
+		assert!(XcmPallet::take_trace().is_empty());
+	});
+}
+
 #[test]
 fn calling_payment_api_with_a_lower_version_works() {
 	let transfer_amount = 100u128;
@@ -465,3 +531,112 @@ fn calling_payment_api_with_a_lower_version_works() {
 		.unwrap();
 	assert!(execution_fees.is_ok());
 }
+
+// Same scenario as `fee_estimation_for_teleport`, but all fees are estimated by a single call
+// to the `TransferFeesApi`.
+#[test]
+fn transfer_fees_estimation_for_teleport() {
+	sp_tracing::init_for_tests();
+	let who = 1; // AccountId = u64.
+	let balances = vec![(who, 100 + DeliveryFees::get() + ExistentialDeposit::get())];
+	let assets = vec![(1, who, 50)];
+	new_test_ext_with_balances_and_assets(balances, assets).execute_with(|| {
+		let client = TestClient;
+		let runtime_api = client.runtime_api();
+		let call = RuntimeCall::XcmPallet(pallet_xcm::Call::transfer_assets {
+			dest: Box::new(VersionedLocation::from((Parent, Parachain(1000)))),
+			beneficiary: Box::new(VersionedLocation::from(AccountId32 {
+				id: [0u8; 32],
+				network: None,
+			})),
+			assets: Box::new(VersionedAssets::from(vec![
+				(Here, 100u128).into(),
+				(Parent, 20u128).into(),
+			])),
+			fee_asset_item: 1, // Fees are paid with the RelayToken
+			weight_limit: Unlimited,
+		});
+		let origin = OriginCaller::system(RawOrigin::Signed(who));
+		let destination = VersionedLocation::from(Location::new(1, [Parachain(1000)]));
+		// What the `XcmPaymentApi` of "AssetHub" reports for the forwarded message.
+		let asset_hub_fee_info = DestinationFeeInfo {
+			xcm_weight: Weight::from_parts(500, 50),
+			fee_asset: AssetId(Parent.into()).into(),
+			fee: 550,
+		};
+		let send_message = Xcm::<()>::builder_unsafe()
+			.withdraw_asset((Parent, 20u128))
+			.buy_execution((Parent, 20u128), Unlimited)
+			.receive_teleported_asset(((Parent, Parachain(2000)), 100u128))
+			.clear_origin()
+			.deposit_asset(AllCounted(2), [0u8; 32])
+			.build();
+		let forwarded_xcm = |remote_execution| ForwardedXcm {
+			destination: destination.clone(),
+			message: VersionedXcm::from(send_message.clone()),
+			delivery_fees: VersionedAssets::from((Here, DeliveryFees::get())),
+			remote_execution,
+		};
+		let remote_execution = RemoteExecution {
+			weight: Weight::from_parts(500, 50),
+			fee: VersionedAsset::from((Parent, 550u128)),
+		};
+
+		// Destination fee information supplied by the caller.
+		let estimate = runtime_api
+			.estimate_transfer_fees(
+				H256::zero(),
+				origin.clone(),
+				call.clone(),
+				AssetId(HereLocation::get()).into(),
+				vec![(destination.clone(), asset_hub_fee_info.clone())],
+				XCM_VERSION,
+			)
+			.unwrap()
+			.unwrap();
+		assert!(estimate.execution_result.is_ok());
+		assert_eq!(estimate.local_xcm_weight, Some(Weight::from_parts(400, 40)));
+		// The native token is almost free with the `FixedRateOfFungible` of the mock.
+		assert_eq!(estimate.local_execution_fee, Some(VersionedAsset::from((Here, 0u128))));
+		assert_eq!(estimate.forwarded_xcms, vec![forwarded_xcm(Some(remote_execution.clone()))]);
+
+		// Without information about the destination, only local fees are estimated.
+		let estimate = runtime_api
+			.estimate_transfer_fees(
+				H256::zero(),
+				origin.clone(),
+				call.clone(),
+				AssetId(HereLocation::get()).into(),
+				vec![],
+				XCM_VERSION,
+			)
+			.unwrap()
+			.unwrap();
+		assert_eq!(estimate.forwarded_xcms, vec![forwarded_xcm(None)]);
+
+		// Destination fee information cached on chain.
+		assert_ok!(XcmPallet::set_destination_fee_info(
+			RuntimeOrigin::root(),
+			Box::new(destination.clone()),
+			Some(asset_hub_fee_info),
+		));
+		let estimate = runtime_api
+			.estimate_transfer_fees(
+				H256::zero(),
+				origin,
+				call,
+				AssetId(HereLocation::get()).into(),
+				vec![],
+				XCM_VERSION,
+			)
+			.unwrap()
+			.unwrap();
+		assert_eq!(estimate.forwarded_xcms, vec![forwarded_xcm(Some(remote_execution))]);
+
+		// The transfer was not executed.
+		assert_eq!(
+			pallet_balances::Pallet::<mock::TestRuntime>::free_balance(who),
+			100 + DeliveryFees::get() + ExistentialDeposit::get()
+		);
+	});
+}
@@ -45,8 +45,14 @@ use xcm_executor::{
 
 use xcm_runtime_apis::{
 	conversions::{Error as LocationToAccountApiError, LocationToAccountApi},
-	dry_run::{CallDryRunEffects, DryRunApi, Error as XcmDryRunApiError, XcmDryRunEffects},
+	dry_run::{
+		CallDryRunEffects, DryRunApi, Error as XcmDryRunApiError, TracedDryRunEffects,
+		XcmDryRunEffects,
+	},
 	fees::{Error as XcmPaymentApiError, XcmPaymentApi},
+	transfer_fees::{
+		DestinationFeeInfo, Error as TransferFeesApiError, TransferFeeEstimate, TransferFeesApi,
+	},
 	trusted_query::{Error as TrustedQueryApiError, TrustedQueryApi},
 };
 use xcm_simulator::helpers::derive_topic_id;
@@ -376,6 +382,9 @@ impl pallet_xcm::Config for TestRuntime {
 	type RemoteLockConsumerIdentifier = ();
 	type WeightInfo = TestWeightInfo;
 	type AuthorizedAliasConsideration = Disabled;
+	type AssetTrapConsideration = Disabled;
+	type AssetTrapExpiry = ();
+	type RecurringTransferConsideration = Disabled;
 }
 
 #[allow(dead_code)]
@@ -500,6 +509,7 @@ sp_api::mock_impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(3)]
 	impl DryRunApi<Block, RuntimeCall, RuntimeEvent, OriginCaller> for RuntimeApi {
 		fn dry_run_call(
 			origin: OriginCaller,
@@ -519,5 +529,35 @@ sp_api::mock_impl_runtime_apis! {
 		fn dry_run_xcm(origin_location: VersionedLocation, xcm: VersionedXcm<RuntimeCall>) -> Result<XcmDryRunEffects<RuntimeEvent>, XcmDryRunApiError> {
 			pallet_xcm::Pallet::<TestRuntime>::dry_run_xcm::<TestRuntime, XcmRouter, RuntimeCall, XcmConfig>(origin_location, xcm)
 		}
+
+		fn dry_run_call_with_trace(
+			origin: OriginCaller,
+			call: RuntimeCall,
+			result_xcms_version: XcmVersion,
+		) -> Result<TracedDryRunEffects<CallDryRunEffects<RuntimeEvent>>, XcmDryRunApiError> {
+			pallet_xcm::Pallet::<TestRuntime>::dry_run_call_with_trace::<TestRuntime, XcmRouter, OriginCaller, RuntimeCall>(origin, call, result_xcms_version)
+		}
+
+		fn dry_run_xcm_with_trace(origin_location: VersionedLocation, xcm: VersionedXcm<RuntimeCall>) -> Result<TracedDryRunEffects<XcmDryRunEffects<RuntimeEvent>>, XcmDryRunApiError> {
+			pallet_xcm::Pallet::<TestRuntime>::dry_run_xcm_with_trace::<TestRuntime, XcmRouter, RuntimeCall, XcmConfig>(origin_location, xcm)
+		}
+	}
+
+	impl TransferFeesApi<Block, RuntimeCall, OriginCaller> for RuntimeApi {
+		fn estimate_transfer_fees(
+			origin: OriginCaller,
+			call: RuntimeCall,
+			fee_asset: VersionedAssetId,
+			destination_fee_infos: Vec<(VersionedLocation, DestinationFeeInfo)>,
+			result_xcms_version: XcmVersion,
+		) -> Result<TransferFeeEstimate, TransferFeesApiError> {
+			XcmPallet::estimate_transfer_fees::<XcmRouter, OriginCaller, RuntimeCall, Trader>(
+				origin,
+				call,
+				fee_asset,
+				destination_fee_infos,
+				result_xcms_version,
+			)
+		}
 	}
 }
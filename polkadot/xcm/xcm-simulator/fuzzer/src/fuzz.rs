@@ -22,11 +22,11 @@ mod parachain;
 #[allow(unexpected_cfgs)]
 mod relay_chain;
 
+mod network;
+
 use codec::DecodeLimit;
-use polkadot_core_primitives::AccountId;
-use polkadot_parachain_primitives::primitives::Id as ParaId;
-use sp_runtime::{traits::AccountIdConversion, BuildStorage};
-use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain, TestExt};
+use network::*;
+use xcm_simulator::TestExt;
 
 #[cfg(feature = "try-runtime")]
 use frame_support::traits::{TryState, TryStateSelect::All};
@@ -35,57 +35,8 @@ use xcm::{latest::prelude::*, MAX_XCM_DECODE_DEPTH};
 
 use arbitrary::{Arbitrary, Error, Unstructured};
 
-pub const INITIAL_BALANCE: u128 = 1_000_000_000;
-
-decl_test_parachain! {
-	pub struct ParaA {
-		Runtime = parachain::Runtime,
-		XcmpMessageHandler = parachain::MsgQueue,
-		DmpMessageHandler = parachain::MsgQueue,
-		new_ext = para_ext(1),
-	}
-}
-
-decl_test_parachain! {
-	pub struct ParaB {
-		Runtime = parachain::Runtime,
-		XcmpMessageHandler = parachain::MsgQueue,
-		DmpMessageHandler = parachain::MsgQueue,
-		new_ext = para_ext(2),
-	}
-}
-
-decl_test_parachain! {
-	pub struct ParaC {
-		Runtime = parachain::Runtime,
-		XcmpMessageHandler = parachain::MsgQueue,
-		DmpMessageHandler = parachain::MsgQueue,
-		new_ext = para_ext(3),
-	}
-}
-
-decl_test_relay_chain! {
-	pub struct Relay {
-		Runtime = relay_chain::Runtime,
-		RuntimeCall = relay_chain::RuntimeCall,
-		RuntimeEvent = relay_chain::RuntimeEvent,
-		XcmConfig = relay_chain::XcmConfig,
-		MessageQueue = relay_chain::MessageQueue,
-		System = relay_chain::System,
-		new_ext = relay_ext(),
-	}
-}
-
-decl_test_network! {
-	pub struct MockNet {
-		relay_chain = Relay,
-		parachains = vec![
-			(1, ParaA),
-			(2, ParaB),
-			(3, ParaC),
-		],
-	}
-}
+pub type RelayChainPalletXcm = pallet_xcm::Pallet<relay_chain::Runtime>;
+pub type ParachainPalletXcm = pallet_xcm::Pallet<parachain::Runtime>;
 
 // An XCM message that will be generated by the fuzzer through the Arbitrary trait
 struct XcmMessage {
@@ -111,51 +62,6 @@ impl<'a> Arbitrary<'a> for XcmMessage {
 	}
 }
 
-pub fn para_account_id(id: u32) -> relay_chain::AccountId {
-	ParaId::from(id).into_account_truncating()
-}
-
-pub fn para_ext(para_id: u32) -> sp_io::TestExternalities {
-	use parachain::{MsgQueue, Runtime, System};
-
-	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
-
-	pallet_balances::GenesisConfig::<Runtime> {
-		balances: (0..6).map(|i| ([i; 32].into(), INITIAL_BALANCE)).collect(),
-		..Default::default()
-	}
-	.assimilate_storage(&mut t)
-	.unwrap();
-
-	let mut ext = sp_io::TestExternalities::new(t);
-	ext.execute_with(|| {
-		System::set_block_number(1);
-		MsgQueue::set_para_id(para_id.into());
-	});
-	ext
-}
-
-pub fn relay_ext() -> sp_io::TestExternalities {
-	use relay_chain::{Runtime, System};
-
-	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
-
-	let mut balances: Vec<(AccountId, u128)> = vec![];
-	balances.append(&mut (1..=3).map(|i| (para_account_id(i), INITIAL_BALANCE)).collect());
-	balances.append(&mut (0..6).map(|i| ([i; 32].into(), INITIAL_BALANCE)).collect());
-
-	pallet_balances::GenesisConfig::<Runtime> { balances, ..Default::default() }
-		.assimilate_storage(&mut t)
-		.unwrap();
-
-	let mut ext = sp_io::TestExternalities::new(t);
-	ext.execute_with(|| System::set_block_number(1));
-	ext
-}
-
-pub type RelayChainPalletXcm = pallet_xcm::Pallet<relay_chain::Runtime>;
-pub type ParachainPalletXcm = pallet_xcm::Pallet<parachain::Runtime>;
-
 // We check XCM messages recursively for blocklisted messages
 fn recursively_matches_blocklisted_messages(message: &Instruction<()>) -> bool {
 	match message {
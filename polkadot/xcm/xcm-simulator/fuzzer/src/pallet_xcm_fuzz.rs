@@ -0,0 +1,418 @@
+// Copyright (C) Parity Technologies (UK) Ltd.
+// This file is part of Polkadot.
+
+// Polkadot is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Fuzzer dispatching sequences of `pallet-xcm` extrinsics on the test network.
+//!
+//! After every step, it checks that no relay chain tokens were minted across the network, that
+//! the tokens which were lost are at least the ones trapped, and that no version notification
+//! subscription is left waiting for a response.
+
+// We do not declare all features used by `construct_runtime`
+#[allow(unexpected_cfgs)]
+mod parachain;
+
+// We do not declare all features used by `construct_runtime`
+#[allow(unexpected_cfgs)]
+mod relay_chain;
+
+mod network;
+
+use network::*;
+use xcm_simulator::TestExt;
+
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::{TryState, TryStateSelect::All};
+use frame_support::{
+	storage::migration::storage_iter,
+	traits::{fungible::Inspect, Hooks, IntegrityTest, PalletInfoAccess, UnfilteredDispatchable},
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use pallet_xcm::QueryStatus;
+use polkadot_core_primitives::AccountId;
+use sp_runtime::traits::One;
+use xcm::{latest::prelude::*, VersionedAssetId, VersionedAssets, VersionedXcm};
+use xcm_executor::traits::TransferType;
+
+use arbitrary::{Arbitrary, Error, Unstructured};
+
+/// Runs `$body` on `$chain`, optionally with `$runtime` being the runtime of the chain.
+macro_rules! on_chain {
+	($chain:expr, |$runtime:ident| $body:expr) => {
+		match $chain {
+			Chain::Relay => Relay::execute_with(|| {
+				type $runtime = relay_chain::Runtime;
+				$body
+			}),
+			Chain::Para(1) => ParaA::execute_with(|| {
+				type $runtime = parachain::Runtime;
+				$body
+			}),
+			Chain::Para(2) => ParaB::execute_with(|| {
+				type $runtime = parachain::Runtime;
+				$body
+			}),
+			Chain::Para(_) => ParaC::execute_with(|| {
+				type $runtime = parachain::Runtime;
+				$body
+			}),
+		}
+	};
+	($chain:expr, $body:expr) => {
+		match $chain {
+			Chain::Relay => Relay::execute_with(|| $body),
+			Chain::Para(1) => ParaA::execute_with(|| $body),
+			Chain::Para(2) => ParaB::execute_with(|| $body),
+			Chain::Para(_) => ParaC::execute_with(|| $body),
+		}
+	};
+}
+
+// A chain of the test network
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Chain {
+	Relay,
+	Para(u32),
+}
+
+impl Chain {
+	const ALL: [Chain; 4] = [Chain::Relay, Chain::Para(1), Chain::Para(2), Chain::Para(3)];
+
+	// The location of `self` as seen from `from`
+	fn location_from(self, from: Chain) -> Location {
+		match (from, self) {
+			(Chain::Relay, Chain::Relay) => Here.into(),
+			(Chain::Relay, Chain::Para(id)) => Parachain(id).into(),
+			(Chain::Para(_), Chain::Relay) => Parent.into(),
+			(Chain::Para(from), Chain::Para(id)) if from == id => Here.into(),
+			(Chain::Para(_), Chain::Para(id)) => (Parent, Parachain(id)).into(),
+		}
+	}
+
+	// The location of the relay chain token as seen from `self`
+	fn token(self) -> Location {
+		Chain::Relay.location_from(self)
+	}
+}
+
+impl<'a> Arbitrary<'a> for Chain {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self, Error> {
+		Ok(match u.int_in_range(0..=3)? {
+			0 => Chain::Relay,
+			id => Chain::Para(id),
+		})
+	}
+}
+
+// An extrinsic, or a new block, that will be generated by the fuzzer through the Arbitrary trait.
+// Accounts are indices of the accounts funded at genesis.
+#[derive(Debug)]
+enum Action {
+	TransferAssets {
+		from: Chain,
+		to: Chain,
+		sender: u8,
+		beneficiary: u8,
+		amount: u128,
+	},
+	TransferAssetsUsingTypeAndThen {
+		from: Chain,
+		to: Chain,
+		sender: u8,
+		beneficiary: u8,
+		amount: u128,
+		assets_transfer_type: TransferType,
+		fees_transfer_type: TransferType,
+		// Whether the assets are left in holding on the destination, instead of being deposited
+		trap_on_destination: bool,
+	},
+	SubscribeVersion {
+		chain: Chain,
+		target: Chain,
+	},
+	UnsubscribeVersion {
+		chain: Chain,
+		target: Chain,
+	},
+	ForceXcmVersion {
+		chain: Chain,
+		target: Chain,
+		version: XcmVersion,
+	},
+	AdvanceBlock,
+}
+
+fn arbitrary_transfer_type(u: &mut Unstructured, from: Chain) -> Result<TransferType, Error> {
+	Ok(match u.int_in_range(0..=3)? {
+		0 => TransferType::Teleport,
+		1 => TransferType::LocalReserve,
+		2 => TransferType::DestinationReserve,
+		_ => TransferType::RemoteReserve(Chain::Relay.location_from(from).into()),
+	})
+}
+
+impl<'a> Arbitrary<'a> for Action {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self, Error> {
+		Ok(match u.int_in_range(0..=5)? {
+			0 => Action::TransferAssets {
+				from: u.arbitrary()?,
+				to: u.arbitrary()?,
+				sender: u.int_in_range(0..=5)?,
+				beneficiary: u.int_in_range(0..=5)?,
+				amount: u.int_in_range(0..=INITIAL_BALANCE)?,
+			},
+			1 => {
+				let from = u.arbitrary()?;
+				Action::TransferAssetsUsingTypeAndThen {
+					from,
+					to: u.arbitrary()?,
+					sender: u.int_in_range(0..=5)?,
+					beneficiary: u.int_in_range(0..=5)?,
+					amount: u.int_in_range(0..=INITIAL_BALANCE)?,
+					assets_transfer_type: arbitrary_transfer_type(u, from)?,
+					fees_transfer_type: arbitrary_transfer_type(u, from)?,
+					trap_on_destination: u.arbitrary()?,
+				}
+			},
+			2 => Action::SubscribeVersion { chain: u.arbitrary()?, target: u.arbitrary()? },
+			3 => Action::UnsubscribeVersion { chain: u.arbitrary()?, target: u.arbitrary()? },
+			4 => Action::ForceXcmVersion {
+				chain: u.arbitrary()?,
+				target: u.arbitrary()?,
+				version: u.int_in_range(3..=XCM_VERSION)?,
+			},
+			_ => Action::AdvanceBlock,
+		})
+	}
+}
+
+fn account(index: u8) -> AccountId {
+	[index; 32].into()
+}
+
+fn beneficiary(index: u8) -> Location {
+	Location::new(0, AccountId32 { network: None, id: [index; 32] })
+}
+
+fn relay_token(chain: Chain, amount: u128) -> VersionedAssets {
+	Assets::from(Asset::from((chain.token(), amount))).into()
+}
+
+// We dispatch the extrinsic of `action` on the current chain, ignoring its result
+fn dispatch<T: pallet_xcm::Config<AccountId = AccountId>>(action: &Action) {
+	let (origin, call) = match *action {
+		Action::TransferAssets { from, to, sender, beneficiary: who, amount } => (
+			RawOrigin::Signed(account(sender)),
+			pallet_xcm::Call::<T>::transfer_assets {
+				dest: Box::new(to.location_from(from).into()),
+				beneficiary: Box::new(beneficiary(who).into()),
+				assets: Box::new(relay_token(from, amount)),
+				fee_asset_item: 0,
+				weight_limit: Unlimited,
+			},
+		),
+		Action::TransferAssetsUsingTypeAndThen {
+			from,
+			to,
+			sender,
+			beneficiary: who,
+			amount,
+			ref assets_transfer_type,
+			ref fees_transfer_type,
+			trap_on_destination,
+		} => {
+			let custom_xcm_on_dest = match trap_on_destination {
+				true => Xcm::new(),
+				false => Xcm(vec![DepositAsset {
+					assets: Wild(AllCounted(1)),
+					beneficiary: beneficiary(who),
+				}]),
+			};
+			(
+				RawOrigin::Signed(account(sender)),
+				pallet_xcm::Call::<T>::transfer_assets_using_type_and_then {
+					dest: Box::new(to.location_from(from).into()),
+					assets: Box::new(relay_token(from, amount)),
+					assets_transfer_type: Box::new(assets_transfer_type.clone()),
+					remote_fees_id: Box::new(VersionedAssetId::from(AssetId(from.token()))),
+					fees_transfer_type: Box::new(fees_transfer_type.clone()),
+					custom_xcm_on_dest: Box::new(VersionedXcm::from(custom_xcm_on_dest)),
+					weight_limit: Unlimited,
+				},
+			)
+		},
+		Action::SubscribeVersion { chain, target } => (
+			RawOrigin::Root,
+			pallet_xcm::Call::<T>::force_subscribe_version_notify {
+				location: Box::new(target.location_from(chain).into()),
+			},
+		),
+		Action::UnsubscribeVersion { chain, target } => (
+			RawOrigin::Root,
+			pallet_xcm::Call::<T>::force_unsubscribe_version_notify {
+				location: Box::new(target.location_from(chain).into()),
+			},
+		),
+		Action::ForceXcmVersion { chain, target, version } => (
+			RawOrigin::Root,
+			pallet_xcm::Call::<T>::force_xcm_version {
+				location: Box::new(target.location_from(chain)),
+				version,
+			},
+		),
+		Action::AdvanceBlock => unreachable!("blocks are not advanced by an extrinsic; qed"),
+	};
+	let _ = call.dispatch_bypass_filter(origin.into());
+}
+
+fn advance_block<T: pallet_xcm::Config>() {
+	let block_number = frame_system::Pallet::<T>::block_number() + One::one();
+	frame_system::Pallet::<T>::set_block_number(block_number);
+	pallet_xcm::Pallet::<T>::on_initialize(block_number);
+}
+
+// The relay chain tokens on the network: the ones on the relay chain, plus the ones minted on
+// parachains which aren't backed by their sovereign account on the relay chain
+fn total_value() -> i128 {
+	let mut value = Relay::execute_with(relay_chain::Balances::total_issuance) as i128;
+	for id in 1..=3 {
+		let sovereign =
+			Relay::execute_with(|| relay_chain::Balances::total_balance(&para_account_id(id)));
+		let issuance = on_chain!(Chain::Para(id), |Runtime| {
+			pallet_balances::Pallet::<Runtime>::total_issuance()
+		});
+		value += issuance as i128 - sovereign as i128;
+	}
+	value
+}
+
+// The relay chain tokens trapped on the network
+fn total_trapped() -> i128 {
+	let mut trapped = Relay::execute_with(relay_chain::TrappedTokens::get) as i128;
+	for id in 1..=3 {
+		trapped += on_chain!(Chain::Para(id), parachain::TrappedKsm::get()) as i128;
+	}
+	trapped
+}
+
+// We check that every version notification subscription got a response, and that every pending
+// query can still get one
+fn assert_no_stuck_queries<T: pallet_xcm::Config>() {
+	let now = frame_system::Pallet::<T>::block_number();
+	let pallet = <pallet_xcm::Pallet<T> as PalletInfoAccess>::name();
+	for (_, query) in storage_iter::<QueryStatus<BlockNumberFor<T>>>(pallet.as_bytes(), b"Queries")
+	{
+		let stuck = match &query {
+			QueryStatus::VersionNotifier { is_active, .. } => !is_active,
+			QueryStatus::Pending { timeout, .. } => timeout < &now,
+			QueryStatus::Ready { .. } => false,
+		};
+		assert!(!stuck, "Stuck query on {pallet}: {query:?}");
+	}
+}
+
+fn run_input(actions: [Action; 10]) {
+	MockNet::reset();
+
+	#[cfg(not(fuzzing))]
+	println!();
+
+	let initial_value = total_value();
+	let mut value = initial_value;
+	for action in actions {
+		#[cfg(not(fuzzing))]
+		println!("  action: {action:?}");
+
+		match action {
+			Action::AdvanceBlock =>
+				for chain in Chain::ALL {
+					on_chain!(chain, |Runtime| advance_block::<Runtime>());
+				},
+			Action::TransferAssets { from: chain, .. } |
+			Action::TransferAssetsUsingTypeAndThen { from: chain, .. } |
+			Action::SubscribeVersion { chain, .. } |
+			Action::UnsubscribeVersion { chain, .. } |
+			Action::ForceXcmVersion { chain, .. } => {
+				on_chain!(chain, |Runtime| dispatch::<Runtime>(&action));
+			},
+		}
+
+		// We check that no relay chain tokens were minted, and that the trapped ones were lost
+		let new_value = total_value();
+		assert!(new_value <= value, "Relay chain tokens were minted: {value} -> {new_value}");
+		let trapped = total_trapped();
+		assert!(
+			new_value + trapped <= initial_value,
+			"More relay chain tokens were trapped than lost: {} trapped, {} lost",
+			trapped,
+			initial_value - new_value,
+		);
+		value = new_value;
+
+		// We check queries, and run integrity tests and try_runtime invariants
+		for chain in Chain::ALL {
+			on_chain!(chain, |Runtime| assert_no_stuck_queries::<Runtime>());
+		}
+		[ParaA::execute_with, ParaB::execute_with, ParaC::execute_with].iter().for_each(
+			|execute_with| {
+				execute_with(|| {
+					#[cfg(feature = "try-runtime")]
+					parachain::AllPalletsWithSystem::try_state(Default::default(), All).unwrap();
+					parachain::AllPalletsWithSystem::integrity_test();
+				});
+			},
+		);
+		Relay::execute_with(|| {
+			#[cfg(feature = "try-runtime")]
+			relay_chain::AllPalletsWithSystem::try_state(Default::default(), All).unwrap();
+			relay_chain::AllPalletsWithSystem::integrity_test();
+		});
+	}
+}
+
+fn main() {
+	#[cfg(fuzzing)]
+	{
+		loop {
+			honggfuzz::fuzz!(|actions: [Action; 10]| {
+				run_input(actions);
+			})
+		}
+	}
+	#[cfg(not(fuzzing))]
+	{
+		use std::{env, fs, fs::File, io::Read};
+		let args: Vec<_> = env::args().collect();
+		let md = fs::metadata(&args[1]).unwrap();
+		let all_files = match md.is_dir() {
+			true => fs::read_dir(&args[1])
+				.unwrap()
+				.map(|x| x.unwrap().path().to_str().unwrap().to_string())
+				.collect::<Vec<String>>(),
+			false => (args[1..]).to_vec(),
+		};
+		println!("All_files {:?}", all_files);
+		for argument in all_files {
+			println!("Now doing file {:?}", argument);
+			let mut buffer: Vec<u8> = Vec::new();
+			let mut f = File::open(argument).unwrap();
+			f.read_to_end(&mut buffer).unwrap();
+			let mut unstructured = Unstructured::new(&buffer);
+			if let Ok(actions) = unstructured.arbitrary() {
+				run_input(actions);
+			}
+		}
+	}
+}
@@ -42,7 +42,7 @@ use xcm_builder::{
 	ParentIsPreset, SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
 	SovereignSignedViaLocation,
 };
-use xcm_executor::{Config, XcmExecutor};
+use xcm_executor::{traits::DropAssets, AssetsInHolding, Config, XcmExecutor};
 
 pub type TxExtension = (frame_system::CheckNonZeroSender<Runtime>,);
 
@@ -116,6 +116,22 @@ pub type LocalAssetTransactor =
 pub type XcmRouter = super::ParachainXcmRouter<MsgQueue>;
 pub type Barrier = AllowUnpaidExecutionFrom<Everything>;
 
+parameter_types! {
+	/// The amount of KSM trapped on this chain, checked by the `pallet-xcm` fuzzer.
+	pub storage TrappedKsm: Balance = 0;
+}
+
+/// Traps assets with `PolkadotXcm`, keeping track of the amount of KSM trapped.
+pub struct AssetTrap;
+impl DropAssets for AssetTrap {
+	fn drop_assets(origin: &Location, assets: AssetsInHolding, context: &XcmContext) -> Weight {
+		if let Some(amount) = assets.fungible.get(&AssetId(KsmLocation::get())) {
+			TrappedKsm::set(&TrappedKsm::get().saturating_add(*amount));
+		}
+		PolkadotXcm::drop_assets(origin, assets, context)
+	}
+}
+
 pub struct XcmConfig;
 impl Config for XcmConfig {
 	type RuntimeCall = RuntimeCall;
@@ -129,12 +145,12 @@ impl Config for XcmConfig {
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
 	type Trader = FixedRateOfFungible<KsmPerSecondPerByte, ()>;
-	type ResponseHandler = ();
-	type AssetTrap = ();
+	type ResponseHandler = PolkadotXcm;
+	type AssetTrap = AssetTrap;
 	type AssetLocker = ();
 	type AssetExchanger = ();
 	type AssetClaims = ();
-	type SubscriptionService = ();
+	type SubscriptionService = PolkadotXcm;
 	type PalletInstancesInfo = ();
 	type FeeManager = ();
 	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
@@ -345,6 +361,9 @@ impl pallet_xcm::Config for Runtime {
 	type WeightInfo = pallet_xcm::TestWeightInfo;
 	type AdminOrigin = EnsureRoot<AccountId>;
 	type AuthorizedAliasConsideration = Disabled;
+	type AssetTrapConsideration = Disabled;
+	type AssetTrapExpiry = ();
+	type RecurringTransferConsideration = Disabled;
 }
 
 construct_runtime!(
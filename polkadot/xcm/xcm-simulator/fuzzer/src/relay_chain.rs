@@ -43,7 +43,7 @@ use xcm_builder::{
 	FixedWeightBounds, FrameTransactionalProcessor, FungibleAdapter, IsConcrete,
 	SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation,
 };
-use xcm_executor::{Config, XcmExecutor};
+use xcm_executor::{traits::DropAssets, AssetsInHolding, Config, XcmExecutor};
 
 pub type TxExtension = (frame_system::CheckNonZeroSender<Runtime>,);
 
@@ -119,6 +119,22 @@ parameter_types! {
 pub type XcmRouter = super::RelayChainXcmRouter;
 pub type Barrier = AllowUnpaidExecutionFrom<Everything>;
 
+parameter_types! {
+	/// The amount of the native token trapped on this chain, checked by the `pallet-xcm` fuzzer.
+	pub storage TrappedTokens: Balance = 0;
+}
+
+/// Traps assets with `XcmPallet`, keeping track of the amount of the native token trapped.
+pub struct AssetTrap;
+impl DropAssets for AssetTrap {
+	fn drop_assets(origin: &Location, assets: AssetsInHolding, context: &XcmContext) -> Weight {
+		if let Some(amount) = assets.fungible.get(&AssetId(TokenLocation::get())) {
+			TrappedTokens::set(&TrappedTokens::get().saturating_add(*amount));
+		}
+		XcmPallet::drop_assets(origin, assets, context)
+	}
+}
+
 pub struct XcmConfig;
 impl Config for XcmConfig {
 	type RuntimeCall = RuntimeCall;
@@ -132,12 +148,12 @@ impl Config for XcmConfig {
 	type Barrier = Barrier;
 	type Weigher = FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
 	type Trader = FixedRateOfFungible<KsmPerSecondPerByte, ()>;
-	type ResponseHandler = ();
-	type AssetTrap = ();
+	type ResponseHandler = XcmPallet;
+	type AssetTrap = AssetTrap;
 	type AssetLocker = ();
 	type AssetExchanger = ();
 	type AssetClaims = ();
-	type SubscriptionService = ();
+	type SubscriptionService = XcmPallet;
 	type PalletInstancesInfo = ();
 	type FeeManager = ();
 	type MaxAssetsIntoHolding = MaxAssetsIntoHolding;
@@ -181,6 +197,9 @@ impl pallet_xcm::Config for Runtime {
 	type WeightInfo = pallet_xcm::TestWeightInfo;
 	type AdminOrigin = EnsureRoot<AccountId>;
 	type AuthorizedAliasConsideration = Disabled;
+	type AssetTrapConsideration = Disabled;
+	type AssetTrapExpiry = ();
+	type RecurringTransferConsideration = Disabled;
 }
 
 impl origin::Config for Runtime {}
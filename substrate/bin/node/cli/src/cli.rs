@@ -108,4 +108,7 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Inspect and dry-run the multi-block migrations of the local database.
+	Migrations(crate::MigrationsCmd),
 }
@@ -0,0 +1,96 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The `migrations` subcommand, to inspect the multi-block migrations of the local database
+//! without running the node.
+
+use crate::service::FullClient;
+
+use polkadot_sdk::*;
+
+use pallet_migrations_rpc::{MigrationsRuntimeApi, DEFAULT_MAX_STEPS};
+use sc_cli::{CliConfiguration, ImportParams, Result, SharedParams};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+
+use std::{io, sync::Arc};
+
+/// The `migrations` command used to inspect and dry-run the multi-block migrations.
+#[derive(Debug, clap::Parser)]
+pub struct MigrationsCmd {
+	#[allow(missing_docs)]
+	#[clap(subcommand)]
+	pub command: MigrationsSubCmd,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub import_params: ImportParams,
+}
+
+/// A possible migrations sub-command.
+#[derive(Debug, clap::Subcommand)]
+pub enum MigrationsSubCmd {
+	/// Print the progress of the multi-block migrations at the best block.
+	Status,
+	/// Step the migrations on top of the best block until they complete and print the weight of
+	/// every step.
+	///
+	/// The steps are executed on throwaway state, the database is not modified.
+	StepToCompletion {
+		/// The maximum number of steps to take.
+		#[arg(long, default_value_t = DEFAULT_MAX_STEPS)]
+		max_steps: u32,
+	},
+}
+
+impl MigrationsCmd {
+	/// Run the `migrations` subcommand against the given client.
+	pub fn run(&self, client: Arc<FullClient>) -> Result<()> {
+		let api = client.runtime_api();
+		let best_hash = client.info().best_hash;
+		let mut out = io::stdout();
+
+		match self.command {
+			MigrationsSubCmd::Status => {
+				let status = api.status(best_hash).map_err(|e| e.to_string())?;
+				serde_json::to_writer_pretty(&mut out, &status)
+			},
+			MigrationsSubCmd::StepToCompletion { max_steps } => {
+				let dry_run =
+					api.step_to_completion(best_hash, max_steps).map_err(|e| e.to_string())?;
+				serde_json::to_writer_pretty(&mut out, &dry_run)
+			},
+		}
+		.map_err(|e| format!("Error writing JSON: {}", e))?;
+		Ok(())
+	}
+}
+
+impl CliConfiguration for MigrationsCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
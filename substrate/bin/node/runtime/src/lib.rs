@@ -1757,7 +1757,9 @@ impl pallet_vesting::Config for Runtime {
 	type BlockNumberProvider = System;
 	// `VestingInfo` encode length is 36bytes. 28 schedules gets encoded as 1009 bytes, which is the
 	// highest number of schedules that encodes less than 2^10.
+	type TransferPolicyOrigin = EnsureRoot<AccountId>;
 	const MAX_VESTING_SCHEDULES: u32 = 28;
+	const MAX_VESTING_PAGES: u32 = 4;
 }
 
 impl pallet_mmr::Config for Runtime {
@@ -2121,6 +2123,9 @@ impl pallet_nft_fractionalization::Config for Runtime {
 parameter_types! {
 	pub Features: PalletFeatures = PalletFeatures::all_enabled();
 	pub const MaxAttributesPerCall: u32 = 10;
+	pub const MaxRoyaltyRecipients: u32 = 10;
+	pub const RoyaltyDepositPerRecipient: Balance = 1 * DOLLARS;
+	pub const MaxRoyalty: u16 = 2_500;
 }
 
 impl pallet_nfts::Config for Runtime {
@@ -2134,6 +2139,7 @@ impl pallet_nfts::Config for Runtime {
 	type MetadataDepositBase = MetadataDepositBase;
 	type AttributeDepositBase = MetadataDepositBase;
 	type DepositPerByte = MetadataDepositPerByte;
+	type RoyaltyDepositPerRecipient = RoyaltyDepositPerRecipient;
 	type StringLimit = ConstU32<256>;
 	type KeyLimit = ConstU32<64>;
 	type ValueLimit = ConstU32<256>;
@@ -2142,6 +2148,8 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = MaxTips;
 	type MaxDeadlineDuration = MaxDeadlineDuration;
 	type MaxAttributesPerCall = MaxAttributesPerCall;
+	type MaxRoyaltyRecipients = MaxRoyaltyRecipients;
+	type MaxRoyalty = MaxRoyalty;
 	type Features = Features;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as traits::Verify>::Signer;
@@ -3431,6 +3439,16 @@ pallet_revive::impl_runtime_apis_plus_revive!(
 		}
 	}
 
+	impl pallet_migrations::runtime_api::MigrationsApi<Block, BlockNumber> for Runtime {
+		fn status() -> pallet_migrations::MigrationStatus<BlockNumber> {
+			MultiBlockMigrations::status()
+		}
+
+		fn step_to_completion(max_steps: u32) -> pallet_migrations::MigrationsDryRun<BlockNumber> {
+			MultiBlockMigrations::step_to_completion(max_steps)
+		}
+	}
+
 	impl pallet_nfts_runtime_api::NftsApi<Block, AccountId, u32, u32> for Runtime {
 		fn owner(collection: u32, item: u32) -> Option<AccountId> {
 			<Nfts as Inspect<AccountId>>::owner(&collection, &item)
@@ -0,0 +1,226 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Reference hash-based proof of work algorithm.
+//!
+//! A seal is valid if the hash of the block pre-hash, the target difficulty and a nonce, read
+//! as a big endian integer, multiplied by the difficulty doesn't overflow `U256`. In other
+//! words, the expected number of hashes required to find a seal equals the difficulty.
+//!
+//! The difficulty of each block is fetched from the runtime through
+//! [`DifficultyApi`](sp_consensus_pow::DifficultyApi).
+//!
+//! The hash function is pluggable, [`Blake2Hasher`] and [`Sha3Hasher`] are provided. Both
+//! are general purpose hash functions which are not ASIC resistant; they are meant for test
+//! and development networks, or as a template for custom algorithms.
+
+use crate::{Error, PowAlgorithm};
+use codec::{Decode, Encode};
+use sp_api::ProvideRuntimeApi;
+use sp_consensus_pow::{DifficultyApi, Seal as RawSeal};
+use sp_core::{H256, U256};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+/// Hash function used by [`HashPow`].
+pub trait PowHasher: Send + Sync + 'static {
+	/// Hash the given data.
+	fn hash(data: &[u8]) -> H256;
+}
+
+/// Blake2b-256 hasher.
+pub struct Blake2Hasher;
+
+impl PowHasher for Blake2Hasher {
+	fn hash(data: &[u8]) -> H256 {
+		sp_crypto_hashing::blake2_256(data).into()
+	}
+}
+
+/// Sha3-256 hasher.
+pub struct Sha3Hasher;
+
+impl PowHasher for Sha3Hasher {
+	fn hash(data: &[u8]) -> H256 {
+		use sha3::Digest;
+		H256::from_slice(&sha3::Sha3_256::digest(data))
+	}
+}
+
+/// Seal produced by [`HashPow`].
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug)]
+pub struct HashSeal {
+	/// Difficulty the block has been mined at.
+	pub difficulty: U256,
+	/// Hash of the work, see [`Compute`].
+	pub work: H256,
+	/// Nonce.
+	pub nonce: U256,
+}
+
+impl HashSeal {
+	/// Check the seal against the given pre-hash.
+	pub fn verify<H: PowHasher>(&self, pre_hash: &H256) -> bool {
+		let compute =
+			Compute { pre_hash: *pre_hash, difficulty: self.difficulty, nonce: self.nonce };
+		compute.work::<H>() == self.work && hash_meets_difficulty(&self.work, self.difficulty)
+	}
+}
+
+/// Input of the proof of work hash.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, Debug)]
+pub struct Compute {
+	/// Block pre-hash.
+	pub pre_hash: H256,
+	/// Target difficulty.
+	pub difficulty: U256,
+	/// Nonce.
+	pub nonce: U256,
+}
+
+impl Compute {
+	/// Hash of the work.
+	pub fn work<H: PowHasher>(&self) -> H256 {
+		H::hash(&self.encode())
+	}
+
+	/// Compute the seal, returns `None` if the work doesn't meet the difficulty.
+	pub fn seal<H: PowHasher>(&self) -> Option<HashSeal> {
+		let work = self.work::<H>();
+		hash_meets_difficulty(&work, self.difficulty).then(|| HashSeal {
+			difficulty: self.difficulty,
+			work,
+			nonce: self.nonce,
+		})
+	}
+}
+
+/// Check whether the given hash meets the difficulty.
+pub fn hash_meets_difficulty(hash: &H256, difficulty: U256) -> bool {
+	let num_hash = U256::from_big_endian(hash.as_bytes());
+	let (_, overflowed) = num_hash.overflowing_mul(difficulty);
+	!overflowed
+}
+
+/// Reference [`PowAlgorithm`] using `H` as hash function.
+///
+/// The difficulty is read from the runtime [`DifficultyApi`] at the parent block.
+pub struct HashPow<B, H, C> {
+	client: Arc<C>,
+	_phantom: PhantomData<(B, H)>,
+}
+
+impl<B, H, C> HashPow<B, H, C> {
+	/// Create a new instance.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _phantom: PhantomData }
+	}
+}
+
+impl<B, H, C> Clone for HashPow<B, H, C> {
+	fn clone(&self) -> Self {
+		Self::new(self.client.clone())
+	}
+}
+
+impl<B, H, C> PowAlgorithm<B> for HashPow<B, H, C>
+where
+	B: BlockT<Hash = H256>,
+	H: PowHasher,
+	C: ProvideRuntimeApi<B>,
+	C::Api: DifficultyApi<B, U256>,
+{
+	type Difficulty = U256;
+
+	fn difficulty(&self, parent: B::Hash) -> Result<Self::Difficulty, Error<B>> {
+		self.client
+			.runtime_api()
+			.difficulty(parent)
+			.map_err(|err| Error::Runtime(format!("Fetching difficulty failed: {}", err)))
+	}
+
+	fn preliminary_verify(
+		&self,
+		pre_hash: &B::Hash,
+		seal: &RawSeal,
+	) -> Result<Option<bool>, Error<B>> {
+		// The work can be checked against the difficulty claimed by the seal. Whether this is
+		// the right difficulty is checked by `verify`, once the parent is available.
+		let Ok(seal) = HashSeal::decode(&mut &seal[..]) else { return Ok(Some(false)) };
+		Ok(Some(seal.verify::<H>(pre_hash)))
+	}
+
+	fn verify(
+		&self,
+		_parent: &BlockId<B>,
+		pre_hash: &B::Hash,
+		_pre_digest: Option<&[u8]>,
+		seal: &RawSeal,
+		difficulty: Self::Difficulty,
+	) -> Result<bool, Error<B>> {
+		let Ok(seal) = HashSeal::decode(&mut &seal[..]) else { return Ok(false) };
+		Ok(seal.difficulty == difficulty && seal.verify::<H>(pre_hash))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn mine<H: PowHasher>(pre_hash: H256, difficulty: U256) -> HashSeal {
+		(0u64..)
+			.find_map(|nonce| Compute { pre_hash, difficulty, nonce: nonce.into() }.seal::<H>())
+			.unwrap()
+	}
+
+	#[test]
+	fn hash_meets_difficulty_works() {
+		assert!(hash_meets_difficulty(&H256::repeat_byte(0xff), U256::one()));
+		assert!(!hash_meets_difficulty(&H256::repeat_byte(0xff), 2.into()));
+		assert!(hash_meets_difficulty(&H256::zero(), U256::MAX));
+
+		let mut hash = H256::repeat_byte(0xff);
+		hash.0[0] = 0x0f;
+		assert!(hash_meets_difficulty(&hash, 16.into()));
+		assert!(!hash_meets_difficulty(&hash, 17.into()));
+	}
+
+	fn seal_verification_works<H: PowHasher>() {
+		let pre_hash = H256::repeat_byte(1);
+		let difficulty = U256::from(1_000);
+		let seal = mine::<H>(pre_hash, difficulty);
+		assert!(seal.verify::<H>(&pre_hash));
+
+		// Different pre-hash.
+		assert!(!seal.verify::<H>(&H256::repeat_byte(2)));
+		// Different nonce.
+		assert!(!HashSeal { nonce: seal.nonce + 1, ..seal.clone() }.verify::<H>(&pre_hash));
+		// Claiming a lower difficulty changes the work.
+		assert!(!HashSeal { difficulty: 1.into(), ..seal.clone() }.verify::<H>(&pre_hash));
+	}
+
+	#[test]
+	fn blake2_seal_verification_works() {
+		seal_verification_works::<Blake2Hasher>();
+	}
+
+	#[test]
+	fn sha3_seal_verification_works() {
+		seal_verification_works::<Sha3Hasher>();
+	}
+}
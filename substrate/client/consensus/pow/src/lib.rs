@@ -38,10 +38,20 @@
 //! for the auxiliary storage. It is also possible to just use the runtime
 //! as the storage, but it is not recommended as it won't work well with light
 //! clients.
+//!
+//! A reference hash-based [`PowAlgorithm`] is provided in the [`algorithm`] module,
+//! together with a multi-threaded [`CpuMiner`] which can be plugged to the
+//! [`MiningHandle`]. The difficulty adjustment is left to the runtime, for
+//! instance through `pallet-pow-difficulty`.
 
+pub mod algorithm;
+mod miner;
 mod worker;
 
-pub use crate::worker::{MiningBuild, MiningHandle, MiningMetadata};
+pub use crate::{
+	miner::CpuMiner,
+	worker::{MiningBuild, MiningHandle, MiningMetadata},
+};
 
 use crate::worker::UntilImportedOrTimeout;
 use codec::{Decode, Encode};
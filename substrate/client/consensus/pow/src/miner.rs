@@ -0,0 +1,143 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Multi-threaded CPU miner for [`HashPow`].
+
+use crate::{
+	algorithm::{Compute, HashPow, PowHasher},
+	MiningHandle, LOG_TARGET,
+};
+use codec::Encode;
+use log::*;
+use sp_core::{H256, U256};
+use sp_runtime::traits::Block as BlockT;
+use std::{
+	num::NonZeroUsize,
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+	thread::{self, JoinHandle},
+	time::Duration,
+};
+
+/// Number of nonces tried before checking whether the mining build changed.
+const NONCES_PER_ROUND: usize = 10_000;
+
+/// How long to wait before polling the mining handle again when there is nothing to mine.
+const IDLE_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Multi-threaded CPU miner for [`HashPow`].
+///
+/// Each thread searches the seal of the current mining build of the [`MiningHandle`], starting
+/// from a random nonce, and submits the first valid seal found. Threads switch to the new
+/// build as soon as the handle version changes.
+///
+/// Mining stops when the miner is dropped.
+pub struct CpuMiner {
+	stop: Arc<AtomicBool>,
+	threads: Vec<JoinHandle<()>>,
+}
+
+impl CpuMiner {
+	/// Start mining with the given number of threads.
+	pub fn start<B, H, C, L, Proof>(
+		handle: MiningHandle<B, HashPow<B, H, C>, L, Proof>,
+		threads: NonZeroUsize,
+	) -> Self
+	where
+		B: BlockT<Hash = H256>,
+		H: PowHasher,
+		HashPow<B, H, C>: crate::PowAlgorithm<B, Difficulty = U256>,
+		C: 'static,
+		L: sc_consensus::JustificationSyncLink<B> + 'static,
+		Proof: 'static,
+		MiningHandle<B, HashPow<B, H, C>, L, Proof>: Send,
+	{
+		let stop = Arc::new(AtomicBool::new(false));
+		let threads = (0..threads.get())
+			.map(|idx| {
+				let handle = handle.clone();
+				let stop = stop.clone();
+				thread::Builder::new()
+					.name(format!("pow-miner-{}", idx))
+					.spawn(move || mine::<B, H, C, L, Proof>(handle, stop))
+					.expect("Spawning a thread should not fail; qed")
+			})
+			.collect();
+
+		Self { stop, threads }
+	}
+}
+
+impl Drop for CpuMiner {
+	fn drop(&mut self) {
+		self.stop.store(true, Ordering::Relaxed);
+		for thread in self.threads.drain(..) {
+			if thread.join().is_err() {
+				warn!(target: LOG_TARGET, "CPU miner thread panicked");
+			}
+		}
+	}
+}
+
+fn mine<B, H, C, L, Proof>(
+	handle: MiningHandle<B, HashPow<B, H, C>, L, Proof>,
+	stop: Arc<AtomicBool>,
+) where
+	B: BlockT<Hash = H256>,
+	H: PowHasher,
+	HashPow<B, H, C>: crate::PowAlgorithm<B, Difficulty = U256>,
+	L: sc_consensus::JustificationSyncLink<B>,
+{
+	while !stop.load(Ordering::Relaxed) {
+		let version = handle.version();
+		let Some(metadata) = handle.metadata() else {
+			thread::sleep(IDLE_BACKOFF);
+			continue
+		};
+
+		let mut compute = Compute {
+			pre_hash: metadata.pre_hash,
+			difficulty: metadata.difficulty,
+			nonce: U256::from_big_endian(&rand::random::<[u8; 32]>()),
+		};
+
+		let seal = 'search: loop {
+			for _ in 0..NONCES_PER_ROUND {
+				if let Some(seal) = compute.seal::<H>() {
+					break 'search Some(seal)
+				}
+				compute.nonce = compute.nonce.overflowing_add(U256::one()).0;
+			}
+			if stop.load(Ordering::Relaxed) || handle.version() != version {
+				break None
+			}
+		};
+
+		// Another thread might have already submitted a seal for this build.
+		if let Some(seal) = seal.filter(|_| handle.version() == version) {
+			debug!(
+				target: LOG_TARGET,
+				"Found seal for block on top of {} (nonce: {})", metadata.best_hash, seal.nonce,
+			);
+			// The handle checks that the build didn't change in the meantime.
+			futures::executor::block_on(handle.submit(seal.encode()));
+		}
+	}
+}
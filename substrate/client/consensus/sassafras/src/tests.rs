@@ -0,0 +1,372 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Sassafras client tests.
+
+use super::*;
+use crate::{
+	authorship::{claim_slot, generate_epoch_tickets},
+	verification::{check_header, VerificationParams},
+};
+
+use sc_consensus_slots::CheckedHeader;
+use sp_application_crypto::AppCrypto;
+use sp_consensus_sassafras::TicketId;
+use sp_core::crypto::VrfSecret;
+use sp_keyring::BandersnatchKeyring as Keyring;
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystorePtr};
+use sp_runtime::{Digest, DigestItem};
+use std::sync::Arc;
+use substrate_test_runtime_client::runtime::{Block as TestBlock, Header as TestHeader};
+
+const EPOCH_LENGTH: u32 = 10;
+
+fn create_keystore(authority: Keyring) -> KeystorePtr {
+	let keystore = MemoryKeystore::new();
+	keystore
+		.bandersnatch_generate_new(AuthorityId::ID, Some(&authority.to_seed()))
+		.expect("Creates authority key");
+	Arc::new(keystore)
+}
+
+fn test_epoch(config: EpochConfiguration) -> Epoch {
+	sp_consensus_sassafras::Epoch {
+		index: 1,
+		start: 100.into(),
+		length: EPOCH_LENGTH,
+		randomness: [7; 32],
+		authorities: vec![
+			Keyring::Alice.public().into(),
+			Keyring::Bob.public().into(),
+			Keyring::Charlie.public().into(),
+		],
+		config,
+	}
+	.into()
+}
+
+// Generous configuration: every generated ticket is below the threshold.
+fn lax_config() -> EpochConfiguration {
+	EpochConfiguration { redundancy_factor: 1000, attempts_number: 4 }
+}
+
+fn fallback_slot_for(epoch: &Epoch, authority_idx: AuthorityIndex) -> Slot {
+	(*epoch.start..*epoch.start + EPOCH_LENGTH as u64)
+		.map(Slot::from)
+		.find(|slot| fallback_authority_index(*slot, epoch) == Some(authority_idx))
+		.expect("epoch is long enough to find a fallback slot for each authority")
+}
+
+fn seal_header(
+	number: u64,
+	claim: &SlotClaim,
+	author: &AuthorityId,
+	keystore: &KeystorePtr,
+) -> TestHeader {
+	let mut header = TestHeader::new(
+		number,
+		Default::default(),
+		Default::default(),
+		Default::default(),
+		Digest { logs: vec![DigestItem::from(claim)] },
+	);
+	let signature: AuthoritySignature = keystore
+		.bandersnatch_sign(AuthorityId::ID, author.as_ref(), header.hash().as_ref())
+		.unwrap()
+		.unwrap()
+		.into();
+	header.digest_mut().push(DigestItem::from(&signature));
+	header
+}
+
+fn verify(
+	header: TestHeader,
+	slot_now: Slot,
+	epoch: &Epoch,
+	ticket: Option<(TicketId, TicketBody)>,
+) -> Result<CheckedHeader<TestHeader, verification::VerifiedHeaderInfo>, Error<TestBlock>> {
+	let claim = find_slot_claim::<TestBlock>(&header).unwrap();
+	check_header::<TestBlock>(VerificationParams { header, claim: &claim, slot_now, epoch, ticket })
+}
+
+#[test]
+fn epoch_increment_works() {
+	let epoch = test_epoch(lax_config());
+
+	let descriptor = NextEpochDescriptor {
+		randomness: [9; 32],
+		authorities: vec![Keyring::Dave.public().into()],
+		config: None,
+	};
+	let next = epoch.increment(descriptor.clone());
+	assert_eq!(next.index, 2);
+	assert_eq!(next.start, epoch.start + EPOCH_LENGTH as u64);
+	assert_eq!(next.randomness, [9; 32]);
+	assert_eq!(next.authorities, descriptor.authorities);
+	assert_eq!(next.config, epoch.config);
+	assert!(next.tickets_aux.is_empty());
+
+	let config = EpochConfiguration { redundancy_factor: 1, attempts_number: 2 };
+	let next = epoch.increment(NextEpochDescriptor { config: Some(config), ..descriptor });
+	assert_eq!(next.config, config);
+}
+
+#[test]
+fn epoch_clone_for_slot_works() {
+	let mut epoch = test_epoch(lax_config());
+	epoch
+		.tickets_aux
+		.insert(1, (0, TicketSecret { attempt_idx: 0, erased_secret: [0; 32] }));
+
+	// Same epoch.
+	let cloned = epoch.clone_for_slot(epoch.start + EPOCH_LENGTH as u64 - 1);
+	assert_eq!(cloned, epoch);
+
+	// Two skipped epochs.
+	let cloned = epoch.clone_for_slot(epoch.start + 2 * EPOCH_LENGTH as u64 + 3);
+	assert_eq!(cloned.index, epoch.index + 2);
+	assert_eq!(cloned.start, epoch.start + 2 * EPOCH_LENGTH as u64);
+	assert_eq!(cloned.randomness, epoch.randomness);
+	assert!(cloned.tickets_aux.is_empty());
+}
+
+#[test]
+fn fallback_claim_works() {
+	let keystore = create_keystore(Keyring::Alice);
+	let epoch = test_epoch(lax_config());
+
+	let alice_slot = fallback_slot_for(&epoch, 0);
+	let (claim, author) = claim_slot(alice_slot, &epoch, None, &keystore).unwrap();
+	assert_eq!(author, Keyring::Alice.public().into());
+	assert_eq!(claim.authority_idx, 0);
+	assert_eq!(claim.slot, alice_slot);
+	assert!(claim.ticket_claim.is_none());
+
+	let bob_slot = fallback_slot_for(&epoch, 1);
+	assert!(claim_slot(bob_slot, &epoch, None, &keystore).is_none());
+}
+
+#[test]
+fn tickets_generation_and_claim_works() {
+	let keystore = create_keystore(Keyring::Bob);
+	let mut epoch = test_epoch(lax_config());
+	let ring_ctx = vrf::RingContext::new_testing();
+
+	let tickets = generate_epoch_tickets(&mut epoch, &keystore, &ring_ctx);
+	assert_eq!(tickets.len(), lax_config().attempts_number as usize);
+	assert_eq!(epoch.tickets_aux.len(), tickets.len());
+
+	// Tickets are verifiable using the ring verifier, as done by the runtime.
+	let pks: Vec<_> = epoch.authorities.iter().map(|a| *a.as_ref()).collect();
+	let verifier = ring_ctx.verifier(&pks);
+	for ticket in &tickets {
+		let input = vrf::ticket_id_input(&epoch.randomness, ticket.body.attempt_idx, epoch.index);
+		let sign_data = vrf::ticket_body_sign_data(&ticket.body, input);
+		assert!(ticket.signature.ring_vrf_verify(&sign_data, &verifier));
+
+		let ticket_id = vrf::make_ticket_id(&ticket.signature.pre_output);
+		let (authority_idx, secret) = epoch.tickets_aux.get(&ticket_id).unwrap();
+		assert_eq!(*authority_idx, 1);
+		assert_eq!(secret.attempt_idx, ticket.body.attempt_idx);
+	}
+
+	// The ticket owner claims the slot regardless of the fallback assignment.
+	let ticket = &tickets[0];
+	let ticket_id = vrf::make_ticket_id(&ticket.signature.pre_output);
+	let slot = fallback_slot_for(&epoch, 0);
+	let (claim, author) =
+		claim_slot(slot, &epoch, Some((ticket_id, ticket.body.clone())), &keystore).unwrap();
+	assert_eq!(author, Keyring::Bob.public().into());
+	assert!(claim.ticket_claim.is_some());
+
+	// Somebody else's ticket can't be claimed.
+	let other_keystore = create_keystore(Keyring::Alice);
+	let other_epoch = test_epoch(lax_config());
+	assert!(claim_slot(
+		slot,
+		&other_epoch,
+		Some((ticket_id, ticket.body.clone())),
+		&other_keystore
+	)
+	.is_none());
+}
+
+#[test]
+fn fallback_header_verification_works() {
+	let keystore = create_keystore(Keyring::Alice);
+	let epoch = test_epoch(lax_config());
+
+	let slot = fallback_slot_for(&epoch, 0);
+	let (claim, author) = claim_slot(slot, &epoch, None, &keystore).unwrap();
+	let header = seal_header(1, &claim, &author, &keystore);
+
+	// Valid header.
+	match verify(header.clone(), slot, &epoch, None).unwrap() {
+		CheckedHeader::Checked(pre_header, info) => {
+			assert_eq!(info.author, author);
+			assert_eq!(pre_header.digest().logs().len(), 1);
+		},
+		CheckedHeader::Deferred(..) => panic!("Unexpected deferred header"),
+	}
+
+	// Header from the future.
+	assert!(matches!(
+		verify(header.clone(), Slot::from(*slot - 1), &epoch, None).unwrap(),
+		CheckedHeader::Deferred(_, s) if s == slot
+	));
+
+	// The runtime expects a ticket claim for the slot.
+	let body = TicketBody {
+		attempt_idx: 0,
+		erased_public: Default::default(),
+		revealed_public: Default::default(),
+	};
+	assert!(matches!(
+		verify(header.clone(), slot, &epoch, Some((0, body))),
+		Err(Error::MissingTicketClaim(s)) if s == slot
+	));
+
+	// Not the fallback author for the slot.
+	let bob_slot = fallback_slot_for(&epoch, 1);
+	let data = vrf::slot_claim_sign_data(&epoch.randomness, bob_slot, epoch.index);
+	let claim = SlotClaim {
+		authority_idx: 0,
+		slot: bob_slot,
+		vrf_signature: Keyring::Alice.pair().vrf_sign(&data),
+		ticket_claim: None,
+	};
+	let header = seal_header(1, &claim, &author, &keystore);
+	assert!(matches!(verify(header, bob_slot, &epoch, None), Err(Error::InvalidAuthor(..))));
+
+	// VRF signature not bound to the claimed slot.
+	let data = vrf::slot_claim_sign_data(&epoch.randomness, slot + 1, epoch.index);
+	let claim = SlotClaim {
+		authority_idx: 0,
+		slot,
+		vrf_signature: Keyring::Alice.pair().vrf_sign(&data),
+		ticket_claim: None,
+	};
+	let header = seal_header(1, &claim, &author, &keystore);
+	assert!(matches!(verify(header, slot, &epoch, None), Err(Error::VrfVerificationFailed)));
+}
+
+#[test]
+fn ticket_header_verification_works() {
+	let keystore = create_keystore(Keyring::Charlie);
+	let mut epoch = test_epoch(lax_config());
+	let ring_ctx = vrf::RingContext::new_testing();
+
+	let tickets = generate_epoch_tickets(&mut epoch, &keystore, &ring_ctx);
+	let ticket_id = vrf::make_ticket_id(&tickets[0].signature.pre_output);
+	let ticket = (ticket_id, tickets[0].body.clone());
+
+	let slot = epoch.start + 1;
+	let (claim, author) = claim_slot(slot, &epoch, Some(ticket.clone()), &keystore).unwrap();
+	let header = seal_header(1, &claim, &author, &keystore);
+
+	// Valid header.
+	assert!(matches!(
+		verify(header.clone(), slot, &epoch, Some(ticket.clone())).unwrap(),
+		CheckedHeader::Checked(..)
+	));
+
+	// Ticket claim for a slot without ticket.
+	assert!(matches!(
+		verify(header.clone(), slot, &epoch, None),
+		Err(Error::UnexpectedTicketClaim(s)) if s == slot
+	));
+
+	// Claim using the erased key of a different ticket.
+	let other_ticket_id = vrf::make_ticket_id(&tickets[1].signature.pre_output);
+	let other_ticket = (other_ticket_id, tickets[1].body.clone());
+	assert!(matches!(
+		verify(header, slot, &epoch, Some(other_ticket)),
+		Err(Error::TicketClaimVerificationFailed)
+	));
+
+	// Tampered seal.
+	let mut header = seal_header(1, &claim, &author, &keystore);
+	*header.digest_mut().logs.last_mut().unwrap() =
+		DigestItem::from(&AuthoritySignature::from(Keyring::Charlie.sign(b"foo")));
+	assert!(matches!(verify(header, slot, &epoch, Some(ticket)), Err(Error::BadSignature(_))));
+}
+
+// Emulates a small network of nodes running the full protocol for one epoch: tickets are
+// generated by every node, the runtime keeps and assigns them to slots and every block is
+// authored by the expected node and accepted by all the others.
+#[test]
+fn nodes_author_and_verify_epoch_blocks() {
+	let authorities = [Keyring::Alice, Keyring::Bob, Keyring::Charlie];
+	let config = EpochConfiguration { redundancy_factor: 1, attempts_number: 8 };
+	let ring_ctx = vrf::RingContext::new_testing();
+
+	let nodes: Vec<_> = authorities
+		.iter()
+		.map(|authority| {
+			let keystore = create_keystore(*authority);
+			let mut epoch = test_epoch(config);
+			let tickets = generate_epoch_tickets(&mut epoch, &keystore, &ring_ctx);
+			(keystore, epoch, tickets)
+		})
+		.collect();
+
+	// Runtime side: sort the tickets and assign them to the epoch slots ("outside-in").
+	let mut tickets: Vec<(TicketId, TicketBody)> = nodes
+		.iter()
+		.flat_map(|(_, _, tickets)| tickets.iter())
+		.map(|t| (vrf::make_ticket_id(&t.signature.pre_output), t.body.clone()))
+		.collect();
+	tickets.sort_by_key(|(id, _)| *id);
+	tickets.truncate(EPOCH_LENGTH as usize);
+	let slot_ticket = |slot_idx: u32| {
+		let ticket_idx = if slot_idx < EPOCH_LENGTH / 2 {
+			2 * slot_idx + 1
+		} else {
+			2 * (EPOCH_LENGTH - (slot_idx + 1))
+		};
+		tickets.get(ticket_idx as usize).cloned()
+	};
+
+	let mut ticket_blocks = 0;
+	for slot_idx in 0..EPOCH_LENGTH {
+		let slot = nodes[0].1.start + slot_idx as u64;
+		let ticket = slot_ticket(slot_idx);
+
+		let claims: Vec<_> = nodes
+			.iter()
+			.filter_map(|(keystore, epoch, _)| {
+				claim_slot(slot, epoch, ticket.clone(), keystore)
+					.map(|(claim, author)| seal_header(1, &claim, &author, keystore))
+			})
+			.collect();
+
+		// Exactly one node is entitled to author the block.
+		assert_eq!(claims.len(), 1, "slot {} claimed by {} nodes", slot, claims.len());
+		if ticket.is_some() {
+			ticket_blocks += 1;
+		}
+
+		for (_, epoch, _) in &nodes {
+			assert!(matches!(
+				verify(claims[0].clone(), slot, epoch, ticket.clone()).unwrap(),
+				CheckedHeader::Checked(..)
+			));
+		}
+	}
+	assert_eq!(ticket_blocks, tickets.len().min(EPOCH_LENGTH as usize));
+}
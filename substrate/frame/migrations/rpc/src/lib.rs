@@ -0,0 +1,120 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the multi-block migrations pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::error::ErrorObject, Extensions};
+use pallet_migrations::{MigrationStatus, MigrationsDryRun};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_migrations::runtime_api::MigrationsApi as MigrationsRuntimeApi;
+
+/// The number of steps that `migrations_stepToCompletion` takes if no limit is given.
+pub const DEFAULT_MAX_STEPS: u32 = 1_000;
+
+#[rpc(client, server)]
+pub trait MigrationsApi<BlockHash, BlockNumber> {
+	/// The progress of the multi-block migrations at the given block.
+	#[method(name = "migrations_status")]
+	fn status(&self, at: Option<BlockHash>) -> RpcResult<MigrationStatus<BlockNumber>>;
+
+	/// Dry-run the remaining migration steps on top of the given block.
+	///
+	/// Takes at most `max_steps` steps, or [`DEFAULT_MAX_STEPS`] if not given.
+	#[method(name = "migrations_stepToCompletion", with_extensions)]
+	fn step_to_completion(
+		&self,
+		max_steps: Option<u32>,
+		at: Option<BlockHash>,
+	) -> RpcResult<MigrationsDryRun<BlockNumber>>;
+}
+
+/// Provides RPC methods to inspect the multi-block migrations.
+pub struct Migrations<C, B> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Migrations<C, B> {
+	/// Creates a new instance of the Migrations Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, BlockNumber> MigrationsApiServer<<Block as BlockT>::Hash, BlockNumber>
+	for Migrations<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: MigrationsRuntimeApi<Block, BlockNumber>,
+	BlockNumber: Codec + serde::Serialize + Send + Sync + 'static,
+{
+	fn status(&self, at: Option<Block::Hash>) -> RpcResult<MigrationStatus<BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.status(at_hash).map_err(|e| {
+			ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query the migration status.",
+				Some(e.to_string()),
+			)
+		})
+	}
+
+	fn step_to_completion(
+		&self,
+		ext: &Extensions,
+		max_steps: Option<u32>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<MigrationsDryRun<BlockNumber>> {
+		sc_rpc_api::check_if_safe(ext)?;
+
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.step_to_completion(at_hash, max_steps.unwrap_or(DEFAULT_MAX_STEPS))
+			.map_err(|e| {
+				ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to dry-run the migrations.",
+					Some(e.to_string()),
+				)
+			})
+	}
+}
@@ -148,6 +148,7 @@ mod benchmarking;
 pub mod migrations;
 mod mock;
 pub mod mock_helpers;
+pub mod runtime_api;
 mod tests;
 pub mod weights;
 
@@ -171,7 +172,9 @@ use frame_system::{
 	pallet_prelude::{BlockNumberFor, *},
 	Pallet as System,
 };
-use sp_runtime::Saturating;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{traits::One, Saturating};
 
 /// Points to the next migration to execute.
 #[derive(
@@ -281,6 +284,68 @@ impl<Id> HistoricCleanupSelector<Id> {
 	}
 }
 
+/// Progress of the multi-block migrations, as returned by [`Pallet::status`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MigrationStatus<BlockNumber> {
+	/// The currently active migration, if any.
+	pub active: Option<ActiveMigrationStatus<BlockNumber>>,
+	/// Whether the migrations got stuck and require governance intervention.
+	pub stuck: bool,
+	/// Whether extrinsics are paused because migrations are ongoing.
+	pub extrinsics_paused: bool,
+}
+
+/// Progress of the currently active migration.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ActiveMigrationStatus<BlockNumber> {
+	/// The identifier of the migration.
+	///
+	/// `None` if the index points past the last migration, which happens when the upgrade is
+	/// about to complete.
+	pub id: Option<Vec<u8>>,
+	/// The index of the migration within [`Config::Migrations`].
+	pub index: u32,
+	/// The number of migrations in [`Config::Migrations`].
+	pub migrations: u32,
+	/// The encoded length of the inner cursor of the migration.
+	pub cursor_len: u32,
+	/// The number of blocks that the migration took so far.
+	///
+	/// Migrations take at most one step per block, hence this is also what
+	/// [`SteppedMigration::max_steps`] is checked against.
+	pub steps: BlockNumber,
+	/// The estimated number of remaining steps, if provided by [`SteppedMigration::estimate`].
+	pub remaining_steps: Option<u32>,
+}
+
+/// The weight consumed by a single step of the migrations.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MigrationStepReport<BlockNumber> {
+	/// The block in which the step was executed.
+	pub block: BlockNumber,
+	/// The index of the migration that was active at the start of the step.
+	pub index: u32,
+	/// The weight consumed by the step.
+	pub weight: Weight,
+}
+
+/// Outcome of [`Pallet::step_to_completion`].
+#[derive(Clone, Eq, PartialEq, Encode, Decode, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MigrationsDryRun<BlockNumber> {
+	/// The executed steps, in order.
+	pub steps: Vec<MigrationStepReport<BlockNumber>>,
+	/// The status after the last step.
+	pub status: MigrationStatus<BlockNumber>,
+}
+
 /// Convenience alias for [`MigrationCursor`].
 pub type CursorOf<T> = MigrationCursor<RawCursorOf<T>, BlockNumberFor<T>>;
 
@@ -848,6 +913,57 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// The current progress of the multi-block migrations.
+	pub fn status() -> MigrationStatus<BlockNumberFor<T>> {
+		let cursor = Cursor::<T>::get();
+
+		let active = cursor.as_ref().and_then(|cursor| cursor.as_active()).map(|active| {
+			let inner_cursor = active.inner_cursor.as_ref().map(|c| &c[..]);
+			ActiveMigrationStatus {
+				id: T::Migrations::nth_id(active.index),
+				index: active.index,
+				migrations: T::Migrations::len(),
+				cursor_len: inner_cursor.map_or(0, |c| c.len() as u32),
+				steps: System::<T>::block_number().saturating_sub(active.started_at),
+				remaining_steps: T::Migrations::nth_estimate(active.index, inner_cursor),
+			}
+		});
+
+		MigrationStatus {
+			active,
+			stuck: matches!(cursor, Some(MigrationCursor::Stuck)),
+			extrinsics_paused: <T as frame_system::Config>::MultiBlockMigrator::ongoing(),
+		}
+	}
+
+	/// Step the migrations until they are no longer active or `max_steps` steps were taken,
+	/// reporting the weight consumed by each step.
+	///
+	/// If no migrations are ongoing, the configured migrations are onboarded first, as on a
+	/// runtime upgrade. Every step is executed as if in the next block.
+	///
+	/// This writes to storage and is only meant to be used on throwaway state, for example
+	/// through [`runtime_api::MigrationsApi`] or when testing an upgrade on a snapshot.
+	pub fn step_to_completion(max_steps: u32) -> MigrationsDryRun<BlockNumberFor<T>> {
+		if !Cursor::<T>::exists() {
+			Self::onboard_new_mbms();
+		}
+
+		let mut steps = Vec::new();
+		for _ in 0..max_steps {
+			let Some(MigrationCursor::Active(cursor)) = Cursor::<T>::get() else { break };
+
+			let block = System::<T>::block_number().saturating_add(One::one());
+			System::<T>::set_block_number(block);
+			let weight = Self::progress_mbms(block);
+
+			log::debug!("MBM #{} took {weight} in block {block:?}", cursor.index);
+			steps.push(MigrationStepReport { block, index: cursor.index, weight });
+		}
+
+		MigrationsDryRun { steps, status: Self::status() }
+	}
+
 	/// The maximal weight of calling the private `Self::exec_migration` function.
 	pub fn exec_migration_max_weight() -> Weight {
 		T::WeightInfo::exec_migration_complete()
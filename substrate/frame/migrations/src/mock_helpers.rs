@@ -119,6 +119,17 @@ impl SteppedMigrations for MockedMigrations {
 		Self::nth_step(n, cursor, meter)
 	}
 
+	fn nth_estimate(n: u32, cursor: Option<&[u8]>) -> Option<u32> {
+		let (kind, steps) = MIGRATIONS::get().get(n as usize).copied()?;
+		if matches!(kind, TimeoutAfter) {
+			return None
+		}
+
+		let count: u32 = cursor.and_then(|mut c| Decode::decode(&mut c).ok()).unwrap_or(0);
+		// One step per count plus the final one that completes the migration.
+		Some(steps.saturating_sub(count).saturating_add(1))
+	}
+
 	fn nth_max_steps(n: u32) -> Option<Option<u32>> {
 		MIGRATIONS::get().get(n as usize).map(|(_, s)| Some(*s))
 	}
@@ -0,0 +1,39 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the FRAME Migrations pallet.
+
+use crate::{MigrationStatus, MigrationsDryRun};
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	/// Inspect the multi-block migrations, as used by `pallet-migrations-rpc`.
+	///
+	/// Runtimes have to opt in by implementing it, as the kitchensink runtime does.
+	pub trait MigrationsApi<BlockNumber>
+	where
+		BlockNumber: Codec
+	{
+		/// The current progress of the multi-block migrations.
+		fn status() -> MigrationStatus<BlockNumber>;
+
+		/// Step the migrations until they complete or `max_steps` steps were taken.
+		///
+		/// Only meant to be called on throwaway state, since it changes storage.
+		fn step_to_completion(max_steps: u32) -> MigrationsDryRun<BlockNumber>;
+	}
+}
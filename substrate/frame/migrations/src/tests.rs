@@ -22,7 +22,8 @@ use frame_support::{pallet_prelude::Weight, traits::OnRuntimeUpgrade};
 use crate::{
 	mock::{Test as T, *},
 	mock_helpers::{MockedMigrationKind::*, *},
-	Cursor, Event, FailedMigrationHandling, MigrationCursor,
+	ActiveMigrationStatus, Cursor, Event, FailedMigrationHandling, MigrationCursor,
+	MigrationStatus,
 };
 
 #[docify::export]
@@ -367,6 +368,84 @@ fn migration_timeout_errors() {
 	});
 }
 
+#[test]
+fn status_works() {
+	test_closure(|| {
+		assert_eq!(
+			Migrations::status(),
+			MigrationStatus { active: None, stuck: false, extrinsics_paused: false }
+		);
+
+		MockedMigrations::set(vec![(SucceedAfter, 2), (TimeoutAfter, 3)]);
+		System::set_block_number(1);
+		Migrations::on_runtime_upgrade();
+		run_to_block(2);
+
+		assert_eq!(
+			Migrations::status(),
+			MigrationStatus {
+				active: Some(ActiveMigrationStatus {
+					id: Some(mocked_id(SucceedAfter, 2).into_inner()),
+					index: 0,
+					migrations: 2,
+					cursor_len: 4,
+					steps: 1,
+					remaining_steps: Some(2),
+				}),
+				stuck: false,
+				extrinsics_paused: true,
+			}
+		);
+
+		// Migrations that never terminate cannot give an estimate.
+		run_to_block(5);
+		let active = Migrations::status().active.unwrap();
+		assert_eq!((active.index, active.remaining_steps), (1, None));
+
+		Cursor::<T>::set(Some(MigrationCursor::Stuck));
+		assert_eq!(
+			Migrations::status(),
+			MigrationStatus { active: None, stuck: true, extrinsics_paused: true }
+		);
+	});
+}
+
+#[test]
+fn step_to_completion_works() {
+	use Event::*;
+	test_closure(|| {
+		MockedMigrations::set(vec![(SucceedAfter, 0), (SucceedAfter, 2)]);
+		System::set_block_number(1);
+
+		// Onboards the migrations when none are ongoing.
+		let dry_run = Migrations::step_to_completion(1);
+		assert_eq!(dry_run.steps.len(), 1);
+		assert_eq!((dry_run.steps[0].block, dry_run.steps[0].index), (2, 0));
+		let active = dry_run.status.active.unwrap();
+		assert_eq!((active.index, active.steps, active.remaining_steps), (1, 0, Some(2)));
+
+		let dry_run = Migrations::step_to_completion(10);
+		assert_eq!(
+			dry_run.steps.iter().map(|s| (s.block, s.index)).collect::<Vec<_>>(),
+			vec![(3, 1), (4, 1)]
+		);
+		assert_eq!(
+			dry_run.status,
+			MigrationStatus { active: None, stuck: false, extrinsics_paused: false }
+		);
+
+		assert_eq!(historic(), vec![mocked_id(SucceedAfter, 0), mocked_id(SucceedAfter, 2)]);
+		assert_events(vec![
+			UpgradeStarted { migrations: 2 },
+			MigrationCompleted { index: 0, took: 1 },
+			MigrationAdvanced { index: 1, took: 0 },
+			MigrationAdvanced { index: 1, took: 1 },
+			MigrationCompleted { index: 1, took: 2 },
+			UpgradeCompleted,
+		]);
+	});
+}
+
 #[cfg(feature = "try-runtime")]
 #[test]
 fn try_runtime_success_case() {
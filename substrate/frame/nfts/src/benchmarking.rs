@@ -211,6 +211,27 @@ fn default_collection_config<T: Config<I>, I: 'static>() -> CollectionConfigFor<
 	make_collection_config::<T, I>(CollectionSetting::empty())
 }
 
+fn royalty_config<T: Config<I>, I: 'static>(recipients: u32) -> RoyaltyConfigOf<T, I> {
+	let recipients = (0..recipients)
+		.map(|i| RoyaltyRecipient { account: account("royalty", i, SEED), basis_points: 1 })
+		.collect::<Vec<_>>();
+	RoyaltyConfig { recipients: recipients.try_into().unwrap() }
+}
+
+fn set_royalty<T: Config<I>, I: 'static>(collection: T::CollectionId, recipients: u32) {
+	let config = royalty_config::<T, I>(recipients);
+	CollectionRoyaltyOf::<T, I>::insert(
+		collection,
+		CollectionRoyalty { deposit: Zero::zero(), config },
+	);
+}
+
+fn sale_price<T: Config<I>, I: 'static>() -> ItemPrice<T, I> {
+	// Big enough for every recipient of a `royalty_config` to receive the existential deposit,
+	// so that every royalty is paid and creates the account of its recipient.
+	T::Currency::minimum_balance().saturating_mul(MAX_BASIS_POINTS.into())
+}
+
 fn default_item_config() -> ItemConfig {
 	ItemConfig { settings: ItemSettings::all_enabled() }
 }
@@ -662,13 +683,16 @@ benchmarks_instance_pallet! {
 	}
 
 	buy_item {
+		let r in 0 .. T::MaxRoyaltyRecipients::get();
 		let (collection, seller, _) = create_collection::<T, I>();
 		let (item, ..) = mint_item::<T, I>(0);
 		let buyer: T::AccountId = account("buyer", 0, SEED);
 		let buyer_lookup = T::Lookup::unlookup(buyer.clone());
-		let price = ItemPrice::<T, I>::from(0u32);
+		let price = sale_price::<T, I>();
+		set_royalty::<T, I>(collection, r);
 		let origin = SystemOrigin::Signed(seller.clone()).into();
 		Nfts::<T, I>::set_price(origin, collection, item, Some(price), Some(buyer_lookup))?;
+		T::Currency::make_free_balance_be(&seller, T::Currency::minimum_balance());
 		T::Currency::make_free_balance_be(&buyer, DepositBalanceOf::<T, I>::max_value());
 	}: _(SystemOrigin::Signed(buyer.clone()), collection, item, price)
 	verify {
@@ -750,16 +774,18 @@ benchmarks_instance_pallet! {
 	}
 
 	claim_swap {
+		let r in 0 .. T::MaxRoyaltyRecipients::get();
 		let (collection, caller, _) = create_collection::<T, I>();
 		let (item1, ..) = mint_item::<T, I>(0);
 		let (item2, ..) = mint_item::<T, I>(1);
-		let price = ItemPrice::<T, I>::from(0u32);
+		let price = sale_price::<T, I>();
 		let price_direction = PriceDirection::Receive;
 		let price_with_direction = PriceWithDirection { amount: price, direction: price_direction };
 		let duration = T::MaxDeadlineDuration::get();
 		let target: T::AccountId = account("target", 0, SEED);
 		let target_lookup = T::Lookup::unlookup(target.clone());
-		T::Currency::make_free_balance_be(&target, T::Currency::minimum_balance());
+		T::Currency::make_free_balance_be(&target, DepositBalanceOf::<T, I>::max_value());
+		set_royalty::<T, I>(collection, r);
 		let origin = SystemOrigin::Signed(caller.clone());
 		T::BlockNumberProvider::set_block_number(One::one());
 		Nfts::<T, I>::transfer(origin.clone().into(), collection, item2, target_lookup)?;
@@ -772,6 +798,7 @@ benchmarks_instance_pallet! {
 			Some(price_with_direction.clone()),
 			duration,
 		)?;
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
 	}: _(SystemOrigin::Signed(target.clone()), collection, item2, collection, item1, Some(price_with_direction.clone()))
 	verify {
 		let current_block = T::BlockNumberProvider::current_block_number();
@@ -878,5 +905,13 @@ benchmarks_instance_pallet! {
 		);
 	}
 
+	set_collection_royalty {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let royalty = royalty_config::<T, I>(T::MaxRoyaltyRecipients::get());
+	}: _(SystemOrigin::Signed(caller.clone()), collection, Some(royalty.clone()))
+	verify {
+		assert_last_event::<T, I>(Event::CollectionRoyaltySet { collection, royalty }.into());
+	}
+
 	impl_benchmark_test_suite!(Nfts, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -0,0 +1,166 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains helper methods to configure and pay the royalties of a collection in the
+//! NFTs pallet.
+//! Royalties are paid whenever an item changes hands for a price, i.e. in `buy_item` and in
+//! `claim_swap` when the swap has a price attached.
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement::KeepAlive},
+};
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Sets or removes the royalty configuration of a collection.
+	///
+	/// - `maybe_check_owner`: An optional account ID used to check ownership permission. If `None`,
+	///   it is considered as a root call.
+	/// - `collection`: The ID of the collection whose royalties are being configured.
+	/// - `royalty`: The new royalty configuration, or `None` to remove it.
+	///
+	/// This function checks that the royalties of the collection aren't locked, unless it's a root
+	/// call, and that the sum of the recipients' shares doesn't exceed [`Config::MaxRoyalty`]. A
+	/// deposit of [`Config::RoyaltyDepositPerRecipient`] is reserved from the collection owner for
+	/// every recipient if required by the collection. Emits either a `CollectionRoyaltySet` or a
+	/// `CollectionRoyaltyCleared` event.
+	pub(crate) fn do_set_collection_royalty(
+		maybe_check_owner: Option<T::AccountId>,
+		collection: T::CollectionId,
+		royalty: Option<RoyaltyConfigOf<T, I>>,
+	) -> DispatchResult {
+		ensure!(
+			Self::is_pallet_feature_enabled(PalletFeature::Trading),
+			Error::<T, I>::MethodDisabled
+		);
+
+		let mut details =
+			Collection::<T, I>::get(&collection).ok_or(Error::<T, I>::UnknownCollection)?;
+		if let Some(check_owner) = &maybe_check_owner {
+			ensure!(check_owner == &details.owner, Error::<T, I>::NoPermission);
+		}
+
+		let is_root = maybe_check_owner.is_none();
+		let collection_config = Self::get_collection_config(&collection)?;
+		ensure!(
+			is_root || collection_config.is_setting_enabled(CollectionSetting::UnlockedRoyalties),
+			Error::<T, I>::LockedCollectionRoyalty
+		);
+		if let Some(royalty) = &royalty {
+			let max_royalty = T::MaxRoyalty::get().min(MAX_BASIS_POINTS);
+			ensure!(
+				royalty.total_basis_points() <= max_royalty as u32,
+				Error::<T, I>::RoyaltyTooHigh
+			);
+		}
+
+		CollectionRoyaltyOf::<T, I>::try_mutate_exists(collection, |maybe_royalty| {
+			let old_deposit = maybe_royalty.take().map_or(Zero::zero(), |r| r.deposit);
+			details.owner_deposit.saturating_reduce(old_deposit);
+			let mut deposit = Zero::zero();
+			if let Some(royalty) = &royalty {
+				if !is_root &&
+					collection_config.is_setting_enabled(CollectionSetting::DepositRequired)
+				{
+					deposit = T::RoyaltyDepositPerRecipient::get()
+						.saturating_mul((royalty.recipients.len() as u32).into());
+				}
+			}
+			if deposit > old_deposit {
+				T::Currency::reserve(&details.owner, deposit - old_deposit)?;
+			} else if deposit < old_deposit {
+				T::Currency::unreserve(&details.owner, old_deposit - deposit);
+			}
+			details.owner_deposit.saturating_accrue(deposit);
+
+			Collection::<T, I>::insert(&collection, details);
+
+			match royalty {
+				Some(royalty) => {
+					*maybe_royalty = Some(CollectionRoyalty { deposit, config: royalty.clone() });
+					Self::deposit_event(Event::CollectionRoyaltySet { collection, royalty });
+				},
+				None => Self::deposit_event(Event::CollectionRoyaltyCleared { collection }),
+			}
+			Ok(())
+		})
+	}
+
+	/// Pays the royalties owed on the sale of an item.
+	///
+	/// The royalties are transferred from the `payer` to each recipient configured for the
+	/// `collection`, and a `RoyaltyPaid` event is emitted for every transfer. Returns the total
+	/// amount paid, which the caller should deduct from the amount forwarded to the seller.
+	///
+	/// A royalty which would leave its recipient below the existential deposit can't be paid and
+	/// is skipped, so that it stays with the seller instead of failing the sale.
+	///
+	/// - `collection`: The collection of the item being sold.
+	/// - `item`: The item being sold.
+	/// - `payer`: The account paying the `price`.
+	/// - `price`: The price the item is being sold for.
+	pub(crate) fn do_pay_royalties(
+		collection: T::CollectionId,
+		item: T::ItemId,
+		payer: &T::AccountId,
+		price: ItemPrice<T, I>,
+	) -> Result<ItemPrice<T, I>, DispatchError> {
+		let Some(CollectionRoyalty { config: royalty, .. }) =
+			CollectionRoyaltyOf::<T, I>::get(&collection)
+		else {
+			return Ok(Zero::zero())
+		};
+
+		let mut total: ItemPrice<T, I> = Zero::zero();
+		let minimum_balance = T::Currency::minimum_balance();
+		for (recipient, amount) in royalty.royalties(price) {
+			if T::Currency::total_balance(recipient).saturating_add(amount) < minimum_balance {
+				continue
+			}
+			T::Currency::transfer(payer, recipient, amount, KeepAlive)?;
+			total.saturating_accrue(amount);
+			Self::deposit_event(Event::RoyaltyPaid {
+				collection,
+				item,
+				payer: payer.clone(),
+				recipient: recipient.clone(),
+				amount,
+			});
+		}
+		Ok(total)
+	}
+
+	/// Transfers `price` from the `buyer` to the `seller` of an item, paying the collection's
+	/// royalties out of it first.
+	///
+	/// - `collection`: The collection of the item being sold.
+	/// - `item`: The item being sold.
+	/// - `buyer`: The account paying the `price`.
+	/// - `seller`: The account receiving the `price` minus royalties.
+	/// - `price`: The price the item is being sold for.
+	pub(crate) fn do_pay_for_item(
+		collection: T::CollectionId,
+		item: T::ItemId,
+		buyer: &T::AccountId,
+		seller: &T::AccountId,
+		price: ItemPrice<T, I>,
+	) -> DispatchResult {
+		let royalties = Self::do_pay_royalties(collection, item, buyer, price)?;
+		T::Currency::transfer(buyer, seller, price.saturating_sub(royalties), KeepAlive)
+	}
+}
@@ -82,12 +82,18 @@ impl<T: Config<I>, I: 'static> Inspect<<T as SystemConfig>::AccountId> for Palle
 	/// `item` is `Some`. Otherwise, returns the system attribute value of `collection`
 	/// corresponding to `key`.
 	///
+	/// The [`PalletAttributes::Royalty`] key of a collection maps to its SCALE-encoded
+	/// [`RoyaltyConfig`].
+	///
 	/// By default this is `None`; no attributes are defined.
 	fn system_attribute(
 		collection: &Self::CollectionId,
 		item: Option<&Self::ItemId>,
 		key: &[u8],
 	) -> Option<Vec<u8>> {
+		if item.is_none() && key == PalletAttributes::<Self::CollectionId>::Royalty.encode() {
+			return CollectionRoyaltyOf::<T, I>::get(collection).map(|r| r.config.encode())
+		}
 		let namespace = AttributeNamespace::Pallet;
 		let key = BoundedSlice::<_, _>::try_from(key).ok()?;
 		Attribute::<T, I>::get((collection, item, namespace, key)).map(|a| a.0.into())
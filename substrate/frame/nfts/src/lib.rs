@@ -185,6 +185,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type DepositPerByte: Get<DepositBalanceOf<Self, I>>;
 
+		/// The amount of funds that must be reserved for every royalty recipient of a collection.
+		#[pallet::constant]
+		type RoyaltyDepositPerRecipient: Get<DepositBalanceOf<Self, I>>;
+
 		/// The maximum length of data stored on-chain.
 		#[pallet::constant]
 		type StringLimit: Get<u32>;
@@ -217,6 +221,14 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxAttributesPerCall: Get<u32>;
 
+		/// The max number of accounts that can receive royalties of a single collection.
+		#[pallet::constant]
+		type MaxRoyaltyRecipients: Get<u32>;
+
+		/// The max share of a sale price that can be paid as royalties, in basis points.
+		#[pallet::constant]
+		type MaxRoyalty: Get<u16>;
+
 		/// Disables some of pallet's features.
 		#[pallet::constant]
 		type Features: Get<PalletFeatures>;
@@ -402,6 +414,11 @@ pub mod pallet {
 	pub type CollectionConfigOf<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Blake2_128Concat, T::CollectionId, CollectionConfigFor<T, I>, OptionQuery>;
 
+	/// Royalties paid on every sale of a collection's items.
+	#[pallet::storage]
+	pub type CollectionRoyaltyOf<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, CollectionRoyaltyFor<T, I>, OptionQuery>;
+
 	/// Config of an item.
 	#[pallet::storage]
 	pub type ItemConfigOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
@@ -594,6 +611,18 @@ pub mod pallet {
 			attribute: PalletAttributes<T::CollectionId>,
 			value: BoundedVec<u8, T::ValueLimit>,
 		},
+		/// The royalty configuration of a `collection` was set.
+		CollectionRoyaltySet { collection: T::CollectionId, royalty: RoyaltyConfigOf<T, I> },
+		/// The royalty configuration of a `collection` was removed.
+		CollectionRoyaltyCleared { collection: T::CollectionId },
+		/// A royalty was paid on the sale of an `item`.
+		RoyaltyPaid {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			payer: T::AccountId,
+			recipient: T::AccountId,
+			amount: ItemPrice<T, I>,
+		},
 	}
 
 	#[pallet::error]
@@ -688,6 +717,12 @@ pub mod pallet {
 		CollectionNotEmpty,
 		/// The witness data should be provided.
 		WitnessRequired,
+		/// The sum of the royalty shares exceeds `MaxRoyalty`.
+		RoyaltyTooHigh,
+		/// Items of this collection can't be transferred by a delegate without paying royalties.
+		RoyaltyRequired,
+		/// Collection's royalties are locked.
+		LockedCollectionRoyalty,
 	}
 
 	#[pallet::call]
@@ -1030,6 +1065,12 @@ pub mod pallet {
 
 			Self::do_transfer(collection, item, dest, |_, details| {
 				if details.owner != origin {
+					let collection_config = Self::get_collection_config(&collection)?;
+					ensure!(
+						collection_config
+							.is_setting_enabled(CollectionSetting::RoyaltyFreeTransfers),
+						Error::<T, I>::RoyaltyRequired
+					);
 					let deadline =
 						details.approvals.get(&origin).ok_or(Error::<T, I>::NoPermission)?;
 					if let Some(d) = deadline {
@@ -1760,7 +1801,7 @@ pub mod pallet {
 		///
 		/// Emits `ItemBought` on success.
 		#[pallet::call_index(32)]
-		#[pallet::weight(T::WeightInfo::buy_item())]
+		#[pallet::weight(T::WeightInfo::buy_item(T::MaxRoyaltyRecipients::get()))]
 		pub fn buy_item(
 			origin: OriginFor<T>,
 			collection: T::CollectionId,
@@ -1860,7 +1901,7 @@ pub mod pallet {
 		///
 		/// Emits `SwapClaimed` on success.
 		#[pallet::call_index(36)]
-		#[pallet::weight(T::WeightInfo::claim_swap())]
+		#[pallet::weight(T::WeightInfo::claim_swap(T::MaxRoyaltyRecipients::get()))]
 		pub fn claim_swap(
 			origin: OriginFor<T>,
 			send_collection: T::CollectionId,
@@ -1931,6 +1972,37 @@ pub mod pallet {
 			Self::validate_signature(&Encode::encode(&data), &signature, &signer)?;
 			Self::do_set_attributes_pre_signed(origin, data, signer)
 		}
+
+		/// Set (or reset) the royalties paid on every sale of the collection's items.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Owner of
+		/// the `collection`.
+		///
+		/// Royalties are deducted from the price paid in `buy_item` and `claim_swap` and
+		/// transferred to the recipients before the remainder goes to the seller.
+		///
+		/// If the origin is Signed, then funds of signer are reserved according to the formula:
+		/// `RoyaltyDepositPerRecipient * recipients.len` taking into account any already reserved
+		/// funds.
+		///
+		/// - `collection`: The identifier of the collection.
+		/// - `royalty`: The recipients and their share in basis points, which may not add up to
+		///   more than `MaxRoyalty`. Pass `None` to reset.
+		///
+		/// Emits `CollectionRoyaltySet` on success if the royalty is not `None`.
+		/// Emits `CollectionRoyaltyCleared` on success if the royalty is `None`.
+		#[pallet::call_index(39)]
+		#[pallet::weight(T::WeightInfo::set_collection_royalty())]
+		pub fn set_collection_royalty(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			royalty: Option<RoyaltyConfigOf<T, I>>,
+		) -> DispatchResult {
+			let maybe_check_owner = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
+			Self::do_set_collection_royalty(maybe_check_owner, collection, royalty)
+		}
 	}
 }
 
@@ -3875,3 +3875,427 @@ fn clear_collection_metadata_works() {
 		assert_eq!(Balances::reserved_balance(&account(1)), 10);
 	});
 }
+
+#[test]
+fn set_collection_royalty_should_work() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let collection_id = 0;
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+
+		let royalty = RoyaltyConfig {
+			recipients: bvec![
+				RoyaltyRecipient { account: user_1.clone(), basis_points: 500 },
+				RoyaltyRecipient { account: user_2.clone(), basis_points: 250 },
+			],
+		};
+
+		assert_noop!(
+			Nfts::set_collection_royalty(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				Some(royalty.clone()),
+			),
+			Error::<Test>::NoPermission
+		);
+
+		assert_noop!(
+			Nfts::set_collection_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				Some(RoyaltyConfig {
+					recipients: bvec![
+						RoyaltyRecipient { account: user_1.clone(), basis_points: 6_000 },
+						RoyaltyRecipient { account: user_2.clone(), basis_points: 4_001 },
+					],
+				}),
+			),
+			Error::<Test>::RoyaltyTooHigh
+		);
+		// Royalties are capped by `MaxRoyalty`, for the force origin as well.
+		assert_noop!(
+			Nfts::set_collection_royalty(
+				RuntimeOrigin::root(),
+				collection_id,
+				Some(RoyaltyConfig {
+					recipients: bvec![
+						RoyaltyRecipient { account: user_1.clone(), basis_points: 3_000 },
+						RoyaltyRecipient { account: user_2.clone(), basis_points: 2_001 },
+					],
+				}),
+			),
+			Error::<Test>::RoyaltyTooHigh
+		);
+
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(royalty.clone()),
+		));
+		assert_eq!(
+			CollectionRoyaltyOf::<Test>::get(collection_id).map(|r| r.config),
+			Some(royalty.clone())
+		);
+		assert!(events().contains(&Event::<Test>::CollectionRoyaltySet {
+			collection: collection_id,
+			royalty: royalty.clone(),
+		}));
+
+		// The royalty configuration is exposed as a system attribute of the collection.
+		let key = PalletAttributes::<<Test as Config>::CollectionId>::Royalty.encode();
+		assert_eq!(
+			<Nfts as Inspect<AccountIdOf<Test>>>::system_attribute(&collection_id, None, &key),
+			Some(royalty.encode())
+		);
+
+		assert_ok!(Nfts::set_collection_royalty(RuntimeOrigin::root(), collection_id, None));
+		assert_eq!(CollectionRoyaltyOf::<Test>::get(collection_id), None);
+		assert!(events()
+			.contains(&Event::<Test>::CollectionRoyaltyCleared { collection: collection_id }));
+		assert_eq!(
+			<Nfts as Inspect<AccountIdOf<Test>>>::system_attribute(&collection_id, None, &key),
+			None
+		);
+	});
+}
+
+#[test]
+fn collection_royalty_deposit_and_lock_should_work() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let collection_id = 0;
+		let recipient = |id| RoyaltyRecipient { account: account(id), basis_points: 100 };
+
+		Balances::make_free_balance_be(&user_1, 100);
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			collection_config_with_all_settings_enabled()
+		));
+
+		// A deposit is reserved for every recipient.
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(RoyaltyConfig { recipients: bvec![recipient(2), recipient(3)] }),
+		));
+		assert_eq!(CollectionRoyaltyOf::<Test>::get(collection_id).unwrap().deposit, 2);
+		assert_eq!(Collection::<Test>::get(collection_id).unwrap().owner_deposit, 2);
+		assert_eq!(Balances::reserved_balance(&user_1), 2);
+
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(RoyaltyConfig { recipients: bvec![recipient(2)] }),
+		));
+		assert_eq!(Collection::<Test>::get(collection_id).unwrap().owner_deposit, 1);
+		assert_eq!(Balances::reserved_balance(&user_1), 1);
+
+		// Once locked, the owner can neither change nor clear the royalties.
+		assert_ok!(Nfts::lock_collection(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			CollectionSettings::from_disabled(CollectionSetting::UnlockedRoyalties.into()),
+		));
+		assert_noop!(
+			Nfts::set_collection_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				Some(RoyaltyConfig { recipients: bvec![recipient(2), recipient(3)] }),
+			),
+			Error::<Test>::LockedCollectionRoyalty
+		);
+		assert_noop!(
+			Nfts::set_collection_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				None,
+			),
+			Error::<Test>::LockedCollectionRoyalty
+		);
+
+		// The force origin can, and the deposit is returned.
+		assert_ok!(Nfts::set_collection_royalty(RuntimeOrigin::root(), collection_id, None));
+		assert_eq!(CollectionRoyaltyOf::<Test>::get(collection_id), None);
+		assert_eq!(Collection::<Test>::get(collection_id).unwrap().owner_deposit, 0);
+		assert_eq!(Balances::reserved_balance(&user_1), 0);
+	});
+}
+
+#[test]
+fn buy_item_should_pay_royalties() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let user_4 = account(4);
+		let collection_id = 0;
+		let item_id = 1;
+		let price = 200;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_1, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_3, initial_balance);
+		Balances::make_free_balance_be(&user_4, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_1.clone(),
+			None
+		));
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(RoyaltyConfig {
+				recipients: bvec![
+					RoyaltyRecipient { account: user_3.clone(), basis_points: 500 },
+					RoyaltyRecipient { account: user_4.clone(), basis_points: 250 },
+				],
+			}),
+		));
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			Some(price),
+			None,
+		));
+
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_id,
+			price
+		));
+
+		// 5% and 2.5% of the price go to the royalty recipients, the rest to the seller.
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - price);
+		assert_eq!(Balances::total_balance(&user_3), initial_balance + 10);
+		assert_eq!(Balances::total_balance(&user_4), initial_balance + 5);
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 185);
+		assert_eq!(Item::<Test>::get(collection_id, item_id).unwrap().owner, user_2);
+
+		assert!(events().contains(&Event::<Test>::RoyaltyPaid {
+			collection: collection_id,
+			item: item_id,
+			payer: user_2.clone(),
+			recipient: user_3.clone(),
+			amount: 10,
+		}));
+	});
+}
+
+#[test]
+fn royalties_below_existential_deposit_are_not_paid_to_new_accounts() {
+	new_test_ext().execute_with(|| {
+		ExistentialDeposit::set(10);
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let user_4 = account(4);
+		let user_5 = account(5);
+		let collection_id = 0;
+		let item_id = 1;
+		let price = 200;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_1, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_5, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_1.clone(),
+			None
+		));
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(RoyaltyConfig {
+				recipients: bvec![
+					RoyaltyRecipient { account: user_3.clone(), basis_points: 500 },
+					RoyaltyRecipient { account: user_4.clone(), basis_points: 250 },
+					RoyaltyRecipient { account: user_5.clone(), basis_points: 250 },
+				],
+			}),
+		));
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			Some(price),
+			None,
+		));
+
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_id,
+			price
+		));
+
+		// `user_3` doesn't exist but its royalty covers the existential deposit.
+		assert_eq!(Balances::total_balance(&user_3), 10);
+		// The royalty of `user_4` can't create its account, so it stays with the seller.
+		assert_eq!(Balances::total_balance(&user_4), 0);
+		assert!(!events().iter().any(|e| matches!(
+			e,
+			Event::<Test>::RoyaltyPaid { recipient, .. } if *recipient == user_4
+		)));
+		// An existing account receives a royalty below the existential deposit.
+		assert_eq!(Balances::total_balance(&user_5), initial_balance + 5);
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 185);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - price);
+		assert_eq!(Item::<Test>::get(collection_id, item_id).unwrap().owner, user_2);
+	});
+}
+
+#[test]
+fn claim_swap_should_pay_royalties() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let collection_id = 0;
+		let item_1 = 1;
+		let item_2 = 2;
+		let price = 100;
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Receive };
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_1, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_3, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_1,
+			user_1.clone(),
+			None,
+		));
+		assert_ok!(Nfts::force_mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_2,
+			user_2.clone(),
+			default_item_config(),
+		));
+		assert_ok!(Nfts::set_collection_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(RoyaltyConfig {
+				recipients: bvec![RoyaltyRecipient {
+					account: user_3.clone(),
+					basis_points: 1_000
+				}],
+			}),
+		));
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_1,
+			collection_id,
+			Some(item_2),
+			Some(price_with_direction.clone()),
+			2,
+		));
+
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_2,
+			collection_id,
+			item_1,
+			Some(price_with_direction),
+		));
+
+		// `user_1` sells `item_1` for the price, so 10% of it goes to the royalty recipient.
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - price);
+		assert_eq!(Balances::total_balance(&user_3), initial_balance + 10);
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 90);
+		assert_eq!(Item::<Test>::get(collection_id, item_1).unwrap().owner, user_2);
+		assert_eq!(Item::<Test>::get(collection_id, item_2).unwrap().owner, user_1);
+	});
+}
+
+#[test]
+fn royalty_free_transfers_setting_should_work() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let collection_id = 0;
+		let item_id = 1;
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			collection_config_from_disabled_settings(
+				CollectionSetting::DepositRequired | CollectionSetting::RoyaltyFreeTransfers
+			)
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_1.clone(),
+			None
+		));
+		assert_ok!(Nfts::approve_transfer(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_2.clone(),
+			None
+		));
+
+		// Delegates can't move the item without going through a royalty-paying sale.
+		assert_noop!(
+			Nfts::transfer(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				item_id,
+				user_3.clone()
+			),
+			Error::<Test>::RoyaltyRequired
+		);
+
+		// The owner can still transfer it.
+		assert_ok!(Nfts::transfer(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_3.clone()
+		));
+		assert_eq!(Item::<Test>::get(collection_id, item_id).unwrap().owner, user_3);
+	});
+}
@@ -21,13 +21,16 @@ use super::*;
 use crate::macros::*;
 use alloc::{vec, vec::Vec};
 use codec::{DecodeWithMemTracking, EncodeLike};
+use core::fmt::Debug;
 use enumflags2::{bitflags, BitFlags};
 use frame_support::{
 	pallet_prelude::{BoundedVec, MaxEncodedLen},
 	traits::Get,
-	BoundedBTreeMap, BoundedBTreeSet,
+	BoundedBTreeMap, BoundedBTreeSet, CloneNoBound, EqNoBound, PartialEqNoBound,
+	RuntimeDebugNoBound,
 };
 use scale_info::{build::Fields, meta_type, Path, Type, TypeInfo, TypeParameter};
+use sp_runtime::{traits::AtLeast32BitUnsigned, Perbill};
 
 pub type BlockNumberFor<T, I = ()> =
 	<<T as Config<I>>::BlockNumberProvider as BlockNumberProvider>::BlockNumber;
@@ -81,6 +84,15 @@ pub type PreSignedMintOf<T, I = ()> = PreSignedMint<
 	BlockNumberFor<T, I>,
 	BalanceOf<T, I>,
 >;
+/// A type alias for the royalty configuration of a collection.
+pub type RoyaltyConfigOf<T, I = ()> =
+	RoyaltyConfig<<T as SystemConfig>::AccountId, <T as Config<I>>::MaxRoyaltyRecipients>;
+/// A type alias for the royalties of a collection and their deposit.
+pub type CollectionRoyaltyFor<T, I = ()> = CollectionRoyalty<
+	DepositBalanceOf<T, I>,
+	<T as SystemConfig>::AccountId,
+	<T as Config<I>>::MaxRoyaltyRecipients,
+>;
 /// A type alias for the pre-signed minting configuration on the attribute level of an item.
 pub type PreSignedAttributesOf<T, I = ()> = PreSignedAttributes<
 	<T as Config<I>>::CollectionId,
@@ -295,6 +307,99 @@ pub struct PriceWithDirection<Amount> {
 	pub direction: PriceDirection,
 }
 
+/// The number of basis points that make up 100%.
+pub const MAX_BASIS_POINTS: u16 = 10_000;
+
+/// A single recipient of the royalties paid on secondary sales.
+#[derive(
+	Clone,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct RoyaltyRecipient<AccountId> {
+	/// The account receiving the royalty.
+	pub account: AccountId,
+	/// The share of the sale price paid to `account`, in basis points (1/100 of a percent).
+	pub basis_points: u16,
+}
+
+/// Royalty configuration of a collection.
+#[derive(
+	CloneNoBound,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	EqNoBound,
+	PartialEqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxRecipients))]
+#[codec(mel_bound(AccountId: MaxEncodedLen))]
+pub struct RoyaltyConfig<AccountId: Clone + Eq + Debug, MaxRecipients: Get<u32>> {
+	/// Accounts receiving a share of every sale price of the collection's items.
+	pub recipients: BoundedVec<RoyaltyRecipient<AccountId>, MaxRecipients>,
+}
+
+impl<AccountId: Clone + Eq + Debug, MaxRecipients: Get<u32>>
+	RoyaltyConfig<AccountId, MaxRecipients>
+{
+	/// The sum of the basis points of all the recipients.
+	pub fn total_basis_points(&self) -> u32 {
+		self.recipients.iter().map(|r| r.basis_points as u32).sum()
+	}
+
+	/// Returns the amount owed to each recipient when an item is sold for `price`.
+	///
+	/// Recipients whose share rounds down to zero are skipped.
+	pub fn royalties<Balance: AtLeast32BitUnsigned + Copy>(
+		&self,
+		price: Balance,
+	) -> Vec<(&AccountId, Balance)> {
+		self.recipients
+			.iter()
+			.map(|r| {
+				let share = Perbill::from_rational(r.basis_points as u32, MAX_BASIS_POINTS as u32);
+				(&r.account, share * price)
+			})
+			.filter(|(_, amount)| !amount.is_zero())
+			.collect()
+	}
+}
+
+/// The royalties of a collection.
+#[derive(
+	CloneNoBound,
+	Encode,
+	Decode,
+	EqNoBound,
+	PartialEqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxRecipients))]
+#[codec(mel_bound(Deposit: MaxEncodedLen, AccountId: MaxEncodedLen))]
+pub struct CollectionRoyalty<
+	Deposit: Clone + Eq + Debug,
+	AccountId: Clone + Eq + Debug,
+	MaxRecipients: Get<u32>,
+> {
+	/// The balance deposited for the royalties.
+	///
+	/// This pays for the recipients stored in `config`.
+	pub deposit: Deposit,
+	/// The recipients of the royalties and their share.
+	pub config: RoyaltyConfig<AccountId, MaxRecipients>,
+}
+
 /// Support for up to 64 user-enabled features on a collection.
 #[bitflags]
 #[repr(u64)]
@@ -310,6 +415,12 @@ pub enum CollectionSetting {
 	UnlockedMaxSupply,
 	/// When this isn't set then the deposit is required to hold the items of this collection.
 	DepositRequired,
+	/// Items in this collection can be transferred by approved delegates. When disabled, items
+	/// can only change hands through `buy_item` or `claim_swap`, which pay the collection's
+	/// royalties, or by the owner themselves.
+	RoyaltyFreeTransfers,
+	/// The royalties of this collection can be modified.
+	UnlockedRoyalties,
 }
 
 /// Wrapper type for `BitFlags<CollectionSetting>` that implements `Codec`.
@@ -445,6 +556,9 @@ pub enum PalletAttributes<CollectionId> {
 	UsedToClaim(CollectionId),
 	/// Marks an item as being restricted from transferring.
 	TransferDisabled,
+	/// The royalty configuration of a collection. Exposed read-only through
+	/// `nonfungibles_v2::Inspect::system_attribute`.
+	Royalty,
 }
 
 /// Collection's configuration.
@@ -104,13 +104,14 @@ pub trait WeightInfo {
 	fn set_collection_max_supply() -> Weight;
 	fn update_mint_settings() -> Weight;
 	fn set_price() -> Weight;
-	fn buy_item() -> Weight;
+	fn buy_item(r: u32, ) -> Weight;
 	fn pay_tips(n: u32, ) -> Weight;
 	fn create_swap() -> Weight;
 	fn cancel_swap() -> Weight;
-	fn claim_swap() -> Weight;
+	fn claim_swap(r: u32, ) -> Weight;
 	fn mint_pre_signed(n: u32, ) -> Weight;
 	fn set_attributes_pre_signed(n: u32, ) -> Weight;
+	fn set_collection_royalty() -> Weight;
 }
 
 /// Weights for `pallet_nfts` using the Substrate node and recommended hardware.
@@ -673,7 +674,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Nfts::Account` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::PendingSwapOf` (r:0 w:1)
 	/// Proof: `Nfts::PendingSwapOf` (`max_values`: None, `max_size`: Some(71), added: 2546, mode: `MaxEncodedLen`)
-	fn buy_item() -> Weight {
+	/// Not benchmarked with royalties yet, to be regenerated with the benchmark bot. Bounded by the
+	/// weight measured without royalties, the read of `CollectionRoyaltyOf` and one transfer to
+	/// a new account per royalty recipient.
+	/// The range of component `r` is `[0, 10]`.
+	fn buy_item(r: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `613`
 		//  Estimated: `4326`
@@ -681,6 +686,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(51_155_000, 4326)
 			.saturating_add(T::DbWeight::get().reads(6_u64))
 			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(Weight::from_parts(0, 2836))
+			.saturating_add(Weight::from_parts(52_000_000, 3593).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads_writes(1_u64, 1_u64).saturating_mul(r.into()))
 	}
 	/// The range of component `n` is `[0, 10]`.
 	fn pay_tips(n: u32, ) -> Weight {
@@ -734,7 +743,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: `Nfts::Account` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::ItemPriceOf` (r:0 w:2)
 	/// Proof: `Nfts::ItemPriceOf` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
-	fn claim_swap() -> Weight {
+	/// Not benchmarked with royalties yet, to be regenerated with the benchmark bot. Bounded by the
+	/// weight measured without royalties, the read of `CollectionRoyaltyOf` and one transfer to
+	/// a new account per royalty recipient.
+	/// The range of component `r` is `[0, 10]`.
+	fn claim_swap(r: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `742`
 		//  Estimated: `7662`
@@ -742,6 +755,10 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(84_109_000, 7662)
 			.saturating_add(T::DbWeight::get().reads(9_u64))
 			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(Weight::from_parts(0, 2836))
+			.saturating_add(Weight::from_parts(52_000_000, 3593).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads_writes(1_u64, 1_u64).saturating_mul(r.into()))
 	}
 	/// Storage: `Nfts::CollectionRoleOf` (r:2 w:0)
 	/// Proof: `Nfts::CollectionRoleOf` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
@@ -803,6 +820,11 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 2954).saturating_mul(n.into()))
 	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by
+	/// `set_collection_metadata`, which also reads the collection and writes a single entry.
+	fn set_collection_royalty() -> Weight {
+		<Self as WeightInfo>::set_collection_metadata()
+	}
 }
 
 // For backwards compatibility and tests.
@@ -1364,7 +1386,11 @@ impl WeightInfo for () {
 	/// Proof: `Nfts::Account` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::PendingSwapOf` (r:0 w:1)
 	/// Proof: `Nfts::PendingSwapOf` (`max_values`: None, `max_size`: Some(71), added: 2546, mode: `MaxEncodedLen`)
-	fn buy_item() -> Weight {
+	/// Not benchmarked with royalties yet, to be regenerated with the benchmark bot. Bounded by the
+	/// weight measured without royalties, the read of `CollectionRoyaltyOf` and one transfer to
+	/// a new account per royalty recipient.
+	/// The range of component `r` is `[0, 10]`.
+	fn buy_item(r: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `613`
 		//  Estimated: `4326`
@@ -1372,6 +1398,10 @@ impl WeightInfo for () {
 		Weight::from_parts(51_155_000, 4326)
 			.saturating_add(RocksDbWeight::get().reads(6_u64))
 			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(Weight::from_parts(0, 2836))
+			.saturating_add(Weight::from_parts(52_000_000, 3593).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads_writes(1_u64, 1_u64).saturating_mul(r.into()))
 	}
 	/// The range of component `n` is `[0, 10]`.
 	fn pay_tips(n: u32, ) -> Weight {
@@ -1425,7 +1455,11 @@ impl WeightInfo for () {
 	/// Proof: `Nfts::Account` (`max_values`: None, `max_size`: Some(88), added: 2563, mode: `MaxEncodedLen`)
 	/// Storage: `Nfts::ItemPriceOf` (r:0 w:2)
 	/// Proof: `Nfts::ItemPriceOf` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
-	fn claim_swap() -> Weight {
+	/// Not benchmarked with royalties yet, to be regenerated with the benchmark bot. Bounded by the
+	/// weight measured without royalties, the read of `CollectionRoyaltyOf` and one transfer to
+	/// a new account per royalty recipient.
+	/// The range of component `r` is `[0, 10]`.
+	fn claim_swap(r: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `742`
 		//  Estimated: `7662`
@@ -1433,6 +1467,10 @@ impl WeightInfo for () {
 		Weight::from_parts(84_109_000, 7662)
 			.saturating_add(RocksDbWeight::get().reads(9_u64))
 			.saturating_add(RocksDbWeight::get().writes(10_u64))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(Weight::from_parts(0, 2836))
+			.saturating_add(Weight::from_parts(52_000_000, 3593).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads_writes(1_u64, 1_u64).saturating_mul(r.into()))
 	}
 	/// Storage: `Nfts::CollectionRoleOf` (r:2 w:0)
 	/// Proof: `Nfts::CollectionRoleOf` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
@@ -1494,4 +1532,9 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 2954).saturating_mul(n.into()))
 	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by
+	/// `set_collection_metadata`, which also reads the collection and writes a single entry.
+	fn set_collection_royalty() -> Weight {
+		<Self as WeightInfo>::set_collection_metadata()
+	}
 }
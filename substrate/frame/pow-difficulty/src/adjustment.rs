@@ -0,0 +1,121 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Difficulty adjustment algorithms.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use frame_support::traits::Get;
+use scale_info::TypeInfo;
+use sp_core::U256;
+use sp_runtime::RuntimeDebug;
+
+/// Difficulty type.
+pub type Difficulty = U256;
+
+/// Solve times are capped to this multiple of the target block time.
+///
+/// Prevents a single very slow block from dropping the difficulty too much.
+pub const MAX_SOLVE_TIME_FACTOR: u64 = 6;
+
+/// Timestamp and difficulty of a block.
+#[derive(
+	Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct DifficultySample {
+	/// Block timestamp, in milliseconds.
+	pub timestamp: u64,
+	/// Difficulty the block has been mined at.
+	pub difficulty: Difficulty,
+}
+
+/// Difficulty adjustment algorithm.
+pub trait DifficultyAdjustment {
+	/// Compute the difficulty of the next block.
+	///
+	/// `samples` are the most recent blocks, oldest first. The solve time of a block is the
+	/// difference between its timestamp and the one of the previous sample.
+	///
+	/// Returns `None` if the samples are not enough to compute a new difficulty.
+	fn next_difficulty(samples: &[DifficultySample], target_block_time: u64) -> Option<Difficulty>;
+}
+
+/// Solve time of a block given the timestamps of the block and of its parent.
+///
+/// The result is bounded to `[1, MAX_SOLVE_TIME_FACTOR * target_block_time]`.
+pub fn solve_time(parent_timestamp: u64, timestamp: u64, target_block_time: u64) -> u64 {
+	let max = target_block_time.saturating_mul(MAX_SOLVE_TIME_FACTOR).max(1);
+	timestamp.saturating_sub(parent_timestamp).clamp(1, max)
+}
+
+/// Linearly weighted moving average.
+///
+/// The next difficulty is the average difficulty of the samples scaled by the ratio between
+/// the target block time and the weighted average of the solve times, where the solve time
+/// of the `i`-th most ancient block has weight `i`. Reacts quickly to hash rate changes while
+/// being resistant to timestamp manipulation.
+pub struct Lwma;
+
+impl DifficultyAdjustment for Lwma {
+	fn next_difficulty(samples: &[DifficultySample], target_block_time: u64) -> Option<Difficulty> {
+		if samples.len() < 2 {
+			return None
+		}
+		let n = samples.len() - 1;
+
+		let mut weighted_solve_times = U256::zero();
+		let mut total_difficulty = U256::zero();
+		for (i, pair) in samples.windows(2).enumerate() {
+			let solve_time = solve_time(pair[0].timestamp, pair[1].timestamp, target_block_time);
+			weighted_solve_times =
+				weighted_solve_times.saturating_add(U256::from(i + 1) * U256::from(solve_time));
+			total_difficulty = total_difficulty.saturating_add(pair[1].difficulty);
+		}
+
+		// With `k = n * (n + 1) / 2` being the sum of the weights:
+		// `next = (total_difficulty / n) * target_block_time * k / weighted_solve_times`
+		let next = total_difficulty
+			.saturating_mul(target_block_time.into())
+			.saturating_mul((n + 1).into()) /
+			weighted_solve_times.saturating_mul(2.into());
+		Some(next)
+	}
+}
+
+/// Exponential moving average.
+///
+/// The next difficulty only depends on the difficulty and solve time of the last block:
+///
+/// `next = difficulty * N * T / ((N - 1) * T + solve_time)`
+///
+/// where `T` is the target block time and `N` is the smoothing window given by `Smoothing`.
+/// Larger windows result in slower but more stable adjustments.
+pub struct Ema<Smoothing>(PhantomData<Smoothing>);
+
+impl<Smoothing: Get<u32>> DifficultyAdjustment for Ema<Smoothing> {
+	fn next_difficulty(samples: &[DifficultySample], target_block_time: u64) -> Option<Difficulty> {
+		let [.., parent, last] = samples else { return None };
+
+		let window = U256::from(Smoothing::get().max(1));
+		let target = U256::from(target_block_time);
+		let solve_time = solve_time(parent.timestamp, last.timestamp, target_block_time);
+
+		let den = (window - 1).saturating_mul(target).saturating_add(solve_time.into());
+		let next = last.difficulty.saturating_mul(window).saturating_mul(target) / den;
+		Some(next)
+	}
+}
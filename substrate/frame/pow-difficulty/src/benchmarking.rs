@@ -0,0 +1,52 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the PoW difficulty pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::v2::*;
+use frame_support::{pallet_prelude::*, traits::Hooks};
+use frame_system::pallet_prelude::BlockNumberFor;
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	// Worst case: the history is full, thus the oldest sample is evicted and the adjustment
+	// algorithm runs over the whole window.
+	#[benchmark]
+	fn on_finalize() {
+		let target = T::TargetBlockTime::get();
+		let samples = (0..T::HistoryDepth::get() as u64)
+			.map(|i| DifficultySample { timestamp: i * target, difficulty: 1_000_000.into() })
+			.collect::<alloc::vec::Vec<_>>();
+		let history = BoundedVec::<_, T::HistoryDepth>::try_from(samples).unwrap();
+		History::<T>::put(history);
+		CurrentDifficulty::<T>::put(Difficulty::from(1_000_000));
+
+		#[block]
+		{
+			Pallet::<T>::on_finalize(BlockNumberFor::<T>::from(1u32));
+		}
+
+		assert_eq!(History::<T>::get().len(), T::HistoryDepth::get() as usize);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,168 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # PoW Difficulty Pallet
+//!
+//! Runtime difficulty adjustment for Proof-of-Work chains.
+//!
+//! ## Overview
+//!
+//! At the end of every block the pallet records the block timestamp together with the
+//! difficulty the block has been mined at, keeping the last [`Config::HistoryDepth`] samples.
+//! The difficulty of the next block is then computed by the configured
+//! [`DifficultyAdjustment`] algorithm and bounded below by [`Config::MinDifficulty`].
+//!
+//! The current difficulty is returned by [`Pallet::difficulty`], which is meant to back the
+//! `sp_consensus_pow::DifficultyApi` runtime API queried by the client:
+//!
+//! ```ignore
+//! impl sp_consensus_pow::DifficultyApi<Block, sp_core::U256> for Runtime {
+//! 	fn difficulty() -> sp_core::U256 {
+//! 		PowDifficulty::difficulty()
+//! 	}
+//! }
+//! ```
+//!
+//! Two algorithms are provided: [`Lwma`] (linearly weighted moving average) and [`Ema`]
+//! (exponential moving average).
+//!
+//! The pallet relies on the block timestamp, thus it must be placed after the timestamp
+//! pallet in the runtime.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod adjustment;
+mod benchmarking;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use frame_support::traits::UnixTime;
+use sp_runtime::SaturatedConversion;
+
+pub use adjustment::*;
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Source of the block timestamps.
+		type UnixTime: UnixTime;
+
+		/// Algorithm used to compute the difficulty of the next block.
+		type DifficultyAdjustment: DifficultyAdjustment;
+
+		/// Target block time, in milliseconds.
+		#[pallet::constant]
+		type TargetBlockTime: Get<u64>;
+
+		/// Number of recent blocks kept for the difficulty adjustment.
+		///
+		/// The adjustment algorithm considers the solve times of the last
+		/// `HistoryDepth - 1` blocks.
+		#[pallet::constant]
+		type HistoryDepth: Get<u32>;
+
+		/// Lower bound of the difficulty.
+		#[pallet::constant]
+		type MinDifficulty: Get<Difficulty>;
+
+		/// Weight information for the pallet hooks.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// Difficulty of the next block.
+	#[pallet::storage]
+	pub type CurrentDifficulty<T> = StorageValue<_, Difficulty, ValueQuery>;
+
+	/// Timestamp and difficulty of the most recent blocks, oldest first.
+	#[pallet::storage]
+	pub type History<T: Config> =
+		StorageValue<_, BoundedVec<DifficultySample, T::HistoryDepth>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// Difficulty of the first block.
+		pub initial_difficulty: Difficulty,
+		#[serde(skip)]
+		pub _config: core::marker::PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			CurrentDifficulty::<T>::put(self.initial_difficulty.max(T::MinDifficulty::get()));
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			T::WeightInfo::on_finalize()
+		}
+
+		fn on_finalize(_n: BlockNumberFor<T>) {
+			let now = T::UnixTime::now().as_millis().saturated_into::<u64>();
+			Self::note_block(now);
+		}
+
+		fn integrity_test() {
+			assert!(T::HistoryDepth::get() >= 2, "`HistoryDepth` must be at least 2");
+			assert!(T::TargetBlockTime::get() > 0, "`TargetBlockTime` must be non-zero");
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Difficulty of the next block.
+	pub fn difficulty() -> Difficulty {
+		CurrentDifficulty::<T>::get()
+	}
+
+	/// Record the current block and adjust the difficulty of the next one.
+	pub(crate) fn note_block(timestamp: u64) {
+		let difficulty = CurrentDifficulty::<T>::get();
+
+		let mut history = History::<T>::get();
+		if history.len() >= T::HistoryDepth::get() as usize && !history.is_empty() {
+			history.remove(0);
+		}
+		let _ = history.try_push(DifficultySample { timestamp, difficulty });
+
+		let next = T::DifficultyAdjustment::next_difficulty(&history, T::TargetBlockTime::get())
+			.unwrap_or(difficulty)
+			.max(T::MinDifficulty::get());
+
+		History::<T>::put(history);
+		CurrentDifficulty::<T>::put(next);
+	}
+}
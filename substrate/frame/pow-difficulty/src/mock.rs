@@ -0,0 +1,97 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for the PoW difficulty pallet.
+
+use crate::{self as pallet_pow_difficulty, Difficulty, Lwma};
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64, Hooks},
+};
+use sp_runtime::BuildStorage;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub const TARGET_BLOCK_TIME: u64 = 6_000;
+pub const HISTORY_DEPTH: u32 = 11;
+pub const INITIAL_DIFFICULTY: u64 = 1_000_000;
+pub const MIN_DIFFICULTY: u64 = 1_000;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Timestamp: pallet_timestamp,
+		PowDifficulty: pallet_pow_difficulty,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub MinDifficulty: Difficulty = MIN_DIFFICULTY.into();
+}
+
+impl pallet_pow_difficulty::Config for Test {
+	type UnixTime = Timestamp;
+	type DifficultyAdjustment = Lwma;
+	type TargetBlockTime = ConstU64<TARGET_BLOCK_TIME>;
+	type HistoryDepth = ConstU32<HISTORY_DEPTH>;
+	type MinDifficulty = MinDifficulty;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let t = RuntimeGenesisConfig {
+		system: Default::default(),
+		pow_difficulty: pallet_pow_difficulty::GenesisConfig {
+			initial_difficulty: INITIAL_DIFFICULTY.into(),
+			..Default::default()
+		},
+	}
+	.build_storage()
+	.unwrap();
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Finalize the current block with the given timestamp and move to the next one.
+pub fn finalize_block(timestamp: u64) {
+	let number = System::block_number();
+	pallet_timestamp::Now::<Test>::put(timestamp);
+	PowDifficulty::on_finalize(number);
+	System::set_block_number(number + 1);
+}
+
+/// Finalize `count` blocks, each produced `solve_time` milliseconds after its parent.
+pub fn produce_blocks(count: u32, solve_time: u64) {
+	for _ in 0..count {
+		let now = pallet_timestamp::Now::<Test>::get();
+		finalize_block(now + solve_time);
+	}
+}
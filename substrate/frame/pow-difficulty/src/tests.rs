@@ -0,0 +1,166 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the PoW difficulty pallet.
+
+use crate::{mock::*, *};
+use frame_support::traits::ConstU32;
+use sp_runtime::BuildStorage;
+
+fn samples(solve_times: &[u64], difficulty: u64) -> Vec<DifficultySample> {
+	let mut timestamp = 0;
+	let mut samples = vec![DifficultySample { timestamp, difficulty: difficulty.into() }];
+	for solve_time in solve_times {
+		timestamp += solve_time;
+		samples.push(DifficultySample { timestamp, difficulty: difficulty.into() });
+	}
+	samples
+}
+
+#[test]
+fn genesis_config_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(PowDifficulty::difficulty(), INITIAL_DIFFICULTY.into());
+		assert!(History::<Test>::get().is_empty());
+	});
+}
+
+#[test]
+fn genesis_difficulty_is_bounded_by_min_difficulty() {
+	let t = RuntimeGenesisConfig {
+		system: Default::default(),
+		pow_difficulty: GenesisConfig { initial_difficulty: 1.into(), ..Default::default() },
+	}
+	.build_storage()
+	.unwrap();
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		assert_eq!(PowDifficulty::difficulty(), MIN_DIFFICULTY.into());
+	});
+}
+
+#[test]
+fn history_is_bounded() {
+	new_test_ext().execute_with(|| {
+		finalize_block(1_000);
+		assert_eq!(
+			History::<Test>::get().into_inner(),
+			vec![DifficultySample { timestamp: 1_000, difficulty: INITIAL_DIFFICULTY.into() }]
+		);
+		// Not enough samples yet.
+		assert_eq!(PowDifficulty::difficulty(), INITIAL_DIFFICULTY.into());
+
+		produce_blocks(2 * HISTORY_DEPTH, TARGET_BLOCK_TIME);
+		let history = History::<Test>::get();
+		assert_eq!(history.len(), HISTORY_DEPTH as usize);
+		assert_eq!(history.last().unwrap().timestamp, pallet_timestamp::Now::<Test>::get());
+		assert!(history.windows(2).all(|w| w[1].timestamp - w[0].timestamp == TARGET_BLOCK_TIME));
+	});
+}
+
+#[test]
+fn difficulty_is_stable_at_target_block_time() {
+	new_test_ext().execute_with(|| {
+		produce_blocks(3 * HISTORY_DEPTH, TARGET_BLOCK_TIME);
+		assert_eq!(PowDifficulty::difficulty(), INITIAL_DIFFICULTY.into());
+	});
+}
+
+#[test]
+fn difficulty_follows_block_time() {
+	new_test_ext().execute_with(|| {
+		// Blocks twice as fast as the target.
+		produce_blocks(HISTORY_DEPTH, TARGET_BLOCK_TIME / 2);
+		let increased = PowDifficulty::difficulty();
+		assert!(increased > INITIAL_DIFFICULTY.into());
+
+		// Blocks twice as slow as the target.
+		produce_blocks(HISTORY_DEPTH, TARGET_BLOCK_TIME * 2);
+		let decreased = PowDifficulty::difficulty();
+		assert!(decreased < increased);
+	});
+}
+
+#[test]
+fn difficulty_is_bounded_by_min_difficulty() {
+	new_test_ext().execute_with(|| {
+		produce_blocks(10 * HISTORY_DEPTH, 100 * TARGET_BLOCK_TIME);
+		assert_eq!(PowDifficulty::difficulty(), MIN_DIFFICULTY.into());
+	});
+}
+
+#[test]
+fn solve_time_is_bounded() {
+	assert_eq!(solve_time(10, 10, TARGET_BLOCK_TIME), 1);
+	assert_eq!(solve_time(10, 5, TARGET_BLOCK_TIME), 1);
+	assert_eq!(solve_time(0, TARGET_BLOCK_TIME, TARGET_BLOCK_TIME), TARGET_BLOCK_TIME);
+	assert_eq!(
+		solve_time(0, u64::MAX, TARGET_BLOCK_TIME),
+		MAX_SOLVE_TIME_FACTOR * TARGET_BLOCK_TIME
+	);
+	assert_eq!(solve_time(0, 10, 0), 1);
+}
+
+#[test]
+fn lwma_works() {
+	let target = TARGET_BLOCK_TIME;
+
+	assert_eq!(Lwma::next_difficulty(&[], target), None);
+	assert_eq!(Lwma::next_difficulty(&samples(&[], 1_000), target), None);
+
+	// On target.
+	assert_eq!(Lwma::next_difficulty(&samples(&[target; 10], 1_000), target), Some(1_000.into()));
+	// Twice as fast.
+	assert_eq!(
+		Lwma::next_difficulty(&samples(&[target / 2; 10], 1_000), target),
+		Some(2_000.into())
+	);
+	// Twice as slow.
+	assert_eq!(Lwma::next_difficulty(&samples(&[target * 2; 10], 1_000), target), Some(500.into()));
+	// Recent solve times weigh more.
+	let mut solve_times = [target; 10];
+	solve_times[9] = target / 2;
+	let recent = Lwma::next_difficulty(&samples(&solve_times, 1_000), target).unwrap();
+	solve_times.swap(0, 9);
+	let ancient = Lwma::next_difficulty(&samples(&solve_times, 1_000), target).unwrap();
+	assert!(recent > ancient);
+	assert!(ancient > 1_000.into());
+}
+
+#[test]
+fn ema_works() {
+	type Ema10 = Ema<ConstU32<10>>;
+	let target = TARGET_BLOCK_TIME;
+
+	assert_eq!(Ema10::next_difficulty(&samples(&[], 1_000), target), None);
+
+	// On target.
+	assert_eq!(Ema10::next_difficulty(&samples(&[target], 1_000), target), Some(1_000.into()));
+	// Only the last solve time matters.
+	assert_eq!(
+		Ema10::next_difficulty(&samples(&[target * 5, target], 1_000), target),
+		Some(1_000.into())
+	);
+	// Twice as fast: 1000 * 10 / 9.5
+	assert_eq!(Ema10::next_difficulty(&samples(&[target / 2], 1_000), target), Some(1_052.into()));
+	// Twice as slow: 1000 * 10 / 11
+	assert_eq!(Ema10::next_difficulty(&samples(&[target * 2], 1_000), target), Some(909.into()));
+	// Without smoothing the difficulty follows the solve time.
+	assert_eq!(
+		Ema::<ConstU32<1>>::next_difficulty(&samples(&[target * 2], 1_000), target),
+		Some(500.into())
+	);
+}
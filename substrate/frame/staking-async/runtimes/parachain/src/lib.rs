@@ -478,7 +478,9 @@ parameter_types! {
 }
 
 impl pallet_vesting::Config for Runtime {
+	type TransferPolicyOrigin = EnsureRoot<AccountId>;
 	const MAX_VESTING_SCHEDULES: u32 = 100;
+	const MAX_VESTING_PAGES: u32 = 4;
 	type BlockNumberProvider = RelayChainBlockNumberProvider;
 	type BlockNumberToBalance = ConvertInto;
 	type Currency = Balances;
@@ -1025,6 +1027,10 @@ parameter_types! {
 	pub const NftsMetadataDepositBase: Balance = UniquesMetadataDepositBase::get();
 	pub const NftsAttributeDepositBase: Balance = UniquesAttributeDepositBase::get();
 	pub const NftsDepositPerByte: Balance = UniquesDepositPerByte::get();
+	// The account id and the share of a recipient.
+	pub const NftsRoyaltyDepositPerRecipient: Balance = deposit(0, 34);
+	// A quarter of the sale price.
+	pub const NftsMaxRoyalty: u16 = 2_500;
 }
 
 impl pallet_nfts::Config for Runtime {
@@ -1040,6 +1046,7 @@ impl pallet_nfts::Config for Runtime {
 	type MetadataDepositBase = NftsMetadataDepositBase;
 	type AttributeDepositBase = NftsAttributeDepositBase;
 	type DepositPerByte = NftsDepositPerByte;
+	type RoyaltyDepositPerRecipient = NftsRoyaltyDepositPerRecipient;
 	type StringLimit = ConstU32<256>;
 	type KeyLimit = ConstU32<64>;
 	type ValueLimit = ConstU32<256>;
@@ -1048,6 +1055,8 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = ConstU32<10>;
 	type MaxDeadlineDuration = NftsMaxDeadlineDuration;
 	type MaxAttributesPerCall = ConstU32<10>;
+	type MaxRoyaltyRecipients = ConstU32<10>;
+	type MaxRoyalty = NftsMaxRoyalty;
 	type Features = NftsPalletFeatures;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as Verify>::Signer;
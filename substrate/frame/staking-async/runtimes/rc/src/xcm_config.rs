@@ -25,7 +25,8 @@ use crate::{governance::pallet_custom_origins::Treasurer, Balance, RuntimeHoldRe
 use frame_support::{
 	parameter_types,
 	traits::{
-		fungible::HoldConsideration, Contains, Equals, Everything, LinearStoragePrice, Nothing,
+		fungible::HoldConsideration, Contains, Disabled, Equals, Everything, LinearStoragePrice,
+		Nothing,
 	},
 };
 use frame_system::EnsureRoot;
@@ -323,4 +324,7 @@ impl pallet_xcm::Config for Runtime {
 		AuthorizeAliasHoldReason,
 		LinearStoragePrice<DepositPerItem, DepositPerByte, Balance>,
 	>;
+	type AssetTrapConsideration = Disabled;
+	type AssetTrapExpiry = ();
+	type RecurringTransferConsideration = Disabled;
 }
@@ -504,6 +504,14 @@ pub trait SteppedMigration {
 		None
 	}
 
+	/// Estimate the number of steps that this migration still needs, starting from `cursor`.
+	///
+	/// Purely informational: it is used to report the progress of the migration and is never
+	/// relied upon for its execution. `None` means that no estimate is available.
+	fn estimate(_cursor: Option<&Self::Cursor>) -> Option<u32> {
+		None
+	}
+
 	/// Try to migrate as much as possible with the given weight.
 	///
 	/// **ANY STORAGE CHANGES MUST BE ROLLED-BACK BY THE CALLER UPON ERROR.** This is necessary
@@ -701,6 +709,14 @@ pub trait SteppedMigrations {
 	/// Is guaranteed to return `Some` if `n < Self::len()`.
 	fn nth_max_steps(n: u32) -> Option<Option<u32>>;
 
+	/// The [`SteppedMigration::estimate`] of the `n`th migration, given its encoded `cursor`.
+	///
+	/// Returns `None` if the index is out of bounds, the cursor cannot be decoded or the migration
+	/// does not provide an estimate.
+	fn nth_estimate(_n: u32, _cursor: Option<&[u8]>) -> Option<u32> {
+		None
+	}
+
 	/// Do a [`SteppedMigration::step`] on the `n`th migration.
 	///
 	/// Is guaranteed to return `Some` if `n < Self::len()`.
@@ -836,6 +852,17 @@ impl<T: SteppedMigration> SteppedMigrations for T {
 			.defensive_proof("nth_max_steps should only be called with n==0")
 	}
 
+	fn nth_estimate(n: u32, cursor: Option<&[u8]>) -> Option<u32> {
+		if !n.is_zero() {
+			return None
+		}
+
+		match cursor {
+			Some(mut cursor) => T::estimate(Some(&T::Cursor::decode(&mut cursor).ok()?)),
+			None => T::estimate(None),
+		}
+	}
+
 	fn nth_step(
 		n: u32,
 		cursor: Option<Vec<u8>>,
@@ -1006,6 +1033,20 @@ impl SteppedMigrations for Tuple {
 		None
 	}
 
+	fn nth_estimate(n: u32, cursor: Option<&[u8]>) -> Option<u32> {
+		let mut i = 0;
+
+		for_tuples!( #(
+			if (i + Tuple::len()) > n {
+				return Tuple::nth_estimate(n - i, cursor)
+			}
+
+			i += Tuple::len();
+		)* );
+
+		None
+	}
+
 	fn cursor_max_encoded_len() -> usize {
 		let mut max_len = 0;
 
@@ -1101,6 +1142,10 @@ mod tests {
 		fn max_steps() -> Option<u32> {
 			Some(2)
 		}
+
+		fn estimate(cursor: Option<&Self::Cursor>) -> Option<u32> {
+			Some(if cursor.is_some() { 1 } else { 2 })
+		}
 	}
 
 	pub struct F0;
@@ -1140,6 +1185,18 @@ mod tests {
 		assert_eq!(<(M0, M1)>::nth_max_steps(2), None);
 	}
 
+	#[test]
+	fn estimates_work() {
+		assert_eq!(M0::estimate(None), None);
+		assert_eq!(M2::estimate(None), Some(2));
+
+		assert_eq!(<Triple as SteppedMigrations>::nth_estimate(0, None), None);
+		assert_eq!(<Triple as SteppedMigrations>::nth_estimate(2, None), Some(2));
+		assert_eq!(<Triple as SteppedMigrations>::nth_estimate(2, Some(&[][..])), Some(1));
+		// Out of bounds.
+		assert_eq!(<Triple as SteppedMigrations>::nth_estimate(3, None), None);
+	}
+
 	#[test]
 	fn tuple_migrations_work() {
 		assert_eq!(<() as SteppedMigrations>::len(), 0);
@@ -55,16 +55,20 @@ fn add_vesting_schedules<T: Config>(
 
 	T::BlockNumberProvider::set_block_number(BlockNumberFor::<T>::zero());
 
-	let mut total_locked: BalanceOf<T> = Zero::zero();
-	for _ in 0..n {
-		total_locked += locked;
+	if n.is_zero() {
+		return Ok(Zero::zero())
+	}
 
-		let schedule = VestingInfo::new(locked, per_block, starting_block.into());
-		assert_ok!(Pallet::<T>::do_vested_transfer(&source, target, schedule));
+	// Identical schedules would be merged when added through `do_vested_transfer`, so they are
+	// written to storage directly.
+	let total_locked = locked.checked_mul(&n.into()).unwrap();
+	T::Currency::transfer(&source, target, total_locked, ExistenceRequirement::AllowDeath)
+		.map_err(|_| "Failed to transfer the vested funds")?;
 
-		// Top up to guarantee we can always transfer another schedule.
-		T::Currency::make_free_balance_be(&source, BalanceOf::<T>::max_value());
-	}
+	let schedule = VestingInfo::new(locked, per_block, starting_block.into());
+	let schedules = alloc::vec![schedule; n as usize];
+	Vesting::<T>::insert(target, BoundedVec::try_from(schedules).unwrap());
+	Pallet::<T>::write_lock(target, total_locked);
 
 	Ok(total_locked)
 }
@@ -220,7 +224,8 @@ mod benchmarks {
 		let per_block = transfer_amount.checked_div(&20_u32.into()).unwrap();
 		expected_balance += transfer_amount;
 
-		let vesting_schedule = VestingInfo::new(transfer_amount, per_block, 1_u32.into());
+		// Starts at block 2 so it is not merged with the existing schedules.
+		let vesting_schedule = VestingInfo::new(transfer_amount, per_block, 2_u32.into());
 
 		#[extrinsic_call]
 		_(RawOrigin::Signed(caller.clone()), target_lookup, vesting_schedule);
@@ -261,7 +266,8 @@ mod benchmarks {
 		let per_block = transfer_amount.checked_div(&20_u32.into()).unwrap();
 		expected_balance += transfer_amount;
 
-		let vesting_schedule = VestingInfo::new(transfer_amount, per_block, 1_u32.into());
+		// Starts at block 2 so it is not merged with the existing schedules.
+		let vesting_schedule = VestingInfo::new(transfer_amount, per_block, 2_u32.into());
 
 		#[extrinsic_call]
 		_(RawOrigin::Root, source_lookup, target_lookup, vesting_schedule);
@@ -435,6 +441,81 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn transfer_vesting(
+		l: Linear<0, { MaxLocksOf::<T>::get() - 1 }>,
+		s: Linear<1, T::MAX_VESTING_SCHEDULES>,
+	) -> Result<(), BenchmarkError> {
+		TransferPolicy::<T>::put(VestingTransferPolicy::Partial);
+
+		let caller = whitelisted_caller::<T::AccountId>();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
+		add_locks::<T>(&caller, l as u8);
+		let expected_balance = add_vesting_schedules::<T>(&caller, s)?;
+
+		let target = account::<T::AccountId>("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		T::Currency::make_free_balance_be(&target, T::Currency::minimum_balance());
+
+		// Half of the last schedule, so that it is split.
+		let amount = T::MinVestedTransfer::get() * 10_u32.into();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), target_lookup, s - 1, Some(amount));
+
+		assert_eq!(
+			Pallet::<T>::vesting_balance(&caller),
+			Some(expected_balance - amount),
+			"Source lock not correctly updated",
+		);
+		assert_eq!(
+			Pallet::<T>::vesting_balance(&target),
+			Some(amount),
+			"Target lock not correctly updated",
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_transfer_policy() -> Result<(), BenchmarkError> {
+		let origin = T::TransferPolicyOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, VestingTransferPolicy::Partial);
+
+		assert_eq!(TransferPolicy::<T>::get(), VestingTransferPolicy::Partial);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn update_overflow_page(s: Linear<1, T::MAX_VESTING_SCHEDULES>) -> Result<(), BenchmarkError> {
+		let caller = whitelisted_caller::<T::AccountId>();
+		T::Currency::make_free_balance_be(&caller, T::Currency::minimum_balance());
+		let expected_balance = add_vesting_schedules::<T>(&caller, s)?;
+
+		// Move the schedules to an overflow page, leaving the first page empty so that all of
+		// them are moved back.
+		let schedules = Vesting::<T>::take(&caller).unwrap();
+		VestingPages::<T>::insert(&caller, 1, VestingPage { schedules });
+		VestingPagesInfo::<T>::insert(&caller, PagesInfo { last_page: 1 });
+
+		let mut first_page = Vec::new();
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T>::refresh_pages(&caller, &mut first_page));
+		}
+
+		assert_eq!(first_page.len(), s as usize);
+		assert_eq!(Pallet::<T>::vesting_balance(&caller), Some(expected_balance));
+		assert!(VestingPagesInfo::<T>::get(&caller).is_none());
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite! {
 		Pallet,
 		mock::ExtBuilder::default().existential_deposit(256).build(),
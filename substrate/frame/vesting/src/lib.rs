@@ -42,6 +42,19 @@
 //! - `vest` - Update the lock, reducing it in line with the amount "vested" so far.
 //! - `vest_other` - Update the lock of another account, reducing it in line with the amount
 //!   "vested" so far.
+//! - `transfer_vesting` - Move a vesting position, or part of it, to another account, as allowed by
+//!   the `TransferPolicy`.
+//! - `set_transfer_policy` - Change the `TransferPolicy`.
+//!
+//! ### Schedule pages
+//!
+//! An account may have up to `MaxVestingSchedules` vesting schedules in `Vesting`, and as many in
+//! each of up to `MaxVestingPages` overflow pages of `VestingPages`. Schedule indices are global:
+//! index `i` refers to position `i % MaxVestingSchedules` of page `i / MaxVestingSchedules`. A new
+//! schedule unlocking over the same blocks as an existing one is merged into it.
+//!
+//! Each `vest` drops the completed schedules of every page and moves the schedules of the last
+//! pages to the first page as it frees up.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -75,8 +88,8 @@ use frame_system::pallet_prelude::BlockNumberFor;
 use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{
-		AtLeast32BitUnsigned, BlockNumberProvider, Bounded, Convert, MaybeSerializeDeserialize,
-		One, Saturating, StaticLookup, Zero,
+		AtLeast32BitUnsigned, BlockNumberProvider, Bounded, CheckedAdd, Convert,
+		MaybeSerializeDeserialize, One, Saturating, StaticLookup, UniqueSaturatedInto, Zero,
 	},
 	DispatchError, RuntimeDebug,
 };
@@ -151,6 +164,61 @@ impl<T: Config> Get<u32> for MaxVestingSchedulesGet<T> {
 	}
 }
 
+/// Index of a page of vesting schedules. Page `0` is the `Vesting` storage entry itself.
+pub type PageIndex = u32;
+
+/// An overflow page of vesting schedules, used once the first page of an account is full.
+#[derive(
+	Encode,
+	Decode,
+	frame_support::CloneNoBound,
+	frame_support::PartialEqNoBound,
+	frame_support::EqNoBound,
+	frame_support::RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound(T: Config))]
+pub struct VestingPage<T: Config> {
+	/// The schedules of this page.
+	pub schedules:
+		BoundedVec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>, MaxVestingSchedulesGet<T>>,
+}
+
+/// Bookkeeping of the overflow pages of an account.
+#[derive(
+	Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug, MaxEncodedLen, TypeInfo,
+)]
+pub struct PagesInfo {
+	/// The highest page index in use.
+	pub last_page: PageIndex,
+}
+
+/// Which vesting positions may be moved to another account with `transfer_vesting`.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	Default,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub enum VestingTransferPolicy {
+	/// Vesting positions cannot be transferred.
+	#[default]
+	Disabled,
+	/// Only whole vesting schedules can be transferred.
+	Full,
+	/// Whole schedules as well as parts of them can be transferred.
+	Partial,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -204,8 +272,20 @@ pub mod pallet {
 		///     parachain is lagging its block production to avoid clock skew.
 		type BlockNumberProvider: BlockNumberProvider<BlockNumber = BlockNumberFor<Self>>;
 
-		/// Maximum number of vesting schedules an account may have at a given moment.
+		/// Origin allowed to change the [`TransferPolicy`].
+		type TransferPolicyOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Maximum number of vesting schedules stored in a single page.
+		///
+		/// Schedules beyond the first page are kept in [`VestingPages`] and are moved back to the
+		/// first page as it frees up.
 		const MAX_VESTING_SCHEDULES: u32;
+
+		/// Maximum number of overflow pages of an account.
+		///
+		/// Every page is read to compute the lock of an account, so this bounds the weight of all
+		/// the calls updating it.
+		const MAX_VESTING_PAGES: u32;
 	}
 
 	#[pallet::extra_constants]
@@ -214,6 +294,11 @@ pub mod pallet {
 		fn max_vesting_schedules() -> u32 {
 			T::MAX_VESTING_SCHEDULES
 		}
+
+		#[pallet::constant_name(MaxVestingPages)]
+		fn max_vesting_pages() -> u32 {
+			T::MAX_VESTING_PAGES
+		}
 	}
 
 	#[pallet::hooks]
@@ -232,6 +317,25 @@ pub mod pallet {
 		BoundedVec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>, MaxVestingSchedulesGet<T>>,
 	>;
 
+	/// Overflow pages of vesting schedules of a given account, starting at page `1`.
+	#[pallet::storage]
+	pub type VestingPages<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Twox64Concat,
+		PageIndex,
+		VestingPage<T>,
+	>;
+
+	/// Bookkeeping of the overflow pages of a given account.
+	#[pallet::storage]
+	pub type VestingPagesInfo<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, PagesInfo>;
+
+	/// The policy for transferring vesting positions between accounts.
+	#[pallet::storage]
+	pub type TransferPolicy<T: Config> = StorageValue<_, VestingTransferPolicy, ValueQuery>;
+
 	/// Storage version of the pallet.
 	///
 	/// New networks start with latest version, as determined by the genesis build.
@@ -293,6 +397,15 @@ pub mod pallet {
 		VestingUpdated { account: T::AccountId, unvested: BalanceOf<T> },
 		/// An \[account\] has become fully vested.
 		VestingCompleted { account: T::AccountId },
+		/// A vesting position has been moved from one account to another.
+		VestingTransferred {
+			from: T::AccountId,
+			to: T::AccountId,
+			schedule_index: u32,
+			amount: BalanceOf<T>,
+		},
+		/// The policy for transferring vesting positions has been changed.
+		TransferPolicySet { policy: VestingTransferPolicy },
 	}
 
 	/// Error for the vesting pallet.
@@ -300,15 +413,18 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// The account given is not vesting.
 		NotVesting,
-		/// The account already has `MaxVestingSchedules` count of schedules and thus
-		/// cannot add another one. Consider merging existing schedules in order to add another.
+		/// A page of schedules would exceed `MaxVestingSchedules`.
 		AtMaxVestingSchedules,
+		/// All the overflow pages of the account are full.
+		AtMaxVestingPages,
 		/// Amount being transferred is too low to create a vesting schedule.
 		AmountLow,
 		/// An index was out of bounds of the vesting schedules.
 		ScheduleIndexOutOfBounds,
 		/// Failed to create a new schedule because some parameter was invalid.
 		InvalidScheduleParams,
+		/// The transfer of the vesting position is not allowed by the current policy.
+		TransferNotAllowed,
 	}
 
 	#[pallet::call]
@@ -325,6 +441,7 @@ pub mod pallet {
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::vest_locked(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
 			.max(T::WeightInfo::vest_unlocked(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES))
+			.saturating_add(Pallet::<T>::overflow_pages_weight())
 		)]
 		pub fn vest(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
@@ -345,6 +462,7 @@ pub mod pallet {
 		#[pallet::call_index(1)]
 		#[pallet::weight(T::WeightInfo::vest_other_locked(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
 			.max(T::WeightInfo::vest_other_unlocked(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES))
+			.saturating_add(Pallet::<T>::overflow_pages_weight())
 		)]
 		pub fn vest_other(origin: OriginFor<T>, target: AccountIdLookupOf<T>) -> DispatchResult {
 			ensure_signed(origin)?;
@@ -368,6 +486,7 @@ pub mod pallet {
 		#[pallet::call_index(2)]
 		#[pallet::weight(
 			T::WeightInfo::vested_transfer(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
+			.saturating_add(Pallet::<T>::overflow_pages_weight())
 		)]
 		pub fn vested_transfer(
 			origin: OriginFor<T>,
@@ -396,6 +515,7 @@ pub mod pallet {
 		#[pallet::call_index(3)]
 		#[pallet::weight(
 			T::WeightInfo::force_vested_transfer(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
+			.saturating_add(Pallet::<T>::overflow_pages_weight())
 		)]
 		pub fn force_vested_transfer(
 			origin: OriginFor<T>,
@@ -430,10 +550,14 @@ pub mod pallet {
 		///
 		/// - `schedule1_index`: index of the first schedule to merge.
 		/// - `schedule2_index`: index of the second schedule to merge.
+		///
+		/// The merged schedule takes the place of the first schedule if they are in different
+		/// pages.
 		#[pallet::call_index(4)]
 		#[pallet::weight(
 			T::WeightInfo::not_unlocking_merge_schedules(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
 			.max(T::WeightInfo::unlocking_merge_schedules(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES))
+			.saturating_add(Pallet::<T>::overflow_pages_weight())
 		)]
 		pub fn merge_schedules(
 			origin: OriginFor<T>,
@@ -444,19 +568,20 @@ pub mod pallet {
 			if schedule1_index == schedule2_index {
 				return Ok(())
 			};
-			let schedule1_index = schedule1_index as usize;
-			let schedule2_index = schedule2_index as usize;
+			let (page1, schedule1_index) = Self::locate(schedule1_index);
+			let (page2, schedule2_index) = Self::locate(schedule2_index);
+			if page1 != page2 {
+				return Self::merge_across_pages(
+					&who,
+					(page1, schedule1_index),
+					(page2, schedule2_index),
+				)
+			}
 
-			let schedules = Vesting::<T>::get(&who).ok_or(Error::<T>::NotVesting)?;
 			let merge_action =
 				VestingAction::Merge { index1: schedule1_index, index2: schedule2_index };
 
-			let (schedules, locked_now) = Self::exec_action(schedules.to_vec(), merge_action)?;
-
-			Self::write_vesting(&who, schedules)?;
-			Self::write_lock(&who, locked_now);
-
-			Ok(())
+			Self::exec_on_page(&who, page1, merge_action)
 		}
 
 		/// Force remove a vesting schedule
@@ -468,6 +593,7 @@ pub mod pallet {
 		#[pallet::call_index(5)]
 		#[pallet::weight(
 			T::WeightInfo::force_remove_vesting_schedule(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
+			.saturating_add(Pallet::<T>::overflow_pages_weight())
 		)]
 		pub fn force_remove_vesting_schedule(
 			origin: OriginFor<T>,
@@ -477,16 +603,68 @@ pub mod pallet {
 			ensure_root(origin)?;
 			let who = T::Lookup::lookup(target)?;
 
-			let schedules_count = Vesting::<T>::decode_len(&who).unwrap_or_default();
-			ensure!(schedule_index < schedules_count as u32, Error::<T>::InvalidScheduleParams);
+			let (page, index) = Self::locate(schedule_index);
+			let schedules_count = Self::page_schedules(&who, page).map_or(0, |s| s.len());
+			ensure!(index < schedules_count, Error::<T>::InvalidScheduleParams);
+			let has_pages = VestingPagesInfo::<T>::contains_key(&who);
 
 			Self::remove_vesting_schedule(&who, schedule_index)?;
 
-			Ok(Some(T::WeightInfo::force_remove_vesting_schedule(
+			let mut weight = T::WeightInfo::force_remove_vesting_schedule(
 				MaxLocksOf::<T>::get(),
 				schedules_count as u32,
-			))
-			.into())
+			);
+			if has_pages {
+				weight.saturating_accrue(Self::overflow_pages_weight());
+			}
+			Ok(Some(weight).into())
+		}
+
+		/// Move a vesting position of the sender to another account.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the transfer must be allowed by
+		/// the current [`TransferPolicy`].
+		///
+		/// - `target`: The account receiving the vesting position.
+		/// - `schedule_index`: The index of the schedule to transfer from.
+		/// - `amount`: The amount of the remaining locked funds to transfer, or `None` to transfer
+		///   the whole schedule. Partial transfers require the `Partial` policy, and both parts
+		///   must be at least `MinVestedTransfer`.
+		///
+		/// The transferred funds keep unlocking until the end of the original schedule.
+		///
+		/// Emits `VestingTransferred`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(
+			T::WeightInfo::transfer_vesting(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
+			.saturating_add(Pallet::<T>::overflow_pages_weight())
+		)]
+		pub fn transfer_vesting(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			schedule_index: u32,
+			amount: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let target = T::Lookup::lookup(target)?;
+			Self::do_transfer_vesting(&who, &target, schedule_index, amount)
+		}
+
+		/// Set the policy for transferring vesting positions with `transfer_vesting`.
+		///
+		/// The dispatch origin for this call must be `TransferPolicyOrigin`.
+		///
+		/// Emits `TransferPolicySet`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::set_transfer_policy())]
+		pub fn set_transfer_policy(
+			origin: OriginFor<T>,
+			policy: VestingTransferPolicy,
+		) -> DispatchResult {
+			T::TransferPolicyOrigin::ensure_origin(origin)?;
+			TransferPolicy::<T>::put(policy);
+			Self::deposit_event(Event::<T>::TransferPolicySet { policy });
+			Ok(())
 		}
 	}
 }
@@ -652,14 +830,351 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Unlock any vested funds of `who`.
+	///
+	/// The completed schedules of the overflow pages are dropped too, and the schedules of the
+	/// last overflow pages are moved to the first page as far as it has room for them.
 	fn do_vest(who: T::AccountId) -> DispatchResult {
-		let schedules = Vesting::<T>::get(&who).ok_or(Error::<T>::NotVesting)?;
+		let schedules = Vesting::<T>::get(&who);
+		ensure!(
+			schedules.is_some() || VestingPagesInfo::<T>::contains_key(&who),
+			Error::<T>::NotVesting
+		);
 
-		let (schedules, locked_now) =
-			Self::exec_action(schedules.to_vec(), VestingAction::Passive)?;
+		let (mut schedules, _) =
+			Self::exec_action(schedules.unwrap_or_default().to_vec(), VestingAction::Passive)?;
+		Self::refresh_pages(&who, &mut schedules)?;
+		let locked_now =
+			Self::locked_at(&schedules, T::BlockNumberProvider::current_block_number());
 
 		Self::write_vesting(&who, schedules)?;
-		Self::write_lock(&who, locked_now);
+		Self::update_lock(&who, locked_now);
+
+		Ok(())
+	}
+
+	/// Drop the completed schedules of the overflow pages of `who`, and move as many of their
+	/// schedules as fit to `schedules`, the first page, starting from the last page.
+	fn refresh_pages(
+		who: &T::AccountId,
+		schedules: &mut Vec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>>,
+	) -> DispatchResult {
+		let Some(info) = VestingPagesInfo::<T>::get(who) else { return Ok(()) };
+
+		for page in (1..=info.last_page).rev() {
+			let Some(page_schedules) = Self::page_schedules(who, page) else { continue };
+			let (mut page_schedules, _) =
+				Self::report_schedule_updates(page_schedules, VestingAction::Passive);
+
+			let room = (T::MAX_VESTING_SCHEDULES as usize)
+				.saturating_sub(schedules.len())
+				.min(page_schedules.len());
+			schedules.extend(page_schedules.drain(..room));
+
+			Self::write_page(who, page, page_schedules)?;
+		}
+
+		Ok(())
+	}
+
+	/// Split a schedule index into the index of its page and its index within that page.
+	fn locate(schedule_index: u32) -> (PageIndex, usize) {
+		let page_size = T::MAX_VESTING_SCHEDULES;
+		(schedule_index / page_size, (schedule_index % page_size) as usize)
+	}
+
+	/// The schedules of the given page of `who`, if any.
+	fn page_schedules(
+		who: &T::AccountId,
+		page: PageIndex,
+	) -> Option<Vec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>>> {
+		if page.is_zero() {
+			Vesting::<T>::get(who).map(|schedules| schedules.into_inner())
+		} else {
+			VestingPages::<T>::get(who, page).map(|page| page.schedules.into_inner())
+		}
+	}
+
+	/// The amount locked by `schedules` at block `now`.
+	fn locked_at(
+		schedules: &[VestingInfo<BalanceOf<T>, BlockNumberFor<T>>],
+		now: BlockNumberFor<T>,
+	) -> BalanceOf<T> {
+		schedules.iter().fold(Zero::zero(), |total, schedule| {
+			schedule.locked_at::<T::BlockNumberToBalance>(now).saturating_add(total)
+		})
+	}
+
+	/// The amount currently locked by the first page of `who`.
+	fn first_page_locked(who: &T::AccountId) -> BalanceOf<T> {
+		let now = T::BlockNumberProvider::current_block_number();
+		Vesting::<T>::get(who).map_or(Zero::zero(), |schedules| Self::locked_at(&schedules, now))
+	}
+
+	/// The amount currently locked by the overflow pages of `who`.
+	fn overflow_locked(who: &T::AccountId) -> BalanceOf<T> {
+		let now = T::BlockNumberProvider::current_block_number();
+		VestingPages::<T>::iter_prefix_values(who).fold(Zero::zero(), |total, page| {
+			Self::locked_at(&page.schedules, now).saturating_add(total)
+		})
+	}
+
+	/// The weight of reading and updating every overflow page of an account.
+	fn overflow_pages_weight() -> Weight {
+		T::WeightInfo::update_overflow_page(T::MAX_VESTING_SCHEDULES)
+			.saturating_mul(T::MAX_VESTING_PAGES.into())
+	}
+
+	/// Write the vesting lock of `who`, given the amount locked by its first page.
+	fn update_lock(who: &T::AccountId, first_page_locked: BalanceOf<T>) {
+		Self::write_lock(who, first_page_locked.saturating_add(Self::overflow_locked(who)));
+	}
+
+	/// Write the schedules of the given page of `who` to storage.
+	fn write_page(
+		who: &T::AccountId,
+		page: PageIndex,
+		schedules: Vec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>>,
+	) -> DispatchResult {
+		if page.is_zero() {
+			return Self::write_vesting(who, schedules)
+		}
+
+		let schedules: BoundedVec<_, MaxVestingSchedulesGet<T>> =
+			schedules.try_into().map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
+
+		let mut info = VestingPagesInfo::<T>::get(who).unwrap_or_default();
+
+		if schedules.is_empty() {
+			VestingPages::<T>::remove(who, page);
+			// Skip the pages emptied before this one.
+			while !info.last_page.is_zero() && !VestingPages::<T>::contains_key(who, info.last_page)
+			{
+				info.last_page -= 1;
+			}
+		} else {
+			VestingPages::<T>::insert(who, page, VestingPage { schedules });
+			info.last_page = info.last_page.max(page);
+		}
+
+		if info.last_page.is_zero() {
+			VestingPagesInfo::<T>::remove(who);
+		} else {
+			VestingPagesInfo::<T>::insert(who, info);
+		}
+
+		Ok(())
+	}
+
+	/// Execute a `VestingAction` against the given page of `who` and update its lock.
+	fn exec_on_page(who: &T::AccountId, page: PageIndex, action: VestingAction) -> DispatchResult {
+		let schedules = Self::page_schedules(who, page).ok_or(Error::<T>::NotVesting)?;
+		let (schedules, locked_now) = Self::exec_action(schedules, action)?;
+
+		Self::write_page(who, page, schedules)?;
+		let first_page_locked =
+			if page.is_zero() { locked_now } else { Self::first_page_locked(who) };
+		Self::update_lock(who, first_page_locked);
+
+		Ok(())
+	}
+
+	/// Merge the schedules at the given locations of different pages of `who`, putting the merged
+	/// schedule in place of the first one, and update its lock.
+	fn merge_across_pages(
+		who: &T::AccountId,
+		(page1, index1): (PageIndex, usize),
+		(page2, index2): (PageIndex, usize),
+	) -> DispatchResult {
+		let schedules1 = Self::page_schedules(who, page1);
+		let schedules2 = Self::page_schedules(who, page2);
+		ensure!(schedules1.is_some() || schedules2.is_some(), Error::<T>::NotVesting);
+		let (schedules1, schedules2) =
+			(schedules1.unwrap_or_default(), schedules2.unwrap_or_default());
+		let schedule1 = *schedules1.get(index1).ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
+		let schedule2 = *schedules2.get(index2).ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
+
+		let (mut schedules1, _) =
+			Self::report_schedule_updates(schedules1, VestingAction::Remove { index: index1 });
+		let (schedules2, _) =
+			Self::report_schedule_updates(schedules2, VestingAction::Remove { index: index2 });
+
+		let now = T::BlockNumberProvider::current_block_number();
+		if let Some(merged) = Self::merge_vesting_info(now, schedule1, schedule2) {
+			// The page of the first schedule has room for the merged one, since it was removed.
+			schedules1.push(merged);
+		}
+
+		Self::write_page(who, page1, schedules1)?;
+		Self::write_page(who, page2, schedules2)?;
+		Self::update_lock(who, Self::first_page_locked(who));
+
+		Ok(())
+	}
+
+	/// Merge `schedule` into one of `schedules` which unlocks over the same blocks, if any, and
+	/// return its index.
+	///
+	/// Such schedules are merged by summing their `locked` and `per_block`, which keeps the exact
+	/// same amount locked at every block.
+	fn merge_identical(
+		schedules: &mut [VestingInfo<BalanceOf<T>, BlockNumberFor<T>>],
+		schedule: VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
+	) -> Option<usize> {
+		let ending_block = schedule.ending_block_as_balance::<T::BlockNumberToBalance>();
+		schedules.iter_mut().enumerate().find_map(|(index, existing)| {
+			if existing.starting_block() != schedule.starting_block() ||
+				existing.ending_block_as_balance::<T::BlockNumberToBalance>() != ending_block
+			{
+				return None
+			}
+			let locked = existing.locked().checked_add(&schedule.locked())?;
+			let per_block = existing.per_block().checked_add(&schedule.per_block())?;
+			*existing = VestingInfo::new(locked, per_block, schedule.starting_block());
+			Some(index)
+		})
+	}
+
+	/// Add `schedule` to the overflow pages of `who`, merging it into an existing schedule or
+	/// putting it in the first page with room. Returns the index of the schedule.
+	fn add_to_overflow_pages(
+		who: &T::AccountId,
+		schedule: VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
+	) -> Result<u32, DispatchError> {
+		let mut free_page = None;
+		for page in 1..=T::MAX_VESTING_PAGES {
+			let mut schedules = Self::page_schedules(who, page).unwrap_or_default();
+			if let Some(index) = Self::merge_identical(&mut schedules, schedule) {
+				Self::write_page(who, page, schedules)?;
+				return Ok(Self::schedule_index(page, index))
+			}
+			if free_page.is_none() && schedules.len() < T::MAX_VESTING_SCHEDULES as usize {
+				free_page = Some((page, schedules));
+			}
+		}
+
+		let (page, mut schedules) = free_page.ok_or(Error::<T>::AtMaxVestingPages)?;
+		schedules.push(schedule);
+		let index = schedules.len() - 1;
+		Self::write_page(who, page, schedules)?;
+
+		Ok(Self::schedule_index(page, index))
+	}
+
+	/// The global index of the schedule at `index` of the given page.
+	fn schedule_index(page: PageIndex, index: usize) -> u32 {
+		page.saturating_mul(T::MAX_VESTING_SCHEDULES).saturating_add(index as u32)
+	}
+
+	/// Whether `schedule` can be added to `who`, either merged into an existing schedule or on a
+	/// page with room, counting the completed schedules of the first page as dropped.
+	fn has_room_for(
+		who: &T::AccountId,
+		schedule: VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
+	) -> bool {
+		let now = T::BlockNumberProvider::current_block_number();
+		(0..=T::MAX_VESTING_PAGES).any(|page| {
+			let mut schedules = Self::page_schedules(who, page).unwrap_or_default();
+			if page.is_zero() {
+				schedules.retain(|s| !s.locked_at::<T::BlockNumberToBalance>(now).is_zero());
+			}
+			schedules.len() < T::MAX_VESTING_SCHEDULES as usize ||
+				Self::merge_identical(&mut schedules, schedule).is_some()
+		})
+	}
+
+	// Create a `VestingInfo` locking `locked` from the current block, or the start of `schedule`
+	// if later, until the end of `schedule`.
+	//
+	// `per_block` is rounded up, and the start is delayed by the blocks this saves, so that the
+	// new schedule ends at the same block as `schedule`.
+	fn split_vesting_info(
+		now: BlockNumberFor<T>,
+		schedule: VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
+		locked: BalanceOf<T>,
+	) -> VestingInfo<BalanceOf<T>, BlockNumberFor<T>> {
+		let div_ceil = |a: BalanceOf<T>, b: BalanceOf<T>| {
+			let quotient = a / b;
+			if (a % b).is_zero() {
+				quotient
+			} else {
+				quotient.saturating_add(One::one())
+			}
+		};
+
+		let starting_block = now.max(schedule.starting_block());
+		let duration = schedule
+			.ending_block_as_balance::<T::BlockNumberToBalance>()
+			.saturating_sub(T::BlockNumberToBalance::convert(starting_block))
+			.max(One::one());
+		let per_block = div_ceil(locked, duration).max(One::one());
+		let delay: u128 =
+			duration.saturating_sub(div_ceil(locked, per_block)).unique_saturated_into();
+
+		VestingInfo::new(
+			locked,
+			per_block,
+			starting_block.saturating_add(delay.unique_saturated_into()),
+		)
+	}
+
+	// Move `amount` of the schedule at `schedule_index` of `source`, or all of it if `None`, to
+	// `target`.
+	fn do_transfer_vesting(
+		source: &T::AccountId,
+		target: &T::AccountId,
+		schedule_index: u32,
+		amount: Option<BalanceOf<T>>,
+	) -> DispatchResult {
+		let policy = TransferPolicy::<T>::get();
+		ensure!(
+			policy != VestingTransferPolicy::Disabled && source != target,
+			Error::<T>::TransferNotAllowed
+		);
+
+		let (page, index) = Self::locate(schedule_index);
+		let schedules = Self::page_schedules(source, page).ok_or(Error::<T>::NotVesting)?;
+		let schedule = *schedules.get(index).ok_or(Error::<T>::ScheduleIndexOutOfBounds)?;
+
+		let now = T::BlockNumberProvider::current_block_number();
+		let remaining = schedule.locked_at::<T::BlockNumberToBalance>(now);
+		let amount = amount.unwrap_or(remaining);
+		ensure!(amount >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+		ensure!(amount <= remaining, Error::<T>::InvalidScheduleParams);
+
+		let kept = remaining.saturating_sub(amount);
+		if !kept.is_zero() {
+			ensure!(policy == VestingTransferPolicy::Partial, Error::<T>::TransferNotAllowed);
+			ensure!(kept >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+		}
+
+		// Take the schedule out of the source page, putting back the part which is kept.
+		let (mut schedules, mut locked_now) =
+			Self::exec_action(schedules, VestingAction::Remove { index })?;
+		if !kept.is_zero() {
+			schedules.push(Self::split_vesting_info(now, schedule, kept));
+			locked_now = locked_now.saturating_add(kept);
+		}
+		Self::write_page(source, page, schedules)?;
+		let first_page_locked =
+			if page.is_zero() { locked_now } else { Self::first_page_locked(source) };
+		Self::update_lock(source, first_page_locked);
+
+		T::Currency::transfer(source, target, amount, ExistenceRequirement::AllowDeath)?;
+
+		let moved = Self::split_vesting_info(now, schedule, amount);
+		Self::add_vesting_schedule(
+			target,
+			moved.locked(),
+			moved.per_block(),
+			moved.starting_block(),
+		)?;
+
+		Self::deposit_event(Event::<T>::VestingTransferred {
+			from: source.clone(),
+			to: target.clone(),
+			schedule_index,
+			amount,
+		});
 
 		Ok(())
 	}
@@ -718,21 +1233,19 @@ where
 
 	/// Get the amount that is currently being vested and cannot be transferred out of this account.
 	fn vesting_balance(who: &T::AccountId) -> Option<BalanceOf<T>> {
-		if let Some(v) = Vesting::<T>::get(who) {
-			let now = T::BlockNumberProvider::current_block_number();
-			let total_locked_now = v.iter().fold(Zero::zero(), |total, schedule| {
-				schedule.locked_at::<T::BlockNumberToBalance>(now).saturating_add(total)
-			});
-			Some(T::Currency::free_balance(who).min(total_locked_now))
-		} else {
-			None
+		if !Vesting::<T>::contains_key(who) && !VestingPagesInfo::<T>::contains_key(who) {
+			return None
 		}
+
+		let total_locked_now =
+			Self::first_page_locked(who).saturating_add(Self::overflow_locked(who));
+		Some(T::Currency::free_balance(who).min(total_locked_now))
 	}
 
 	/// Adds a vesting schedule to a given account.
 	///
-	/// If the account has `MaxVestingSchedules`, an Error is returned and nothing
-	/// is updated.
+	/// A schedule unlocking over the same blocks as an existing one is merged into it. Otherwise
+	/// it is added to the first page, or to the overflow pages if the first page is full.
 	///
 	/// On success, a linearly reducing amount of funds will be locked. In order to realise any
 	/// reduction of the lock over time as it diminishes, the account owner must use `vest` or
@@ -757,24 +1270,32 @@ where
 			return Err(Error::<T>::InvalidScheduleParams.into())
 		};
 
-		let mut schedules = Vesting::<T>::get(who).unwrap_or_default();
-
-		// NOTE: we must push the new schedule so that `exec_action`
-		// will give the correct new locked amount.
-		ensure!(schedules.try_push(vesting_schedule).is_ok(), Error::<T>::AtMaxVestingSchedules);
-
-		debug_assert!(schedules.len() > 0, "schedules cannot be empty after insertion");
-		let schedule_index = schedules.len() - 1;
-		Self::deposit_event(Event::<T>::VestingCreated {
-			account: who.clone(),
-			schedule_index: schedule_index as u32,
-		});
+		let mut schedules = Vesting::<T>::get(who).unwrap_or_default().into_inner();
+
+		// NOTE: we must add the new schedule to the first page before calling `exec_action`
+		// so that it gives the correct new locked amount.
+		let schedule_index = match Self::merge_identical(&mut schedules, vesting_schedule) {
+			Some(index) => index as u32,
+			None => {
+				let max_schedules = T::MAX_VESTING_SCHEDULES as usize;
+				if schedules.len() >= max_schedules {
+					// Make room by dropping the schedules which have completed.
+					schedules = Self::report_schedule_updates(schedules, VestingAction::Passive).0;
+				}
+				if schedules.len() < max_schedules {
+					schedules.push(vesting_schedule);
+					(schedules.len() - 1) as u32
+				} else {
+					Self::add_to_overflow_pages(who, vesting_schedule)?
+				}
+			},
+		};
+		Self::deposit_event(Event::<T>::VestingCreated { account: who.clone(), schedule_index });
 
-		let (schedules, locked_now) =
-			Self::exec_action(schedules.to_vec(), VestingAction::Passive)?;
+		let (schedules, locked_now) = Self::exec_action(schedules, VestingAction::Passive)?;
 
 		Self::write_vesting(who, schedules)?;
-		Self::write_lock(who, locked_now);
+		Self::update_lock(who, locked_now);
 
 		Ok(())
 	}
@@ -787,29 +1308,20 @@ where
 		per_block: BalanceOf<T>,
 		starting_block: BlockNumberFor<T>,
 	) -> DispatchResult {
+		let schedule = VestingInfo::new(locked, per_block, starting_block);
 		// Check for `per_block` or `locked` of 0.
-		if !VestingInfo::new(locked, per_block, starting_block).is_valid() {
+		if !schedule.is_valid() {
 			return Err(Error::<T>::InvalidScheduleParams.into())
 		}
-
-		ensure!(
-			(Vesting::<T>::decode_len(who).unwrap_or_default() as u32) < T::MAX_VESTING_SCHEDULES,
-			Error::<T>::AtMaxVestingSchedules
-		);
+		ensure!(Self::has_room_for(who, schedule), Error::<T>::AtMaxVestingPages);
 
 		Ok(())
 	}
 
 	/// Remove a vesting schedule for a given account.
 	fn remove_vesting_schedule(who: &T::AccountId, schedule_index: u32) -> DispatchResult {
-		let schedules = Vesting::<T>::get(who).ok_or(Error::<T>::NotVesting)?;
-		let remove_action = VestingAction::Remove { index: schedule_index as usize };
-
-		let (schedules, locked_now) = Self::exec_action(schedules.to_vec(), remove_action)?;
-
-		Self::write_vesting(who, schedules)?;
-		Self::write_lock(who, locked_now);
-		Ok(())
+		let (page, index) = Self::locate(schedule_index);
+		Self::exec_on_page(who, page, VestingAction::Remove { index })
 	}
 }
 
@@ -54,10 +54,12 @@ impl Config for Test {
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
 	const MAX_VESTING_SCHEDULES: u32 = 3;
+	const MAX_VESTING_PAGES: u32 = 3;
 	type MinVestedTransfer = MinVestedTransfer;
 	type WeightInfo = ();
 	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
 	type BlockNumberProvider = System;
+	type TransferPolicyOrigin = frame_system::EnsureRoot<u64>;
 }
 
 pub struct ExtBuilder {
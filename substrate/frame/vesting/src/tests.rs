@@ -413,43 +413,191 @@ fn vested_transfer_correctly_fails() {
 }
 
 #[test]
-fn vested_transfer_allows_max_schedules() {
+fn vested_transfer_overflows_into_pages() {
 	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
 		let mut user_4_free_balance = Balances::free_balance(&4);
 		let max_schedules = <Test as Config>::MAX_VESTING_SCHEDULES;
-		let sched = VestingInfo::new(
-			<Test as Config>::MinVestedTransfer::get(),
-			1, // Vest over 2 * 256 blocks.
-			10,
-		);
-
-		// Add max amount schedules to user 4.
-		for _ in 0..max_schedules {
-			assert_ok!(Vesting::vested_transfer(Some(13).into(), 4, sched));
+		let min_transfer = <Test as Config>::MinVestedTransfer::get();
+		// Schedules starting at different blocks, so that they are not merged.
+		let scheds: Vec<_> = (0..max_schedules as u64 + 2)
+			.map(|i| VestingInfo::new(min_transfer, 1, 10 + i))
+			.collect();
+
+		for sched in &scheds {
+			assert_ok!(Vesting::vested_transfer(Some(13).into(), 4, *sched));
 		}
 
-		// The schedules count towards vesting balance
-		let transferred_amount = <Test as Config>::MinVestedTransfer::get() * max_schedules as u64;
+		// The first page is full, so the last two schedules overflowed into page 1,
+		System::assert_has_event(Event::VestingCreated { account: 4, schedule_index: 4 }.into());
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), scheds[..3].to_vec());
+		assert_eq!(VestingPages::<Test>::get(&4, 1).unwrap().schedules, scheds[3..].to_vec());
+		assert_eq!(VestingPagesInfo::<Test>::get(&4), Some(PagesInfo { last_page: 1 }));
+		// and all the schedules count towards vesting balance
+		let transferred_amount = min_transfer * scheds.len() as u64;
 		assert_eq!(Vesting::vesting_balance(&4), Some(transferred_amount));
 		// and free balance.
 		user_4_free_balance += transferred_amount;
 		assert_eq!(Balances::free_balance(&4), user_4_free_balance);
 
-		// Cannot insert a 4th vesting schedule when `MaxVestingSchedules` === 3,
+		// Schedules of overflow pages are addressed by their global index.
+		assert_ok!(Vesting::force_remove_vesting_schedule(RawOrigin::Root.into(), 4, 3));
+		assert_eq!(VestingPages::<Test>::get(&4, 1).unwrap().schedules, scheds[4..].to_vec());
+		assert_eq!(Vesting::vesting_balance(&4), Some(transferred_amount - min_transfer));
 		assert_noop!(
-			Vesting::vested_transfer(Some(3).into(), 4, sched),
-			Error::<Test>::AtMaxVestingSchedules,
+			Vesting::force_remove_vesting_schedule(RawOrigin::Root.into(), 4, 4),
+			Error::<Test>::InvalidScheduleParams
 		);
-		// so the free balance does not change.
-		assert_eq!(Balances::free_balance(&4), user_4_free_balance);
 
-		// Account 4 has fully vested when all the schedules end,
-		System::set_block_number(
-			<Test as Config>::MinVestedTransfer::get() + sched.starting_block(),
-		);
+		// Account 4 has fully vested when the last schedule ends,
+		System::set_block_number(min_transfer + scheds[4].starting_block());
 		assert_eq!(Vesting::vesting_balance(&4), Some(0));
-		// and after unlocking its schedules are removed from storage.
+		// and after unlocking all the schedules are removed from storage.
 		vest_and_assert_no_vesting::<Test>(4);
+		assert_eq!(VestingPagesInfo::<Test>::get(&4), None);
+		assert_eq!(VestingPages::<Test>::get(&4, 1), None);
+		assert_eq!(Vesting::vesting_balance(&4), None);
+	});
+}
+
+#[test]
+fn vest_refills_first_page_from_overflow_pages() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let min_transfer = <Test as Config>::MinVestedTransfer::get();
+		// Fill the first page with schedules vesting in a single block,
+		let short_scheds: Vec<_> =
+			(0..3).map(|i| VestingInfo::new(min_transfer, min_transfer, 10 + i)).collect();
+		// and overflow with longer ones.
+		let long_scheds = vec![
+			VestingInfo::new(min_transfer * 2, 1, 10),
+			VestingInfo::new(min_transfer * 2, 1, 11),
+		];
+		for sched in short_scheds.iter().chain(long_scheds.iter()) {
+			assert_ok!(Vesting::vested_transfer(Some(13).into(), 4, *sched));
+		}
+		assert_eq!(VestingPages::<Test>::get(&4, 1).unwrap().schedules, long_scheds);
+
+		// Once the short schedules completed, vesting moves the overflowed ones to the first page.
+		System::set_block_number(20);
+		assert_ok!(Vesting::vest(Some(4).into()));
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), long_scheds);
+		assert_eq!(VestingPages::<Test>::get(&4, 1), None);
+		assert_eq!(VestingPagesInfo::<Test>::get(&4), None);
+
+		let locked =
+			long_scheds[0].locked_at::<Identity>(20) + long_scheds[1].locked_at::<Identity>(20);
+		assert_eq!(Vesting::vesting_balance(&4), Some(locked));
+		System::assert_last_event(Event::VestingUpdated { account: 4, unvested: locked }.into());
+	});
+}
+
+#[test]
+fn vest_refreshes_every_overflow_page() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let min_transfer = <Test as Config>::MinVestedTransfer::get();
+		// Fill the first page and the last overflow page with long schedules,
+		let long_scheds: Vec<_> =
+			(0..6).map(|i| VestingInfo::new(min_transfer * 10, 1, 10 + i)).collect();
+		// and the overflow pages in between with schedules vesting in a single block.
+		let short_scheds: Vec<_> =
+			(0..6).map(|i| VestingInfo::new(min_transfer, min_transfer, 20 + i)).collect();
+		for sched in long_scheds[..3]
+			.iter()
+			.chain(short_scheds.iter())
+			.chain(long_scheds[3..].iter())
+		{
+			assert_ok!(Vesting::vested_transfer(Some(13).into(), 4, *sched));
+		}
+		assert_eq!(VestingPages::<Test>::get(&4, 3).unwrap().schedules, long_scheds[3..].to_vec());
+
+		// Once the short schedules completed, they no longer count towards vesting balance,
+		System::set_block_number(30);
+		let locked: u64 = long_scheds.iter().map(|sched| sched.locked_at::<Identity>(30)).sum();
+		assert_eq!(Vesting::vesting_balance(&4), Some(locked));
+
+		// and vesting drops them from every overflow page.
+		assert_ok!(Vesting::vest(Some(4).into()));
+		assert_eq!(VestingPages::<Test>::get(&4, 1), None);
+		assert_eq!(VestingPages::<Test>::get(&4, 2), None);
+		assert_eq!(VestingPages::<Test>::get(&4, 3).unwrap().schedules, long_scheds[3..].to_vec());
+		assert_eq!(VestingPagesInfo::<Test>::get(&4), Some(PagesInfo { last_page: 3 }));
+		assert_eq!(Vesting::vesting_balance(&4), Some(locked));
+		System::assert_last_event(Event::VestingUpdated { account: 4, unvested: locked }.into());
+	});
+}
+
+#[test]
+fn vested_transfer_fails_once_all_pages_are_full() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let min_transfer = <Test as Config>::MinVestedTransfer::get();
+		let capacity =
+			<Test as Config>::MAX_VESTING_SCHEDULES * (<Test as Config>::MAX_VESTING_PAGES + 1);
+		// Schedules starting at different blocks, so that they are not merged.
+		let scheds: Vec<_> = (0..capacity as u64)
+			.map(|i| VestingInfo::new(min_transfer, 1, 10 + i))
+			.collect();
+		for sched in &scheds {
+			assert_ok!(Vesting::vested_transfer(Some(13).into(), 4, *sched));
+		}
+		assert_eq!(
+			VestingPagesInfo::<Test>::get(&4),
+			Some(PagesInfo { last_page: <Test as Config>::MAX_VESTING_PAGES })
+		);
+
+		// Every page is full, so no other schedule can be added,
+		let extra_sched = VestingInfo::new(min_transfer, 1, 100);
+		assert_noop!(
+			Vesting::vested_transfer(Some(13).into(), 4, extra_sched),
+			Error::<Test>::AtMaxVestingPages
+		);
+		assert_noop!(
+			Vesting::force_vested_transfer(RawOrigin::Root.into(), 13, 4, extra_sched),
+			Error::<Test>::AtMaxVestingPages
+		);
+		// unless it is merged into an existing one,
+		let last = scheds.len() as u32 - 1;
+		assert_ok!(Vesting::vested_transfer(Some(13).into(), 4, scheds[last as usize]));
+		System::assert_last_event(
+			Event::VestingCreated { account: 4, schedule_index: last }.into(),
+		);
+
+		// or a page frees up.
+		assert_ok!(Vesting::force_remove_vesting_schedule(RawOrigin::Root.into(), 4, 4));
+		assert_ok!(Vesting::vested_transfer(Some(13).into(), 4, extra_sched));
+		System::assert_last_event(Event::VestingCreated { account: 4, schedule_index: 5 }.into());
+	});
+}
+
+#[test]
+fn merge_schedules_across_pages() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let sched0 = VestingInfo::new(ED * 20, ED, 10);
+		let sched1 = VestingInfo::new(ED * 10, ED, 11);
+		let sched2 = VestingInfo::new(ED * 10, ED, 12);
+		let sched3 = VestingInfo::new(ED * 10, ED, 13);
+		for sched in [sched0, sched1, sched2, sched3] {
+			assert_ok!(Vesting::vested_transfer(Some(13).into(), 4, sched));
+		}
+		assert_eq!(VestingPages::<Test>::get(&4, 1).unwrap().schedules, vec![sched3]);
+
+		// Merge a schedule of the first page with one of the overflow page.
+		assert_ok!(Vesting::merge_schedules(Some(4).into(), 0, 3));
+
+		// The merged schedule starts with the latest schedule and ends with the longest one.
+		let merged_sched = VestingInfo::new(
+			ED * 30,
+			ED * 30 / 17, // Vest over the 17 blocks between 13 and 30.
+			13,
+		);
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![sched1, sched2, merged_sched]);
+		assert_eq!(VestingPages::<Test>::get(&4, 1), None);
+		assert_eq!(VestingPagesInfo::<Test>::get(&4), None);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 50));
+
+		// An index of a page which does not exist is out of bounds.
+		assert_noop!(
+			Vesting::merge_schedules(Some(4).into(), 0, 3),
+			Error::<Test>::ScheduleIndexOutOfBounds
+		);
 	});
 }
 
@@ -567,37 +715,41 @@ fn force_vested_transfer_correctly_fails() {
 }
 
 #[test]
-fn force_vested_transfer_allows_max_schedules() {
+fn force_vested_transfer_merges_identical_schedules() {
 	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
 		let mut user_4_free_balance = Balances::free_balance(&4);
-		let max_schedules = <Test as Config>::MAX_VESTING_SCHEDULES;
+		let schedules_count = <Test as Config>::MAX_VESTING_SCHEDULES as u64 + 1;
 		let sched = VestingInfo::new(
 			<Test as Config>::MinVestedTransfer::get(),
 			1, // Vest over 2 * 256 blocks.
 			10,
 		);
 
-		// Add max amount schedules to user 4.
-		for _ in 0..max_schedules {
+		// Add more schedules than fit in a page to user 4.
+		for _ in 0..schedules_count {
 			assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 13, 4, sched));
+			System::assert_has_event(
+				Event::VestingCreated { account: 4, schedule_index: 0 }.into(),
+			);
 		}
 
+		// They all unlock over the same blocks, so they are merged into a single schedule.
+		let merged_sched = VestingInfo::new(
+			sched.locked() * schedules_count,
+			sched.per_block() * schedules_count,
+			10,
+		);
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![merged_sched]);
+		assert_eq!(VestingPagesInfo::<Test>::get(&4), None);
+
 		// The schedules count towards vesting balance.
-		let transferred_amount = <Test as Config>::MinVestedTransfer::get() * max_schedules as u64;
+		let transferred_amount = sched.locked() * schedules_count;
 		assert_eq!(Vesting::vesting_balance(&4), Some(transferred_amount));
 		// and free balance.
 		user_4_free_balance += transferred_amount;
 		assert_eq!(Balances::free_balance(&4), user_4_free_balance);
 
-		// Cannot insert a 4th vesting schedule when `MaxVestingSchedules` === 3
-		assert_noop!(
-			Vesting::force_vested_transfer(RawOrigin::Root.into(), 3, 4, sched),
-			Error::<Test>::AtMaxVestingSchedules,
-		);
-		// so the free balance does not change.
-		assert_eq!(Balances::free_balance(&4), user_4_free_balance);
-
-		// Account 4 has fully vested when all the schedules end,
+		// Account 4 has fully vested when the original schedules end,
 		System::set_block_number(<Test as Config>::MinVestedTransfer::get() + 10);
 		assert_eq!(Vesting::vesting_balance(&4), Some(0));
 		// and after unlocking its schedules are removed from storage.
@@ -617,20 +769,30 @@ fn merge_schedules_that_have_not_started() {
 		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0]);
 		assert_eq!(Balances::usable_balance(&2), 0);
 
-		// Add a schedule that is identical to the one that already exists.
+		// Adding a schedule that is identical to the one that already exists merges them. The
+		// new schedule finishes at the same time as the original, just with double the amount.
 		assert_ok!(Vesting::vested_transfer(Some(3).into(), 2, sched0));
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched0]);
+		let sched1 = VestingInfo::new(sched0.locked() * 2, sched0.per_block() * 2, 10);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched1]);
 		assert_eq!(Balances::usable_balance(&2), 0);
+
+		// Add a schedule that starts at the same block but vests twice as fast.
+		let sched2 = VestingInfo::new(
+			ED * 20,
+			ED * 2, // Vest over 10 blocks.
+			10,
+		);
+		assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, sched2));
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched1, sched2]);
 		assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
 
-		// Since we merged identical schedules, the new schedule finishes at the same
-		// time as the original, just with double the amount.
-		let sched1 = VestingInfo::new(
-			sched0.locked() * 2,
-			sched0.per_block() * 2,
-			10, // Starts at the block the schedules are merged/
+		// The merged schedule starts at the same block and finishes with the longest schedule.
+		let sched3 = VestingInfo::new(
+			sched1.locked() + sched2.locked(),
+			ED * 3, // Vest over 20 blocks.
+			10,
 		);
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched1]);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched3]);
 
 		assert_eq!(Balances::usable_balance(&2), 0);
 	});
@@ -1002,12 +1164,22 @@ fn merge_schedules_throws_proper_errors() {
 		assert_noop!(Vesting::merge_schedules(Some(4).into(), 0, 1), Error::<Test>::NotVesting);
 
 		// There are enough schedules to merge but an index is non-existent.
-		Vesting::vested_transfer(Some(3).into(), 2, sched0).unwrap();
-		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched0]);
+		let sched1 = VestingInfo::new(
+			ED * 10,
+			ED, // 10 block duration.
+			10,
+		);
+		Vesting::vested_transfer(Some(3).into(), 2, sched1).unwrap();
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0, sched1]);
 		assert_noop!(
 			Vesting::merge_schedules(Some(2).into(), 0, 2),
 			Error::<Test>::ScheduleIndexOutOfBounds
 		);
+		// An index of an overflow page which does not exist is out of bounds.
+		assert_noop!(
+			Vesting::merge_schedules(Some(2).into(), 0, 3),
+			Error::<Test>::ScheduleIndexOutOfBounds
+		);
 
 		// It is a storage noop with no errors if the indexes are the same.
 		assert_storage_noop!(Vesting::merge_schedules(Some(2).into(), 0, 0).unwrap());
@@ -1262,3 +1434,172 @@ fn vested_transfer_impl_works() {
 		);
 	});
 }
+
+#[test]
+fn set_transfer_policy_works() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		assert_eq!(TransferPolicy::<Test>::get(), VestingTransferPolicy::Disabled);
+
+		assert_noop!(
+			Vesting::set_transfer_policy(Some(1).into(), VestingTransferPolicy::Full),
+			BadOrigin
+		);
+
+		assert_ok!(Vesting::set_transfer_policy(
+			RawOrigin::Root.into(),
+			VestingTransferPolicy::Partial
+		));
+		assert_eq!(TransferPolicy::<Test>::get(), VestingTransferPolicy::Partial);
+		System::assert_last_event(
+			Event::TransferPolicySet { policy: VestingTransferPolicy::Partial }.into(),
+		);
+	});
+}
+
+#[test]
+fn transfer_vesting_works() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Account 2 should already have a vesting schedule.
+		let sched0 = VestingInfo::new(
+			ED * 20,
+			ED, // Vest over 20 blocks.
+			10,
+		);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![sched0]);
+
+		// Vesting positions cannot be transferred by default.
+		assert_noop!(
+			Vesting::transfer_vesting(Some(2).into(), 4, 0, None),
+			Error::<Test>::TransferNotAllowed
+		);
+
+		assert_ok!(Vesting::set_transfer_policy(
+			RawOrigin::Root.into(),
+			VestingTransferPolicy::Full
+		));
+		// Only whole schedules can be transferred under the `Full` policy,
+		assert_noop!(
+			Vesting::transfer_vesting(Some(2).into(), 4, 0, Some(ED * 10)),
+			Error::<Test>::TransferNotAllowed
+		);
+		// and not to the same account.
+		assert_noop!(
+			Vesting::transfer_vesting(Some(2).into(), 2, 0, None),
+			Error::<Test>::TransferNotAllowed
+		);
+		assert_noop!(
+			Vesting::transfer_vesting(Some(2).into(), 4, 1, None),
+			Error::<Test>::ScheduleIndexOutOfBounds
+		);
+		assert_noop!(
+			Vesting::transfer_vesting(Some(4).into(), 2, 0, None),
+			Error::<Test>::NotVesting
+		);
+
+		assert_ok!(Vesting::transfer_vesting(Some(2).into(), 4, 0, None));
+		System::assert_last_event(
+			Event::VestingTransferred { from: 2, to: 4, schedule_index: 0, amount: ED * 20 }.into(),
+		);
+
+		// The schedule has not started yet, so it moves to account 4 unchanged.
+		assert_eq!(VestingStorage::<Test>::get(&2), None);
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![sched0]);
+		assert_eq!(Balances::free_balance(&2), 0);
+		assert_eq!(Balances::free_balance(&4), ED * 60);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 20));
+	});
+}
+
+#[test]
+fn transfer_vesting_partial_splits_schedule() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		assert_ok!(Vesting::set_transfer_policy(
+			RawOrigin::Root.into(),
+			VestingTransferPolicy::Partial
+		));
+
+		// Half way through the schedule of account 2, half of it is still locked.
+		System::set_block_number(20);
+		assert_eq!(Vesting::vesting_balance(&2), Some(ED * 10));
+
+		// Both parts must be at least `MinVestedTransfer`,
+		assert_noop!(
+			Vesting::transfer_vesting(Some(2).into(), 4, 0, Some(ED)),
+			Error::<Test>::AmountLow
+		);
+		assert_noop!(
+			Vesting::transfer_vesting(Some(2).into(), 4, 0, Some(ED * 9)),
+			Error::<Test>::AmountLow
+		);
+		// and no more than what is still locked can be transferred.
+		assert_noop!(
+			Vesting::transfer_vesting(Some(2).into(), 4, 0, Some(ED * 11)),
+			Error::<Test>::InvalidScheduleParams
+		);
+
+		assert_ok!(Vesting::transfer_vesting(Some(2).into(), 4, 0, Some(ED * 5)));
+
+		// Both parts keep unlocking until the end of the original schedule.
+		let split_sched = VestingInfo::new(
+			ED * 5,
+			ED / 2, // Vest over the 10 remaining blocks.
+			20,
+		);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![split_sched]);
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![split_sched]);
+		assert_eq!(Vesting::vesting_balance(&2), Some(ED * 5));
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 5));
+		assert_eq!(Balances::free_balance(&2), ED * 15);
+		assert_eq!(Balances::free_balance(&4), ED * 45);
+
+		System::set_block_number(30);
+		assert_eq!(Vesting::vesting_balance(&2), Some(0));
+		assert_eq!(Vesting::vesting_balance(&4), Some(0));
+	});
+}
+
+#[test]
+fn transfer_vesting_partial_keeps_ending_block() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		assert_ok!(Vesting::set_transfer_policy(
+			RawOrigin::Root.into(),
+			VestingTransferPolicy::Partial
+		));
+
+		// Split the 10 remaining blocks of the schedule of account 2 in uneven parts.
+		System::set_block_number(20);
+		assert_ok!(Vesting::transfer_vesting(Some(2).into(), 4, 0, Some(ED * 5 + 7)));
+
+		// `per_block` is rounded up so that neither part ends after the original schedule.
+		let moved_sched = VestingInfo::new(ED * 5 + 7, 129, 20);
+		let kept_sched = VestingInfo::new(ED * 5 - 7, 128, 20);
+		assert_eq!(VestingStorage::<Test>::get(&2).unwrap(), vec![kept_sched]);
+		assert_eq!(VestingStorage::<Test>::get(&4).unwrap(), vec![moved_sched]);
+		assert_eq!(moved_sched.ending_block_as_balance::<Identity>(), 30);
+		assert_eq!(kept_sched.ending_block_as_balance::<Identity>(), 30);
+
+		System::set_block_number(29);
+		assert!(Vesting::vesting_balance(&2).unwrap() > 0);
+		assert!(Vesting::vesting_balance(&4).unwrap() > 0);
+		System::set_block_number(30);
+		assert_eq!(Vesting::vesting_balance(&2), Some(0));
+		assert_eq!(Vesting::vesting_balance(&4), Some(0));
+	});
+}
+
+#[test]
+fn split_vesting_info_delays_start_to_keep_ending_block() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// 10 over 4 blocks unlocks 3 per block, which takes all 4 blocks.
+		let sched = VestingInfo::new(ED * 20, ED * 5, 10);
+		let split = Vesting::split_vesting_info(10, sched, 10);
+		assert_eq!(split, VestingInfo::new(10, 3, 10));
+
+		// 10 over 6 blocks unlocks 2 per block, which takes 5 blocks, so the start is delayed
+		// by a block.
+		let sched = VestingInfo::new(ED * 12, ED * 2, 10);
+		let split = Vesting::split_vesting_info(10, sched, 10);
+		assert_eq!(split, VestingInfo::new(10, 2, 11));
+		assert_eq!(split.ending_block_as_balance::<Identity>(), 16);
+	});
+}
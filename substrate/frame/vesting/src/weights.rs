@@ -81,6 +81,9 @@ pub trait WeightInfo {
 	fn not_unlocking_merge_schedules(l: u32, s: u32, ) -> Weight;
 	fn unlocking_merge_schedules(l: u32, s: u32, ) -> Weight;
 	fn force_remove_vesting_schedule(l: u32, s: u32, ) -> Weight;
+	fn transfer_vesting(l: u32, s: u32, ) -> Weight;
+	fn set_transfer_policy() -> Weight;
+	fn update_overflow_page(s: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_vesting` using the Substrate node and recommended hardware.
@@ -285,6 +288,31 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by
+	/// `force_remove_vesting_schedule` on the source account, `force_vested_transfer` to the target
+	/// account and the read of `Vesting::TransferPolicy`.
+	/// The range of component `l` is `[0, 49]`.
+	/// The range of component `s` is `[1, 28]`.
+	fn transfer_vesting(l: u32, s: u32, ) -> Weight {
+		<Self as WeightInfo>::force_remove_vesting_schedule(l, s)
+			.saturating_add(<Self as WeightInfo>::force_vested_transfer(l, s))
+			.saturating_add(Weight::from_parts(0, 496))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. A single write of
+	/// `Vesting::TransferPolicy`, with a rough estimate of the execution time.
+	fn set_transfer_policy() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by `vest_unlocked`,
+	/// which decodes and writes as many schedules, and the proof sizes of `Vesting::VestingPages`
+	/// and `Vesting::VestingPagesInfo`.
+	/// The range of component `s` is `[1, 28]`.
+	fn update_overflow_page(s: u32, ) -> Weight {
+		<Self as WeightInfo>::vest_unlocked(0, s)
+			.saturating_add(Weight::from_parts(0, 6071))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -488,4 +516,29 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by
+	/// `force_remove_vesting_schedule` on the source account, `force_vested_transfer` to the target
+	/// account and the read of `Vesting::TransferPolicy`.
+	/// The range of component `l` is `[0, 49]`.
+	/// The range of component `s` is `[1, 28]`.
+	fn transfer_vesting(l: u32, s: u32, ) -> Weight {
+		<Self as WeightInfo>::force_remove_vesting_schedule(l, s)
+			.saturating_add(<Self as WeightInfo>::force_vested_transfer(l, s))
+			.saturating_add(Weight::from_parts(0, 496))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. A single write of
+	/// `Vesting::TransferPolicy`, with a rough estimate of the execution time.
+	fn set_transfer_policy() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Not benchmarked yet, to be regenerated with the benchmark bot. Bounded by `vest_unlocked`,
+	/// which decodes and writes as many schedules, and the proof sizes of `Vesting::VestingPages`
+	/// and `Vesting::VestingPagesInfo`.
+	/// The range of component `s` is `[1, 28]`.
+	fn update_overflow_page(s: u32, ) -> Weight {
+		<Self as WeightInfo>::vest_unlocked(0, s)
+			.saturating_add(Weight::from_parts(0, 6071))
+	}
 }